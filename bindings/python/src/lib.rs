@@ -0,0 +1,62 @@
+// lib.rs
+// 分析核心的 Python 绑定（pyo3），便于用原 Python 实现逐项核对 Rust 公式
+
+use std::collections::HashMap;
+
+use betawordlist_lib::analysis::{
+    corpus_pipeline, nlp::RuleTokenizer, options::AnalysisOptions, word_analyzer,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// 指标字典：字段名 → 取值，None 对应 Python 的 None
+type MetricsDict = HashMap<&'static str, Option<f64>>;
+
+/// 单词分布指标分析器
+#[pyclass(name = "CorpusWordAnalyzer")]
+struct PyCorpusWordAnalyzer {
+    inner: word_analyzer::CorpusWordAnalyzer,
+}
+
+#[pymethods]
+impl PyCorpusWordAnalyzer {
+    /// v 为各部分频次，part_sizes 为各部分词数，total 缺省时取 part_sizes 之和；
+    /// 接受 list 或 numpy 数组等任意数值序列，两者长度不同时抛出 ValueError
+    #[new]
+    #[pyo3(signature = (v, part_sizes, total = None))]
+    fn new(v: Vec<f64>, part_sizes: Vec<f64>, total: Option<f64>) -> PyResult<Self> {
+        let total = total.unwrap_or_else(|| part_sizes.iter().sum());
+        let inner = word_analyzer::CorpusWordAnalyzer::new(v, part_sizes, total)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    /// 计算全部指标，返回 dict
+    fn metrics(&self) -> MetricsDict {
+        self.inner
+            .calculate_all_metrics()
+            .fields()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// 用规则分词器分析若干文本（每段文本为一个语料部分），返回 (词, 词性, 频次, 指标 dict) 列表
+#[pyfunction]
+fn analyze_texts(texts: Vec<String>) -> Vec<(String, String, f64, MetricsDict)> {
+    corpus_pipeline::analyze_texts(&RuleTokenizer, &texts, &AnalysisOptions::default())
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let metrics = entry.metrics.fields().into_iter().collect();
+            (entry.word, entry.pos, entry.frequency, metrics)
+        })
+        .collect()
+}
+
+#[pymodule]
+fn betawordlist(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCorpusWordAnalyzer>()?;
+    m.add_function(wrap_pyfunction!(analyze_texts, m)?)?;
+    Ok(())
+}
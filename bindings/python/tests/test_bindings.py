@@ -0,0 +1,56 @@
+# test_bindings.py
+# 以公式的参考值核对绑定导出的 DP 与 Juilland's D
+
+import math
+
+import pytest
+
+import betawordlist
+
+
+def metrics(v, part_sizes, total=None):
+    return betawordlist.CorpusWordAnalyzer(v, part_sizes, total).metrics()
+
+
+def test_gries_example():
+    # Gries (2008) 的示例：5 个部分共 50 词，频次 1..5
+    m = metrics([1, 2, 3, 4, 5], [9, 10, 10, 10, 11])
+    assert m["dp"] == pytest.approx(0.18)
+    # 1 - CV(p) / sqrt(n - 1)，p 为各部分的相对频率，CV 取总体标准差
+    p = [1 / 9, 2 / 10, 3 / 10, 4 / 10, 5 / 11]
+    mean = sum(p) / len(p)
+    sd = math.sqrt(sum((x - mean) ** 2 for x in p) / len(p))
+    assert m["juilland_d"] == pytest.approx(1 - sd / mean / math.sqrt(len(p) - 1))
+    assert m["juilland_d"] == pytest.approx(0.7851504534504508)
+
+
+def test_even_distribution():
+    m = metrics([5, 5, 5, 5], [100, 100, 100, 100])
+    assert m["dp"] == pytest.approx(0.0)
+    assert m["juilland_d"] == pytest.approx(1.0)
+    assert m["range"] == 4
+
+
+def test_single_part():
+    # 全部出现在 4 个等大部分之一：DP = 1 - 1/4，D = 0
+    m = metrics([0, 0, 0, 4], [100, 100, 100, 100])
+    assert m["dp"] == pytest.approx(0.75)
+    assert m["juilland_d"] == pytest.approx(0.0)
+    assert m["range"] == 1
+
+
+def test_total_defaults_to_part_sizes():
+    assert metrics([2, 0, 5], [100, 80, 120]) == metrics([2, 0, 5], [100, 80, 120], 300)
+
+
+def test_part_length_mismatch():
+    with pytest.raises(ValueError):
+        betawordlist.CorpusWordAnalyzer([1, 2, 3], [100, 100])
+
+
+def test_analyze_texts():
+    entries = betawordlist.analyze_texts(["我在图书馆看书。", "他在公园看花。"])
+    # 规则分词器逐字切分：两个部分分别有 8 与 7 词，"在" 各出现一次
+    _, pos, frequency, m = next(e for e in entries if e[0] == "在")
+    assert (pos, frequency, m["range"]) == ("p", 2, 2)
+    assert m["dp"] == pytest.approx(0.5 * (abs(1 / 2 - 8 / 15) + abs(1 / 2 - 7 / 15)))
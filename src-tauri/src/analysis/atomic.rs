@@ -0,0 +1,164 @@
+// atomic.rs
+// 原子写入：先写同目录下的临时文件并落盘，再重命名覆盖目标，崩溃或磁盘写满时不会留下半截文件
+//
+// 所有导出、会话与检查点都经 write_atomic 或 AtomicFile 写出；失败时临时文件被删除，原有的目标文件保持不变。
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::analysis::paths;
+
+/// 写文件失败的原因，均带目标路径
+#[derive(Debug)]
+pub enum WriteError {
+    /// 磁盘空间或配额不足
+    DiskFull(String),
+    /// 没有写入目标目录的权限
+    PermissionDenied(String),
+    /// 目标文件正被其他程序打开（Windows 上 Excel 打开的 CSV 无法覆盖）
+    Locked(String),
+    Io(String, io::Error),
+}
+
+impl WriteError {
+    /// 按 io 错误归类
+    pub fn new(path: &Path, error: io::Error) -> Self {
+        let path = paths::display(path);
+        match error.kind() {
+            io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded => WriteError::DiskFull(path),
+            io::ErrorKind::PermissionDenied => WriteError::PermissionDenied(path),
+            _ => WriteError::Io(path, error),
+        }
+    }
+
+    /// 重命名覆盖目标时失败：Windows 上目标被占用表现为共享冲突、锁冲突或拒绝访问
+    fn on_replace(path: &Path, error: io::Error) -> Self {
+        if cfg!(windows) && path.exists() && matches!(error.raw_os_error(), Some(5 | 32 | 33)) {
+            return WriteError::Locked(paths::display(path));
+        }
+        Self::new(path, error)
+    }
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WriteError::DiskFull(path) => write!(f, "磁盘空间不足，未能写入 {path}"),
+            WriteError::PermissionDenied(path) => write!(f, "没有写入 {path} 的权限"),
+            WriteError::Locked(path) => {
+                write!(f, "{path} 正被其他程序（如 Excel）打开，请关闭后重试")
+            }
+            WriteError::Io(path, e) => write!(f, "写入 {path} 失败: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl From<WriteError> for io::Error {
+    fn from(e: WriteError) -> Self {
+        let kind = match &e {
+            WriteError::Io(_, inner) => inner.kind(),
+            WriteError::DiskFull(_) => io::ErrorKind::StorageFull,
+            WriteError::PermissionDenied(_) | WriteError::Locked(_) => {
+                io::ErrorKind::PermissionDenied
+            }
+        };
+        io::Error::new(kind, e.to_string())
+    }
+}
+
+/// 原子写入 path：write 写入带缓冲的临时文件，成功后落盘并重命名覆盖目标
+pub fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<(), WriteError> {
+    let mut file = AtomicFile::create(path)?;
+    write(&mut file).map_err(|e| WriteError::new(path, e))?;
+    file.commit()
+}
+
+/// 原子写入中的文件：写入同目录的临时文件，commit 时落盘并重命名覆盖目标；
+/// 未 commit 即丢弃（写入出错或 panic）时删除临时文件。需同时写出多个文件时代替 write_atomic
+pub struct AtomicFile {
+    path: PathBuf,
+    tmp: PathBuf,
+    /// commit 后为 None
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    pub fn create(path: &Path) -> Result<Self, WriteError> {
+        let tmp = temp_path(path);
+        let file = File::create(&tmp).map_err(|e| WriteError::new(path, e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            tmp,
+            writer: Some(BufWriter::new(file)),
+        })
+    }
+
+    /// 目标路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 落盘并重命名覆盖目标
+    pub fn commit(mut self) -> Result<(), WriteError> {
+        let Some(writer) = self.writer.take() else {
+            return Ok(());
+        };
+        let synced = writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)
+            .and_then(|file| file.sync_all());
+        if let Err(e) = synced {
+            fs::remove_file(&self.tmp).ok();
+            return Err(WriteError::new(&self.path, e));
+        }
+        if let Err(e) = fs::rename(&self.tmp, &self.path) {
+            fs::remove_file(&self.tmp).ok();
+            return Err(WriteError::on_replace(&self.path, e));
+        }
+        // 目录项也落盘，重命名本身在断电后仍然有效
+        #[cfg(unix)]
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            File::open(dir).and_then(|d| d.sync_all()).ok();
+        }
+        Ok(())
+    }
+
+    fn writer(&mut self) -> io::Result<&mut BufWriter<File>> {
+        self.writer
+            .as_mut()
+            .ok_or_else(|| io::Error::other("文件已提交"))
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer()?.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            fs::remove_file(&self.tmp).ok();
+        }
+    }
+}
+
+/// 与目标同目录的隐藏临时文件，重命名不跨文件系统
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+    path.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
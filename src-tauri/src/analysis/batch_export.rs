@@ -0,0 +1,211 @@
+// batch_export.rs
+// 一次导出多种格式：csv / tsv / xlsx / json 与 antconc / lancsbox 词表共用一次对结果的遍历，逐条目交给各格式的写出端
+//
+// 各格式写入 <path_base>.<扩展名>；某一格式失败（无法创建、写满、被占用）不影响其余格式，
+// 失败格式的临时文件被删除，已有的同名文件保持不变。vertical 需按元数据重新分词，单独写出。
+// 开启 report 时最后写出运行报告 <path_base>.report.json，列出各格式写出的文件，见 run_report。
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    atomic::{AtomicFile, WriteError},
+    corpus_pipeline::AnalysisResult,
+    export::{self, export_rows, ExportOptions, RowSink, TableSink, XlsxSink},
+    interop::{InteropSink, InteropTool},
+    json_export::JsonSink,
+    nlp::Tokenizer,
+    paths,
+    run_report::RunReport,
+    vertical::{self, VerticalOptions},
+};
+
+/// 批量导出选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchOptions {
+    /// csv / tsv / xlsx 的数值格式、词性名称与排名列；tsv 的字段分隔符固定为制表符
+    pub table: ExportOptions,
+    /// vertical 的选项；per_file 时写入目录 <path_base>/，否则写入 <path_base>.vrt
+    pub vertical: VerticalOptions,
+    /// 另写出运行报告 <path_base>.report.json，在报告中以 report 格式列出
+    pub report: bool,
+}
+
+/// 一种格式的导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatReport {
+    pub format: String,
+    /// 写出文件的绝对路径；失败时为空
+    pub written: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// 逐条目写出的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowFormat {
+    Csv,
+    Tsv,
+    Xlsx,
+    Json,
+    /// 按其他工具导入词表的列布局，见 interop
+    Interop(InteropTool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Rows(RowFormat),
+    Vertical,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name.trim().to_ascii_lowercase().as_str() {
+            "csv" => Format::Rows(RowFormat::Csv),
+            "tsv" => Format::Rows(RowFormat::Tsv),
+            "xlsx" => Format::Rows(RowFormat::Xlsx),
+            "json" => Format::Rows(RowFormat::Json),
+            "vertical" | "vrt" => Format::Vertical,
+            name => Format::Rows(RowFormat::Interop(InteropTool::parse(name)?)),
+        })
+    }
+}
+
+impl RowFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            RowFormat::Csv => "csv",
+            RowFormat::Tsv => "tsv",
+            RowFormat::Xlsx => "xlsx",
+            RowFormat::Json => "json",
+            RowFormat::Interop(tool) => tool.extension(),
+        }
+    }
+}
+
+/// 按 formats 的顺序导出并逐一报告；重复的格式只导出一次。
+/// csv 或 tsv 成功时，另在旁边写出 .meta.json 元数据（路径记入首个成功的表格格式）
+pub fn export_batch(
+    result: &AnalysisResult,
+    nlp: Option<&dyn Tokenizer>,
+    path_base: &Path,
+    formats: &[String],
+    options: &BatchOptions,
+) -> Vec<FormatReport> {
+    let mut reports: Vec<FormatReport> = Vec::new();
+    let mut requested: Vec<Option<Format>> = Vec::new();
+    for name in formats {
+        let format = Format::parse(name);
+        if format.is_some() && requested.contains(&format) {
+            continue;
+        }
+        requested.push(format);
+        reports.push(FormatReport {
+            format: name.trim().to_ascii_lowercase(),
+            written: Vec::new(),
+            error: format
+                .is_none()
+                .then(|| format!("未知的导出格式: {}", name.trim())),
+        });
+    }
+
+    // 各逐条目格式共用一次遍历
+    let mut targets: Vec<(usize, RowFormat, AtomicFile)> = Vec::new();
+    for (i, format) in requested.iter().enumerate() {
+        if let Some(Format::Rows(format)) = *format {
+            match AtomicFile::create(&with_suffix(path_base, format.extension())) {
+                Ok(file) => targets.push((i, format, file)),
+                Err(e) => reports[i].error = Some(e.to_string()),
+            }
+        }
+    }
+    let tsv_options = ExportOptions {
+        delimiter: '\t',
+        ..options.table.clone()
+    };
+    let outcomes = {
+        let mut sinks: Vec<Box<dyn RowSink + '_>> = targets
+            .iter_mut()
+            .map(|(_, format, file)| -> Box<dyn RowSink + '_> {
+                match format {
+                    RowFormat::Csv => Box::new(TableSink::new(file, &options.table)),
+                    RowFormat::Tsv => Box::new(TableSink::new(file, &tsv_options)),
+                    RowFormat::Xlsx => Box::new(XlsxSink::new(file, &options.table)),
+                    RowFormat::Json => Box::new(JsonSink::new(file)),
+                    RowFormat::Interop(tool) => Box::new(InteropSink::new(file, *tool)),
+                }
+            })
+            .collect();
+        let mut sinks: Vec<&mut (dyn RowSink + '_)> =
+            sinks.iter_mut().map(|s| s.as_mut()).collect();
+        export_rows(result, &options.table.rank_metrics(), &mut sinks)
+    };
+    let mut sidecar_written = false;
+    for ((i, format, file), outcome) in targets.into_iter().zip(outcomes) {
+        let path = file.path().to_path_buf();
+        let committed = outcome
+            .map_err(|e| WriteError::new(&path, e))
+            .and_then(|()| file.commit());
+        if let Err(e) = committed {
+            reports[i].error = Some(e.to_string());
+            continue;
+        }
+        reports[i].written.push(absolute(&path));
+        if matches!(format, RowFormat::Csv | RowFormat::Tsv) && !sidecar_written {
+            sidecar_written = true;
+            match export::write_metadata_sidecar(&path, &result.metadata, options.table.definitions)
+            {
+                Ok(sidecar) => reports[i].written.push(absolute(&sidecar)),
+                Err(e) => reports[i].error = Some(format!("元数据导出失败: {e}")),
+            }
+        }
+    }
+
+    if let Some(i) = requested.iter().position(|f| *f == Some(Format::Vertical)) {
+        let target = if options.vertical.per_file {
+            path_base.to_path_buf()
+        } else {
+            with_suffix(path_base, "vrt")
+        };
+        match nlp {
+            None => reports[i].error = Some("NLP模型未加载".to_string()),
+            Some(nlp) => {
+                match vertical::export(nlp, &result.metadata, &target, &options.vertical) {
+                    Ok(written) => {
+                        reports[i].written = written.iter().map(|p| absolute(p)).collect()
+                    }
+                    Err(e) => reports[i].error = Some(e.to_string()),
+                }
+            }
+        }
+    }
+    if options.report {
+        let outputs = reports.iter().flat_map(|r| r.written.clone()).collect();
+        let path = with_suffix(path_base, "report.json");
+        let written = RunReport::succeeded(result, None, outputs).write(&path);
+        reports.push(FormatReport {
+            format: "report".to_string(),
+            written: written
+                .is_ok()
+                .then(|| absolute(&path))
+                .into_iter()
+                .collect(),
+            error: written.err().map(|e| e.to_string()),
+        });
+    }
+    reports
+}
+
+/// <path_base>.<扩展名>；path_base 中已有的点不视为扩展名
+fn with_suffix(path_base: &Path, extension: &str) -> PathBuf {
+    let mut path = OsString::from(path_base.as_os_str());
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+fn absolute(path: &Path) -> String {
+    paths::display(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+}
@@ -0,0 +1,273 @@
+// bootstrap.rs
+// 语料层面的自助法：有放回地重抽语料部分，估计类符数、TTR 的分布与高频词前 N 名的稳定性
+//
+// 只在计数之后进行，依赖结果中保留的各部分词频（本次分析的计数或 keep_parts）；不含短语。
+// 每轮重抽使用由种子与轮次推出的独立随机序列，结果与线程数无关，同一种子总得到相同结果；
+// 未指定种子时由结果的分析种子（metadata.seed）推出。
+
+use std::collections::HashMap;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{corpus_pipeline::AnalysisResult, seed};
+
+/// 重抽轮数上限
+pub const MAX_REPLICATES: usize = 10_000;
+
+/// 一组重抽结果的分位数摘要；分位数按线性插值计算
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuantileSummary {
+    pub mean: f64,
+    pub min: f64,
+    pub p2_5: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p97_5: f64,
+    pub max: f64,
+}
+
+impl QuantileSummary {
+    fn from_samples(mut samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(f64::total_cmp);
+        let quantile = |q: f64| {
+            let position = q * (samples.len() - 1) as f64;
+            let lower = position.floor() as usize;
+            let upper = position.ceil() as usize;
+            samples[lower] + (samples[upper] - samples[lower]) * (position - lower as f64)
+        };
+        Self {
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            min: samples[0],
+            p2_5: quantile(0.025),
+            p25: quantile(0.25),
+            median: quantile(0.5),
+            p75: quantile(0.75),
+            p97_5: quantile(0.975),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// 全语料前 2N 名中的一个词在重抽中留在前 N 名的比例
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopStability {
+    pub word: String,
+    pub pos: String,
+    /// 全语料中的名次，从 1 开始；同频按 (词, 词性) 排序
+    pub rank: usize,
+    pub frequency: f64,
+    /// 留在前 N 名的轮次百分比（0–100）
+    pub top_n_percent: f64,
+}
+
+/// 语料层面的自助法结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusBootstrap {
+    pub replicates: usize,
+    pub top_n: usize,
+    pub seed: u64,
+    /// 重抽语料的类符数（频次大于 0 的词数）
+    pub types: QuantileSummary,
+    /// 重抽语料的总词数；各部分大小不同，总词数随重抽变化
+    pub tokens: QuantileSummary,
+    /// 类符数 / 总词数
+    pub ttr: QuantileSummary,
+    /// 全语料前 2N 名的词，按名次排列
+    pub top_stability: Vec<TopStability>,
+}
+
+/// 各部分的稀疏词频：部分 -> [(词序号, 频次)]
+struct PartVectors {
+    keys: Vec<(String, String)>,
+    parts: Vec<Vec<(usize, f64)>>,
+    sizes: Vec<f64>,
+}
+
+impl PartVectors {
+    /// 按 (词, 词性) 排序编号，编号与哈希表的遍历顺序无关
+    fn collect(result: &AnalysisResult) -> Option<Self> {
+        let mut counts: Vec<(usize, String, String, f64)> = Vec::new();
+        let retained = result.for_each_part_count(|part, word, pos, v| {
+            counts.push((part, word.to_string(), pos.to_string(), v))
+        });
+        if !retained {
+            return None;
+        }
+        let mut keys: Vec<(String, String)> = counts
+            .iter()
+            .map(|(_, word, pos, _)| (word.clone(), pos.clone()))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let index: HashMap<&(String, String), usize> =
+            keys.iter().enumerate().map(|(i, key)| (key, i)).collect();
+        let sizes: Vec<f64> = result
+            .metadata
+            .parts
+            .iter()
+            .map(|p| p.tokens as f64)
+            .collect();
+        let mut parts = vec![Vec::new(); sizes.len()];
+        for (part, word, pos, v) in counts {
+            if let Some(vector) = parts.get_mut(part) {
+                vector.push((index[&(word, pos)], v));
+            }
+        }
+        Some(Self { keys, parts, sizes })
+    }
+
+    /// 按抽中的部分累加词频
+    fn totals(&self, drawn: &[usize], totals: &mut [f64]) {
+        totals.fill(0.0);
+        for &part in drawn {
+            for &(word, v) in &self.parts[part] {
+                totals[word] += v;
+            }
+        }
+    }
+}
+
+/// 按频次降序、同频按词序号升序的前 n 个词序号
+fn top_words(totals: &[f64], n: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..totals.len()).filter(|&i| totals[i] > 0.0).collect();
+    let by_rank = |a: &usize, b: &usize| totals[*b].total_cmp(&totals[*a]).then(a.cmp(b));
+    if order.len() > n {
+        order.select_nth_unstable_by(n, by_rank);
+        order.truncate(n);
+    }
+    order.sort_unstable_by(by_rank);
+    order
+}
+
+/// SplitMix64：每轮重抽一个独立序列，不引入额外依赖
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64, replicate: u64) -> Self {
+        let mut rng = Self(seed ^ replicate.wrapping_mul(0xd1b5_4a32_d192_ed03));
+        rng.next();
+        rng
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// [0, n) 中的均匀整数
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        ((self.next() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+/// 一轮重抽的结果
+struct Replicate {
+    types: f64,
+    tokens: f64,
+    /// 被跟踪的词中留在前 N 名的位置（下标对应 tracked）
+    stayed: Vec<usize>,
+}
+
+/// 有放回地重抽 replicates 轮语料部分，每轮抽取与原语料相同的部分数；各轮并行计算
+pub fn bootstrap_corpus(
+    result: &AnalysisResult,
+    replicates: usize,
+    top_n: usize,
+    seed: Option<u64>,
+) -> Result<CorpusBootstrap, String> {
+    if replicates == 0 || replicates > MAX_REPLICATES {
+        return Err(format!("重抽轮数须在 1 到 {MAX_REPLICATES} 之间"));
+    }
+    if top_n == 0 {
+        return Err("top_n 须大于 0".to_string());
+    }
+    let vectors =
+        PartVectors::collect(result).ok_or("结果未保留各部分词频，请重新分析或开启 keep_parts")?;
+    let part_count = vectors.parts.len();
+    if part_count < 2 {
+        return Err("至少需要两个语料部分才能重抽".to_string());
+    }
+    let seed = seed.unwrap_or_else(|| seed::derive_seed(result.metadata.seed, seed::BOOTSTRAP));
+
+    let all_parts: Vec<usize> = (0..part_count).collect();
+    let mut full = vec![0.0; vectors.keys.len()];
+    vectors.totals(&all_parts, &mut full);
+    let tracked = top_words(&full, top_n.saturating_mul(2));
+    let tracked_position: HashMap<usize, usize> =
+        tracked.iter().enumerate().map(|(i, &w)| (w, i)).collect();
+
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(replicates);
+    let run = |worker: usize| {
+        let mut totals = vec![0.0; vectors.keys.len()];
+        let mut drawn = vec![0; part_count];
+        (worker..replicates)
+            .step_by(workers)
+            .map(|replicate| {
+                let mut rng = SplitMix64::new(seed, replicate as u64);
+                for slot in drawn.iter_mut() {
+                    *slot = rng.below(part_count);
+                }
+                vectors.totals(&drawn, &mut totals);
+                Replicate {
+                    types: totals.iter().filter(|&&v| v > 0.0).count() as f64,
+                    tokens: drawn.iter().map(|&p| vectors.sizes[p]).sum(),
+                    stayed: top_words(&totals, top_n)
+                        .into_iter()
+                        .filter_map(|w| tracked_position.get(&w).copied())
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let outcomes: Vec<Replicate> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| scope.spawn(move || run(worker)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("重抽线程异常退出"))
+            .collect()
+    });
+
+    let mut stayed = vec![0usize; tracked.len()];
+    for outcome in &outcomes {
+        for &i in &outcome.stayed {
+            stayed[i] += 1;
+        }
+    }
+    let ttr = outcomes
+        .iter()
+        .filter(|o| o.tokens > 0.0)
+        .map(|o| o.types / o.tokens)
+        .collect();
+    Ok(CorpusBootstrap {
+        replicates,
+        top_n,
+        seed,
+        types: QuantileSummary::from_samples(outcomes.iter().map(|o| o.types).collect()),
+        tokens: QuantileSummary::from_samples(outcomes.iter().map(|o| o.tokens).collect()),
+        ttr: QuantileSummary::from_samples(ttr),
+        top_stability: tracked
+            .iter()
+            .zip(stayed)
+            .enumerate()
+            .map(|(i, (&w, count))| TopStability {
+                word: vectors.keys[w].0.clone(),
+                pos: vectors.keys[w].1.clone(),
+                rank: i + 1,
+                frequency: full[w],
+                top_n_percent: count as f64 * 100.0 / replicates as f64,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,54 @@
+// burstiness.rs
+// 文件内滑窗分布度：将单个文件的词序列切为等长窗口，以窗口为部分计算 DP 与 Juilland D
+
+use std::collections::HashMap;
+
+use crate::analysis::word_analyzer::CorpusWordAnalyzer;
+
+/// 计算文件内各 (词, 词性) 在窗口间的 (DP, Juilland D)
+///
+/// 每 window 个词为一个窗口，末尾不足一窗的词单独成窗；不足两个窗口时无法衡量分布，返回空表。
+///
+/// ```
+/// use betawordlist_lib::analysis::burstiness::window_dispersion;
+///
+/// // 目标词集中在文本开头 vs. 同一批词均匀打散
+/// let mut clustered = vec![("a".to_string(), "n".to_string()); 20];
+/// clustered.extend(vec![("x".to_string(), "n".to_string()); 980]);
+/// let shuffled: Vec<_> = (0..1000).map(|i| clustered[(i % 50) * 20 + i / 50].clone()).collect();
+///
+/// let key = ("a".to_string(), "n".to_string());
+/// let (dp_clustered, d_clustered) = window_dispersion(&clustered, 50)[&key];
+/// let (dp_shuffled, d_shuffled) = window_dispersion(&shuffled, 50)[&key];
+/// assert!(dp_clustered > 0.9 && dp_shuffled < 0.05);
+/// assert!(d_clustered < 0.1 && d_shuffled > 0.95);
+/// ```
+pub fn window_dispersion(
+    word_pos: &[(String, String)],
+    window: usize,
+) -> HashMap<(String, String), (f64, f64)> {
+    let window = window.max(1);
+    let window_count = word_pos.len().div_ceil(window);
+    if window_count < 2 {
+        return HashMap::new();
+    }
+
+    let mut window_freqs: HashMap<&(String, String), Vec<f64>> = HashMap::new();
+    for (i, token) in word_pos.iter().enumerate() {
+        window_freqs
+            .entry(token)
+            .or_insert_with(|| vec![0.0; window_count])[i / window] += 1.0;
+    }
+    let window_sizes: Vec<f64> = word_pos.chunks(window).map(|c| c.len() as f64).collect();
+    let total = word_pos.len() as f64;
+
+    window_freqs
+        .into_iter()
+        .filter_map(|(token, freqs)| {
+            let analyzer = CorpusWordAnalyzer::new(freqs, window_sizes.clone(), total).ok()?;
+            let dp = analyzer.get_dp()?;
+            let d = analyzer.get_juilland_d()?;
+            Some((token.clone(), (dp, d)))
+        })
+        .collect()
+}
@@ -0,0 +1,123 @@
+// character_families.rs
+// 字族：由最终词表拆出多字词的组成字，统计每个字出现在多少个不同的词中（字族大小）及这些词的总频次
+//
+// 只用分析结果中的条目，不重新分词。同一词形的不同词性合为一个词类型；短语、词性序列、
+// 标点与未出现的目标词不参与。字按 text::graphemes 切分，一个词中重复的字只计一次。
+// 字族中只出现一次的词（hapax）数可与字族大小一起用于估计构词能产性。
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    phrases::PHRASE_POS,
+    posgrams::POSGRAM_POS,
+    text,
+    token_policy::{self, PUNCTUATION_POS},
+    word_entry::WordEntry,
+};
+
+/// 字族统计选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CharacterFamilyOptions {
+    /// 含拉丁字母的词（如 "T恤"、"iPhone"）也参与统计
+    pub include_latin: bool,
+}
+
+/// 一个字的字族
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterFamily {
+    pub character: String,
+    /// 含此字的不同多字词数
+    pub family_size: usize,
+    /// 这些词的总频次
+    pub family_frequency: f64,
+    /// 其中频次为 1 的词数
+    pub hapax_members: usize,
+    /// 含此字的词，按频次降序（同频次按词形）
+    pub members: Vec<String>,
+}
+
+/// 一个多字词的组成
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordComposition {
+    pub word: String,
+    /// 各词性条目的频次之和
+    pub frequency: f64,
+    /// 按顺序的组成字，重复的字照原样列出
+    pub components: Vec<String>,
+}
+
+/// 字族统计结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CharacterFamilies {
+    /// 按字族大小降序，其次按字族频次降序、字
+    pub characters: Vec<CharacterFamily>,
+    /// 按频次降序，其次按词形
+    pub words: Vec<WordComposition>,
+}
+
+/// 由条目统计各字的字族与各多字词的组成
+pub fn character_families(
+    entries: &[WordEntry],
+    options: &CharacterFamilyOptions,
+) -> CharacterFamilies {
+    let mut frequencies: BTreeMap<&str, f64> = BTreeMap::new();
+    for entry in entries {
+        let synthetic = [PHRASE_POS, POSGRAM_POS, PUNCTUATION_POS].contains(&entry.pos.as_str());
+        if synthetic || entry.frequency <= 0.0 {
+            continue;
+        }
+        if !options.include_latin && entry.word.chars().any(token_policy::is_latin_letter) {
+            continue;
+        }
+        *frequencies.entry(&entry.word).or_insert(0.0) += entry.frequency;
+    }
+
+    let mut words: Vec<WordComposition> = frequencies
+        .into_iter()
+        .filter_map(|(word, frequency)| {
+            let components = text::graphemes(word);
+            (components.len() >= 2).then(|| WordComposition {
+                word: word.to_string(),
+                frequency,
+                components: components.into_iter().map(str::to_string).collect(),
+            })
+        })
+        .collect();
+    // BTreeMap 已按词形排序，稳定排序后同频次的词仍按词形
+    words.sort_by(|a, b| b.frequency.total_cmp(&a.frequency));
+
+    let mut families: HashMap<&str, CharacterFamily> = HashMap::new();
+    for word in &words {
+        let mut seen = Vec::new();
+        for component in &word.components {
+            if seen.contains(&component) {
+                continue;
+            }
+            seen.push(component);
+            let family = families
+                .entry(component)
+                .or_insert_with(|| CharacterFamily {
+                    character: component.clone(),
+                    family_size: 0,
+                    family_frequency: 0.0,
+                    hapax_members: 0,
+                    members: Vec::new(),
+                });
+            family.family_size += 1;
+            family.family_frequency += word.frequency;
+            family.hapax_members += usize::from(word.frequency == 1.0);
+            family.members.push(word.word.clone());
+        }
+    }
+    let mut characters: Vec<CharacterFamily> = families.into_values().collect();
+    characters.sort_by(|a, b| {
+        b.family_size
+            .cmp(&a.family_size)
+            .then(b.family_frequency.total_cmp(&a.family_frequency))
+            .then_with(|| a.character.cmp(&b.character))
+    });
+    CharacterFamilies { characters, words }
+}
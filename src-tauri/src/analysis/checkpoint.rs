@@ -0,0 +1,114 @@
+// checkpoint.rs
+// 长时间分析的断点续跑：按块保存已完成部分的词频，中断后可从断点继续
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    corpus_pipeline::PartCounts,
+    options::AnalysisOptions,
+};
+
+/// 一个检查点块：从 start 开始的若干连续部分
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckpointChunk {
+    start: usize,
+    parts: Vec<PartCounts>,
+}
+
+/// 某次分析（文件列表 + 选项 + 分词引擎）对应的检查点目录
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    dir: PathBuf,
+}
+
+/// 检查点概况，供前端提示是否续跑
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointInfo {
+    pub completed_files: usize,
+    pub total_files: usize,
+}
+
+impl Checkpoint {
+    /// 在 root 下按 文件列表、选项与引擎 的哈希定位检查点目录；三者任一变化都不会误用旧检查点
+    pub fn new(
+        root: &Path,
+        file_paths: &[PathBuf],
+        options: &AnalysisOptions,
+        engine: &str,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(engine.as_bytes());
+        // 按原始字节哈希路径，非 UTF-8 路径也能区分
+        for path in file_paths {
+            hasher.update(path.as_os_str().as_encoded_bytes());
+            hasher.update([0]);
+        }
+        hasher.update(serde_json::to_vec(options).unwrap_or_default());
+        let key = format!("{:x}", hasher.finalize());
+        Self {
+            dir: root.join(&key[..32]),
+        }
+    }
+
+    /// 载入从第 0 个部分起连续完成的部分；目录不存在或块损坏时只取其前的连续部分
+    pub fn load(&self) -> Vec<PartCounts> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut chunks: Vec<CheckpointChunk> = read_dir
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| fs::read(path).ok())
+            .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+            .collect();
+        chunks.sort_by_key(|chunk| chunk.start);
+
+        let mut parts = Vec::new();
+        for chunk in chunks {
+            if chunk.start != parts.len() {
+                break;
+            }
+            parts.extend(chunk.parts);
+        }
+        parts
+    }
+
+    /// 检查点概况；没有已完成部分时返回 None
+    pub fn info(&self, total_files: usize) -> Option<CheckpointInfo> {
+        let completed_files = self.load().len().min(total_files);
+        (completed_files > 0).then_some(CheckpointInfo {
+            completed_files,
+            total_files,
+        })
+    }
+
+    /// 原子写入一个块，中途崩溃不会留下半个块
+    pub fn save_chunk(&self, start: usize, parts: &[PartCounts]) -> Result<(), WriteError> {
+        #[derive(Serialize)]
+        struct ChunkRef<'a> {
+            start: usize,
+            parts: &'a [PartCounts],
+        }
+
+        fs::create_dir_all(&self.dir).map_err(|e| WriteError::new(&self.dir, e))?;
+        let target = self.dir.join(format!("chunk_{start:08}.json"));
+        write_atomic(&target, |w| {
+            Ok(serde_json::to_writer(w, &ChunkRef { start, parts })?)
+        })
+    }
+
+    /// 删除检查点
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
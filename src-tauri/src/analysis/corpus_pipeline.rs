@@ -1,75 +1,2364 @@
 // corpus_pipeline.rs
 // 语料批量处理主流程，负责文件读取、NLP分析、停用词过滤、分布指标计算
 
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::analysis::{
-    dispersion_metrics::DispersionMetrics, nlp::LtpNlp, word_analyzer::CorpusWordAnalyzer,
+    burstiness,
+    checkpoint::Checkpoint,
+    entry_flags, entry_id, event_forwarder,
+    examples::ExampleSampler,
+    filter::{
+        self, DenominatorPolicy, FileRemoval, FilterCounts, FilterEffect, FilterReason,
+        FilterReport, RemovedType, TokenFilter,
+    },
+    language::{LowHanFile, ScreenMode, ScreenedFile, ScriptHistogram},
+    memory::{self, DensePartsExceeded, MemoryLimitExceeded, DENSE_CELL_LIMIT},
+    metadata::{CorpusMetadata, FileInfo, PartInfo, PartMerges, Truncation},
+    near_duplicates::{self, DuplicatePair, NearDuplicateOptions},
+    nlp::{Tokenizer, WhitespaceTokenizer},
+    oov::PairCounter,
+    options::{AnalysisOptions, PartitionMode, METRIC_OPTIONS},
+    partition_advice, paths,
+    phrases::{PhraseMatcher, PHRASE_POS},
+    pos_histogram,
+    posgrams::{self, POSGRAM_POS},
+    progress::{
+        report_metric_progress, AnalysisComplete, AnalysisProgress, FileCounted, MetricProgress,
+        ProgressBatch, ProgressCadence, ProgressPhase, ProgressReporter, TerminalGuard,
+        TokenEstimate,
+    },
+    sampling, seed,
+    segment_watchdog::{SegmentWatchdog, Segmented},
+    spectrum::simple_good_turing,
+    structural::{self, NoiseSequence},
+    tag_confidence,
+    targets::{self, TargetWords, ABSENT_POS},
+    text::{self, TextUnits},
+    token_policy::{self, TokenPolicyCounts, PUNCTUATION_POS},
+    word_analyzer::{AnalyzerConfig, CorpusWordAnalyzer, PartLengthMismatch},
+    word_entry::{SurfaceVariant, WordEntry, OTHER_SURFACE_FORM},
 };
-use tauri::Emitter;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-/// 进度事件结构体
-#[derive(serde::Serialize, Clone)]
-pub struct ProgressEvent {
-    pub current: usize,
-    pub total: usize,
-    pub file: String,
+/// 分析结果：词表条目与元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisResult {
+    pub entries: Vec<WordEntry>,
+    pub metadata: CorpusMetadata,
+    /// 各部分词频，仅在开启 keep_parts 时保留，供会话合并
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<PartCounts>,
+    /// 本次分析的计数，供 recompute_metrics 复用；不写入会话文件
+    #[serde(skip)]
+    pub counts: Option<Arc<CorpusCounter>>,
 }
 
-/// 处理单个文本文件，返回 (词, 词性) 二元组
-fn process_file(nlp: &LtpNlp, file_path: &str) -> Vec<(String, String)> {
-    let content = fs::read_to_string(file_path).unwrap_or_default();
-    nlp.segment_pos(&content)
+/// 一个条目的详情：条目本身，以及频率最高的部分与该部分的来源文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryDetail {
+    pub entry: WordEntry,
+    pub max_part: Option<PartInfo>,
+}
+
+/// 分析结果摘要：start_analysis 只返回摘要，条目留在后端按页取用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    /// 同 metadata.run_id
+    pub run_id: Option<String>,
+    pub types: usize,
+    pub tokens: u64,
+    pub duration_ms: u64,
+    /// 读取失败的文件（"路径: 原因"），同 metadata.file_errors
+    pub errors: Vec<String>,
+    /// 速览模式截断了词表，见 metadata.truncation
+    #[serde(default)]
+    pub truncated: bool,
+    /// 未扣除任何词元类别的词数，同 metadata.total_raw_tokens；tokens 为扣除后的分母
+    #[serde(default)]
+    pub raw_tokens: u64,
+    /// 语料部分数
+    #[serde(default)]
+    pub parts: usize,
+    /// 句数与段数，同 metadata.sentences / metadata.paragraphs
+    #[serde(default)]
+    pub sentences: Option<u64>,
+    #[serde(default)]
+    pub paragraphs: Option<u64>,
+}
+
+/// 语料规模：词数、词型数与各部分的词数；tokens 即分布指标与标准化频率的分母，
+/// 与分母策略（metadata.size_exclusions）一致
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorpusTotals {
+    /// 分词后的全部词元数，未扣除任何类别
+    pub raw_tokens: u64,
+    /// 扣除 size_exclusions 后的词数，即 metadata.total_tokens
+    pub tokens: u64,
+    /// 词表中词条的频次之和（不含短语、词性序列与未出现的目标词）；未启用过滤与最低频次时等于 tokens
+    pub listed_tokens: f64,
+    pub types: usize,
+    pub parts: usize,
+    pub size_exclusions: Vec<String>,
+    /// 各部分的词数，下标对应 metadata.parts
+    pub by_part: Vec<PartTotals>,
+    /// 语料的字符构成，同 metadata.scripts
+    #[serde(default)]
+    pub scripts: ScriptHistogram,
+    /// 句数与段数，同 metadata.sentences / metadata.paragraphs
+    #[serde(default)]
+    pub sentences: Option<u64>,
+    #[serde(default)]
+    pub paragraphs: Option<u64>,
+}
+
+/// 一个语料部分的词数，取自 PartInfo
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartTotals {
+    pub index: usize,
+    pub source: String,
+    pub sub_index: Option<usize>,
+    pub tokens: u64,
+    pub raw_tokens: u64,
+}
+
+/// start_analysis 的返回值：默认为摘要，return_entries 时为完整结果（过渡期兼容旧调用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AnalysisResponse {
+    Summary(AnalysisSummary),
+    Full(Box<AnalysisResult>),
+}
+
+impl AnalysisResult {
+    /// 结果摘要；duration_ms 为调用方测得的分析耗时
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::{
+    ///     corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    /// };
+    ///
+    /// let texts = ["甲 乙 乙", "甲"].map(String::from);
+    /// let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    /// let summary = result.summary(12);
+    /// assert_eq!((summary.types, summary.tokens, summary.duration_ms), (2, 4, 12));
+    /// assert_eq!((summary.raw_tokens, summary.parts), (4, 2));
+    /// assert!(summary.run_id.is_none() && summary.errors.is_empty());
+    /// ```
+    pub fn summary(&self, duration_ms: u64) -> AnalysisSummary {
+        AnalysisSummary {
+            run_id: self.metadata.run_id.clone(),
+            types: self.metadata.total_types,
+            tokens: self.metadata.total_tokens,
+            duration_ms,
+            errors: self.metadata.file_errors.clone(),
+            truncated: self.metadata.truncation.is_some(),
+            raw_tokens: self.metadata.total_raw_tokens,
+            parts: self.metadata.parts.len(),
+            sentences: self.metadata.sentences,
+            paragraphs: self.metadata.paragraphs,
+        }
+    }
+
+    /// 语料规模与各部分的词数
+    pub fn totals(&self) -> CorpusTotals {
+        let metadata = &self.metadata;
+        CorpusTotals {
+            raw_tokens: metadata.total_raw_tokens,
+            tokens: metadata.total_tokens,
+            listed_tokens: self
+                .entries
+                .iter()
+                .filter(|e| ![PHRASE_POS, POSGRAM_POS, ABSENT_POS].contains(&e.pos.as_str()))
+                .map(|e| e.frequency)
+                .sum(),
+            types: metadata.total_types,
+            parts: metadata.parts.len(),
+            size_exclusions: metadata.size_exclusions.clone(),
+            by_part: metadata
+                .parts
+                .iter()
+                .map(|part| PartTotals {
+                    index: part.index,
+                    source: part.source.clone(),
+                    sub_index: part.sub_index,
+                    tokens: part.tokens,
+                    raw_tokens: part.raw_tokens,
+                })
+                .collect(),
+            scripts: metadata.scripts,
+            sentences: metadata.sentences,
+            paragraphs: metadata.paragraphs,
+        }
+    }
+
+    /// 按 (词, 词性) 查找条目详情
+    pub fn entry_detail(&self, word: &str, pos: &str) -> Option<EntryDetail> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.word == word && e.pos == pos)?;
+        Some(EntryDetail {
+            entry: entry.clone(),
+            max_part: entry
+                .max_part_index
+                .and_then(|part| self.metadata.parts.get(part))
+                .cloned(),
+        })
+    }
+
+    /// 某个词在各部分的频次，下标即 metadata.parts 中的部分序号；未保留各部分词频时为 None
+    pub fn part_frequencies(&self, word: &str, pos: &str) -> Option<Vec<f64>> {
+        if self.parts.is_empty() || self.parts.len() != self.metadata.parts.len() {
+            return None;
+        }
+        let frequencies = self
+            .parts
+            .iter()
+            .map(|part| {
+                let found = if pos == PHRASE_POS || pos == POSGRAM_POS {
+                    let counts = if pos == PHRASE_POS {
+                        &part.phrase_counts
+                    } else {
+                        &part.posgram_counts
+                    };
+                    counts.iter().find(|(p, _)| p == word).map(|(_, v)| *v)
+                } else {
+                    part.counts
+                        .iter()
+                        .find(|(w, p, _)| w == word && p == pos)
+                        .map(|(_, _, v)| *v)
+                };
+                found.unwrap_or(0.0)
+            })
+            .collect();
+        Some(frequencies)
+    }
+
+    /// 依次访问各部分中每个词的频次 (部分序号, 词, 词性, 频次)，不含短语；
+    /// 优先使用本次分析的计数，其次使用 keep_parts 保留的各部分词频，两者都没有时返回 false
+    pub fn for_each_part_count(&self, mut f: impl FnMut(usize, &str, &str, f64)) -> bool {
+        if let Some(counts) = &self.counts {
+            for ((word, pos), freqs) in &counts.vocab_map {
+                for (i, &v) in freqs.iter().enumerate() {
+                    if v > 0.0 {
+                        f(i, word, pos, v);
+                    }
+                }
+            }
+            return true;
+        }
+        if self.parts.is_empty() || self.parts.len() != self.metadata.parts.len() {
+            return false;
+        }
+        for (i, part) in self.parts.iter().enumerate() {
+            for (word, pos, v) in &part.counts {
+                f(i, word, pos, *v);
+            }
+        }
+        true
+    }
+}
+
+/// 单个语料部分的词频统计：(词, 词性, 频次) 与部分总词数
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PartCounts {
+    pub counts: Vec<(String, String, f64)>,
+    /// 部分词数：原始词数扣除 options.denominator 等选项指定的词元，即分布指标所用的分母
+    pub size: f64,
+    /// 原始词数：分词所得的全部词元，含此后被移除的结构性噪声与空白词元
+    #[serde(default)]
+    pub raw_size: f64,
+    /// 文件内滑窗 (词, 词性, DP, Juilland D)；未开启滑窗分析时为空
+    #[serde(default)]
+    pub window_dispersion: Vec<(String, String, f64, f64)>,
+    /// 按 token_policy 移除的词元数
+    #[serde(default)]
+    pub removed: TokenPolicyCounts,
+    /// 引号内的 (词, 词性, 频次)；未开启 protect_quotes 时为空
+    #[serde(default)]
+    pub quoted_counts: Vec<(String, String, f64)>,
+    /// 停用词/词性过滤统计
+    #[serde(default)]
+    pub filtered: FilterCounts,
+    /// 被停用词/词性过滤移出词表的 (原因, 词, 词性, 频次)；结构性噪声为 (原因, 序列, 规则, 出现次数)
+    #[serde(default)]
+    pub filtered_counts: Vec<(FilterReason, String, String, f64)>,
+    /// 短语表中各短语的出现次数；未设置短语表时为空
+    #[serde(default)]
+    pub phrase_counts: Vec<(String, f64)>,
+    /// 各词性序列的出现次数；未开启 posgrams 时为空
+    #[serde(default)]
+    pub posgram_counts: Vec<(String, f64)>,
+    /// 各词性的词元数，含标点与被过滤的词元
+    #[serde(default)]
+    pub pos_counts: Vec<(String, u64)>,
+    /// (词, 词性, 首次位置, 末次位置)，位置为部分内此前计入部分词数（size）的词元数，被过滤或扣除的词元不占位置，
+    /// 因而与 size 同一尺度、可按 size 累加跨部分；未开启 track_positions 时为空
+    #[serde(default)]
+    pub positions: Vec<(String, String, f64, f64)>,
+    /// 拉丁字母词元的 (词, 词性, 折叠前的写法, 频次)；latin_case 不为 fold 时为空
+    #[serde(default)]
+    pub case_variants: Vec<(String, String, String, f64)>,
+    /// 组成本部分的原始句子序号，见 PartInfo::units；未合并时为空
+    #[serde(default)]
+    pub units: Vec<usize>,
+}
+
+/// 分析中止的原因
+#[derive(Debug, Clone)]
+pub enum AnalysisAbort {
+    /// 估算内存超过 memory_limit_mb
+    MemoryLimit(MemoryLimitExceeded),
+    /// 逐句切分的部分过多
+    DenseParts(DensePartsExceeded),
+    /// 经 ProgressReporter::cancelled 取消
+    Cancelled,
+    /// 严格模式下有文件出错：各文件的 "路径: 原因" 与被放弃的分词批次
+    FileErrors(Vec<String>),
+    /// 条目的词频向量与部分列表不符（计数有误，不应出现）："词/词性" 与长度
+    PartLengths(String, PartLengthMismatch),
+}
+
+impl fmt::Display for AnalysisAbort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnalysisAbort::MemoryLimit(e) => e.fmt(f),
+            AnalysisAbort::DenseParts(e) => e.fmt(f),
+            AnalysisAbort::Cancelled => f.write_str("分析已取消"),
+            AnalysisAbort::FileErrors(problems) => write!(
+                f,
+                "严格模式下有 {} 个问题，分析已中止: {}",
+                problems.len(),
+                problems.join("; ")
+            ),
+            AnalysisAbort::PartLengths(entry, e) => {
+                write!(f, "条目 {entry} 的{e}，计数有误，分析已中止")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnalysisAbort {}
+
+impl From<MemoryLimitExceeded> for AnalysisAbort {
+    fn from(e: MemoryLimitExceeded) -> Self {
+        AnalysisAbort::MemoryLimit(e)
+    }
+}
+
+impl From<DensePartsExceeded> for AnalysisAbort {
+    fn from(e: DensePartsExceeded) -> Self {
+        AnalysisAbort::DenseParts(e)
+    }
+}
+
+/// 分词控制：批与批之间检查取消，设置 segment_timeout_ms 时经看门狗分词；只用于 analyze_corpus
+struct SegmentControl<'c, 'scope, 'env> {
+    reporter: &'c dyn ProgressReporter,
+    watchdog: Option<SegmentWatchdog<'scope, 'env>>,
+    /// 被放弃的批次，记入 metadata.warnings
+    skipped: RefCell<Vec<String>>,
+    /// 本次读取的各文件的字符构成；两遍计数时第二遍覆盖第一遍
+    scripts: RefCell<HashMap<String, ScriptHistogram>>,
+    /// 本次读取的各文件的句数与段数，及是否改用空白分词；同 scripts
+    units: RefCell<HashMap<String, (TextUnits, bool)>>,
+}
+
+impl SegmentControl<'_, '_, '_> {
+    /// 按 batch_size 句一批分词（0 表示整段文本一批）；已取消时不再分词，返回已得的词元
+    fn segment(&self, nlp: &dyn Tokenizer, text: &str, batch_size: usize, source: &Path) -> Tokens {
+        let sentences = if batch_size == 0 {
+            vec![text]
+        } else {
+            text::split_text_sentences(text)
+        };
+        let mut word_pos = Vec::new();
+        let cancelled = || self.reporter.cancelled();
+        for (k, batch) in sentences.chunks(batch_size.max(1)).enumerate() {
+            if cancelled() {
+                break;
+            }
+            let Some(watchdog) = &self.watchdog else {
+                word_pos.extend(nlp.segment_pos_batch(batch).into_iter().flatten());
+                continue;
+            };
+            let skipped = match watchdog.segment_pos_batch(batch, &cancelled) {
+                Segmented::Done(sentences) => {
+                    word_pos.extend(sentences.into_iter().flatten());
+                    continue;
+                }
+                Segmented::Cancelled => break,
+                Segmented::TimedOut => {
+                    format!("超过 {} ms", watchdog.timeout().as_millis())
+                }
+                Segmented::Failed => "出错".to_string(),
+            };
+            let note = format!(
+                "{}: 第 {} 批（{} 句）分词{skipped}，已跳过",
+                paths::display(source),
+                k + 1,
+                batch.len()
+            );
+            // 两遍计数时同一批会遇到两次
+            let mut notes = self.skipped.borrow_mut();
+            if !notes.contains(&note) {
+                notes.push(note);
+            }
+        }
+        word_pos
+    }
+}
+
+/// 处理一个任务：文件本身及按近重复策略并入的文件，词元流与被移除的结构性噪声依次拼接。
+/// 无法读取的文件按空文本计，"路径: 原因" 记入 errors，不影响同一任务中的其他文件
+fn process_task(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    whitespace: bool,
+    merged: &[Task],
+    options: &AnalysisOptions,
+    control: Option<&SegmentControl>,
+    errors: &mut Vec<String>,
+) -> (Tokens, Vec<NoiseSequence>) {
+    let mut word_pos = Tokens::new();
+    let mut noise = Vec::new();
+    let files = std::iter::once((file_path, whitespace))
+        .chain(merged.iter().map(|(path, ws)| (path.as_path(), *ws)));
+    for (path, whitespace) in files {
+        match process_file(nlp, path, whitespace, options, control) {
+            Ok((more, more_noise)) => {
+                word_pos.extend(more);
+                noise.extend(more_noise);
+            }
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+    (word_pos, noise)
+}
+
+/// 处理单个文本文件，返回 (词, 词性) 二元组；whitespace 为真时改用空白分词，不经分词控制
+fn process_file(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    whitespace: bool,
+    options: &AnalysisOptions,
+    control: Option<&SegmentControl>,
+) -> io::Result<(Tokens, Vec<NoiseSequence>)> {
+    let content = fs::read_to_string(file_path)?;
+    if let Some(control) = control {
+        control
+            .scripts
+            .borrow_mut()
+            .insert(paths::display(file_path), ScriptHistogram::of(&content));
+        control.units.borrow_mut().insert(
+            paths::display(file_path),
+            (TextUnits::of(&content), whitespace),
+        );
+    }
+    if whitespace {
+        return Ok(segment(&WhitespaceTokenizer, &content, options));
+    }
+    match control {
+        Some(control) => {
+            let word_pos = control.segment(nlp, &content, options.segment_batch_size, file_path);
+            Ok(strip_noise(&content, word_pos, options))
+        }
+        None => Ok(segment(nlp, &content, options)),
+    }
+}
+
+/// 分词（按 segment_batch_size 分批）；开启 structural_noise 时随即移除结构性噪声，行首信息只在此时可由原文还原
+fn segment(
+    nlp: &dyn Tokenizer,
+    text: &str,
+    options: &AnalysisOptions,
+) -> (Tokens, Vec<NoiseSequence>) {
+    let word_pos = if options.segment_batch_size == 0 {
+        nlp.segment_pos(text)
+    } else {
+        text::split_text_sentences(text)
+            .chunks(options.segment_batch_size)
+            .flat_map(|batch| nlp.segment_pos_batch(batch))
+            .flatten()
+            .collect()
+    };
+    strip_noise(text, word_pos, options)
+}
+
+/// 拼合分词器拆开的 emoji（见 token_policy::join_emoji），开启 structural_noise 时再移除结构性噪声
+fn strip_noise(
+    text: &str,
+    word_pos: Tokens,
+    options: &AnalysisOptions,
+) -> (Tokens, Vec<NoiseSequence>) {
+    let word_pos = token_policy::join_emoji(word_pos);
+    if options.structural_noise {
+        structural::strip(text, word_pos)
+    } else {
+        (word_pos, Vec::new())
+    }
+}
+
+/// 近重复检测：按策略去掉或合并文件，返回保留的任务、各任务并入的文件与检测到的文件对
+fn resolve_duplicates(
+    tasks: Vec<Task>,
+    options: &NearDuplicateOptions,
+) -> (Vec<Task>, Vec<Vec<Task>>, Vec<DuplicatePair>) {
+    let files: Vec<PathBuf> = tasks.iter().map(|(path, _)| path.clone()).collect();
+    let resolution = near_duplicates::resolve(&files, options);
+    let (kept, merged) = resolution
+        .groups
+        .iter()
+        .map(|group| {
+            let members = group[1..].iter().map(|&i| tasks[i].clone()).collect();
+            (tasks[group[0]].clone(), members)
+        })
+        .unzip();
+    (kept, merged, resolution.pairs)
+}
+
+/// 语言筛查：返回待分析的 (文件, 是否改用空白分词) 与命中筛查的文件；跳过的文件不形成语料部分
+fn screen_files(
+    file_paths: &[PathBuf],
+    options: &AnalysisOptions,
+) -> (Vec<(PathBuf, bool)>, Vec<ScreenedFile>) {
+    let mut tasks = Vec::with_capacity(file_paths.len());
+    let mut screened = Vec::new();
+    for path in file_paths {
+        match options.language_screen.screen(path) {
+            Some(file) => {
+                if file.action == ScreenMode::Whitespace {
+                    tasks.push((path.clone(), true));
+                }
+                screened.push(file);
+            }
+            None => tasks.push((path.clone(), false)),
+        }
+    }
+    (tasks, screened)
+}
+
+/// (词, 词性) 词元流
+type Tokens = Vec<(String, String)>;
+
+/// 待分析的文件及是否改用空白分词
+type Task = (PathBuf, bool);
+
+/// 按切分方式把一段词元流分为语料部分：(块序号, 组成的句子序号, 词元)；逐句切分时空文本不形成部分
+///
+/// 开启 merge_small_parts 时相邻的句子按分词所得的词元数合并（见 partition_advice::merge_groups），
+/// 块序号取首句的序号
+fn partition(
+    word_pos: Tokens,
+    options: &AnalysisOptions,
+) -> Vec<(Option<usize>, Vec<usize>, Tokens)> {
+    match options.partition {
+        PartitionMode::PerFile => vec![(None, Vec::new(), word_pos)],
+        PartitionMode::PerSentence if options.merge_small_parts => {
+            let sentences: Vec<&[(String, String)]> = text::split_sentences(&word_pos).collect();
+            let sizes: Vec<usize> = sentences.iter().map(|s| s.len()).collect();
+            partition_advice::merge_groups(&sizes, options.min_part_tokens)
+                .into_iter()
+                .map(|group| {
+                    let tokens = sentences[group.clone()].concat();
+                    (Some(group.start), group.collect(), tokens)
+                })
+                .collect()
+        }
+        PartitionMode::PerSentence => text::split_sentences(&word_pos)
+            .enumerate()
+            .map(|(i, sentence)| (Some(i), Vec::new(), sentence.to_vec()))
+            .collect(),
+    }
+}
+
+/// 统计 partition 所得的一个部分
+fn count_unit(
+    (units, tokens): (Vec<usize>, Tokens),
+    noise: &[NoiseSequence],
+    options: &AnalysisOptions,
+) -> PartCounts {
+    PartCounts {
+        units,
+        ..count_part(tokens, noise, options)
+    }
+}
+
+/// 统计单个部分的词频；开启滑窗分析时一并计算文件内分布度
+///
+/// 停用词/词性过滤将词元移出词表，是否同时扣除部分词数见 options.denominator；开启 protect_quotes 时引号内的词元不受过滤。
+/// 同时记下未经任何扣除的原始词数（含结构性噪声与清理时丢弃的词元）
+///
+/// 词元清理与拉丁字母大小写折叠最先进行，滑窗、引号与短语匹配都在此后的词元流上计算。
+/// noise 为分词时已移除的结构性噪声，只记入过滤统计；逐句切分时记入文件的第一个部分
+fn count_part(
+    word_pos: Vec<(String, String)>,
+    noise: &[NoiseSequence],
+    options: &AnalysisOptions,
+) -> PartCounts {
+    let noise_tokens: usize = noise.iter().map(|sequence| sequence.tokens).sum();
+    let raw_size = (word_pos.len() + noise_tokens) as f64;
+    let mut tally = PartTally::new(options);
+    let (word_pos, mut surfaces) = normalize_tokens(word_pos, options, &mut tally.removed);
+    let window_dispersion = window_dispersion(&word_pos, options, tally.targets.as_ref());
+    let phrase_counts = phrase_counts(&word_pos, options);
+    let posgram_counts = options
+        .posgrams
+        .map(|pg| posgrams::count(&word_pos, pg.n).into_iter().collect())
+        .unwrap_or_default();
+    let quoted = if options.protect_quotes {
+        filter::quoted_mask(&word_pos)
+    } else {
+        Vec::new()
+    };
+    tally.add_noise(noise);
+    for (i, (w, p)) in word_pos.into_iter().enumerate() {
+        let in_quote = quoted.get(i).copied().unwrap_or(false);
+        let surface = surfaces.get_mut(i).and_then(Option::take);
+        tally.add_token(w, p, in_quote, surface);
+    }
+    if !options.denominator.structural_noise {
+        tally.size += noise_tokens as f64;
+    }
+    PartCounts {
+        raw_size,
+        window_dispersion,
+        phrase_counts,
+        posgram_counts,
+        ..tally.finish()
+    }
+}
+
+/// 按词元策略清理词元，再折叠拉丁字母大小写；返回折叠后的词元流与各词元折叠前的写法
+fn normalize_tokens(
+    word_pos: Tokens,
+    options: &AnalysisOptions,
+    removed: &mut TokenPolicyCounts,
+) -> (Tokens, Vec<Option<String>>) {
+    let word_pos = if options.token_policy.sanitize {
+        token_policy::sanitize(word_pos, removed)
+    } else {
+        word_pos
+    };
+    token_policy::fold_latin_case(word_pos, options.latin_case)
+}
+
+/// 文件内滑窗 (词, 词性, DP, Juilland D)，目标词模式下只保留目标词；未开启滑窗分析时为空
+fn window_dispersion(
+    word_pos: &[(String, String)],
+    options: &AnalysisOptions,
+    targets: Option<&TargetWords>,
+) -> Vec<(String, String, f64, f64)> {
+    options
+        .burstiness_window
+        .map(|window| {
+            burstiness::window_dispersion(word_pos, window)
+                .into_iter()
+                .filter(|((w, _), _)| targets.is_none_or(|t| t.contains(w)))
+                .map(|((w, p), (dp, d))| (w, p, dp, d))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 短语表中各短语的出现次数；短语在完整的词元流上匹配，不受词元策略与过滤影响
+fn phrase_counts(word_pos: &[(String, String)], options: &AnalysisOptions) -> Vec<(String, f64)> {
+    if options.phrases.is_empty() {
+        return Vec::new();
+    }
+    PhraseMatcher::new(&options.phrases)
+        .count(word_pos)
+        .into_iter()
+        .collect()
+}
+
+/// count_part 逐词元累计的计数：部分词数、词表频次，以及过滤统计、引号内频次、出现位置与写法
+struct PartTally<'a> {
+    options: &'a AnalysisOptions,
+    token_filter: TokenFilter<'a>,
+    targets: Option<TargetWords>,
+    size: f64,
+    removed: TokenPolicyCounts,
+    filtered: FilterCounts,
+    counts: HashMap<(String, String), f64>,
+    quoted_counts: HashMap<(String, String), f64>,
+    filtered_counts: HashMap<(FilterReason, String, String), f64>,
+    positions: HashMap<(String, String), (f64, f64)>,
+    case_variants: HashMap<(String, String, String), f64>,
+    pos_counts: BTreeMap<String, u64>,
+}
+
+impl<'a> PartTally<'a> {
+    fn new(options: &'a AnalysisOptions) -> Self {
+        Self {
+            options,
+            token_filter: TokenFilter::new(options),
+            targets: TargetWords::new(options),
+            size: 0.0,
+            removed: TokenPolicyCounts::default(),
+            filtered: FilterCounts::default(),
+            counts: HashMap::new(),
+            quoted_counts: HashMap::new(),
+            filtered_counts: HashMap::new(),
+            positions: HashMap::new(),
+            case_variants: HashMap::new(),
+            pos_counts: BTreeMap::new(),
+        }
+    }
+
+    /// 记入分词时已移除的结构性噪声：(原因, 序列, 规则, 出现次数)
+    fn add_noise(&mut self, noise: &[NoiseSequence]) {
+        for sequence in noise {
+            self.filtered.structural_noise += sequence.tokens as u64;
+            let key = (
+                FilterReason::StructuralNoise,
+                sequence.text.clone(),
+                sequence.rule.to_string(),
+            );
+            *self.filtered_counts.entry(key).or_insert(0.0) += 1.0;
+        }
+    }
+
+    /// 记入一个词元：先按词元策略计入部分词数，再经停用词/词性过滤，余下的词元计入词表
+    fn add_token(&mut self, w: String, p: String, in_quote: bool, surface: Option<String>) {
+        *self.pos_counts.entry(p.clone()).or_insert(0) += 1;
+        // 位置取此前计入部分词数的词元数，与部分词数同一尺度，不是词元流中的序号
+        let offset = self.size;
+        if !self.count_size(&w, &p) || self.filter_out(&w, &p, in_quote) {
+            return;
+        }
+        // 目标词模式下其余词元只计入部分词数
+        if self.targets.as_ref().is_none_or(|t| t.contains(&w)) {
+            self.record(w, p, in_quote, offset, surface);
+        }
+    }
+
+    /// 按词元策略计入部分词数；词元被策略移出词表时返回 false
+    fn count_size(&mut self, w: &str, p: &str) -> bool {
+        let policy = self.options.token_policy;
+        if p == PUNCTUATION_POS {
+            if policy.exclude_punctuation_from_sizes {
+                self.removed.punctuation_from_sizes += 1;
+            } else {
+                self.size += 1.0;
+            }
+            if policy.exclude_punctuation_from_list {
+                self.removed.punctuation_from_list += 1;
+                return false;
+            }
+        } else if policy.exclude_symbols && token_policy::is_symbol_token(w) {
+            self.removed.symbols += 1;
+            if !self.options.denominator.symbols {
+                self.size += 1.0;
+            }
+            return false;
+        } else {
+            self.size += 1.0;
+        }
+        true
+    }
+
+    /// 停用词/词性过滤，词元被移出词表时返回 true；引号内的词元只记为豁免
+    fn filter_out(&mut self, w: &str, p: &str, in_quote: bool) -> bool {
+        let Some(reason) = self.token_filter.check(w, p) else {
+            return false;
+        };
+        if in_quote {
+            self.filtered.quote_exempted += 1;
+            return false;
+        }
+        match reason {
+            FilterReason::Stopword => self.filtered.stopwords += 1,
+            FilterReason::ExcludedPos => self.filtered.excluded_pos += 1,
+            // 结构性噪声在分词时已移出词元流
+            FilterReason::StructuralNoise => {}
+        }
+        let already_excluded =
+            p == PUNCTUATION_POS && self.options.token_policy.exclude_punctuation_from_sizes;
+        if self.options.denominator.excludes(reason) && !already_excluded {
+            self.size -= 1.0;
+        }
+        *self
+            .filtered_counts
+            .entry((reason, w.to_string(), p.to_string()))
+            .or_insert(0.0) += 1.0;
+        true
+    }
+
+    /// 计入词表，并记下引号内频次、首末位置与折叠前的写法
+    fn record(
+        &mut self,
+        w: String,
+        p: String,
+        in_quote: bool,
+        offset: f64,
+        surface: Option<String>,
+    ) {
+        if in_quote {
+            *self
+                .quoted_counts
+                .entry((w.clone(), p.clone()))
+                .or_insert(0.0) += 1.0;
+        }
+        if self.options.track_positions {
+            self.positions
+                .entry((w.clone(), p.clone()))
+                .or_insert((offset, offset))
+                .1 = offset;
+        }
+        if let Some(surface) = surface {
+            *self
+                .case_variants
+                .entry((w.clone(), p.clone(), surface))
+                .or_insert(0.0) += 1.0;
+        }
+        *self.counts.entry((w, p)).or_insert(0.0) += 1.0;
+    }
+
+    /// 逐词元累计的部分；原始词数、滑窗、短语与词性序列由 count_part 补上
+    fn finish(self) -> PartCounts {
+        PartCounts {
+            counts: self
+                .counts
+                .into_iter()
+                .map(|((w, p), v)| (w, p, v))
+                .collect(),
+            size: self.size,
+            removed: self.removed,
+            quoted_counts: self
+                .quoted_counts
+                .into_iter()
+                .map(|((w, p), v)| (w, p, v))
+                .collect(),
+            filtered: self.filtered,
+            filtered_counts: self
+                .filtered_counts
+                .into_iter()
+                .map(|((reason, w, p), v)| (reason, w, p, v))
+                .collect(),
+            pos_counts: self.pos_counts.into_iter().collect(),
+            positions: self
+                .positions
+                .into_iter()
+                .map(|((w, p), (first, last))| (w, p, first, last))
+                .collect(),
+            case_variants: self
+                .case_variants
+                .into_iter()
+                .map(|((w, p, surface), v)| (w, p, surface, v))
+                .collect(),
+            ..PartCounts::default()
+        }
+    }
 }
 
 /// 主流程：批量处理文件，统计词频，计算分布指标
+///
+/// 无法读取的文件按空部分计入 metadata.file_errors；取消时保留检查点以便续跑，其他中止删除检查点
 pub fn analyze_corpus(
-    nlp: &LtpNlp,
-    file_paths: &[String],
-    app_handle: Option<&tauri::AppHandle>,
-) -> Vec<(String, String, DispersionMetrics)> {
-    let mut vocab_map = std::collections::HashMap::<(String, String), Vec<f64>>::new();
-    let mut part_sizes = Vec::new();
-
-    // 1. 逐文件分词与统计
-    let total_files = file_paths.len();
-    for (i, file) in file_paths.iter().enumerate() {
-        let word_pos = process_file(nlp, file);
-        if let Some(handle) = app_handle {
-            let progress = ProgressEvent {
-                current: i + 1,
-                total: total_files,
-                file: file.to_string(),
-            };
-            handle.emit("progress", progress).ok();
+    nlp: &dyn Tokenizer,
+    file_paths: &[PathBuf],
+    options: &AnalysisOptions,
+    checkpoint: Option<&Checkpoint>,
+    reporter: &dyn ProgressReporter,
+) -> Result<AnalysisResult, AnalysisAbort> {
+    // 被看门狗放弃的分词调用在此作用域中运行到结束
+    thread::scope(|scope| {
+        let control = SegmentControl {
+            reporter,
+            watchdog: options
+                .segment_timeout_ms
+                .map(|ms| SegmentWatchdog::new(scope, nlp, Duration::from_millis(ms))),
+            skipped: RefCell::new(Vec::new()),
+            scripts: RefCell::new(HashMap::new()),
+            units: RefCell::new(HashMap::new()),
+        };
+        analyze_corpus_with(nlp, file_paths, options, checkpoint, &control)
+    })
+}
+
+fn analyze_corpus_with(
+    nlp: &dyn Tokenizer,
+    file_paths: &[PathBuf],
+    options: &AnalysisOptions,
+    checkpoint: Option<&Checkpoint>,
+    control: &SegmentControl,
+) -> Result<AnalysisResult, AnalysisAbort> {
+    let reporter = control.reporter;
+    let started = Instant::now();
+    let terminal = TerminalGuard::new(reporter);
+    let mut metadata = CorpusMetadata::new(nlp.engine(), options);
+    metadata.warnings = options.warnings();
+    // 试点抽样：此后的筛查、进度与语料部分都只涉及样本
+    let sampled;
+    let file_paths = match &options.sampling {
+        Some(sampling) => {
+            let seed = seed::derive_seed(metadata.seed, seed::SAMPLING);
+            let (files, sample) = sampling::sample_files(file_paths, sampling, seed);
+            metadata.sample = Some(sample);
+            sampled = files;
+            &sampled[..]
+        }
+        None => file_paths,
+    };
+    let (tasks, screened) = screen_files(file_paths, options);
+    let (tasks, merged, near_duplicates) = match &options.near_duplicates {
+        Some(near) => resolve_duplicates(tasks, near),
+        None => {
+            let merged = vec![Vec::new(); tasks.len()];
+            (tasks, merged, Vec::new())
+        }
+    };
+    let total_files = tasks.len();
+    let mut estimate = TokenEstimate::for_files(tasks.iter().map(|(path, _)| path.as_path()));
+    let per_file = options.partition == PartitionMode::PerFile;
+    // 未固定种子时每次抽出的样本不同，已完成的部分不可复用
+    let checkpoint =
+        checkpoint.filter(|_| per_file && (options.sampling.is_none() || options.seed.is_some()));
+    let mut counter = CorpusCounter::new(if per_file { total_files } else { 0 });
+    let memory_limit = options.memory_limit_mb.map(|mb| mb << 20);
+    let two_pass_min = options.two_pass_min_frequency();
+    // 两遍计数时进度按两遍合计
+    let total_steps = total_files * if two_pass_min.is_some() { 2 } else { 1 };
+    let offset = total_steps - total_files;
+    // 文件很多时进度事件合并上报，间隔接近转发的限速间隔
+    let mut cadence = ProgressCadence::new(total_files, total_steps, event_forwarder::MIN_INTERVAL);
+
+    // 1. 载入检查点中已完成的部分
+    let mut resumed = checkpoint.map(Checkpoint::load).unwrap_or_default();
+    resumed.truncate(total_files);
+    let resumed_count = resumed.len();
+
+    // 2. 两遍计数的第一遍：由总频次确定保留的词表
+    if let Some(min_frequency) = two_pass_min {
+        let fresh = tasks
+            .iter()
+            .enumerate()
+            .skip(resumed_count)
+            .take_while(|_| !reporter.cancelled())
+            .map(|(i, (file, ws))| {
+                // 读取失败在第二遍记入 file_errors
+                let (word_pos, noise) = process_task(
+                    nlp,
+                    file,
+                    *ws,
+                    &merged[i],
+                    options,
+                    Some(control),
+                    &mut Vec::new(),
+                );
+                let part = count_part(word_pos, &noise, options);
+                if let Some(batch) = cadence.files_done(1, i + 1, started.elapsed()) {
+                    reporter.progress(&counter.progress(
+                        i + 1,
+                        total_steps,
+                        file,
+                        &estimate,
+                        batch,
+                    ));
+                }
+                part
+            });
+        let (survivors, removed) =
+            surviving_vocab(resumed.iter().cloned().chain(fresh), min_frequency);
+        if reporter.cancelled() {
+            return Err(cancel(checkpoint, resumed_count, &[], terminal));
+        }
+        counter.restrict_to(survivors, removed, options);
+    }
+
+    for (i, (part, (file, _))) in resumed.iter().zip(&tasks).enumerate() {
+        counter.add_part(paths::display(file), None, part);
+        estimate.file_done(i);
+        report_counted(reporter, &counter, part, options);
+    }
+    let mut kept = if options.keep_parts {
+        resumed
+    } else {
+        Vec::new()
+    };
+    if let Err(exceeded) = counter.check_memory(memory_limit, resumed_count) {
+        return Err(abort(exceeded.into(), checkpoint, terminal));
+    }
+    if let Some(last) = resumed_count.checked_sub(1) {
+        let current = offset + resumed_count;
+        if let Some(batch) = cadence.files_done(resumed_count, current, started.elapsed()) {
+            let file = &tasks[last].0;
+            reporter.progress(&counter.progress(current, total_steps, file, &estimate, batch));
+        }
+    }
+
+    // 3. 逐文件分词，按切分方式形成语料部分
+    let checkpoint_every = options.checkpoint_every.filter(|&n| n > 0);
+    let mut pending = Vec::new();
+    let mut file_errors = Vec::new();
+    let mut sampler = example_sampler(options, metadata.seed);
+    let mut pairs = options.oov_pairs.map(PairCounter::new);
+    for (i, (file, whitespace)) in tasks.iter().enumerate().skip(resumed_count) {
+        if reporter.cancelled() {
+            return Err(cancel(checkpoint, i, &pending, terminal));
+        }
+        let (word_pos, noise) = process_task(
+            nlp,
+            file,
+            *whitespace,
+            &merged[i],
+            options,
+            Some(control),
+            &mut file_errors,
+        );
+        // 分词中途取消时该文件只分了一部分，不计入
+        if reporter.cancelled() {
+            return Err(cancel(checkpoint, i, &pending, terminal));
+        }
+        if let Some(sampler) = &mut sampler {
+            sampler.add_text(&word_pos);
+        }
+        if let Some(pairs) = &mut pairs {
+            pairs.add_text(&word_pos);
+        }
+        for (k, (sub_index, units, tokens)) in partition(word_pos, options).into_iter().enumerate()
+        {
+            let part = count_unit((units, tokens), if k == 0 { &noise } else { &[] }, options);
+            counter.add_part(paths::display(file), sub_index, &part);
+            report_counted(reporter, &counter, &part, options);
+            if options.keep_parts {
+                kept.push(part.clone());
+            }
+            if let (Some(cp), Some(every)) = (checkpoint, checkpoint_every) {
+                pending.push(part);
+                if pending.len() >= every {
+                    // 检查点写入失败不影响分析本身
+                    cp.save_chunk(i + 1 - pending.len(), &pending).ok();
+                    pending.clear();
+                }
+            }
+        }
+        if let Err(exceeded) = counter.check_memory(memory_limit, i + 1) {
+            return Err(abort(exceeded.into(), checkpoint, terminal));
+        }
+        if let Err(exceeded) = counter.check_dense(i + 1) {
+            return Err(abort(exceeded.into(), checkpoint, terminal));
         }
-        let mut local_counter = std::collections::HashMap::<(String, String), f64>::new();
-        for (w, p) in word_pos {
-            *local_counter.entry((w, p)).or_insert(0.0) += 1.0;
+        estimate.file_done(i);
+        let current = offset + i + 1;
+        if let Some(batch) = cadence.files_done(1, current, started.elapsed()) {
+            reporter.progress(&counter.progress(current, total_steps, file, &estimate, batch));
         }
+    }
 
-        // 统计当前文件词频并更新全局词频表
-        let idx = part_sizes.len();
-        let mut file_sum = 0.0;
-        for (k, v) in local_counter.iter() {
-            vocab_map
-                .entry(k.clone())
-                .or_insert_with(|| vec![0.0; file_paths.len()])[idx] = *v;
-            file_sum += v;
+    if options.strict {
+        let problems: Vec<String> = file_errors
+            .iter()
+            .chain(control.skipped.borrow().iter())
+            .cloned()
+            .collect();
+        if !problems.is_empty() {
+            return Err(abort(
+                AnalysisAbort::FileErrors(problems),
+                checkpoint,
+                terminal,
+            ));
         }
-        part_sizes.push(file_sum);
     }
+    if let Some(sampler) = sampler {
+        counter.examples = sampler.into_examples();
+    }
+    metadata.adjacent_singles = pairs.map(PairCounter::finish);
+    metadata.warnings.extend(control.skipped.take());
+    // 词型很多时计算指标耗时可观，作为第二个进度阶段上报；事件沿用文件阶段最后的计数信息
+    let last_file = tasks
+        .last()
+        .map_or(Path::new(""), |(path, _)| path.as_path());
+    let base = counter.progress(
+        total_steps,
+        total_steps,
+        last_file,
+        &estimate,
+        ProgressBatch {
+            files: 0,
+            files_per_second: None,
+        },
+    );
+    let result = report_metric_progress(reporter, &base, |progress| {
+        counter.into_result(metadata, Some(progress))
+    });
+    let mut result = match result {
+        Ok(result) => result,
+        Err(e) => return Err(abort(e, checkpoint, terminal)),
+    };
+
+    // 文件指纹在分析完成后采集，开启哈希时不拖慢进度反馈
+    result.metadata.files = tasks
+        .iter()
+        .map(|(path, _)| FileInfo::collect(path, options.hash_files))
+        .collect();
+    result.metadata.model_files = nlp
+        .model_files()
+        .iter()
+        .map(|path| FileInfo::collect(Path::new(path), options.hash_files))
+        .collect();
+    record_scripts(&mut result.metadata, control.scripts.take());
+    record_units(&mut result.metadata, control.units.take());
+    result.metadata.screened_files = screened;
+    result.metadata.near_duplicates = near_duplicates;
+    result.metadata.resumed_parts = resumed_count;
+    result.metadata.file_errors = file_errors;
+    result.parts = kept;
 
-    let total_words: f64 = part_sizes.iter().sum();
+    if let Some(cp) = checkpoint {
+        cp.clear().ok();
+    }
+    terminal.complete(&AnalysisComplete {
+        types: result.metadata.total_types,
+        tokens: result.metadata.total_tokens,
+        duration_ms: started.elapsed().as_millis() as u64,
+        errors: result.metadata.file_errors.clone(),
+    });
+    Ok(result)
+}
 
-    // 2. 计算分布指标
-    vocab_map
+/// 把各文件的句数与段数记入 files 与语料合计；改用空白分词的文件只计段数，
+/// 其文本多不以中文句末标点断句，按 split_text_sentences 得到的句数没有意义
+fn record_units(metadata: &mut CorpusMetadata, units: HashMap<String, (TextUnits, bool)>) {
+    let sentences =
+        |&(units, whitespace): &(TextUnits, bool)| (!whitespace).then_some(units.sentences);
+    for file in &mut metadata.files {
+        let file_units = units.get(&file.path);
+        file.sentences = file_units.and_then(sentences);
+        file.paragraphs = file_units.map(|(units, _)| units.paragraphs);
+    }
+    let counted: Vec<u64> = units.values().filter_map(sentences).collect();
+    metadata.sentences = (!counted.is_empty()).then(|| counted.iter().sum());
+    metadata.paragraphs =
+        (!units.is_empty()).then(|| units.values().map(|(units, _)| units.paragraphs).sum());
+}
+
+/// 把各文件的字符构成记入 files 与语料合计，汉字占比低于 language_screen.min_han_ratio 的文件另行标出；
+/// 从检查点恢复的文件未重新读取，没有字符构成
+fn record_scripts(metadata: &mut CorpusMetadata, scripts: HashMap<String, ScriptHistogram>) {
+    let threshold = metadata.options.language_screen.min_han_ratio;
+    for file in &mut metadata.files {
+        file.scripts = scripts.get(&file.path).copied();
+    }
+    let mut scripts: Vec<(String, ScriptHistogram)> = scripts.into_iter().collect();
+    scripts.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path, histogram) in scripts {
+        metadata.scripts.add(&histogram);
+        if let Some(han_ratio) = histogram.han_ratio().filter(|&r| r < threshold) {
+            metadata.low_han_files.push(LowHanFile { path, han_ratio });
+        }
+    }
+    if !metadata.low_han_files.is_empty() {
+        metadata.warnings.push(format!(
+            "{} 个文件的汉字占比低于 {:.0}%，见 low_han_files；可按 language_screen 跳过或改用空白分词",
+            metadata.low_han_files.len(),
+            threshold * 100.0
+        ));
+    }
+}
+
+/// 设置 file_counts_top 时，为刚并入的部分上报 file-counted
+fn report_counted(
+    reporter: &dyn ProgressReporter,
+    counter: &CorpusCounter,
+    part: &PartCounts,
+    options: &AnalysisOptions,
+) {
+    if let (Some(top), Some(info)) = (options.file_counts_top, counter.last_part()) {
+        reporter.file_counted(&FileCounted::new(info, &part.counts, top));
+    }
+}
+
+/// 中止分析：删除检查点，不留临时文件，并上报 error
+fn abort(
+    exceeded: AnalysisAbort,
+    checkpoint: Option<&Checkpoint>,
+    terminal: TerminalGuard,
+) -> AnalysisAbort {
+    if let Some(cp) = checkpoint {
+        cp.clear().ok();
+    }
+    terminal.error(exceeded.to_string());
+    exceeded
+}
+
+/// 取消分析：把 completed 个文件中尚未写入检查点的部分 pending 补写，保留检查点，并上报 error
+fn cancel(
+    checkpoint: Option<&Checkpoint>,
+    completed: usize,
+    pending: &[PartCounts],
+    terminal: TerminalGuard,
+) -> AnalysisAbort {
+    if let Some(cp) = checkpoint.filter(|_| !pending.is_empty()) {
+        cp.save_chunk(completed - pending.len(), pending).ok();
+    }
+    terminal.error(AnalysisAbort::Cancelled.to_string());
+    AnalysisAbort::Cancelled
+}
+
+/// 按分析选项（含语言筛查与结构性噪声）对单个文件分词，返回有序词元流；被语言筛查跳过时返回 None
+pub fn tokenize_file(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    options: &AnalysisOptions,
+) -> Option<Vec<(String, String)>> {
+    screen_and_segment(nlp, file_path, options).map(|(word_pos, _)| word_pos)
+}
+
+/// 分词并统计单个文件，供增量分析缓存复用；被语言筛查跳过时返回 None
+pub fn count_file(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    options: &AnalysisOptions,
+) -> Option<PartCounts> {
+    screen_and_segment(nlp, file_path, options)
+        .map(|(word_pos, noise)| count_part(word_pos, &noise, options))
+}
+
+/// 分词并按切分方式统计单个文件的各部分 (块序号, 词频)，供选择性重新分析替换；被语言筛查跳过时返回 None
+pub fn count_file_parts(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    options: &AnalysisOptions,
+) -> Option<Vec<(Option<usize>, PartCounts)>> {
+    let (word_pos, noise) = screen_and_segment(nlp, file_path, options)?;
+    let parts = partition(word_pos, options)
         .into_iter()
-        .map(|((w, p), freq_vec)| {
-            let analyzer =
-                CorpusWordAnalyzer::new(freq_vec.clone(), part_sizes.clone(), total_words);
-            let metrics = analyzer.calculate_all_metrics();
-            (w, p, metrics)
+        .enumerate()
+        .map(|(k, (sub_index, units, tokens))| {
+            let part = count_unit((units, tokens), if k == 0 { &noise } else { &[] }, options);
+            (sub_index, part)
         })
-        .collect()
+        .collect();
+    Some(parts)
+}
+
+fn screen_and_segment(
+    nlp: &dyn Tokenizer,
+    file_path: &Path,
+    options: &AnalysisOptions,
+) -> Option<(Tokens, Vec<NoiseSequence>)> {
+    let whitespace = match options.language_screen.screen(file_path) {
+        Some(file) if file.action == ScreenMode::Skip => return None,
+        Some(_) => true,
+        None => false,
+    };
+    // 读取失败按空文本处理，与 analyze_corpus 一致
+    Some(process_file(nlp, file_path, whitespace, options, None).unwrap_or_default())
+}
+
+/// 由已统计好的各部分汇总分析结果；parts 与 file_paths 一一对应（按文件切分）
+pub fn analyze_parts<'a>(
+    nlp: &dyn Tokenizer,
+    file_paths: &[PathBuf],
+    parts: impl IntoIterator<Item = &'a PartCounts>,
+    options: &AnalysisOptions,
+) -> AnalysisResult {
+    let mut counter = CorpusCounter::new(file_paths.len());
+    let mut kept = Vec::new();
+    for (file, part) in file_paths.iter().zip(parts) {
+        counter.add_part(paths::display(file), None, part);
+        if options.keep_parts {
+            kept.push(part.clone());
+        }
+    }
+    let mut result = counter.into_result_in_memory(CorpusMetadata::new(nlp.engine(), options));
+    result.parts = kept;
+    result.metadata.files = file_paths
+        .iter()
+        .map(|path| FileInfo::collect(path, options.hash_files))
+        .collect();
+    result.metadata.model_files = nlp
+        .model_files()
+        .iter()
+        .map(|path| FileInfo::collect(Path::new(path), options.hash_files))
+        .collect();
+    result
+}
+
+/// 直接分析内存中的文本，每段文本（逐句切分时每个句子）为一个语料部分（供绑定与脚本使用）
+pub fn analyze_texts(
+    nlp: &dyn Tokenizer,
+    texts: &[String],
+    options: &AnalysisOptions,
+) -> AnalysisResult {
+    let mut metadata = CorpusMetadata::new(nlp.engine(), options);
+    if options.partition == PartitionMode::PerFile {
+        metadata.partition_mode = "per_text".to_string();
+    }
+    metadata.warnings = options.warnings();
+    let mut counter = CorpusCounter::new(texts.len());
+    if let Some(min_frequency) = options.two_pass_min_frequency() {
+        let parts = texts.iter().map(|text| {
+            let (word_pos, noise) = segment(nlp, text, options);
+            count_part(word_pos, &noise, options)
+        });
+        let (survivors, removed) = surviving_vocab(parts, min_frequency);
+        counter.restrict_to(survivors, removed, options);
+    }
+    let mut kept = Vec::new();
+    let mut sampler = example_sampler(options, metadata.seed);
+    let mut pairs = options.oov_pairs.map(PairCounter::new);
+    for (i, text) in texts.iter().enumerate() {
+        let (word_pos, noise) = segment(nlp, text, options);
+        if let Some(sampler) = &mut sampler {
+            sampler.add_text(&word_pos);
+        }
+        if let Some(pairs) = &mut pairs {
+            pairs.add_text(&word_pos);
+        }
+        for (k, (sub_index, units, tokens)) in partition(word_pos, options).into_iter().enumerate()
+        {
+            let part = count_unit((units, tokens), if k == 0 { &noise } else { &[] }, options);
+            counter.add_part(format!("text_{i}"), sub_index, &part);
+            if options.keep_parts {
+                kept.push(part);
+            }
+        }
+    }
+    if let Some(sampler) = sampler {
+        counter.examples = sampler.into_examples();
+    }
+    metadata.adjacent_singles = pairs.map(PairCounter::finish);
+    let mut result = counter.into_result_in_memory(metadata);
+    result.parts = kept;
+    result
+}
+
+/// 开启 collect_examples 时创建例句采样器，种子由分析种子推出
+fn example_sampler(options: &AnalysisOptions, seed: u64) -> Option<ExampleSampler> {
+    (options.collect_examples > 0).then(|| {
+        ExampleSampler::new(
+            options.collect_examples,
+            options.example_min_frequency,
+            options.latin_case,
+            seed::derive_seed(seed, seed::EXAMPLES),
+        )
+    })
+}
+
+/// 由各部分词频与给定元数据汇总分析结果（供会话合并）；sources 为各部分的 (来源, 块序号)，
+/// 开启 keep_parts 时结果保留这些部分
+pub fn aggregate_parts(
+    parts: Vec<PartCounts>,
+    sources: Vec<(String, Option<usize>)>,
+    metadata: CorpusMetadata,
+) -> Result<AnalysisResult, String> {
+    let mut counter = CorpusCounter::new(parts.len());
+    for ((source, sub_index), part) in sources.into_iter().zip(&parts) {
+        counter.add_part(source, sub_index, part);
+    }
+    let keep_parts = metadata.options.keep_parts;
+    let mut result = counter
+        .into_result(metadata, None)
+        .map_err(|e| e.to_string())?;
+    if keep_parts {
+        result.parts = parts;
+    }
+    Ok(result)
+}
+
+/// 以上次分析保留的计数按新选项重算指标，不重新读取与分词
+///
+/// 只允许改动 METRIC_OPTIONS 中的选项；改动其他影响计数的选项，或结果不含计数（如从会话文件载入）时返回错误。
+pub fn recompute_metrics(
+    previous: &AnalysisResult,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult, String> {
+    let Some(counts) = &previous.counts else {
+        return Err("当前结果不含计数（可能载入自会话文件），请重新分析".to_string());
+    };
+    let previous_options = &previous.metadata.options;
+    let conflicts: Vec<String> = previous_options
+        .material_changes(options)
+        .into_iter()
+        .filter(|key| !METRIC_OPTIONS.contains(&key.as_str()))
+        .collect();
+    if !conflicts.is_empty() {
+        return Err(format!(
+            "选项 {} 影响计数，需重新分析",
+            conflicts.join(", ")
+        ));
+    }
+    // 两遍计数时低于原 min_frequency 的词没有保留计数
+    if let Some(counted) = previous_options.two_pass_min_frequency() {
+        if options.min_frequency.is_none_or(|min| min < counted) {
+            return Err(format!(
+                "上次分析以两遍计数只保留了频次不低于 {counted} 的词，min_frequency 不能低于该值，需重新分析"
+            ));
+        }
+    }
+
+    let mut metadata = previous.metadata.clone();
+    // 不影响数值的选项沿用上次分析
+    metadata.options.rate_base = options.rate_base;
+    metadata.options.range_threshold = options.range_threshold;
+    metadata.options.pos_confidence = options.pos_confidence;
+    metadata.options.min_frequency = options.min_frequency;
+    metadata.options.quick_look = options.quick_look;
+    metadata.warnings = metadata.options.warnings();
+    metadata.min_frequency_removed = 0;
+    metadata.inconsistent_entries = 0;
+    metadata.sanitized_values = 0;
+    metadata.sanitized_by_field.clear();
+    let mut result = counts
+        .to_result(metadata, None)
+        .map_err(|e| e.to_string())?;
+    result.parts = previous.parts.clone();
+    result.counts = Some(Arc::clone(counts));
+    Ok(result)
+}
+
+/// 可在已有计数上事后施加的过滤选项
+const FILTER_OPTIONS: &[&str] = &["stopwords", "excluded_pos"];
+
+/// 以上次分析保留的计数施加新的停用词与词性过滤，并按新的指标选项重算，不重新读取与分词
+///
+/// 停用词表与排除词性只能增加；上次分析开启 protect_quotes 时不能事后过滤
+pub fn apply_filters(
+    previous: &AnalysisResult,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult, String> {
+    let Some(counts) = &previous.counts else {
+        return Err("当前结果不含计数（可能载入自会话文件），请重新分析".to_string());
+    };
+    let previous_options = &previous.metadata.options;
+    for (name, before, after) in [
+        ("停用词", &previous_options.stopwords, &options.stopwords),
+        (
+            "排除词性",
+            &previous_options.excluded_pos,
+            &options.excluded_pos,
+        ),
+    ] {
+        let restored: Vec<&str> = before
+            .iter()
+            .filter(|item| !after.contains(item))
+            .map(String::as_str)
+            .collect();
+        if !restored.is_empty() {
+            return Err(format!(
+                "{name} {} 已在上次分析中移出词表，没有保留计数，需重新分析",
+                restored.join(", ")
+            ));
+        }
+    }
+    let changed = previous_options.changed_options(options);
+    let refilter = FILTER_OPTIONS
+        .iter()
+        .any(|key| changed.iter().any(|c| c == key));
+    if refilter && previous_options.protect_quotes {
+        return Err(
+            "上次分析开启了 protect_quotes，无法区分引号内外的词元，需重新分析".to_string(),
+        );
+    }
+
+    let mut metadata = previous.metadata.clone();
+    metadata.options.stopwords = options.stopwords.clone();
+    metadata.options.excluded_pos = options.excluded_pos.clone();
+    let (before, after) = (previous_options.denominator, options.denominator);
+    if (before.symbols, before.structural_noise) == (after.symbols, after.structural_noise) {
+        metadata.options.denominator = after;
+    }
+    let filter = TokenFilter::new(&metadata.options);
+    let filtered = AnalysisResult {
+        entries: Vec::new(),
+        parts: previous
+            .parts
+            .iter()
+            .map(|part| refilter_part(part, &filter, previous_options, &metadata.options))
+            .collect(),
+        counts: Some(Arc::new(counts.refiltered(
+            &filter,
+            previous_options,
+            &metadata.options,
+        ))),
+        metadata,
+    };
+    let mut result = recompute_metrics(&filtered, options)?;
+    if refilter {
+        let source = previous
+            .metadata
+            .run_id
+            .as_deref()
+            .map_or_else(|| "已有结果".to_string(), |id| format!("结果 {id}"));
+        result.metadata.warnings.push(format!(
+            "事后过滤：在{source}的计数上新增停用词 {} 个、排除词性 {} 个，未重新分词",
+            options.stopwords.len() - previous_options.stopwords.len(),
+            options.excluded_pos.len() - previous_options.excluded_pos.len()
+        ));
+    }
+    Ok(result)
+}
+
+/// 在保留的部分词频上施加过滤，同 CorpusCounter::refiltered
+fn refilter_part(
+    part: &PartCounts,
+    filter: &TokenFilter,
+    previous: &AnalysisOptions,
+    options: &AnalysisOptions,
+) -> PartCounts {
+    let mut part = part.clone();
+    part.size += size_adjustment(&part.filtered, previous, options);
+    let mut kept = Vec::with_capacity(part.counts.len());
+    for (w, p, v) in std::mem::take(&mut part.counts) {
+        let Some(reason) = filter.check(&w, &p) else {
+            kept.push((w, p, v));
+            continue;
+        };
+        record_filtered(&mut part.filtered, reason, v);
+        if excludes_from_size(options, reason, &p) {
+            part.size -= v;
+        }
+        part.filtered_counts.push((reason, w, p, v));
+    }
+    part.counts = kept;
+    part
+}
+
+/// 改动 denominator 中停用词与词性两项时，已移出词表的词元对部分词数的调整量
+///
+/// 按 token_policy 已扣除的标点若同时是停用词，这里不再区分，会重复调整
+fn size_adjustment(
+    filtered: &FilterCounts,
+    previous: &AnalysisOptions,
+    options: &AnalysisOptions,
+) -> f64 {
+    [
+        (FilterReason::Stopword, filtered.stopwords),
+        (FilterReason::ExcludedPos, filtered.excluded_pos),
+    ]
+    .into_iter()
+    .map(|(reason, tokens)| {
+        match (
+            previous.denominator.excludes(reason),
+            options.denominator.excludes(reason),
+        ) {
+            (false, true) => -(tokens as f64),
+            (true, false) => tokens as f64,
+            _ => 0.0,
+        }
+    })
+    .sum()
+}
+
+/// 记入一项过滤统计
+fn record_filtered(counts: &mut FilterCounts, reason: FilterReason, tokens: f64) {
+    match reason {
+        FilterReason::Stopword => counts.stopwords += tokens as u64,
+        FilterReason::ExcludedPos => counts.excluded_pos += tokens as u64,
+        FilterReason::StructuralNoise => {}
+    }
+}
+
+/// 因 reason 移出词表的词元是否还需从部分词数中扣除；已按 token_policy 扣除的标点不再重复扣除
+fn excludes_from_size(options: &AnalysisOptions, reason: FilterReason, pos: &str) -> bool {
+    options.denominator.excludes(reason)
+        && !(pos == PUNCTUATION_POS && options.token_policy.exclude_punctuation_from_sizes)
+}
+
+/// 两遍计数的第一遍：只累计各 (词, 词性) 的总频次，返回达到 min_frequency 的词与被淘汰的词型
+fn surviving_vocab(
+    parts: impl Iterator<Item = PartCounts>,
+    min_frequency: f64,
+) -> (HashSet<(String, String)>, Vec<RemovedType>) {
+    let mut totals = HashMap::<(String, String), f64>::new();
+    for part in parts {
+        for (w, p, v) in part.counts {
+            *totals.entry((w, p)).or_insert(0.0) += v;
+        }
+    }
+    let mut survivors = HashSet::new();
+    let mut removed = Vec::new();
+    for ((word, pos), frequency) in totals {
+        if frequency >= min_frequency {
+            survivors.insert((word, pos));
+        } else {
+            removed.push(RemovedType {
+                word,
+                pos,
+                frequency,
+            });
+        }
+    }
+    (survivors, removed)
+}
+
+/// 全语料词频表：每个 (词, 词性) 在各部分的频次，以及各部分词数
+#[derive(Debug, Clone)]
+pub struct CorpusCounter {
+    vocab_map: HashMap<(String, String), Vec<f64>>,
+    part_sizes: Vec<f64>,
+    /// 各部分的原始词数，与 part_sizes 一一对应
+    raw_sizes: Vec<f64>,
+    /// 各部分的 (来源, 块序号)，与 part_sizes 一一对应
+    part_sources: Vec<(String, Option<usize>)>,
+    /// 计数时各词频向量的长度；部分数事先未知时（逐句切分）按需倍增。
+    /// 向量只在已并入的部分上有值，into_result 时按最终的部分列表统一定长
+    part_count: usize,
+    /// 文件内滑窗 (DP 之和, D 之和, 文件数)，用于求均值
+    window_sums: HashMap<(String, String), (f64, f64, f64)>,
+    removed: TokenPolicyCounts,
+    /// 各词在引号内的总频次
+    quoted_totals: HashMap<(String, String), f64>,
+    /// 各词 (首次出现的部分, 全语料首次位置, 全语料末次位置)
+    positions: HashMap<(String, String), (usize, f64, f64)>,
+    /// 各词折叠前各写法的总频次
+    case_variants: HashMap<(String, String), HashMap<String, f64>>,
+    /// 已并入各部分的词数之和，即下一部分首个词元的全语料位置
+    tokens_before: f64,
+    filtered: FilterCounts,
+    /// 各部分的停用词/词性过滤统计，与 part_sizes 一一对应
+    part_filtered: Vec<FilterCounts>,
+    /// 各部分各词性的词元数，与 part_sizes 一一对应
+    part_pos_counts: Vec<BTreeMap<String, u64>>,
+    /// 各部分由哪些原始句子合并而成，与 part_sizes 一一对应
+    part_units: Vec<Vec<usize>>,
+    /// 各 (原因, 词, 词性) 被过滤移出词表的总频次
+    filtered_totals: HashMap<(FilterReason, String, String), f64>,
+    /// 以上各表的估算字节数，见 memory 模块
+    estimated_bytes: u64,
+    /// 两遍计数时只统计这些词；None 表示统计全部
+    survivors: Option<HashSet<(String, String)>>,
+    /// 第一遍已淘汰的词型数
+    pre_removed: usize,
+    /// 第一遍淘汰的词型中总频次最高的若干个
+    pre_removed_top: Vec<RemovedType>,
+    /// 目标词模式下第一遍已淘汰的目标词，不作为未出现的目标词
+    pre_removed_targets: HashSet<String>,
+    /// 各部分中属于第一遍已淘汰词型的词元数
+    pre_removed_by_part: Vec<f64>,
+    /// 各词的例句
+    examples: HashMap<(String, String), Vec<String>>,
+    /// 各短语在各部分的出现次数，与词频表分开
+    phrase_map: HashMap<String, Vec<f64>>,
+    /// 各词性序列在各部分的出现次数，与词频表分开
+    posgram_map: HashMap<String, Vec<f64>>,
+}
+
+impl CorpusCounter {
+    fn new(part_count: usize) -> Self {
+        Self {
+            vocab_map: HashMap::new(),
+            part_sizes: Vec::with_capacity(part_count),
+            raw_sizes: Vec::with_capacity(part_count),
+            part_sources: Vec::with_capacity(part_count),
+            part_count,
+            window_sums: HashMap::new(),
+            removed: TokenPolicyCounts::default(),
+            quoted_totals: HashMap::new(),
+            positions: HashMap::new(),
+            case_variants: HashMap::new(),
+            tokens_before: 0.0,
+            filtered: FilterCounts::default(),
+            part_filtered: Vec::with_capacity(part_count),
+            part_pos_counts: Vec::with_capacity(part_count),
+            part_units: Vec::with_capacity(part_count),
+            filtered_totals: HashMap::new(),
+            estimated_bytes: 0,
+            survivors: None,
+            pre_removed: 0,
+            pre_removed_top: Vec::new(),
+            pre_removed_by_part: Vec::new(),
+            pre_removed_targets: HashSet::new(),
+            examples: HashMap::new(),
+            phrase_map: HashMap::new(),
+            posgram_map: HashMap::new(),
+        }
+    }
+
+    /// 只统计第一遍保留下来的词
+    fn restrict_to(
+        &mut self,
+        survivors: HashSet<(String, String)>,
+        removed: Vec<RemovedType>,
+        options: &AnalysisOptions,
+    ) {
+        // 目标词模式下被淘汰的都是目标词，数量有限
+        if !options.target_words.is_empty() {
+            self.pre_removed_targets = removed.iter().map(|r| r.word.clone()).collect();
+        }
+        self.survivors = Some(survivors);
+        self.pre_removed = removed.len();
+        self.pre_removed_top = filter::top_removed(removed);
+    }
+
+    fn keeps(&self, key: &(String, String)) -> bool {
+        self.survivors.as_ref().is_none_or(|s| s.contains(key))
+    }
+
+    /// 将一个部分的词频并入全局词频表
+    fn add_part(&mut self, source: String, sub_index: Option<usize>, part: &PartCounts) {
+        let idx = self.part_sizes.len();
+        if idx >= self.part_count {
+            self.part_count = (idx + 1).max(self.part_count * 2);
+        }
+        let part_count = self.part_count;
+        let mut pre_removed = 0.0;
+        for (w, p, v) in &part.counts {
+            let key = (w.clone(), p.clone());
+            if !self.keeps(&key) {
+                pre_removed += v;
+                continue;
+            }
+            let freqs = match self.vocab_map.entry(key) {
+                Entry::Occupied(e) => e.into_mut(),
+                Entry::Vacant(e) => {
+                    self.estimated_bytes += memory::entry_bytes::<Vec<f64>>(w, p, part_count * 8);
+                    e.insert(Vec::new())
+                }
+            };
+            if freqs.len() <= idx {
+                freqs.resize(part_count, 0.0);
+            }
+            freqs[idx] = *v;
+        }
+        for (phrase, v) in &part.phrase_counts {
+            let freqs = self.phrase_map.entry(phrase.clone()).or_default();
+            if freqs.len() <= idx {
+                freqs.resize(part_count, 0.0);
+            }
+            freqs[idx] = *v;
+        }
+        for (pattern, v) in &part.posgram_counts {
+            let freqs = self.posgram_map.entry(pattern.clone()).or_default();
+            if freqs.len() <= idx {
+                freqs.resize(part_count, 0.0);
+            }
+            freqs[idx] = *v;
+        }
+        self.part_sizes.push(part.size);
+        self.raw_sizes.push(part.raw_size);
+        self.part_sources.push((source, sub_index));
+        self.removed.add(&part.removed);
+        self.filtered.add(&part.filtered);
+        self.part_filtered.push(part.filtered);
+        self.part_pos_counts
+            .push(part.pos_counts.iter().cloned().collect());
+        self.part_units.push(part.units.clone());
+        if self.survivors.is_some() {
+            self.pre_removed_by_part.push(pre_removed);
+        }
+        for (reason, w, p, v) in &part.filtered_counts {
+            let entry = self.filtered_totals.entry((*reason, w.clone(), p.clone()));
+            if let Entry::Vacant(_) = entry {
+                self.estimated_bytes += memory::entry_bytes::<f64>(w, p, 0);
+            }
+            *entry.or_insert(0.0) += v;
+        }
+        for (w, p, v) in &part.quoted_counts {
+            let key = (w.clone(), p.clone());
+            if !self.keeps(&key) {
+                continue;
+            }
+            let entry = self.quoted_totals.entry(key);
+            if let Entry::Vacant(_) = entry {
+                self.estimated_bytes += memory::entry_bytes::<f64>(w, p, 0);
+            }
+            *entry.or_insert(0.0) += v;
+        }
+        for (w, p, first, last) in &part.positions {
+            let key = (w.clone(), p.clone());
+            if !self.keeps(&key) {
+                continue;
+            }
+            let entry = self.positions.entry(key);
+            if let Entry::Vacant(_) = entry {
+                self.estimated_bytes += memory::entry_bytes::<(usize, f64, f64)>(w, p, 0);
+            }
+            let base = self.tokens_before;
+            entry.or_insert((idx, base + first, base + last)).2 = base + last;
+        }
+        self.tokens_before += part.size;
+        for (w, p, surface, v) in &part.case_variants {
+            let key = (w.clone(), p.clone());
+            if !self.keeps(&key) {
+                continue;
+            }
+            let forms = self.case_variants.entry(key).or_default();
+            if !forms.contains_key(surface) {
+                self.estimated_bytes += memory::entry_bytes::<f64>(surface, "", 0);
+            }
+            *forms.entry(surface.clone()).or_insert(0.0) += v;
+        }
+        for (w, p, dp, d) in &part.window_dispersion {
+            let key = (w.clone(), p.clone());
+            if !self.keeps(&key) {
+                continue;
+            }
+            let entry = self.window_sums.entry(key);
+            if let Entry::Vacant(_) = entry {
+                self.estimated_bytes += memory::entry_bytes::<(f64, f64, f64)>(w, p, 0);
+            }
+            let sums = entry.or_default();
+            sums.0 += dp;
+            sums.1 += d;
+            sums.2 += 1.0;
+        }
+    }
+
+    /// 最近并入的部分
+    fn last_part(&self) -> Option<PartInfo> {
+        let index = self.part_sizes.len().checked_sub(1)?;
+        let (source, sub_index) = &self.part_sources[index];
+        Some(PartInfo {
+            index,
+            source: source.clone(),
+            sub_index: *sub_index,
+            tokens: self.part_sizes[index] as u64,
+            raw_tokens: self.raw_sizes[index] as u64,
+            pos_counts: self.part_pos_counts[index].clone(),
+            units: self.part_units[index].clone(),
+        })
+    }
+
+    /// 进度事件，附带当前词表规模与估算内存
+    fn progress(
+        &self,
+        current: usize,
+        total: usize,
+        file: &Path,
+        estimate: &TokenEstimate,
+        batch: ProgressBatch,
+    ) -> AnalysisProgress {
+        let tokens_done = self.tokens_before as u64;
+        AnalysisProgress {
+            current,
+            total,
+            file: paths::display(file),
+            vocab_entries: self.vocab_map.len(),
+            estimated_bytes: self.estimated_bytes,
+            tokens_done,
+            tokens_total_estimate: estimate.estimate(tokens_done),
+            files_in_event: batch.files,
+            files_per_second: batch.files_per_second,
+            phase: ProgressPhase::Files,
+            types_done: 0,
+            types_total: 0,
+        }
+    }
+
+    /// 估算内存超过上限（字节）时返回中止原因
+    fn check_memory(
+        &self,
+        limit: Option<u64>,
+        files_done: usize,
+    ) -> Result<(), MemoryLimitExceeded> {
+        match limit {
+            Some(limit_bytes) if self.estimated_bytes > limit_bytes => Err(MemoryLimitExceeded {
+                limit_bytes,
+                estimated_bytes: self.estimated_bytes,
+                vocab_entries: self.vocab_map.len(),
+                files_done,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 稠密词频表的单元数（词型 × 部分）超过上限时返回中止原因；只在逐句切分时可能触发
+    fn check_dense(&self, files_done: usize) -> Result<(), DensePartsExceeded> {
+        let parts = self.part_sizes.len();
+        let vocab_entries = self.vocab_map.len();
+        if vocab_entries.saturating_mul(parts) > DENSE_CELL_LIMIT {
+            return Err(DensePartsExceeded {
+                parts,
+                vocab_entries,
+                files_done,
+            });
+        }
+        Ok(())
+    }
+
+    /// 一个词折叠前的各写法，按频次降序（同频按写法），超出 limit 的写法合计为一项 other；只有一种与词相同的写法时为空
+    fn surface_forms(&self, key: &(String, String), limit: usize) -> Vec<SurfaceVariant> {
+        let Some(forms) = self.case_variants.get(key) else {
+            return Vec::new();
+        };
+        if forms.len() == 1 && forms.contains_key(&key.0) {
+            return Vec::new();
+        }
+        let mut variants: Vec<SurfaceVariant> = forms
+            .iter()
+            .map(|(form, &frequency)| SurfaceVariant {
+                form: form.clone(),
+                frequency,
+                other: false,
+            })
+            .collect();
+        variants.sort_by(|a, b| {
+            b.frequency
+                .total_cmp(&a.frequency)
+                .then_with(|| a.form.cmp(&b.form))
+        });
+        if variants.len() > limit {
+            let frequency = variants.drain(limit..).map(|v| v.frequency).sum();
+            variants.push(SurfaceVariant {
+                form: OTHER_SURFACE_FORM.to_string(),
+                frequency,
+                other: true,
+            });
+        }
+        variants
+    }
+
+    /// 事后按过滤器把词移出词表的副本：先按 denominator 的改动调整部分词数，
+    /// 再把新移出的词计入过滤统计，按 options.denominator 从部分词数中扣除
+    fn refiltered(
+        &self,
+        filter: &TokenFilter,
+        previous: &AnalysisOptions,
+        options: &AnalysisOptions,
+    ) -> Self {
+        let mut counter = self.clone();
+        for (size, filtered) in counter.part_sizes.iter_mut().zip(&counter.part_filtered) {
+            *size += size_adjustment(filtered, previous, options);
+        }
+        let removed: Vec<((String, String), FilterReason)> = counter
+            .vocab_map
+            .keys()
+            .filter_map(|key| {
+                filter
+                    .check(&key.0, &key.1)
+                    .map(|reason| (key.clone(), reason))
+            })
+            .collect();
+        for (key, reason) in removed {
+            let Some(freqs) = counter.vocab_map.remove(&key) else {
+                continue;
+            };
+            let excluded = excludes_from_size(options, reason, &key.1);
+            let mut total = 0.0;
+            for (i, &v) in freqs.iter().enumerate() {
+                record_filtered(&mut counter.part_filtered[i], reason, v);
+                if excluded {
+                    counter.part_sizes[i] -= v;
+                }
+                total += v;
+            }
+            record_filtered(&mut counter.filtered, reason, total);
+            *counter
+                .filtered_totals
+                .entry((reason, key.0.clone(), key.1.clone()))
+                .or_insert(0.0) += total;
+            counter.window_sums.remove(&key);
+            counter.quoted_totals.remove(&key);
+            counter.positions.remove(&key);
+            counter.case_variants.remove(&key);
+            counter.examples.remove(&key);
+        }
+        counter
+    }
+
+    /// 各项已启用过滤的效果；min_frequency 按 options 中的当前取值计算
+    fn filter_report(&self, options: &AnalysisOptions, total_words: f64) -> FilterReport {
+        let mut filters = Vec::new();
+        for reason in [
+            FilterReason::Stopword,
+            FilterReason::ExcludedPos,
+            FilterReason::StructuralNoise,
+        ] {
+            let enabled = match reason {
+                FilterReason::Stopword => !options.stopwords.is_empty(),
+                FilterReason::ExcludedPos => !options.excluded_pos.is_empty(),
+                FilterReason::StructuralNoise => options.structural_noise,
+            };
+            if !enabled {
+                continue;
+            }
+            let removed: Vec<RemovedType> = self
+                .filtered_totals
+                .iter()
+                .filter(|((r, _, _), _)| *r == reason)
+                .map(|((_, word, pos), &frequency)| RemovedType {
+                    word: word.clone(),
+                    pos: pos.clone(),
+                    frequency,
+                })
+                .collect();
+            let by_part = self.part_filtered.iter().map(|counts| match reason {
+                FilterReason::Stopword => counts.stopwords as f64,
+                FilterReason::ExcludedPos => counts.excluded_pos as f64,
+                FilterReason::StructuralNoise => counts.structural_noise as f64,
+            });
+            let mut effect = self.filter_effect(reason.as_str(), removed.len(), removed, by_part);
+            if options.denominator.excludes(reason) {
+                effect.corpus_words_removed = effect.tokens_removed;
+            }
+            filters.push(effect);
+        }
+        if let Some(min) = options.min_frequency {
+            // 第一遍已淘汰的词型加上计数后低于当前阈值的词型
+            let mut removed = self.pre_removed_top.clone();
+            let mut by_part = self.pre_removed_by_part.clone();
+            by_part.resize(self.part_sizes.len(), 0.0);
+            let mut types = self.pre_removed;
+            for ((word, pos), freqs) in &self.vocab_map {
+                let frequency: f64 = freqs.iter().sum();
+                if frequency >= min {
+                    continue;
+                }
+                types += 1;
+                for (removed_in_part, v) in by_part.iter_mut().zip(freqs) {
+                    *removed_in_part += v;
+                }
+                removed.push(RemovedType {
+                    word: word.clone(),
+                    pos: pos.clone(),
+                    frequency,
+                });
+            }
+            filters.push(self.filter_effect("min_frequency", types, removed, by_part));
+        }
+        FilterReport {
+            total_corpus_words: total_words as u64,
+            filters,
+        }
+    }
+
+    /// 由被移除的词型与各部分移除的词元数汇总一项过滤的效果，各部分按来源文件合计
+    fn filter_effect(
+        &self,
+        filter: &str,
+        types_removed: usize,
+        removed: Vec<RemovedType>,
+        by_part: impl IntoIterator<Item = f64>,
+    ) -> FilterEffect {
+        let mut by_file: Vec<FileRemoval> = Vec::new();
+        for ((source, _), tokens) in self.part_sources.iter().zip(by_part) {
+            match by_file.last_mut() {
+                Some(last) if last.file == *source => last.tokens += tokens as u64,
+                _ => by_file.push(FileRemoval {
+                    file: source.clone(),
+                    tokens: tokens as u64,
+                }),
+            }
+        }
+        FilterEffect {
+            filter: filter.to_string(),
+            tokens_removed: by_file.iter().map(|f| f.tokens).sum(),
+            types_removed,
+            corpus_words_removed: 0,
+            by_file,
+            top_removed: filter::top_removed(removed),
+        }
+    }
+
+    /// 计算分布指标，生成分析结果；计数本身随结果保留，供仅重算指标时复用。
+    /// 给出 progress 时逐条目累加，供上报计算指标阶段的进度
+    fn into_result(
+        mut self,
+        metadata: CorpusMetadata,
+        progress: Option<&MetricProgress>,
+    ) -> Result<AnalysisResult, AnalysisAbort> {
+        // 两遍计数的保留词表只在计数时使用
+        self.survivors = None;
+        self.resolve_parts();
+        let counts = Arc::new(self);
+        let mut result = counts.to_result(metadata, progress)?;
+        result.counts = Some(counts);
+        Ok(result)
+    }
+
+    /// 同 into_result，供部分全部在内存中逐个并入的调用：词频向量随即按部分数定长，不会与部分列表不符
+    fn into_result_in_memory(self, metadata: CorpusMetadata) -> AnalysisResult {
+        self.into_result(metadata, None)
+            .expect("resolve_parts 已按部分数定长全部词频向量")
+    }
+
+    /// 部分列表至此确定：词频向量在计数时按预估的部分数分配、按需倍增，只在已并入的部分上有值，
+    /// 此处统一定长为最终的部分数，与 part_sizes 一一对应
+    fn resolve_parts(&mut self) {
+        let parts = self.part_sizes.len();
+        let vectors = self
+            .vocab_map
+            .values_mut()
+            .chain(self.phrase_map.values_mut())
+            .chain(self.posgram_map.values_mut());
+        for freqs in vectors {
+            freqs.resize(parts, 0.0);
+        }
+        self.part_count = parts;
+    }
+
+    /// 按最终的部分列表构造分析器；词频向量的长度与部分数不符时返回错误，不作补齐
+    fn analyzer(
+        &self,
+        freq_vec: &[f64],
+        total_words: f64,
+        config: AnalyzerConfig,
+    ) -> Result<CorpusWordAnalyzer, PartLengthMismatch> {
+        CorpusWordAnalyzer::with_config(
+            freq_vec.to_vec(),
+            self.part_sizes.clone(),
+            total_words,
+            config,
+        )
+    }
+
+    /// 由计数计算分布指标，生成分析结果；有条目的词频向量与部分列表不符时中止，不略去条目
+    fn to_result(
+        &self,
+        mut metadata: CorpusMetadata,
+        progress: Option<&MetricProgress>,
+    ) -> Result<AnalysisResult, AnalysisAbort> {
+        metadata
+            .warnings
+            .extend(partition_advice::small_parts_warning(
+                &self.part_sizes,
+                metadata.options.min_part_tokens,
+            ));
+        let part_sizes = &self.part_sizes;
+        let total_words: f64 = part_sizes.iter().sum();
+        // 速览模式按语料规模提高最低频次
+        let quick_look = metadata.options.quick_look;
+        let min_frequency = match (metadata.options.min_frequency, quick_look) {
+            (min, Some(quick)) => Some(min.unwrap_or(0.0).max(quick.min_frequency(total_words))),
+            (min, None) => min,
+        };
+        let vocab: Vec<(&(String, String), &Vec<f64>)> = self
+            .vocab_map
+            .iter()
+            .filter(|(_, freqs)| min_frequency.is_none_or(|min| freqs.iter().sum::<f64>() >= min))
+            .collect();
+        if min_frequency.is_some() {
+            metadata.min_frequency_removed = self.pre_removed + self.vocab_map.len() - vocab.len();
+        }
+        if let Some(progress) = progress {
+            progress.start(vocab.len());
+        }
+
+        // 计算分布指标，并清洗非有限值以免序列化后前端出现空白或排序错乱
+        metadata.total_tokens = total_words as u64;
+        metadata.total_raw_tokens = self.raw_sizes.iter().sum::<f64>() as u64;
+        metadata.size_exclusions = DenominatorPolicy::size_exclusions(&metadata.options);
+        metadata.total_types = vocab.len();
+        metadata.parts = self
+            .part_sources
+            .iter()
+            .zip(part_sizes.iter().zip(&self.raw_sizes))
+            .zip(self.part_pos_counts.iter().zip(&self.part_units))
+            .enumerate()
+            .map(
+                |(index, (((source, sub_index), (&size, &raw)), (pos_counts, units)))| PartInfo {
+                    index,
+                    source: source.clone(),
+                    sub_index: *sub_index,
+                    tokens: size as u64,
+                    raw_tokens: raw as u64,
+                    pos_counts: pos_counts.clone(),
+                    units: units.clone(),
+                },
+            )
+            .collect();
+        if metadata.options.merge_small_parts {
+            metadata.part_merges = Some(PartMerges {
+                min_tokens: metadata.options.min_part_tokens,
+                units: self.part_units.iter().map(Vec::len).sum(),
+                merges: self
+                    .part_units
+                    .iter()
+                    .map(|u| u.len().saturating_sub(1))
+                    .sum(),
+                merged_parts: self.part_units.iter().filter(|u| u.len() > 1).count(),
+            });
+        }
+        metadata.pos_outliers =
+            pos_histogram::outliers(&metadata.parts, &metadata.options.pos_outlier);
+        metadata.token_policy_removed = self.removed;
+        metadata.filter_removed = self.filtered;
+        metadata.filter_report = self.filter_report(&metadata.options, total_words);
+        metadata.estimated_memory_bytes = self.estimated_bytes;
+        let protect_quotes = metadata.options.protect_quotes;
+        let word_totals = metadata
+            .options
+            .pos_confidence
+            .then(|| tag_confidence::word_totals(&self.vocab_map));
+        let track_positions = metadata.options.track_positions;
+        let config = AnalyzerConfig::from(&metadata.options);
+        let max_surface_forms = metadata.options.max_surface_forms;
+        let mut entries: Vec<WordEntry> = vocab
+            .into_iter()
+            .inspect(|_| {
+                if let Some(progress) = progress {
+                    progress.entry_done();
+                }
+            })
+            .map(|(key, freq_vec)| {
+                let frequency = freq_vec.iter().sum();
+                let analyzer = self
+                    .analyzer(freq_vec, total_words, config)
+                    .map_err(|e| AnalysisAbort::PartLengths(format!("{}/{}", key.0, key.1), e))?;
+                let mut metrics = analyzer.calculate_all_metrics();
+                if !analyzer.parts_consistent() {
+                    metadata.inconsistent_entries += 1;
+                }
+                if let Some(&(dp_sum, d_sum, files)) = self.window_sums.get(key) {
+                    metrics.within_file_dp = Some(dp_sum / files);
+                    metrics.within_file_juilland_d = Some(d_sum / files);
+                }
+                metadata.record_sanitized(&metrics.sanitize());
+                let quoted_frequency =
+                    protect_quotes.then(|| self.quoted_totals.get(key).copied().unwrap_or(0.0));
+                let position = self
+                    .positions
+                    .get(key)
+                    .filter(|_| track_positions && total_words > 0.0);
+                let (word, pos) = key;
+                Ok(WordEntry {
+                    char_length: text::char_length(word),
+                    word: word.clone(),
+                    pos: pos.clone(),
+                    id: String::new(),
+                    frequency,
+                    metrics,
+                    gt_adjusted_frequency: None,
+                    quoted_frequency,
+                    pos_tag_share: word_totals.as_ref().and_then(|totals| {
+                        let total = totals[word.as_str()];
+                        (total > 0.0).then(|| frequency / total)
+                    }),
+                    first_occurrence_part: position.map(|&(part, _, _)| part),
+                    first_occurrence_fraction: position.map(|&(_, first, _)| first / total_words),
+                    occurrence_span_fraction: position
+                        .map(|&(_, first, last)| (last - first) / total_words),
+                    max_part_index: analyzer.get_max_part_rate().map(|(part, _)| part),
+                    surface_forms: self.surface_forms(key, max_surface_forms),
+                    examples: self.examples.get(key).cloned().unwrap_or_default(),
+                    flags: Vec::new(),
+                    ranks: BTreeMap::new(),
+                })
+            })
+            .collect::<Result<_, AnalysisAbort>>()?;
+
+        // 基于频次谱的 Simple Good-Turing 平滑
+        if let Some(adjusted) = simple_good_turing(entries.iter().map(|e| e.frequency)) {
+            for entry in &mut entries {
+                entry.gt_adjusted_frequency =
+                    adjusted.get(&(entry.frequency.round() as u64)).copied();
+            }
+        }
+
+        // 按频次降序、同频按词与词性排序，同一语料的结果与导出与哈希表的遍历顺序无关；
+        // 速览模式在计算指标之后按此顺序截断
+        entries.sort_by(|a, b| {
+            b.frequency
+                .total_cmp(&a.frequency)
+                .then_with(|| a.word.cmp(&b.word))
+                .then_with(|| a.pos.cmp(&b.pos))
+        });
+        metadata.truncation = None;
+        if let Some(quick) = quick_look {
+            let types_before = entries.len();
+            entries.truncate(quick.top_n);
+            metadata.truncation = Some(Truncation {
+                top_n: quick.top_n,
+                types_before,
+                min_frequency: min_frequency.unwrap_or(0.0),
+            });
+        }
+
+        // 未出现的目标词、短语与词性序列条目在平滑之后追加，不影响词表的频次谱
+        let counted = self
+            .vocab_map
+            .keys()
+            .map(|(word, _)| word.as_str())
+            .chain(self.pre_removed_targets.iter().map(String::as_str));
+        let absent = vec![0.0; part_sizes.len()];
+        for word in targets::absent_targets(&metadata.options, counted) {
+            let mut metrics = self
+                .analyzer(&absent, total_words, config)
+                .map_err(|e| AnalysisAbort::PartLengths(format!("{word}/{ABSENT_POS}"), e))?
+                .calculate_all_metrics();
+            metadata.record_sanitized(&metrics.sanitize());
+            entries.push(WordEntry {
+                char_length: text::char_length(&word),
+                word,
+                pos: ABSENT_POS.to_string(),
+                id: String::new(),
+                frequency: 0.0,
+                metrics,
+                gt_adjusted_frequency: None,
+                quoted_frequency: protect_quotes.then_some(0.0),
+                pos_tag_share: None,
+                first_occurrence_part: None,
+                first_occurrence_fraction: None,
+                occurrence_span_fraction: None,
+                max_part_index: None,
+                surface_forms: Vec::new(),
+                examples: Vec::new(),
+                flags: Vec::new(),
+                ranks: BTreeMap::new(),
+            });
+        }
+
+        // 词性序列的 char_length 为序列所含词性数
+        let posgram_min = metadata.options.posgrams.map_or(0.0, |pg| pg.min_frequency);
+        let mut phrases: Vec<_> = self
+            .phrase_map
+            .iter()
+            .map(|(phrase, freq_vec)| (phrase, freq_vec, PHRASE_POS, text::char_length(phrase)))
+            .collect();
+        phrases.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut patterns: Vec<_> = self
+            .posgram_map
+            .iter()
+            .filter(|(_, freq_vec)| freq_vec.iter().sum::<f64>() >= posgram_min)
+            .map(|(pattern, freq_vec)| {
+                let length = pattern.split(posgrams::POSGRAM_SEPARATOR).count();
+                (pattern, freq_vec, POSGRAM_POS, length)
+            })
+            .collect();
+        patterns.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (word, freq_vec, pos, char_length) in phrases.into_iter().chain(patterns) {
+            let frequency = freq_vec.iter().sum();
+            let analyzer = self
+                .analyzer(freq_vec, total_words, config)
+                .map_err(|e| AnalysisAbort::PartLengths(format!("{word}/{pos}"), e))?;
+            let mut metrics = analyzer.calculate_all_metrics();
+            if !analyzer.parts_consistent() {
+                metadata.inconsistent_entries += 1;
+            }
+            metadata.record_sanitized(&metrics.sanitize());
+            entries.push(WordEntry {
+                char_length,
+                word: word.clone(),
+                pos: pos.to_string(),
+                id: String::new(),
+                frequency,
+                metrics,
+                gt_adjusted_frequency: None,
+                quoted_frequency: None,
+                pos_tag_share: None,
+                first_occurrence_part: None,
+                first_occurrence_fraction: None,
+                occurrence_span_fraction: None,
+                max_part_index: analyzer.get_max_part_rate().map(|(part, _)| part),
+                surface_forms: Vec::new(),
+                examples: Vec::new(),
+                flags: Vec::new(),
+                ranks: BTreeMap::new(),
+            });
+        }
+
+        let mut result = AnalysisResult {
+            entries,
+            metadata,
+            parts: Vec::new(),
+            counts: None,
+        };
+        entry_id::assign(&mut result);
+        entry_flags::assign(&mut result);
+        Ok(result)
+    }
 }
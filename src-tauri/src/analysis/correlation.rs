@@ -0,0 +1,168 @@
+// correlation.rs
+// 指标间比较：两两 Spearman 相关矩阵与秩差最大的词
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{dispersion_metrics::DispersionMetrics, word_entry::WordEntry};
+
+/// 一对指标的 Spearman 相关系数；n 为两者均非 None 的条目数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricCorrelation {
+    pub metric_a: String,
+    pub metric_b: String,
+    pub n: usize,
+    /// 样本不足两个或任一指标无变异时为 None
+    pub rho: Option<f64>,
+}
+
+/// 两个指标下秩次相差最大的词
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankDisagreement {
+    pub word: String,
+    pub pos: String,
+    pub rank_a: f64,
+    pub rank_b: f64,
+    /// rank_a - rank_b（b 已按相关方向对齐）
+    pub rank_diff: f64,
+}
+
+/// 平均秩（并列取平均），秩从 1 开始；O(n log n)
+fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // 位置 start..end（0 起）对应秩 start+1..=end，取平均
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Pearson 相关系数；任一序列无变异时为 None
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    if x.len() < 2 {
+        return None;
+    }
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (&a, &b) in x.iter().zip(y) {
+        sxy += (a - mean_x) * (b - mean_y);
+        sxx += (a - mean_x).powi(2);
+        syy += (b - mean_y).powi(2);
+    }
+    if sxx <= 0.0 || syy <= 0.0 {
+        return None;
+    }
+    Some((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// 按 FIELD_NAMES 顺序取出每个指标的一列值
+fn metric_columns(entries: &[WordEntry]) -> Vec<Vec<Option<f64>>> {
+    let mut columns = vec![Vec::with_capacity(entries.len()); DispersionMetrics::FIELD_NAMES.len()];
+    for entry in entries {
+        for (column, (_, value)) in columns.iter_mut().zip(entry.metrics.fields()) {
+            column.push(value);
+        }
+    }
+    columns
+}
+
+/// 两列中均非 None 的 (条目下标, a, b)，即成对删除缺失值
+fn complete_pairs(a: &[Option<f64>], b: &[Option<f64>]) -> (Vec<usize>, Vec<f64>, Vec<f64>) {
+    let mut indices = Vec::new();
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        if let (Some(x), Some(y)) = (x, y) {
+            indices.push(i);
+            xs.push(*x);
+            ys.push(*y);
+        }
+    }
+    (indices, xs, ys)
+}
+
+/// 所有指标两两之间的 Spearman 相关，缺失值成对删除
+///
+/// 无缺失的指标只排一次秩；有缺失的指标对按成对完整的子集重新排秩。
+pub fn metric_correlations(entries: &[WordEntry]) -> Vec<MetricCorrelation> {
+    let names = DispersionMetrics::FIELD_NAMES;
+    let columns = metric_columns(entries);
+    let full_ranks: Vec<Option<Vec<f64>>> = columns
+        .iter()
+        .map(|column| {
+            let values: Option<Vec<f64>> = column.iter().copied().collect();
+            values.map(|v| average_ranks(&v))
+        })
+        .collect();
+
+    let mut correlations = Vec::new();
+    for a in 0..names.len() {
+        for b in a + 1..names.len() {
+            let (n, rho) = match (&full_ranks[a], &full_ranks[b]) {
+                (Some(ra), Some(rb)) => (ra.len(), pearson(ra, rb)),
+                _ => {
+                    let (_, xs, ys) = complete_pairs(&columns[a], &columns[b]);
+                    (xs.len(), pearson(&average_ranks(&xs), &average_ranks(&ys)))
+                }
+            };
+            correlations.push(MetricCorrelation {
+                metric_a: names[a].to_string(),
+                metric_b: names[b].to_string(),
+                n,
+                rho,
+            });
+        }
+    }
+    correlations
+}
+
+/// 两个指标下秩差绝对值最大的 limit 个词
+///
+/// 若两指标负相关（如 DP 越大越集中、Juilland D 越大越均匀），先反转 b 的秩再比较，
+/// 使秩差反映的是真正的分歧而非方向相反。指标名无效时返回 None。
+pub fn rank_disagreements(
+    entries: &[WordEntry],
+    metric_a: &str,
+    metric_b: &str,
+    limit: usize,
+) -> Option<Vec<RankDisagreement>> {
+    let names = DispersionMetrics::FIELD_NAMES;
+    let a = names.iter().position(|&n| n == metric_a)?;
+    let b = names.iter().position(|&n| n == metric_b)?;
+    let columns = metric_columns(entries);
+    let (indices, xs, ys) = complete_pairs(&columns[a], &columns[b]);
+
+    let ranks_a = average_ranks(&xs);
+    let mut ranks_b = average_ranks(&ys);
+    if pearson(&ranks_a, &ranks_b).is_some_and(|rho| rho < 0.0) {
+        let top = ranks_b.len() as f64 + 1.0;
+        ranks_b.iter_mut().for_each(|r| *r = top - *r);
+    }
+
+    let mut disagreements: Vec<RankDisagreement> = indices
+        .iter()
+        .zip(ranks_a.iter().zip(&ranks_b))
+        .map(|(&i, (&rank_a, &rank_b))| RankDisagreement {
+            word: entries[i].word.clone(),
+            pos: entries[i].pos.clone(),
+            rank_a,
+            rank_b,
+            rank_diff: rank_a - rank_b,
+        })
+        .collect();
+    disagreements.sort_by(|x, y| y.rank_diff.abs().total_cmp(&x.rank_diff.abs()));
+    disagreements.truncate(limit);
+    Some(disagreements)
+}
@@ -0,0 +1,289 @@
+// count_import.rs
+// 外部计数导入：读取其他工具给出的逐文件词频（长表或宽表），重建各部分词频后照常计算分布指标
+//
+// 长表每行一个 (word, pos, file, count)，列按表头名称识别、顺序不限；宽表表头为 word, pos，之后每个文件一列。
+// 部分词数缺省为该文件各词频次之和，即假定表中含有全部词；另给部分词数表（file, size）时以其为准。
+// 导入的结果未经分词与过滤，选项中只有指标选项（METRIC_OPTIONS）起作用。
+// 以扩展名 .tsv 结尾的文件按制表符分隔，其余按逗号分隔；错误信息中的行号从 1 开始，表头为第 1 行。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::{self, AnalysisResult, PartCounts},
+    metadata::CorpusMetadata,
+    options::AnalysisOptions,
+    paths,
+};
+
+/// 导入结果的分词引擎名称
+pub const IMPORT_ENGINE: &str = "import";
+
+/// 计数表格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatrixFormat {
+    /// 长表：word, pos, file, count
+    Long,
+    /// 宽表：word, pos, 之后每个文件一列
+    Wide,
+}
+
+/// 解析后的计数：文件按出现顺序（宽表为列顺序），各文件的 (词, 词性, 频次)
+#[derive(Default)]
+struct CountTable {
+    files: Vec<String>,
+    counts: Vec<Vec<(String, String, f64)>>,
+}
+
+impl CountTable {
+    fn file_index(&mut self, file: &str) -> usize {
+        match self.files.iter().position(|f| f == file) {
+            Some(i) => i,
+            None => {
+                self.files.push(file.to_string());
+                self.counts.push(Vec::new());
+                self.files.len() - 1
+            }
+        }
+    }
+}
+
+/// 导入计数表并计算分布指标；part_sizes 为部分词数表（file, size），缺省时以各文件频次之和为部分词数
+pub fn import_count_matrix(
+    path: &Path,
+    format: MatrixFormat,
+    part_sizes: Option<&Path>,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult, String> {
+    let table = match format {
+        MatrixFormat::Long => parse_long(&read_table(path)?, delimiter_for(path))?,
+        MatrixFormat::Wide => parse_wide(&read_table(path)?, delimiter_for(path))?,
+    };
+    if table.files.is_empty() {
+        return Err(format!("计数表 {} 中没有任何文件", paths::display(path)));
+    }
+    let sums: Vec<f64> = table
+        .counts
+        .iter()
+        .map(|counts| counts.iter().map(|(_, _, v)| v).sum())
+        .collect();
+    let sizes = match part_sizes {
+        Some(sizes_path) => read_part_sizes(sizes_path, &table.files, &sums)?,
+        None => sums,
+    };
+
+    let mut metadata = CorpusMetadata::new(IMPORT_ENGINE, options);
+    metadata.warnings = options.warnings();
+    metadata.warnings.push(format!(
+        "由外部计数表导入（{}），未经分词与过滤",
+        paths::display(path)
+    ));
+    let sources = table.files.into_iter().map(|file| (file, None)).collect();
+    let parts = table
+        .counts
+        .into_iter()
+        .zip(sizes)
+        .map(|(counts, size)| PartCounts {
+            counts,
+            size,
+            raw_size: size,
+            ..Default::default()
+        })
+        .collect();
+    corpus_pipeline::aggregate_parts(parts, sources, metadata)
+}
+
+fn read_table(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("无法读取计数表 {}: {e}", paths::display(path)))
+}
+
+fn delimiter_for(path: &Path) -> char {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") => '\t',
+        _ => ',',
+    }
+}
+
+/// 非空行的 (行号, 字段)；行号从 1 开始，含空行
+fn records(text: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, String> {
+    text.trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            split_record(line, delimiter)
+                .map(|fields| (i + 1, fields))
+                .ok_or_else(|| format!("第 {} 行：引号未闭合", i + 1))
+        })
+        .collect()
+}
+
+/// 按分隔符拆分一行，支持双引号括起的字段（其中 "" 表示一个引号）；不支持跨行字段，引号未闭合时返回 None
+fn split_record(line: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields.into_iter().map(|f| f.trim().to_string()).collect())
+}
+
+/// 解析频次：须为非负有限数
+fn parse_count(value: &str, row: usize) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(v) if v.is_finite() && v >= 0.0 => Ok(v),
+        Ok(_) => Err(format!("第 {row} 行：频次 {value} 不是非负数")),
+        Err(_) => Err(format!("第 {row} 行：频次无效: {value:?}")),
+    }
+}
+
+fn check_width(fields: &[String], header: &[String], row: usize) -> Result<(), String> {
+    if fields.len() != header.len() {
+        return Err(format!(
+            "第 {row} 行有 {} 列，表头有 {} 列",
+            fields.len(),
+            header.len()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_long(text: &str, delimiter: char) -> Result<CountTable, String> {
+    let mut rows = records(text, delimiter)?.into_iter();
+    let (_, header) = rows.next().ok_or("计数表为空")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("计数表缺少列: {name}"))
+    };
+    let [word, pos, file, count] = [
+        column("word")?,
+        column("pos")?,
+        column("file")?,
+        column("count")?,
+    ];
+    let mut table = CountTable::default();
+    let mut seen: HashMap<(String, String, String), usize> = HashMap::new();
+    for (row, fields) in rows {
+        check_width(&fields, &header, row)?;
+        let v = parse_count(&fields[count], row)?;
+        let key = (
+            fields[word].clone(),
+            fields[pos].clone(),
+            fields[file].clone(),
+        );
+        if let Some(first) = seen.insert(key.clone(), row) {
+            return Err(format!(
+                "第 {row} 行：({}, {}, {}) 与第 {first} 行重复",
+                key.0, key.1, key.2
+            ));
+        }
+        let (w, p, f) = key;
+        if f.is_empty() {
+            return Err(format!("第 {row} 行：文件名为空"));
+        }
+        let index = table.file_index(&f);
+        if v > 0.0 {
+            table.counts[index].push((w, p, v));
+        }
+    }
+    Ok(table)
+}
+
+fn parse_wide(text: &str, delimiter: char) -> Result<CountTable, String> {
+    let mut rows = records(text, delimiter)?.into_iter();
+    let (header_row, header) = rows.next().ok_or("计数表为空")?;
+    let is = |i: usize, name: &str| header.get(i).is_some_and(|h| h.eq_ignore_ascii_case(name));
+    if !is(0, "word") || !is(1, "pos") {
+        return Err(format!("第 {header_row} 行：宽表表头须以 word, pos 开头"));
+    }
+    let mut table = CountTable::default();
+    for file in &header[2..] {
+        if file.is_empty() {
+            return Err(format!("第 {header_row} 行：文件列名为空"));
+        }
+        if table.files.contains(file) {
+            return Err(format!("第 {header_row} 行：文件列 {file} 重复"));
+        }
+        table.file_index(file);
+    }
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    for (row, mut fields) in rows {
+        check_width(&fields, &header, row)?;
+        let values = fields
+            .split_off(2)
+            .iter()
+            .map(|value| parse_count(value, row))
+            .collect::<Result<Vec<f64>, String>>()?;
+        let pos = fields.pop().unwrap_or_default();
+        let word = fields.pop().unwrap_or_default();
+        if let Some(first) = seen.insert((word.clone(), pos.clone()), row) {
+            return Err(format!("第 {row} 行：({word}, {pos}) 与第 {first} 行重复"));
+        }
+        for (counts, v) in table.counts.iter_mut().zip(values) {
+            if v > 0.0 {
+                counts.push((word.clone(), pos.clone(), v));
+            }
+        }
+    }
+    Ok(table)
+}
+
+/// 读取部分词数表（file, size），按 files 的顺序返回；须与计数表的文件一一对应，且不小于该文件的频次之和
+fn read_part_sizes(path: &Path, files: &[String], sums: &[f64]) -> Result<Vec<f64>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("无法读取部分词数表 {}: {e}", paths::display(path)))?;
+    let mut rows = records(&text, delimiter_for(path))?.into_iter();
+    let (_, header) = rows.next().ok_or("部分词数表为空")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("部分词数表缺少列: {name}"))
+    };
+    let (file_column, size_column) = (column("file")?, column("size")?);
+    let mut sizes: Vec<Option<f64>> = vec![None; files.len()];
+    for (row, fields) in rows {
+        check_width(&fields, &header, row)?;
+        let file = &fields[file_column];
+        let Some(index) = files.iter().position(|f| f == file) else {
+            return Err(format!("部分词数表第 {row} 行：文件 {file} 不在计数表中"));
+        };
+        let size = parse_count(&fields[size_column], row).map_err(|e| format!("部分词数表{e}"))?;
+        if sizes[index].is_some() {
+            return Err(format!("部分词数表第 {row} 行：文件 {file} 重复"));
+        }
+        if size < sums[index] {
+            return Err(format!(
+                "部分词数表第 {row} 行：文件 {file} 的部分词数 {size} 小于其频次之和 {}",
+                sums[index]
+            ));
+        }
+        sizes[index] = Some(size);
+    }
+    files
+        .iter()
+        .zip(sizes)
+        .map(|(file, size)| size.ok_or_else(|| format!("部分词数表缺少文件: {file}")))
+        .collect()
+}
@@ -0,0 +1,172 @@
+// coverage.rs
+// 词表覆盖率：已掌握某个词表（如 HSK 1–4）的读者能读懂语料中多大比例的词次与词型
+//
+// 词表不区分词性，同一词的各词性合并计算；标点与纯符号词元不计入。
+// 只出现一次的未覆盖词多为专名或生僻词，另给出不计这些词时的词次覆盖率。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult,
+    token_policy::{is_symbol_token, PUNCTUATION_POS},
+};
+
+/// 一个范围（单个文件或全语料）的覆盖情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coverage {
+    /// 文件路径；全语料为空串
+    pub source: String,
+    pub tokens: f64,
+    pub covered_tokens: f64,
+    /// 词次覆盖率；没有词次时为 None
+    pub token_coverage: Option<f64>,
+    pub types: usize,
+    pub covered_types: usize,
+    pub type_coverage: Option<f64>,
+    /// 在此范围内只出现一次的未覆盖词型数
+    pub uncovered_hapaxes: usize,
+    /// 不计未覆盖 hapax 时的词次覆盖率
+    pub token_coverage_excluding_hapaxes: Option<f64>,
+}
+
+/// 未覆盖的词及其全语料频次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncoveredWord {
+    pub word: String,
+    pub frequency: f64,
+    /// 出现的文件数
+    pub files: usize,
+}
+
+/// 词表覆盖率结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListCoverage {
+    /// 词表中的词数
+    pub list_size: usize,
+    pub corpus: Coverage,
+    /// 各文件的覆盖情况，顺序同分析时的文件顺序
+    pub files: Vec<Coverage>,
+    /// 频次最高的未覆盖词，按频次降序
+    pub uncovered: Vec<UncoveredWord>,
+}
+
+/// 读取词表：每行一个词，"词<TAB>等级" 格式只取第一列；忽略空行与 # 注释行
+pub fn read_word_list(path: &Path) -> io::Result<HashSet<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split('\t').next())
+        .map(|word| word.trim().to_string())
+        .filter(|word| !word.is_empty())
+        .collect())
+}
+
+/// 计算词表在各文件与全语料上的覆盖率；需要各部分词频（本次分析的计数或 keep_parts）
+pub fn list_coverage(
+    result: &AnalysisResult,
+    list: &HashSet<String>,
+    top: usize,
+) -> Result<ListCoverage, String> {
+    // 各部分归入来源文件；逐句切分时同一文件的各句合并
+    let mut sources: Vec<&str> = Vec::new();
+    let mut source_index: HashMap<&str, usize> = HashMap::new();
+    let part_file: Vec<usize> = result
+        .metadata
+        .parts
+        .iter()
+        .map(|part| {
+            *source_index.entry(&part.source).or_insert_with(|| {
+                sources.push(&part.source);
+                sources.len() - 1
+            })
+        })
+        .collect();
+
+    let mut per_file: Vec<HashMap<String, f64>> = vec![HashMap::new(); sources.len()];
+    let found = result.for_each_part_count(|i, word, pos, v| {
+        if pos == PUNCTUATION_POS || is_symbol_token(word) {
+            return;
+        }
+        if let Some(&file) = part_file.get(i) {
+            *per_file[file].entry(word.to_string()).or_insert(0.0) += v;
+        }
+    });
+    if !found {
+        return Err("结果未保留各部分词频，请重新分析或开启 keep_parts".to_string());
+    }
+
+    let mut corpus: HashMap<String, (f64, usize)> = HashMap::new();
+    for words in &per_file {
+        for (word, &v) in words {
+            let entry = corpus.entry(word.clone()).or_insert((0.0, 0));
+            entry.0 += v;
+            entry.1 += 1;
+        }
+    }
+    let totals: HashMap<String, f64> = corpus.iter().map(|(w, &(v, _))| (w.clone(), v)).collect();
+
+    let mut uncovered: Vec<UncoveredWord> = corpus
+        .into_iter()
+        .filter(|(word, _)| !list.contains(word))
+        .map(|(word, (frequency, files))| UncoveredWord {
+            word,
+            frequency,
+            files,
+        })
+        .collect();
+    uncovered.sort_by(|a, b| {
+        b.frequency
+            .total_cmp(&a.frequency)
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    uncovered.truncate(top);
+
+    Ok(ListCoverage {
+        list_size: list.len(),
+        corpus: coverage(String::new(), &totals, list),
+        files: sources
+            .iter()
+            .zip(&per_file)
+            .map(|(source, words)| coverage(source.to_string(), words, list))
+            .collect(),
+        uncovered,
+    })
+}
+
+/// 一个范围内的覆盖情况
+fn coverage(source: String, words: &HashMap<String, f64>, list: &HashSet<String>) -> Coverage {
+    let mut result = Coverage {
+        source,
+        tokens: 0.0,
+        covered_tokens: 0.0,
+        token_coverage: None,
+        types: words.len(),
+        covered_types: 0,
+        type_coverage: None,
+        uncovered_hapaxes: 0,
+        token_coverage_excluding_hapaxes: None,
+    };
+    for (word, &v) in words {
+        result.tokens += v;
+        if list.contains(word) {
+            result.covered_tokens += v;
+            result.covered_types += 1;
+        } else if v == 1.0 {
+            result.uncovered_hapaxes += 1;
+        }
+    }
+    let ratio = |part: f64, whole: f64| (whole > 0.0).then(|| part / whole);
+    result.token_coverage = ratio(result.covered_tokens, result.tokens);
+    result.type_coverage = ratio(result.covered_types as f64, result.types as f64);
+    result.token_coverage_excluding_hapaxes = ratio(
+        result.covered_tokens,
+        result.tokens - result.uncovered_hapaxes as f64,
+    );
+    result
+}
@@ -0,0 +1,196 @@
+// dictionary.rs
+// 频率词典：按调整频次（默认 Juilland U）取前 N 个条目，每 band_size 个分为一段，并给出各段的分布度均值与词性构成
+//
+// 排序键相同时依次按频次降序、词形、词性升序决定先后，截断处的并列也按此取舍，并在 tie_note 中注明。
+// 排序指标缺失的条目（如频次为 0 的目标词）不收入词典。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{corpus_pipeline::AnalysisResult, diff, rank, word_entry::WordEntry};
+
+/// Juilland U：juilland_d × frequency，不是 DispersionMetrics 的字段，只用于词典排序
+pub const JUILLAND_U: &str = "juilland_u";
+
+/// 词典的收词数、排序指标与分段大小
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DictionaryOptions {
+    /// 收词数
+    pub n: usize,
+    /// 排序指标：juilland_u、frequency、gt_adjusted_frequency 或 DispersionMetrics 的字段；
+    /// 分布越集中值越大的指标（见 rank::CONCENTRATION_METRICS）按升序，其余按降序
+    pub order_by: String,
+    /// 每段条目数
+    pub band_size: usize,
+}
+
+impl Default for DictionaryOptions {
+    fn default() -> Self {
+        Self {
+            n: 5000,
+            order_by: JUILLAND_U.to_string(),
+            band_size: 500,
+        }
+    }
+}
+
+impl DictionaryOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.n == 0 {
+            return Err("收词数须大于 0".to_string());
+        }
+        if self.band_size == 0 {
+            return Err("分段大小须大于 0".to_string());
+        }
+        if self.order_by != JUILLAND_U && !diff::is_known_metric(&self.order_by) {
+            return Err(format!("无法排序的指标: {}", self.order_by));
+        }
+        Ok(())
+    }
+}
+
+/// 词典中的一个条目
+#[derive(Debug, Clone)]
+pub struct DictionaryEntry<'a> {
+    /// 所在段，从 1 开始
+    pub band: usize,
+    /// 词典序号，从 1 开始，并列时也各占一个序号
+    pub rank: usize,
+    /// 排序指标的值
+    pub value: f64,
+    pub entry: &'a WordEntry,
+}
+
+/// 一段的汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandSummary {
+    pub band: usize,
+    pub first_rank: usize,
+    pub last_rank: usize,
+    pub entries: usize,
+    /// 排序指标的均值
+    pub mean_value: f64,
+    /// 各指标的均值只计有值的条目；全部缺失时为 None
+    pub mean_juilland_d: Option<f64>,
+    pub mean_dp: Option<f64>,
+    /// 各词性的条目数，按条目数降序、词性升序
+    pub pos_counts: Vec<(String, usize)>,
+}
+
+/// 频率词典
+#[derive(Debug, Clone)]
+pub struct Dictionary<'a> {
+    pub order_by: String,
+    pub entries: Vec<DictionaryEntry<'a>>,
+    pub bands: Vec<BandSummary>,
+    /// 截断处有并列时的说明；没有并列时为 None
+    pub tie_note: Option<String>,
+}
+
+/// 导出频率词典后返回给界面的摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictionaryExport {
+    /// 导出文件的绝对路径
+    pub path: String,
+    pub bands: Vec<BandSummary>,
+    pub tie_note: Option<String>,
+}
+
+/// 条目的排序指标值；须先经 DictionaryOptions::validate 校验
+fn order_value(entry: &WordEntry, order_by: &str) -> Option<f64> {
+    if order_by == JUILLAND_U {
+        return entry.metrics.juilland_d.map(|d| d * entry.frequency);
+    }
+    diff::metric_value(entry, order_by).flatten()
+}
+
+fn mean(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = values
+        .flatten()
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// 取前 N 个条目并分段
+pub fn build<'a>(
+    result: &'a AnalysisResult,
+    options: &DictionaryOptions,
+) -> Result<Dictionary<'a>, String> {
+    options.validate()?;
+    let ascending = rank::CONCENTRATION_METRICS.contains(&options.order_by.as_str());
+    let mut ordered: Vec<(&WordEntry, f64)> = result
+        .entries
+        .iter()
+        .filter_map(|entry| order_value(entry, &options.order_by).map(|v| (entry, v)))
+        .collect();
+    ordered.sort_by(|(a, va), (b, vb)| {
+        let by_value = if ascending {
+            va.total_cmp(vb)
+        } else {
+            vb.total_cmp(va)
+        };
+        by_value
+            .then(b.frequency.total_cmp(&a.frequency))
+            .then_with(|| (&a.word, &a.pos).cmp(&(&b.word, &b.pos)))
+    });
+
+    let tie_note = ordered.get(options.n).and_then(|&(_, first_out)| {
+        let last_in = ordered[options.n - 1].1;
+        if last_in != first_out {
+            return None;
+        }
+        let tied = |&&(_, v): &&(&WordEntry, f64)| v == last_in;
+        let kept = ordered[..options.n].iter().rev().take_while(tied).count();
+        let dropped = ordered[options.n..].iter().take_while(tied).count();
+        Some(format!(
+            "第 {} 名处 {} = {last_in} 有 {} 个条目并列，按频次降序、词形与词性升序收入 {kept} 个、舍去 {dropped} 个",
+            options.n,
+            options.order_by,
+            kept + dropped
+        ))
+    });
+    ordered.truncate(options.n);
+
+    let entries: Vec<DictionaryEntry> = ordered
+        .into_iter()
+        .enumerate()
+        .map(|(i, (entry, value))| DictionaryEntry {
+            band: i / options.band_size + 1,
+            rank: i + 1,
+            value,
+            entry,
+        })
+        .collect();
+    let bands = entries
+        .chunks(options.band_size)
+        .map(|band| {
+            let mut pos_counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for e in band {
+                *pos_counts.entry(e.entry.pos.as_str()).or_default() += 1;
+            }
+            let mut pos_counts: Vec<(String, usize)> = pos_counts
+                .into_iter()
+                .map(|(pos, count)| (pos.to_string(), count))
+                .collect();
+            pos_counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+            BandSummary {
+                band: band[0].band,
+                first_rank: band[0].rank,
+                last_rank: band[band.len() - 1].rank,
+                entries: band.len(),
+                mean_value: band.iter().map(|e| e.value).sum::<f64>() / band.len() as f64,
+                mean_juilland_d: mean(band.iter().map(|e| e.entry.metrics.juilland_d)),
+                mean_dp: mean(band.iter().map(|e| e.entry.metrics.dp)),
+                pos_counts,
+            }
+        })
+        .collect();
+    Ok(Dictionary {
+        order_by: options.order_by.clone(),
+        entries,
+        bands,
+        tie_note,
+    })
+}
@@ -0,0 +1,159 @@
+// diff.rs
+// 两次分析结果的差异：按 (词, 词性) 对齐，列出新增、消失及指定指标变化超过阈值的词
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult, dispersion_metrics::DispersionMetrics,
+    metadata::CorpusMetadata, word_entry::WordEntry,
+};
+
+/// 差异类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    /// 仅出现在 b 中
+    Added,
+    /// 仅出现在 a 中
+    Removed,
+    /// 两边都有，指标变化超过阈值
+    Changed,
+}
+
+/// 一个词的差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub word: String,
+    pub pos: String,
+    pub kind: DiffKind,
+    pub value_a: Option<f64>,
+    pub value_b: Option<f64>,
+    /// value_b - value_a；任一侧缺失时为 None
+    pub change: Option<f64>,
+}
+
+/// 差异报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultDiff {
+    pub metric: String,
+    /// 两次分析的分词引擎、切分方式或选项不一致时的提示，此时差异可能来自设置而非语料
+    pub warnings: Vec<String>,
+    pub entries: Vec<DiffEntry>,
+}
+
+/// 取条目的指标值：frequency、gt_adjusted_frequency 或 DispersionMetrics 的字段；名称无效时为 None
+pub(crate) fn metric_value(entry: &WordEntry, metric: &str) -> Option<Option<f64>> {
+    match metric {
+        "frequency" => Some(Some(entry.frequency)),
+        "gt_adjusted_frequency" => Some(entry.gt_adjusted_frequency),
+        _ => entry
+            .metrics
+            .fields()
+            .into_iter()
+            .find(|(name, _)| *name == metric)
+            .map(|(_, value)| value),
+    }
+}
+
+/// 指标名是否可用于比较
+pub fn is_known_metric(metric: &str) -> bool {
+    matches!(metric, "frequency" | "gt_adjusted_frequency")
+        || DispersionMetrics::FIELD_NAMES.contains(&metric)
+}
+
+/// 比较两次分析的设置，列出会影响数值的差异
+pub fn setting_warnings(a: &CorpusMetadata, b: &CorpusMetadata) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if a.tokenizer != b.tokenizer {
+        warnings.push(format!("分词引擎不同: {} → {}", a.tokenizer, b.tokenizer));
+    }
+    if a.partition_mode != b.partition_mode {
+        warnings.push(format!(
+            "语料切分方式不同: {} → {}",
+            a.partition_mode, b.partition_mode
+        ));
+    }
+    for key in a.options.material_changes(&b.options) {
+        warnings.push(format!("选项 {key} 不同"));
+    }
+    warnings
+}
+
+/// 计算 a → b 的差异；变化量按绝对值降序，新增与消失的词排在其后
+///
+/// 指标名无效时返回 None。
+pub fn diff_results(
+    a: &AnalysisResult,
+    b: &AnalysisResult,
+    metric: &str,
+    threshold: f64,
+) -> Option<ResultDiff> {
+    if !is_known_metric(metric) {
+        return None;
+    }
+    let index_a: HashMap<(&str, &str), &WordEntry> = a
+        .entries
+        .iter()
+        .map(|e| ((e.word.as_str(), e.pos.as_str()), e))
+        .collect();
+    let mut entries = Vec::new();
+    let mut seen = 0;
+    for entry_b in &b.entries {
+        let value_b = metric_value(entry_b, metric).flatten();
+        let key = (entry_b.word.as_str(), entry_b.pos.as_str());
+        let (kind, value_a) = match index_a.get(&key) {
+            Some(entry_a) => {
+                seen += 1;
+                (DiffKind::Changed, metric_value(entry_a, metric).flatten())
+            }
+            None => (DiffKind::Added, None),
+        };
+        let change = value_a.zip(value_b).map(|(x, y)| y - x);
+        // 两边都有时，仅保留变化超过阈值、或一侧指标缺失的词
+        if kind == DiffKind::Changed
+            && change.is_none_or(|c| c.abs() <= threshold)
+            && value_a.is_some() == value_b.is_some()
+        {
+            continue;
+        }
+        entries.push(DiffEntry {
+            word: entry_b.word.clone(),
+            pos: entry_b.pos.clone(),
+            kind,
+            value_a,
+            value_b,
+            change,
+        });
+    }
+    if seen < a.entries.len() {
+        let index_b: HashMap<(&str, &str), ()> = b
+            .entries
+            .iter()
+            .map(|e| ((e.word.as_str(), e.pos.as_str()), ()))
+            .collect();
+        for entry_a in &a.entries {
+            if !index_b.contains_key(&(entry_a.word.as_str(), entry_a.pos.as_str())) {
+                entries.push(DiffEntry {
+                    word: entry_a.word.clone(),
+                    pos: entry_a.pos.clone(),
+                    kind: DiffKind::Removed,
+                    value_a: metric_value(entry_a, metric).flatten(),
+                    value_b: None,
+                    change: None,
+                });
+            }
+        }
+    }
+    entries.sort_by(|x, y| {
+        let abs = |e: &DiffEntry| e.change.map_or(-1.0, f64::abs);
+        abs(y).total_cmp(&abs(x))
+    });
+
+    Some(ResultDiff {
+        metric: metric.to_string(),
+        warnings: setting_warnings(&a.metadata, &b.metadata),
+        entries,
+    })
+}
@@ -3,53 +3,160 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct DispersionMetrics {
-    pub range: usize,
-    pub sd_population: Option<f64>,
-    pub vc_population: Option<f64>,
-    pub juilland_d: Option<f64>,
-    pub carroll_d2: Option<f64>,
-    pub roschengren_s_adj: Option<f64>,
-    pub dp: Option<f64>,
-    pub dp_norm: Option<f64>,
-    pub kl_divergence: Option<f64>,
-    pub jsd_dispersion: Option<f64>,
-    pub hellinger_dispersion: Option<f64>,
-    pub mean_text_frequency_ft: Option<f64>,
-    pub pervasiveness_pt: Option<f64>,
-    pub evenness_da: Option<f64>,
-    pub ft_adjusted_by_pt: Option<f64>,
-    pub ft_adjusted_by_da: Option<f64>,
+/// Display 缺省的小数位数
+pub const DISPLAY_PRECISION: usize = 4;
+
+/// 按小数位数舍入并去掉末尾多余的 0，"-0" 写为 "0"；小数点为 '.'
+///
+/// 舍入基于浮点数的精确十进制值、恰好居中时取偶
+pub fn format_rounded(value: f64, precision: usize) -> String {
+    let mut s = format!("{value:.*}", precision);
+    if s.contains('.') {
+        let trimmed = s.trim_end_matches('0').trim_end_matches('.').len();
+        s.truncate(trimmed);
+    }
+    if s == "-0" {
+        s = "0".to_string();
+    }
+    s
+}
+
+/// 由同一份字段表生成结构体、FIELD_NAMES、fields() 与 float_fields_mut()，新增字段不会漏掉清洗或列名；
+/// count 字段为 usize（部分数），rate 字段为 Option<f64>
+macro_rules! metric_fields {
+    ($($(#[$meta:meta])* $kind:ident $field:ident),* $(,)?) => {
+        #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+        pub struct DispersionMetrics {
+            $($(#[$meta])* pub $field: metric_fields!(@type $kind),)*
+        }
+
+        impl DispersionMetrics {
+            /// 指标字段名，顺序与 fields() 及序列化的字段一致
+            pub const FIELD_NAMES: [&'static str; metric_fields!(@count $($field)*)] =
+                [$(stringify!($field)),*];
+
+            /// 按 FIELD_NAMES 顺序列出所有指标值（部分数转为浮点）
+            pub fn fields(&self) -> [(&'static str, Option<f64>); metric_fields!(@count $($field)*)] {
+                [$((stringify!($field), metric_fields!(@value $kind self.$field))),*]
+            }
+
+            /// 所有浮点指标字段的可变引用及字段名
+            pub fn float_fields_mut(&mut self) -> Vec<(&'static str, &mut Option<f64>)> {
+                [$(metric_fields!(@float $kind stringify!($field), &mut self.$field)),*]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+        }
+    };
+    (@type count) => { usize };
+    (@type rate) => { Option<f64> };
+    (@count) => { 0 };
+    (@count $head:ident $($tail:ident)*) => { 1 + metric_fields!(@count $($tail)*) };
+    (@value count $value:expr) => { Some($value as f64) };
+    (@value rate $value:expr) => { $value };
+    (@float count $name:expr, $place:expr) => { None };
+    (@float rate $name:expr, $place:expr) => { Some(($name, $place)) };
+}
+
+metric_fields! {
+    count range,
+    rate sd_population,
+    rate vc_population,
+    rate juilland_d,
+    rate carroll_d2,
+    rate roschengren_s_adj,
+    /// 未调整的 Roschengren S（部分等权）
+    rate roschengren_s,
+    rate dp,
+    rate dp_norm,
+    rate kl_divergence,
+    rate jsd_dispersion,
+    rate hellinger_dispersion,
+    rate mean_text_frequency_ft,
+    /// FT 按 AnalysisOptions::rate_base 缩放（默认每百万词）
+    rate mean_text_frequency_ft_pm,
+    rate pervasiveness_pt,
+    rate evenness_da,
+    rate ft_adjusted_by_pt,
+    rate ft_adjusted_by_da,
+    /// 最高部分占比 max(v_i) / f
+    rate max_part_share,
+    /// 各部分频率的基尼系数
+    rate gini,
+    /// 各部分频率（v_i / 部分词数，同 FT 未缩放）的最小值，不含词数为 0 的部分；均值即 FT
+    rate min_part_rate,
+    /// 各部分频率的最大值，所在部分见 WordEntry::max_part_index
+    rate max_part_rate,
+    /// 出现次数不少于 AnalysisOptions::range_threshold 的部分数；range 仍为出现过的部分数
+    #[serde(default)]
+    count range_at_threshold,
+    /// range_at_threshold / 部分数
+    #[serde(default)]
+    rate pervasiveness_pt_at_threshold,
+    /// 文件内滑窗 DP 的均值（仅开启滑窗分析时计算）
+    rate within_file_dp,
+    /// 文件内滑窗 Juilland D 的均值（仅开启滑窗分析时计算）
+    rate within_file_juilland_d,
+}
+
+impl DispersionMetrics {
+    /// 指标列的表头，与 to_row 一一对应；CSV 导出与命令行共用，列顺序即 FIELD_NAMES
+    pub fn to_header() -> Vec<String> {
+        Self::FIELD_NAMES.map(String::from).to_vec()
+    }
+
+    /// 按 to_header 的顺序格式化各指标值，见 format_rounded；None 为空字符串
+    pub fn to_row(&self, precision: usize) -> Vec<String> {
+        self.fields()
+            .iter()
+            .map(|(_, value)| {
+                value
+                    .map(|v| format_rounded(v, precision))
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// 以 precision 位小数逐行写出各指标；缺失值写为 "-"
+    pub fn fmt_with_precision(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        precision: usize,
+    ) -> std::fmt::Result {
+        let width = Self::FIELD_NAMES
+            .iter()
+            .map(|name| name.len())
+            .max()
+            .unwrap_or(0);
+        let rows = Self::FIELD_NAMES.iter().zip(self.to_row(precision));
+        for (i, (name, value)) in rows.enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let value = if value.is_empty() { "-" } else { &value };
+            write!(f, "{name:<width$}  {value}")?;
+        }
+        Ok(())
+    }
+
+    /// 将非有限值（NaN/±Inf）清洗为 None，返回被清洗的字段名
+    pub fn sanitize(&mut self) -> Vec<&'static str> {
+        let mut sanitized = Vec::new();
+        for (name, value) in self.float_fields_mut() {
+            if value.is_some_and(|x| !x.is_finite()) {
+                *value = None;
+                sanitized.push(name);
+            }
+        }
+        sanitized
+    }
 }
 
 impl std::fmt::Display for DispersionMetrics {
+    /// 每行一个指标：名称对齐后接数值；精度取格式说明中的精度（如 {:.2}），缺省为 DISPLAY_PRECISION
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "DispersionMetrics {{")?;
-        writeln!(f, "  range: {},", self.range)?;
-        writeln!(f, "  sd_population: {:?},", self.sd_population)?;
-        writeln!(f, "  vc_population: {:?},", self.vc_population)?;
-        writeln!(f, "  juilland_d: {:?},", self.juilland_d)?;
-        writeln!(f, "  carroll_d2: {:?},", self.carroll_d2)?;
-        writeln!(f, "  roschengren_s_adj: {:?},", self.roschengren_s_adj)?;
-        writeln!(f, "  dp: {:?},", self.dp)?;
-        writeln!(f, "  dp_norm: {:?},", self.dp_norm)?;
-        writeln!(f, "  kl_divergence: {:?},", self.kl_divergence)?;
-        writeln!(f, "  jsd_dispersion: {:?},", self.jsd_dispersion)?;
-        writeln!(
-            f,
-            "  hellinger_dispersion: {:?},",
-            self.hellinger_dispersion
-        )?;
-        writeln!(
-            f,
-            "  mean_text_frequency_ft: {:?},",
-            self.mean_text_frequency_ft
-        )?;
-        writeln!(f, "  pervasiveness_pt: {:?},", self.pervasiveness_pt)?;
-        writeln!(f, "  evenness_da: {:?},", self.evenness_da)?;
-        writeln!(f, "  ft_adjusted_by_pt: {:?},", self.ft_adjusted_by_pt)?;
-        writeln!(f, "  ft_adjusted_by_da: {:?}", self.ft_adjusted_by_da)?;
-        write!(f, "}}")
+        let precision = f.precision().unwrap_or(DISPLAY_PRECISION);
+        self.fmt_with_precision(f, precision)
     }
 }
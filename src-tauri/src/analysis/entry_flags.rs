@@ -0,0 +1,101 @@
+// entry_flags.rs
+// 条目标记：标出词形经过规范化、由多种写法合并而来等需要下游留意的条目
+//
+// 标记以名称列表保存在 WordEntry::flags 中，按 FLAGS 的顺序排列；新增标记只需在 FLAGS 中追加一项并在 flags_for
+// 中给出判定，不改变条目与导出的结构。标记都由条目本身决定，分析结束、载入会话时重新计算。
+// 拉丁字母大小写为 lower 时不保留折叠前的写法，无从判断条目是否经过折叠，normalized 与 merged_variants
+// 仅在 latin_case 为 fold 时给出。
+
+use serde::Serialize;
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult, posgrams::POSGRAM_POS, targets::ABSENT_POS,
+    token_policy::is_latin_letter, word_entry::WordEntry,
+};
+
+/// 词形经过规范化：至少有一种折叠前的写法与条目不同
+pub const NORMALIZED: &str = "normalized";
+/// 由两种以上的写法合并而来
+pub const MERGED_VARIANTS: &str = "merged_variants";
+/// 含拉丁字母（含全角字母），词性序列条目除外
+pub const CONTAINS_LATIN: &str = "contains_latin";
+/// 全语料只出现一次
+pub const HAPAX: &str = "hapax";
+/// 语料中未出现的目标词
+pub const ABSENT_TARGET: &str = "absent_target";
+
+/// 一种标记及其说明
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct EntryFlag {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn flag(name: &'static str, description: &'static str) -> EntryFlag {
+    EntryFlag { name, description }
+}
+
+/// 全部标记，条目中的标记按此顺序
+pub const FLAGS: [EntryFlag; 5] = [
+    flag(
+        NORMALIZED,
+        "词形经过规范化（拉丁字母大小写折叠），仅 latin_case 为 fold 时标出",
+    ),
+    flag(
+        MERGED_VARIANTS,
+        "由多种写法合并而来，各写法见 surface_forms",
+    ),
+    flag(CONTAINS_LATIN, "含拉丁字母"),
+    flag(HAPAX, "全语料只出现一次"),
+    flag(ABSENT_TARGET, "语料中未出现的目标词"),
+];
+
+/// 条目应有的标记，按 FLAGS 的顺序
+pub fn flags_for(entry: &WordEntry) -> Vec<String> {
+    FLAGS
+        .iter()
+        .map(|flag| flag.name)
+        .filter(|&name| match name {
+            NORMALIZED => entry
+                .surface_forms
+                .iter()
+                .any(|v| v.other || v.form != entry.word),
+            MERGED_VARIANTS => entry.surface_forms.len() > 1,
+            CONTAINS_LATIN => entry.pos != POSGRAM_POS && entry.word.chars().any(is_latin_letter),
+            HAPAX => entry.frequency == 1.0,
+            ABSENT_TARGET => entry.pos == ABSENT_POS,
+            _ => false,
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// 导出时的标记列：各标记名以 " | " 分隔
+pub fn display(flags: &[String]) -> String {
+    flags.join(" | ")
+}
+
+/// 重新计算各条目的标记
+pub fn assign(result: &mut AnalysisResult) {
+    for entry in &mut result.entries {
+        entry.flags = flags_for(entry);
+    }
+}
+
+/// 校验标记名
+///
+/// ```
+/// use betawordlist_lib::analysis::entry_flags::validate_names;
+///
+/// assert!(validate_names(&["hapax".to_string()]).is_ok());
+/// assert!(validate_names(&["rare".to_string()]).is_err());
+/// ```
+pub fn validate_names(names: &[String]) -> Result<(), String> {
+    match names
+        .iter()
+        .find(|name| !FLAGS.iter().any(|flag| flag.name == name.as_str()))
+    {
+        Some(name) => Err(format!("未知的条目标记: {name}")),
+        None => Ok(()),
+    }
+}
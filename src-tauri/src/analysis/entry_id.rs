@@ -0,0 +1,62 @@
+// entry_id.rs
+// 条目标识：由词形、词性与规范化签名确定的稳定 id，供跨次分析、跨平台连接导出结果
+//
+// id = SHA-256("签名\x1f词\x1f词性") 的前 16 个十六进制字符（64 位）。签名只含改变词形的选项，
+// 两次导出的 metadata.normalization_signature 相同时，同一 id 指同一 (词形, 词性)。
+// 计算方式随 ID_SCHEME 固定；今后若需改动，改用新的方案名，旧方案的 id 保持不变。
+
+use sha2::{Digest, Sha256};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult, options::AnalysisOptions, token_policy::LatinCase,
+};
+
+/// id 的计算方案，写入签名开头
+pub const ID_SCHEME: &str = "id1";
+
+/// 规范化签名：决定条目词形的选项（拉丁字母大小写、词元清理），lower 与 fold 得到相同的词形
+pub fn normalization_signature(options: &AnalysisOptions) -> String {
+    let case = match options.latin_case {
+        LatinCase::Preserve => "preserve",
+        LatinCase::Lower | LatinCase::Fold => "lower",
+    };
+    format!(
+        "{ID_SCHEME};latin_case={case};sanitize={}",
+        u8::from(options.token_policy.sanitize)
+    )
+}
+
+/// 条目 id：16 个小写十六进制字符
+///
+/// ```
+/// use betawordlist_lib::analysis::entry_id;
+///
+/// let signature = "id1;latin_case=preserve;sanitize=0";
+/// let id = entry_id::entry_id("研究", "v", signature);
+/// // 固定的算法，跨平台、跨版本不变
+/// assert_eq!(id, "202d12328ce02db6");
+/// assert_ne!(id, entry_id::entry_id("研究", "n", signature));
+/// assert_ne!(id, entry_id::entry_id("研究", "v", "id1;latin_case=lower;sanitize=0"));
+/// ```
+pub fn entry_id(word: &str, pos: &str, signature: &str) -> String {
+    let mut hasher = Sha256::new();
+    for (i, part) in [signature, word, pos].into_iter().enumerate() {
+        if i > 0 {
+            hasher.update([0x1f]);
+        }
+        hasher.update(part.as_bytes());
+    }
+    hasher.finalize()[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 按结果的选项写入规范化签名与各条目的 id
+pub fn assign(result: &mut AnalysisResult) {
+    let signature = normalization_signature(&result.metadata.options);
+    for entry in &mut result.entries {
+        entry.id = entry_id(&entry.word, &entry.pos, &signature);
+    }
+    result.metadata.normalization_signature = signature;
+}
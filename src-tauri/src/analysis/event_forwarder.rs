@@ -0,0 +1,207 @@
+// event_forwarder.rs
+// 事件转发：分析线程只把事件放入有界队列，由转发线程合并、限速后交给下游上报，分析线程从不等待前端
+//
+// 进度事件只保留最新的一个，来不及送出的中间进度直接被覆盖；file-counted 事件排队，队列满时丢弃并计数；
+// complete / error 从不丢弃，队列满时另存，在队列排空后送出，且总在最新进度之后。
+// 非终止事件之间至少间隔 MIN_INTERVAL，即每秒至多约 20 个。
+
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::analysis::progress::{
+    AnalysisComplete, AnalysisError, AnalysisProgress, FileCounted, ProgressReporter, ReportedEvent,
+};
+
+/// 相邻两个非终止事件的最小间隔
+pub const MIN_INTERVAL: Duration = Duration::from_millis(50);
+/// 队列中等待转发的 file-counted 与终止事件数上限
+pub const QUEUE_CAPACITY: usize = 256;
+
+/// 不经队列的事件：最新进度、队列满时的终止事件与丢弃计数
+#[derive(Default)]
+struct Pending {
+    progress: Option<AnalysisProgress>,
+    terminal: Option<ReportedEvent>,
+    dropped: usize,
+}
+
+/// 经转发线程上报；drop 时关闭队列，转发线程送完剩余事件后自行退出
+pub struct ForwardingReporter {
+    /// 下游；取消状态直接向它查询，不经队列
+    inner: Arc<dyn ProgressReporter>,
+    /// finish 或 drop 后为 None
+    sender: Option<SyncSender<ReportedEvent>>,
+    pending: Arc<Mutex<Pending>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ForwardingReporter {
+    /// 以默认的队列容量与限速间隔启动转发线程
+    pub fn spawn(inner: Arc<dyn ProgressReporter>) -> Self {
+        Self::with_limits(inner, QUEUE_CAPACITY, MIN_INTERVAL)
+    }
+
+    pub fn with_limits(
+        inner: Arc<dyn ProgressReporter>,
+        capacity: usize,
+        interval: Duration,
+    ) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        let thread_pending = pending.clone();
+        let thread_inner = inner.clone();
+        let thread = thread::spawn(move || {
+            forward(thread_inner.as_ref(), &receiver, &thread_pending, interval)
+        });
+        Self {
+            inner,
+            sender: Some(sender),
+            pending,
+            thread: Some(thread),
+        }
+    }
+
+    /// 队列满而丢弃的 file-counted 事件数
+    pub fn dropped(&self) -> usize {
+        self.pending.lock().unwrap().dropped
+    }
+
+    /// 关闭队列并等待转发线程送出剩余事件；命令行与测试用，界面中直接 drop 即可
+    pub fn finish(mut self) {
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+
+    /// 终止事件：队列满时另存，由转发线程在队列排空后送出
+    fn terminal(&self, event: ReportedEvent) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if let Err(TrySendError::Full(event) | TrySendError::Disconnected(event)) =
+            sender.try_send(event)
+        {
+            self.pending.lock().unwrap().terminal = Some(event);
+        }
+    }
+}
+
+impl ProgressReporter for ForwardingReporter {
+    fn progress(&self, event: &AnalysisProgress) {
+        self.pending.lock().unwrap().progress = Some(event.clone());
+    }
+
+    fn file_counted(&self, event: &FileCounted) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if sender
+            .try_send(ReportedEvent::FileCounted(event.clone()))
+            .is_err()
+        {
+            self.pending.lock().unwrap().dropped += 1;
+        }
+    }
+
+    fn complete(&self, event: &AnalysisComplete) {
+        self.terminal(ReportedEvent::Complete(event.clone()));
+    }
+
+    fn error(&self, event: &AnalysisError) {
+        self.terminal(ReportedEvent::Error(event.clone()));
+    }
+
+    fn cancelled(&self) -> bool {
+        self.inner.cancelled()
+    }
+}
+
+impl Drop for ForwardingReporter {
+    fn drop(&mut self) {
+        self.sender.take();
+    }
+}
+
+/// 转发线程：每个间隔至多送出一个非终止事件，进度与队列中的事件轮流送出；
+/// 队列关闭且排空后送出最新进度与另存的终止事件并退出
+fn forward(
+    inner: &dyn ProgressReporter,
+    receiver: &Receiver<ReportedEvent>,
+    pending: &Mutex<Pending>,
+    interval: Duration,
+) {
+    let mut next = Instant::now();
+    let mut progress_turn = true;
+    loop {
+        let wait = next.saturating_duration_since(Instant::now());
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+        progress_turn = !progress_turn;
+        if progress_turn {
+            let progress = pending.lock().unwrap().progress.take();
+            if let Some(progress) = progress {
+                inner.progress(&progress);
+                next = Instant::now() + interval;
+                continue;
+            }
+        }
+        match receiver.try_recv() {
+            Ok(ReportedEvent::FileCounted(event)) => {
+                inner.file_counted(&event);
+                next = Instant::now() + interval;
+            }
+            Ok(event) => {
+                flush_progress(inner, pending);
+                deliver(inner, &event);
+                next = Instant::now() + interval;
+            }
+            Err(TryRecvError::Empty) => {
+                let terminal = pending.lock().unwrap().terminal.take();
+                match terminal {
+                    Some(event) => {
+                        flush_progress(inner, pending);
+                        deliver(inner, &event);
+                        next = Instant::now() + interval;
+                    }
+                    // 空闲时也按间隔轮询，进度随到随送
+                    None => {
+                        let progress = pending.lock().unwrap().progress.take();
+                        if let Some(progress) = progress {
+                            inner.progress(&progress);
+                        }
+                        next = Instant::now() + interval;
+                    }
+                }
+            }
+            Err(TryRecvError::Disconnected) => {
+                flush_progress(inner, pending);
+                let terminal = pending.lock().unwrap().terminal.take();
+                if let Some(event) = terminal {
+                    deliver(inner, &event);
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// 送出尚未送出的最新进度
+fn flush_progress(inner: &dyn ProgressReporter, pending: &Mutex<Pending>) {
+    let progress = pending.lock().unwrap().progress.take();
+    if let Some(progress) = progress {
+        inner.progress(&progress);
+    }
+}
+
+fn deliver(inner: &dyn ProgressReporter, event: &ReportedEvent) {
+    match event {
+        ReportedEvent::Progress(e) => inner.progress(e),
+        ReportedEvent::FileCounted(e) => inner.file_counted(e),
+        ReportedEvent::Complete(e) => inner.complete(e),
+        ReportedEvent::Error(e) => inner.error(e),
+    }
+}
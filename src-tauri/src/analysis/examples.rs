@@ -0,0 +1,134 @@
+// examples.rs
+// 例句采样：分析过程中为每个词以蓄水池抽样保留至多 k 个例句，无需再次扫描语料
+//
+// 第一个文件中出现的词都参与采样；此后只为累计频次达到 example_min_frequency 的词新开蓄水池，
+// 避免大量低频词各自保存例句。随机数种子由分析种子推出，同一语料与种子的抽样结果可复现。
+// 词按与计数相同的大小写折叠归并，例句保留原文写法。
+
+use std::collections::HashMap;
+
+use crate::analysis::{
+    text,
+    token_policy::{self, LatinCase},
+};
+
+/// 例句最多保留的字符数，超出部分以省略号代替
+const MAX_SENTENCE_CHARS: usize = 80;
+
+/// 单个词的蓄水池
+#[derive(Default)]
+struct Reservoir {
+    /// 开始采样后见过的含该词句子数
+    seen: u64,
+    samples: Vec<String>,
+}
+
+/// 跨文件的例句采样器
+pub struct ExampleSampler {
+    k: usize,
+    min_frequency: f64,
+    latin_case: LatinCase,
+    files_seen: usize,
+    /// 各词累计频次，用于第一个文件之后的频次下限判断
+    frequency: HashMap<(String, String), f64>,
+    reservoirs: HashMap<(String, String), Reservoir>,
+    rng: u64,
+}
+
+impl ExampleSampler {
+    pub fn new(k: usize, min_frequency: f64, latin_case: LatinCase, seed: u64) -> Self {
+        Self {
+            k,
+            min_frequency,
+            latin_case,
+            files_seen: 0,
+            frequency: HashMap::new(),
+            reservoirs: HashMap::new(),
+            rng: seed,
+        }
+    }
+
+    /// 采样一个文件的词元流（分析用的同一份分词结果）
+    pub fn add_text(&mut self, word_pos: &[(String, String)]) {
+        let folded: Vec<(String, String)>;
+        let keys = if self.latin_case == LatinCase::Preserve {
+            word_pos
+        } else {
+            folded = word_pos
+                .iter()
+                .map(|(w, p)| {
+                    let w = token_policy::fold_latin(w, self.latin_case).into_owned();
+                    (w, p.clone())
+                })
+                .collect();
+            &folded
+        };
+        let mut start = 0;
+        for sentence in text::split_sentences(word_pos) {
+            let sentence_keys = &keys[start..start + sentence.len()];
+            start += sentence.len();
+            let mut rendered: Option<String> = None;
+            // 去重并排序，使随机数的消耗顺序确定
+            let mut unique: Vec<&(String, String)> = sentence_keys.iter().collect();
+            unique.sort();
+            unique.dedup();
+            for key in unique {
+                if !self.reservoirs.contains_key(key) {
+                    let eligible = self.files_seen == 0
+                        || self.frequency.get(key).copied().unwrap_or(0.0) >= self.min_frequency;
+                    if !eligible {
+                        continue;
+                    }
+                    self.reservoirs.insert(key.clone(), Reservoir::default());
+                }
+                let reservoir = self.reservoirs.get_mut(key).unwrap();
+                reservoir.seen += 1;
+                let slot = if reservoir.samples.len() < self.k {
+                    reservoir.samples.len()
+                } else {
+                    (next_random(&mut self.rng) % reservoir.seen) as usize
+                };
+                if slot >= self.k {
+                    continue;
+                }
+                let example = rendered
+                    .get_or_insert_with(|| {
+                        let words = sentence.iter().map(|(w, _)| w.as_str());
+                        text::join_tokens(words, MAX_SENTENCE_CHARS)
+                    })
+                    .clone();
+                if slot == reservoir.samples.len() {
+                    reservoir.samples.push(example);
+                } else {
+                    reservoir.samples[slot] = example;
+                }
+            }
+        }
+        for key in keys {
+            match self.frequency.get_mut(key) {
+                Some(frequency) => *frequency += 1.0,
+                None => {
+                    self.frequency.insert(key.clone(), 1.0);
+                }
+            }
+        }
+        self.files_seen += 1;
+    }
+
+    /// 取出各词的例句
+    pub fn into_examples(self) -> HashMap<(String, String), Vec<String>> {
+        self.reservoirs
+            .into_iter()
+            .map(|(key, reservoir)| (key, reservoir.samples))
+            .collect()
+    }
+}
+
+/// SplitMix64
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,1336 @@
+// export.rs
+// 结果导出：CSV/TSV 及其 .meta.json 元数据，以及指标比较表、差异报告、词表覆盖率、近重复文件报告、各部分词性分布与频率词典
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    character_families::CharacterFamily,
+    corpus_pipeline::AnalysisResult,
+    correlation::{MetricCorrelation, RankDisagreement},
+    coverage::{Coverage, UncoveredWord},
+    dictionary::{Dictionary, DictionaryEntry},
+    diff::{DiffKind, ResultDiff},
+    dispersion_metrics::{self, DispersionMetrics},
+    entry_flags,
+    filter::FilterReport,
+    group_keywords::GroupKeywords,
+    metadata::{CorpusMetadata, PartInfo},
+    metric_descriptions::{self, MetricDescription},
+    near_duplicates::DuplicatePair,
+    oov::OovCandidate,
+    phrases::PHRASE_POS,
+    pos_histogram::PosOutlier,
+    posgrams::POSGRAM_POS,
+    profile::{OutputProfile, ProfileContext},
+    rank,
+    tagset::{self, LabelLanguage},
+    text,
+    word_entry::{WordEntry, POSITION_FIELDS},
+    xlsx::{Cell, XlsxWriter},
+};
+
+/// 同一单元格内多个例句的分隔符
+const EXAMPLE_SEPARATOR: &str = " | ";
+
+/// 表格导出格式：小数位数、小数点与字段分隔符
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// 小数位数，末尾多余的 0 会被去掉
+    pub precision: usize,
+    /// 小数点：'.' 或 ','（欧陆 Excel）
+    pub decimal_separator: char,
+    /// 字段分隔符，如 ','、';' 或 '\t'（TSV）
+    pub delimiter: char,
+    /// 在 word 列后增加 surface_form 列，写出频次最高的原文写法（见 WordEntry::top_surface_form），
+    /// 便于在开启规范化时回溯原文；columns 中列出 surface_form 时同样写出
+    pub surface_form: bool,
+    /// 在 pos 列后增加 pos_label 列，写出词性的中文或英文名称；None 表示不写
+    pub pos_label: Option<LabelLanguage>,
+    /// 追加 rank_<指标> 排名列，在导出的条目中计算，见 rank 模块
+    pub ranks: Vec<String>,
+    /// 附上指标说明（见 metric_descriptions）：XLSX 另加 definitions 表，.meta.json 另加 metric_definitions
+    pub definitions: bool,
+    /// 条目表的列及其顺序，取自 column_names；为空时写出 entry_header 的全部列。
+    /// 列出的 rank_<指标> 即使不在 ranks 中也会计算
+    pub columns: Vec<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            precision: 6,
+            decimal_separator: '.',
+            delimiter: ',',
+            surface_form: false,
+            pos_label: None,
+            ranks: Vec::new(),
+            definitions: false,
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// 校验格式：小数点只能是 '.' 或 ','，且不能与字段分隔符相同
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.decimal_separator, '.' | ',') {
+            return Err(format!("不支持的小数点: {:?}", self.decimal_separator));
+        }
+        if self.delimiter == self.decimal_separator {
+            return Err(format!("字段分隔符与小数点相同: {:?}", self.delimiter));
+        }
+        if matches!(self.delimiter, '"' | '\n' | '\r') || self.delimiter.is_alphanumeric() {
+            return Err(format!("不支持的字段分隔符: {:?}", self.delimiter));
+        }
+        if self.precision > 17 {
+            return Err(format!("小数位数过大: {}", self.precision));
+        }
+        rank::validate_names(&self.ranks)?;
+        self.validate_columns()
+    }
+
+    /// 校验 columns：列名须取自 column_names 且不重复，列出 pos_label 时须指定其语言
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::export::ExportOptions;
+    ///
+    /// let columns = |names: &[&str]| ExportOptions {
+    ///     columns: names.iter().map(|n| n.to_string()).collect(),
+    ///     ..ExportOptions::default()
+    /// };
+    /// assert!(columns(&["word", "frequency", "juilland_d", "rank_dp"]).validate().is_ok());
+    /// let error = columns(&["word", "zipf"]).validate().unwrap_err();
+    /// assert!(error.contains("zipf") && error.contains("gt_adjusted_frequency"));
+    /// assert!(columns(&["word", "word"]).validate().is_err());
+    /// assert!(columns(&["word", "pos_label"]).validate().is_err());
+    /// ```
+    fn validate_columns(&self) -> Result<(), String> {
+        let known = column_names();
+        for (i, name) in self.columns.iter().enumerate() {
+            if !known.contains(name) {
+                return Err(format!(
+                    "未知的导出列: {name}；可用的列: {}",
+                    known.join(", ")
+                ));
+            }
+            if self.columns[..i].contains(name) {
+                return Err(format!("导出列重复: {name}"));
+            }
+        }
+        if self.pos_label.is_none() && self.columns.iter().any(|c| c == "pos_label") {
+            return Err("导出 pos_label 列须指定词性名称的语言".to_string());
+        }
+        Ok(())
+    }
+
+    /// 需要计算排名的指标：ranks，其后是 columns 中另外列出的 rank_<指标>
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::export::ExportOptions;
+    ///
+    /// let options = ExportOptions {
+    ///     ranks: vec!["dp".into()],
+    ///     columns: ["word", "rank_frequency", "rank_dp"].map(String::from).to_vec(),
+    ///     ..ExportOptions::default()
+    /// };
+    /// assert_eq!(options.rank_metrics(), ["dp", "frequency"]);
+    /// ```
+    pub fn rank_metrics(&self) -> Vec<String> {
+        let mut metrics = self.ranks.clone();
+        for metric in self.columns.iter().filter_map(|c| c.strip_prefix("rank_")) {
+            if !metrics.iter().any(|m| m == metric) {
+                metrics.push(metric.to_string());
+            }
+        }
+        metrics
+    }
+
+    /// 是否写出 surface_form 列
+    fn surface_form_column(&self) -> bool {
+        self.surface_form || self.columns.iter().any(|c| c == "surface_form")
+    }
+
+    /// 按精度格式化数值
+    ///
+    /// 舍入基于浮点数的精确十进制值、恰好居中时取偶（四舍六入五成双），再去掉末尾多余的 0。
+    pub fn format_number(&self, value: f64) -> String {
+        let mut s = dispersion_metrics::format_rounded(value, self.precision);
+        if self.decimal_separator != '.' {
+            s = s.replace('.', &self.decimal_separator.to_string());
+        }
+        s
+    }
+}
+
+/// 按导出格式逐行写出表格，自动处理分隔符与转义
+struct TableWriter<'a, W: Write> {
+    writer: W,
+    options: &'a ExportOptions,
+    row_started: bool,
+}
+
+impl<'a, W: Write> TableWriter<'a, W> {
+    fn new(writer: W, options: &'a ExportOptions) -> Self {
+        Self {
+            writer,
+            options,
+            row_started: false,
+        }
+    }
+
+    fn cell(&mut self, value: &str) -> std::io::Result<()> {
+        if self.row_started {
+            write!(self.writer, "{}", self.options.delimiter)?;
+        }
+        self.row_started = true;
+        write!(self.writer, "{value}")
+    }
+
+    fn text(&mut self, value: &str) -> std::io::Result<()> {
+        let escaped = escape_field(value, self.options.delimiter);
+        self.cell(&escaped)
+    }
+
+    /// 数值单元格；None 写为空单元格
+    fn number(&mut self, value: Option<f64>) -> std::io::Result<()> {
+        match value {
+            Some(v) => {
+                let formatted = self.options.format_number(v);
+                self.cell(&formatted)
+            }
+            None => self.cell(""),
+        }
+    }
+
+    fn integer(&mut self, value: usize) -> std::io::Result<()> {
+        self.cell(&value.to_string())
+    }
+
+    /// 词性单元格；开启 pos_label 时其后跟名称单元格
+    fn pos(&mut self, tag: &str) -> std::io::Result<()> {
+        self.text(tag)?;
+        match self.options.pos_label {
+            Some(language) => self.text(&tagset::label(tag, language)),
+            None => Ok(()),
+        }
+    }
+
+    /// 表头；开启 pos_label 时在 pos 后插入 pos_label
+    fn header(&mut self, names: &[&str]) -> std::io::Result<()> {
+        for name in names {
+            self.cell(name)?;
+            if *name == "pos" && self.options.pos_label.is_some() {
+                self.cell("pos_label")?;
+            }
+        }
+        self.end_row()
+    }
+
+    fn end_row(&mut self) -> std::io::Result<()> {
+        self.row_started = false;
+        writeln!(self.writer)
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// 条目表的一个单元格
+#[derive(Debug, Clone)]
+pub enum Field<'a> {
+    Text(Cow<'a, str>),
+    Integer(usize),
+    Number(f64),
+    Empty,
+}
+
+impl Field<'_> {
+    fn number(value: Option<f64>) -> Self {
+        value.map_or(Field::Empty, Field::Number)
+    }
+
+    fn integer(value: Option<usize>) -> Self {
+        value.map_or(Field::Empty, Field::Integer)
+    }
+}
+
+/// 条目表中随结果而定的列：是否有例句列，max_part_file 所用的部分标签，
+/// 以及 ExportOptions::columns 中各列在全部列中的位置（未采集例句时 examples 没有位置，写为空单元格）
+#[derive(Debug, Clone, Default)]
+pub struct EntryColumns {
+    pub examples: bool,
+    pub part_labels: Vec<String>,
+    selection: Option<Vec<Option<usize>>>,
+}
+
+impl EntryColumns {
+    pub fn new(result: &AnalysisResult, options: &ExportOptions) -> Self {
+        let mut columns = Self {
+            examples: result.metadata.options.collect_examples > 0,
+            part_labels: result.metadata.parts.iter().map(PartInfo::label).collect(),
+            selection: None,
+        };
+        if !options.columns.is_empty() {
+            let all = full_header(options, &columns);
+            columns.selection = Some(
+                options
+                    .columns
+                    .iter()
+                    .map(|name| all.iter().position(|c| c == name))
+                    .collect(),
+            );
+        }
+        columns
+    }
+}
+
+/// 可在 ExportOptions::columns 中指定的列名：entry_header 的各列，指标列与排名列取自指标说明表
+///
+/// ```
+/// use betawordlist_lib::analysis::{export::column_names, metric_descriptions::metric_descriptions};
+///
+/// let names = column_names();
+/// for description in metric_descriptions() {
+///     assert!(names.contains(&description.name.to_string()));
+///     assert!(names.contains(&format!("rank_{}", description.name)));
+/// }
+/// assert!(names.contains(&"examples".to_string()));
+/// ```
+pub fn column_names() -> Vec<String> {
+    let metrics = || {
+        metric_descriptions::metric_descriptions()
+            .iter()
+            .map(|d| d.name)
+    };
+    let mut names: Vec<String> = ["word", "surface_form", "pos", "pos_label", "char_length"]
+        .map(String::from)
+        .to_vec();
+    names.extend(metrics().map(String::from));
+    names.extend(["quoted_frequency", "pos_tag_share"].map(String::from));
+    names.extend(POSITION_FIELDS.map(String::from));
+    names.extend(["max_part_index", "max_part_file", "id", "flags"].map(String::from));
+    names.extend(metrics().map(|m| format!("rank_{m}")));
+    names.push("examples".to_string());
+    names
+}
+
+/// 按 selection 取出各单元格；没有位置的列为空单元格
+fn select<'e>(fields: Vec<Field<'e>>, selection: &Option<Vec<Option<usize>>>) -> Vec<Field<'e>> {
+    let Some(selection) = selection else {
+        return fields;
+    };
+    let mut fields: Vec<Option<Field>> = fields.into_iter().map(Some).collect();
+    selection
+        .iter()
+        .map(|i| i.and_then(|i| fields[i].take()).unwrap_or(Field::Empty))
+        .collect()
+}
+
+/// 条目表的列名：word, pos, char_length, frequency, 各指标列, gt_adjusted_frequency, quoted_frequency, pos_tag_share,
+/// 位置模式的三列（first_occurrence_part, first_occurrence_fraction, occurrence_span_fraction），
+/// 频率最高的部分（max_part_index, max_part_file），条目 id，条目标记（flags，见 entry_flags::display），ranks 与 columns 指定的排名列，采集例句时另有 examples；
+/// 开启 surface_form 时 word 后插入 surface_form，开启 pos_label 时 pos 后插入 pos_label；指定了 ExportOptions::columns 时只取其中的列并按其顺序
+pub fn entry_header(options: &ExportOptions, columns: &EntryColumns) -> Vec<String> {
+    match columns.selection {
+        Some(_) => options.columns.clone(),
+        None => full_header(options, columns),
+    }
+}
+
+/// 未按 columns 挑选的全部列
+fn full_header(options: &ExportOptions, columns: &EntryColumns) -> Vec<String> {
+    let mut header = vec!["word".to_string()];
+    if options.surface_form_column() {
+        header.push("surface_form".to_string());
+    }
+    header.push("pos".to_string());
+    if options.pos_label.is_some() {
+        header.push("pos_label".to_string());
+    }
+    header.extend(["char_length", "frequency"].map(String::from));
+    header.extend(DispersionMetrics::to_header());
+    header.extend(["gt_adjusted_frequency", "quoted_frequency", "pos_tag_share"].map(String::from));
+    header.extend(POSITION_FIELDS.map(String::from));
+    header.extend(["max_part_index", "max_part_file", "id", "flags"].map(String::from));
+    header.extend(options.rank_metrics().iter().map(|m| format!("rank_{m}")));
+    if columns.examples {
+        header.push("examples".to_string());
+    }
+    header
+}
+
+/// 一个条目的各单元格，与 entry_header 一一对应；None 为空单元格
+pub fn entry_fields<'e>(
+    entry: &'e WordEntry,
+    ranks: &BTreeMap<String, usize>,
+    options: &ExportOptions,
+    columns: &EntryColumns,
+) -> Vec<Field<'e>> {
+    let mut fields = vec![Field::Text(Cow::Borrowed(&entry.word))];
+    if options.surface_form_column() {
+        fields.push(Field::Text(Cow::Borrowed(entry.top_surface_form())));
+    }
+    fields.push(Field::Text(Cow::Borrowed(&entry.pos)));
+    if let Some(language) = options.pos_label {
+        fields.push(Field::Text(Cow::Owned(tagset::label(&entry.pos, language))));
+    }
+    fields.push(Field::Integer(entry.char_length));
+    fields.push(Field::Number(entry.frequency));
+    fields.extend(
+        entry
+            .metrics
+            .fields()
+            .map(|(_, value)| Field::number(value)),
+    );
+    fields.push(Field::number(entry.gt_adjusted_frequency));
+    fields.push(Field::number(entry.quoted_frequency));
+    fields.push(Field::number(entry.pos_tag_share));
+    fields.push(Field::integer(entry.first_occurrence_part));
+    fields.push(Field::number(entry.first_occurrence_fraction));
+    fields.push(Field::number(entry.occurrence_span_fraction));
+    fields.push(Field::integer(entry.max_part_index));
+    fields.push(
+        match entry
+            .max_part_index
+            .and_then(|part| columns.part_labels.get(part))
+        {
+            Some(label) => Field::Text(Cow::Owned(label.clone())),
+            None => Field::Empty,
+        },
+    );
+    fields.push(Field::Text(Cow::Borrowed(&entry.id)));
+    fields.push(Field::Text(Cow::Owned(entry_flags::display(&entry.flags))));
+    fields.extend(
+        options
+            .rank_metrics()
+            .iter()
+            .map(|metric| Field::integer(ranks.get(metric).copied())),
+    );
+    if columns.examples {
+        fields.push(Field::Text(Cow::Owned(
+            entry.examples.join(EXAMPLE_SEPARATOR),
+        )));
+    }
+    select(fields, &columns.selection)
+}
+
+/// 逐条目接收结果的写出端；export_rows 只遍历一次结果，依次交给各写出端
+pub trait RowSink {
+    /// 写出表头或文档开头
+    fn begin(&mut self, result: &AnalysisResult) -> io::Result<()>;
+    /// 写出一个条目；ranks 为该条目在 export_rows 所给各指标下的排名
+    fn row(&mut self, entry: &WordEntry, ranks: &BTreeMap<String, usize>) -> io::Result<()>;
+    /// 写出结尾并刷新
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// 遍历一次结果，将每个条目交给各写出端，返回各写出端的结果；某个写出端出错后不再写它，其余照常
+pub fn export_rows(
+    result: &AnalysisResult,
+    ranks: &[String],
+    sinks: &mut [&mut (dyn RowSink + '_)],
+) -> Vec<io::Result<()>> {
+    let mut outcomes: Vec<io::Result<()>> = sinks.iter_mut().map(|s| s.begin(result)).collect();
+    let entries: Vec<_> = result.entries.iter().collect();
+    let entry_ranks = rank::entry_ranks(&entries, ranks);
+    for (entry, entry_ranks) in result.entries.iter().zip(&entry_ranks) {
+        for (sink, outcome) in sinks.iter_mut().zip(&mut outcomes) {
+            if outcome.is_ok() {
+                *outcome = sink.row(entry, entry_ranks);
+            }
+        }
+    }
+    for (sink, outcome) in sinks.iter_mut().zip(&mut outcomes) {
+        if outcome.is_ok() {
+            *outcome = sink.finish();
+        }
+    }
+    outcomes
+}
+
+/// 以 ExportOptions 的格式写出条目表（CSV / TSV 等）
+pub struct TableSink<'a, W: Write> {
+    table: TableWriter<'a, W>,
+    columns: EntryColumns,
+}
+
+impl<'a, W: Write> TableSink<'a, W> {
+    pub fn new(writer: W, options: &'a ExportOptions) -> Self {
+        Self {
+            table: TableWriter::new(writer, options),
+            columns: EntryColumns::default(),
+        }
+    }
+}
+
+impl<W: Write> RowSink for TableSink<'_, W> {
+    fn begin(&mut self, result: &AnalysisResult) -> io::Result<()> {
+        self.columns = EntryColumns::new(result, self.table.options);
+        // 列名不含分隔符与引号，直接写出
+        for name in entry_header(self.table.options, &self.columns) {
+            self.table.cell(&name)?;
+        }
+        self.table.end_row()
+    }
+
+    fn row(&mut self, entry: &WordEntry, ranks: &BTreeMap<String, usize>) -> io::Result<()> {
+        for field in entry_fields(entry, ranks, self.table.options, &self.columns) {
+            match field {
+                Field::Text(text) => self.table.text(&text)?,
+                Field::Integer(v) => self.table.integer(v)?,
+                Field::Number(v) => self.table.number(Some(v))?,
+                Field::Empty => self.table.cell("")?,
+            }
+        }
+        self.table.end_row()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.table.finish()
+    }
+}
+
+/// 写出 XLSX 工作簿：entries 表同 write_csv 的列（数值为数值单元格，不按 precision 舍入），
+/// metadata 表为语料概况，启用了过滤时其后附过滤报告；pos_tags 表为各部分的词性分布；开启 definitions 时另有 definitions 表
+pub struct XlsxSink<'a, W: Write> {
+    xlsx: Option<XlsxWriter<W>>,
+    options: &'a ExportOptions,
+    columns: EntryColumns,
+    metadata: Vec<(&'static str, String)>,
+    filter_report: FilterReport,
+    parts: Vec<PartInfo>,
+    pos_outliers: Vec<PosOutlier>,
+}
+
+impl<'a, W: Write> XlsxSink<'a, W> {
+    pub fn new(writer: W, options: &'a ExportOptions) -> Self {
+        Self {
+            xlsx: Some(XlsxWriter::new(writer)),
+            options,
+            columns: EntryColumns::default(),
+            metadata: Vec::new(),
+            filter_report: FilterReport::default(),
+            parts: Vec::new(),
+            pos_outliers: Vec::new(),
+        }
+    }
+
+    fn xlsx(&mut self) -> io::Result<&mut XlsxWriter<W>> {
+        self.xlsx
+            .as_mut()
+            .ok_or_else(|| io::Error::other("工作簿已写完"))
+    }
+}
+
+impl<W: Write> RowSink for XlsxSink<'_, W> {
+    fn begin(&mut self, result: &AnalysisResult) -> io::Result<()> {
+        self.columns = EntryColumns::new(result, self.options);
+        self.metadata = metadata_summary(&result.metadata);
+        self.filter_report = result.metadata.filter_report.clone();
+        self.parts = result.metadata.parts.clone();
+        self.pos_outliers = result.metadata.pos_outliers.clone();
+        let header = entry_header(self.options, &self.columns);
+        let cells: Vec<Cell> = header.iter().map(|name| Cell::Text(name)).collect();
+        let xlsx = self.xlsx()?;
+        xlsx.start_sheet("entries")?;
+        xlsx.row(&cells)
+    }
+
+    fn row(&mut self, entry: &WordEntry, ranks: &BTreeMap<String, usize>) -> io::Result<()> {
+        let fields = entry_fields(entry, ranks, self.options, &self.columns);
+        let cells: Vec<Cell> = fields
+            .iter()
+            .map(|field| match field {
+                Field::Text(text) => Cell::Text(text),
+                Field::Integer(v) => Cell::Number(*v as f64),
+                Field::Number(v) => Cell::Number(*v),
+                Field::Empty => Cell::Empty,
+            })
+            .collect();
+        self.xlsx()?.row(&cells)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let Some(mut xlsx) = self.xlsx.take() else {
+            return Ok(());
+        };
+        xlsx.start_sheet("metadata")?;
+        for (key, value) in &self.metadata {
+            xlsx.row(&[Cell::Text(key), Cell::Text(value)])?;
+        }
+        write_filter_report(&mut xlsx, &self.filter_report)?;
+        write_pos_histogram(&mut xlsx, &self.parts, &self.pos_outliers)?;
+        if self.options.definitions {
+            write_definitions(&mut xlsx)?;
+        }
+        xlsx.finish().map(|_| ())
+    }
+}
+
+/// 指标说明表：metric, definition, range, higher_is_more_even, citation
+fn write_definitions<W: Write>(xlsx: &mut XlsxWriter<W>) -> io::Result<()> {
+    xlsx.start_sheet("definitions")?;
+    xlsx.row(&[
+        Cell::Text("metric"),
+        Cell::Text("definition"),
+        Cell::Text("range"),
+        Cell::Text("higher_is_more_even"),
+        Cell::Text("citation"),
+    ])?;
+    for d in metric_descriptions::metric_descriptions() {
+        let direction = match d.higher_is_more_even {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "",
+        };
+        let range = d.range.to_string();
+        xlsx.row(&[
+            Cell::Text(d.name),
+            Cell::Text(d.definition),
+            Cell::Text(&range),
+            Cell::Text(direction),
+            Cell::Text(d.citation.unwrap_or_default()),
+        ])?;
+    }
+    Ok(())
+}
+
+/// 过滤报告的三张小表：各项过滤的合计、移除最多的词型、各文件的移除数，表间空一行
+fn write_filter_report<W: Write>(
+    xlsx: &mut XlsxWriter<W>,
+    report: &FilterReport,
+) -> io::Result<()> {
+    if report.filters.is_empty() {
+        return Ok(());
+    }
+    let header = |xlsx: &mut XlsxWriter<W>, names: &[&str]| {
+        xlsx.row(&[])?;
+        let cells: Vec<Cell> = names.iter().map(|name| Cell::Text(name)).collect();
+        xlsx.row(&cells)
+    };
+    header(
+        xlsx,
+        &[
+            "filter",
+            "tokens_removed",
+            "types_removed",
+            "corpus_words_removed",
+        ],
+    )?;
+    for effect in &report.filters {
+        xlsx.row(&[
+            Cell::Text(&effect.filter),
+            Cell::Number(effect.tokens_removed as f64),
+            Cell::Number(effect.types_removed as f64),
+            Cell::Number(effect.corpus_words_removed as f64),
+        ])?;
+    }
+    header(xlsx, &["filter", "word", "pos", "frequency"])?;
+    for effect in &report.filters {
+        for removed in &effect.top_removed {
+            xlsx.row(&[
+                Cell::Text(&effect.filter),
+                Cell::Text(&removed.word),
+                Cell::Text(&removed.pos),
+                Cell::Number(removed.frequency),
+            ])?;
+        }
+    }
+    header(xlsx, &["filter", "file", "tokens_removed"])?;
+    for effect in &report.filters {
+        for file in &effect.by_file {
+            xlsx.row(&[
+                Cell::Text(&effect.filter),
+                Cell::Text(&file.file),
+                Cell::Number(file.tokens as f64),
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+/// 语料概况的键值对，供 XLSX 的 metadata 表使用；完整元数据见 .meta.json
+pub fn metadata_summary(metadata: &CorpusMetadata) -> Vec<(&'static str, String)> {
+    vec![
+        ("app_version", metadata.app_version.clone()),
+        ("analyzed_at", metadata.analyzed_at.clone()),
+        ("run_id", metadata.run_id.clone().unwrap_or_default()),
+        ("tokenizer", metadata.tokenizer.clone()),
+        ("partition_mode", metadata.partition_mode.clone()),
+        ("seed", metadata.seed.to_string()),
+        ("strict", metadata.options.strict.to_string()),
+        ("files", metadata.files.len().to_string()),
+        ("parts", metadata.parts.len().to_string()),
+        ("total_tokens", metadata.total_tokens.to_string()),
+        ("total_raw_tokens", metadata.total_raw_tokens.to_string()),
+        ("size_exclusions", metadata.size_exclusions.join(", ")),
+        ("total_types", metadata.total_types.to_string()),
+        (
+            "min_frequency_removed",
+            metadata.min_frequency_removed.to_string(),
+        ),
+        ("file_errors", metadata.file_errors.join("; ")),
+        ("warnings", metadata.warnings.join("; ")),
+        (
+            "pos_outliers",
+            metadata
+                .pos_outliers
+                .iter()
+                .map(|o| format!("{} {}={:.3} (z={:.2})", o.label, o.tag, o.share, o.z))
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+    ]
+}
+
+/// 将分析结果写为表格，列见 entry_header；None 写为空单元格
+pub fn write_csv<W: Write>(
+    writer: W,
+    result: &AnalysisResult,
+    options: &ExportOptions,
+) -> io::Result<()> {
+    let mut sink = TableSink::new(writer, options);
+    export_rows(result, &options.rank_metrics(), &mut [&mut sink]).remove(0)
+}
+
+/// 将词频矩阵写为表格：word, pos, 之后每个语料部分一列（列名为部分标签）；需保留各部分词频（keep_parts）
+pub fn write_frequency_matrix_csv<W: Write>(
+    writer: W,
+    result: &AnalysisResult,
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let labels: Vec<String> = result.metadata.parts.iter().map(PartInfo::label).collect();
+    let mut table = TableWriter::new(writer, options);
+    let mut header = vec!["word", "pos"];
+    header.extend(labels.iter().map(String::as_str));
+    table.header(&header)?;
+
+    let lookups: Vec<HashMap<(&str, &str), f64>> = result
+        .parts
+        .iter()
+        .map(|part| {
+            let words = part
+                .counts
+                .iter()
+                .map(|(w, p, v)| ((w.as_str(), p.as_str()), *v));
+            let phrases = part
+                .phrase_counts
+                .iter()
+                .map(|(phrase, v)| ((phrase.as_str(), PHRASE_POS), *v));
+            let patterns = part
+                .posgram_counts
+                .iter()
+                .map(|(pattern, v)| ((pattern.as_str(), POSGRAM_POS), *v));
+            words.chain(phrases).chain(patterns).collect()
+        })
+        .collect();
+    for entry in &result.entries {
+        table.text(&entry.word)?;
+        table.pos(&entry.pos)?;
+        let key = (entry.word.as_str(), entry.pos.as_str());
+        for lookup in &lookups {
+            table.number(Some(lookup.get(&key).copied().unwrap_or(0.0)))?;
+        }
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 按输出配置将分析结果写为表格；rank_<指标> 列在全部条目中计算
+pub fn write_profile_csv<W: Write>(
+    writer: W,
+    result: &AnalysisResult,
+    profile: &OutputProfile,
+    context: &ProfileContext,
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let entries: Vec<_> = result.entries.iter().collect();
+    let rendered = context.render_table(profile, &entries, options);
+    let mut table = TableWriter::new(writer, options);
+    // 表头由配置给出，不经 header() 自动插入 pos_label
+    for header in &rendered.headers {
+        table.text(header)?;
+    }
+    table.end_row()?;
+    for row in &rendered.rows {
+        for value in row {
+            table.text(value)?;
+        }
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 附上指标说明的元数据：元数据各字段之后另有 metric_definitions
+#[derive(Serialize)]
+struct MetadataWithDefinitions<'a> {
+    #[serde(flatten)]
+    metadata: &'a CorpusMetadata,
+    metric_definitions: &'static [MetricDescription],
+}
+
+/// 在导出文件旁写出同名 .meta.json 元数据（如 result.csv → result.meta.json），返回其路径；
+/// definitions 为真时附上指标说明
+pub fn write_metadata_sidecar(
+    export_path: &Path,
+    metadata: &CorpusMetadata,
+    definitions: bool,
+) -> Result<PathBuf, WriteError> {
+    let sidecar = export_path.with_extension("meta.json");
+    write_atomic(&sidecar, |w| {
+        if definitions {
+            let document = MetadataWithDefinitions {
+                metadata,
+                metric_definitions: metric_descriptions::metric_descriptions(),
+            };
+            Ok(serde_json::to_writer_pretty(w, &document)?)
+        } else {
+            Ok(serde_json::to_writer_pretty(w, metadata)?)
+        }
+    })?;
+    Ok(sidecar)
+}
+
+/// 将指标两两相关写为表格：metric_a, metric_b, n, rho
+pub fn write_correlations_csv<W: Write>(
+    writer: W,
+    correlations: &[MetricCorrelation],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&["metric_a", "metric_b", "n", "rho"])?;
+    for c in correlations {
+        table.text(&c.metric_a)?;
+        table.text(&c.metric_b)?;
+        table.integer(c.n)?;
+        table.number(c.rho)?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将秩差列表写为表格：word, pos, rank_a, rank_b, rank_diff
+pub fn write_disagreements_csv<W: Write>(
+    writer: W,
+    disagreements: &[RankDisagreement],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&["word", "pos", "rank_a", "rank_b", "rank_diff"])?;
+    for d in disagreements {
+        table.text(&d.word)?;
+        table.pos(&d.pos)?;
+        table.number(Some(d.rank_a))?;
+        table.number(Some(d.rank_b))?;
+        table.number(Some(d.rank_diff))?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将差异报告写为表格：word, pos, kind, value_a, value_b, change
+pub fn write_diff_csv<W: Write>(
+    writer: W,
+    diff: &ResultDiff,
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&["word", "pos", "kind", "value_a", "value_b", "change"])?;
+    for entry in &diff.entries {
+        table.text(&entry.word)?;
+        table.pos(&entry.pos)?;
+        table.cell(match entry.kind {
+            DiffKind::Added => "added",
+            DiffKind::Removed => "removed",
+            DiffKind::Changed => "changed",
+        })?;
+        table.number(entry.value_a)?;
+        table.number(entry.value_b)?;
+        table.number(entry.change)?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将词表覆盖率写为表格：各文件一行，末行为全语料（source 为空）
+pub fn write_coverage_csv<W: Write>(
+    writer: W,
+    corpus: &Coverage,
+    files: &[Coverage],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&[
+        "source",
+        "tokens",
+        "covered_tokens",
+        "token_coverage",
+        "token_coverage_excluding_hapaxes",
+        "types",
+        "covered_types",
+        "type_coverage",
+        "uncovered_hapaxes",
+    ])?;
+    for c in files.iter().chain([corpus]) {
+        table.text(&c.source)?;
+        table.number(Some(c.tokens))?;
+        table.number(Some(c.covered_tokens))?;
+        table.number(c.token_coverage)?;
+        table.number(c.token_coverage_excluding_hapaxes)?;
+        table.integer(c.types)?;
+        table.integer(c.covered_types)?;
+        table.number(c.type_coverage)?;
+        table.integer(c.uncovered_hapaxes)?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将未覆盖的词写为表格：word, frequency, files
+pub fn write_uncovered_csv<W: Write>(
+    writer: W,
+    uncovered: &[UncoveredWord],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&["word", "frequency", "files"])?;
+    for u in uncovered {
+        table.text(&u.word)?;
+        table.number(Some(u.frequency))?;
+        table.integer(u.files)?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将字族写为表格：character, family_size, family_frequency, hapax_members, members；
+/// members 为字族中的词，按频次降序以空格分隔
+pub fn write_character_families_csv<W: Write>(
+    writer: W,
+    characters: &[CharacterFamily],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&[
+        "character",
+        "family_size",
+        "family_frequency",
+        "hapax_members",
+        "members",
+    ])?;
+    for c in characters {
+        table.text(&c.character)?;
+        table.integer(c.family_size)?;
+        table.number(Some(c.family_frequency))?;
+        table.integer(c.hapax_members)?;
+        table.text(&c.members.join(" "))?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将未登录词线索写为表格：word, pos, frequency, impact, adjacent_count, adjacent, domain_words；
+/// pos 与 domain_words 以空格分隔，adjacent 为 "连写:次数" 以空格分隔
+pub fn write_oov_csv<W: Write>(
+    writer: W,
+    candidates: &[OovCandidate],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&[
+        "word",
+        "pos",
+        "frequency",
+        "impact",
+        "adjacent_count",
+        "adjacent",
+        "domain_words",
+    ])?;
+    for c in candidates {
+        table.text(&c.word)?;
+        table.text(&c.pos.join(" "))?;
+        table.number(Some(c.frequency))?;
+        table.number(Some(c.impact))?;
+        table.integer(c.adjacent_count as usize)?;
+        let adjacent: Vec<String> = c
+            .adjacent
+            .iter()
+            .map(|p| format!("{}:{}", p.joined, p.count))
+            .collect();
+        table.text(&adjacent.join(" "))?;
+        table.text(&c.domain_words.join(" "))?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 组关键词表的列；CSV 另在最前加 group 列
+const GROUP_KEYWORD_HEADER: [&str; 6] = [
+    "word",
+    "pos",
+    "frequency",
+    "rest_frequency",
+    "log_likelihood",
+    "log_ratio",
+];
+
+/// 将各组关键词写为一张表格：group, word, pos, frequency, rest_frequency, log_likelihood, log_ratio，
+/// 各组的行依次相连
+pub fn write_group_keywords_csv<W: Write>(
+    writer: W,
+    groups: &[GroupKeywords],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    let mut header = vec!["group"];
+    header.extend(GROUP_KEYWORD_HEADER);
+    table.header(&header)?;
+    for group in groups {
+        for k in &group.keywords {
+            table.text(&group.group)?;
+            table.text(&k.word)?;
+            table.pos(&k.pos)?;
+            table.number(Some(k.frequency))?;
+            table.number(Some(k.rest_frequency))?;
+            table.number(Some(k.log_likelihood))?;
+            table.number(Some(k.log_ratio))?;
+            table.end_row()?;
+        }
+    }
+    table.finish()
+}
+
+/// 将各组关键词写为 XLSX：summary 表列出各组的部分数、词数与关键词数，之后每组一张表，列同 CSV（不含 group）。
+/// 表名取组名，去掉 XLSX 不允许的字符并截至 31 个字符，重名时加序号
+pub fn write_group_keywords_xlsx<W: Write>(
+    writer: W,
+    groups: &[GroupKeywords],
+    options: &ExportOptions,
+) -> io::Result<()> {
+    let mut xlsx = XlsxWriter::new(writer);
+    xlsx.start_sheet("summary")?;
+    xlsx.row(&["group", "parts", "tokens", "keywords"].map(Cell::Text))?;
+    for group in groups {
+        xlsx.row(&[
+            Cell::Text(&group.group),
+            Cell::Number(group.parts as f64),
+            Cell::Number(group.tokens),
+            Cell::Number(group.keywords.len() as f64),
+        ])?;
+    }
+
+    let mut used = vec!["summary".to_string()];
+    for group in groups {
+        let name = unique_sheet_name(&group.group, &used);
+        xlsx.start_sheet(&name)?;
+        used.push(name);
+        let mut header = GROUP_KEYWORD_HEADER.map(Cell::Text).to_vec();
+        if options.pos_label.is_some() {
+            header.insert(2, Cell::Text("pos_label"));
+        }
+        xlsx.row(&header)?;
+        for k in &group.keywords {
+            let label = options
+                .pos_label
+                .map(|language| tagset::label(&k.pos, language));
+            let mut cells = vec![Cell::Text(&k.word), Cell::Text(&k.pos)];
+            if let Some(label) = &label {
+                cells.push(Cell::Text(label));
+            }
+            cells.extend([
+                Cell::Number(k.frequency),
+                Cell::Number(k.rest_frequency),
+                Cell::Number(k.log_likelihood),
+                Cell::Number(k.log_ratio),
+            ]);
+            xlsx.row(&cells)?;
+        }
+    }
+    xlsx.finish().map(|_| ())
+}
+
+/// 由任意名称得到可用且不与 used 重复的工作表名
+fn unique_sheet_name(name: &str, used: &[String]) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = text::truncate_utf16(&cleaned, 31).to_string();
+    let base = if cleaned.trim().is_empty() {
+        "group".to_string()
+    } else {
+        cleaned
+    };
+    let taken = |candidate: &str| used.iter().any(|u| u.eq_ignore_ascii_case(candidate));
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!("_{n}");
+            let keep = 31 - suffix.len();
+            format!("{}{suffix}", text::truncate_utf16(&base, keep))
+        })
+        .find(|candidate| !taken(candidate))
+        .expect("总能找到未用的表名")
+}
+
+/// 将近重复文件对写为表格，供人工复核：file_a, file_b, similarity, kept
+pub fn write_near_duplicates_csv<W: Write>(
+    writer: W,
+    pairs: &[DuplicatePair],
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&["file_a", "file_b", "similarity", "kept"])?;
+    for pair in pairs {
+        table.text(&pair.file_a)?;
+        table.text(&pair.file_b)?;
+        table.number(Some(pair.similarity))?;
+        table.text(pair.kept.as_deref().unwrap_or_default())?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 词性分布表的一行
+struct PosHistogramRow<'a> {
+    part: usize,
+    label: String,
+    pos: &'a str,
+    tokens: u64,
+    share: f64,
+    /// 该部分在此词性上被标为占比异常时的 z 分数
+    z: Option<f64>,
+}
+
+const POS_HISTOGRAM_HEADER: [&str; 6] = ["part", "label", "pos", "tokens", "share", "z"];
+
+fn pos_histogram_rows<'a>(
+    parts: &'a [PartInfo],
+    outliers: &[PosOutlier],
+) -> Vec<PosHistogramRow<'a>> {
+    let mut rows = Vec::new();
+    for part in parts {
+        let total: u64 = part.pos_counts.values().sum();
+        for (pos, &tokens) in &part.pos_counts {
+            rows.push(PosHistogramRow {
+                part: part.index,
+                label: part.label(),
+                pos,
+                tokens,
+                share: tokens as f64 / total as f64,
+                z: outliers
+                    .iter()
+                    .find(|o| o.part == part.index && o.tag == *pos)
+                    .map(|o| o.z),
+            });
+        }
+    }
+    rows
+}
+
+/// 将各部分的词性分布写为表格：part, label, pos, tokens, share, z；z 只填在被标为占比异常的部分与词性上
+pub fn write_pos_histogram_csv<W: Write>(
+    writer: W,
+    metadata: &CorpusMetadata,
+    options: &ExportOptions,
+) -> std::io::Result<()> {
+    let mut table = TableWriter::new(writer, options);
+    table.header(&POS_HISTOGRAM_HEADER)?;
+    for row in pos_histogram_rows(&metadata.parts, &metadata.pos_outliers) {
+        table.integer(row.part)?;
+        table.text(&row.label)?;
+        table.pos(row.pos)?;
+        table.cell(&row.tokens.to_string())?;
+        table.number(Some(row.share))?;
+        table.number(row.z)?;
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// XLSX 的 pos_tags 表，列同 write_pos_histogram_csv；没有词性分布时不写
+fn write_pos_histogram<W: Write>(
+    xlsx: &mut XlsxWriter<W>,
+    parts: &[PartInfo],
+    outliers: &[PosOutlier],
+) -> io::Result<()> {
+    let rows = pos_histogram_rows(parts, outliers);
+    if rows.is_empty() {
+        return Ok(());
+    }
+    xlsx.start_sheet("pos_tags")?;
+    xlsx.row(&POS_HISTOGRAM_HEADER.map(Cell::Text))?;
+    for row in &rows {
+        xlsx.row(&[
+            Cell::Number(row.part as f64),
+            Cell::Text(&row.label),
+            Cell::Text(row.pos),
+            Cell::Number(row.tokens as f64),
+            Cell::Number(row.share),
+            row.z.map_or(Cell::Empty, Cell::Number),
+        ])?;
+    }
+    Ok(())
+}
+
+/// 频率词典的列名：band, rank, word, pos, char_length, frequency, juilland_d, dp, range，
+/// 排序指标不在其中时末尾另加一列；开启 pos_label 时 pos 后插入 pos_label
+fn dictionary_header(dictionary: &Dictionary, options: &ExportOptions) -> Vec<String> {
+    let mut header = vec!["band", "rank", "word", "pos"];
+    if options.pos_label.is_some() {
+        header.push("pos_label");
+    }
+    header.extend(["char_length", "frequency", "juilland_d", "dp", "range"]);
+    if !header.contains(&dictionary.order_by.as_str()) {
+        header.push(&dictionary.order_by);
+    }
+    header.into_iter().map(String::from).collect()
+}
+
+/// 词典条目的各单元格，与 dictionary_header 一一对应
+fn dictionary_fields<'e>(
+    item: &'e DictionaryEntry,
+    header: &[String],
+    options: &ExportOptions,
+) -> Vec<Field<'e>> {
+    let entry = item.entry;
+    let mut fields = vec![
+        Field::Integer(item.band),
+        Field::Integer(item.rank),
+        Field::Text(Cow::Borrowed(&entry.word)),
+        Field::Text(Cow::Borrowed(&entry.pos)),
+    ];
+    if let Some(language) = options.pos_label {
+        fields.push(Field::Text(Cow::Owned(tagset::label(&entry.pos, language))));
+    }
+    fields.push(Field::Integer(entry.char_length));
+    fields.push(Field::Number(entry.frequency));
+    fields.push(Field::number(entry.metrics.juilland_d));
+    fields.push(Field::number(entry.metrics.dp));
+    fields.push(Field::Integer(entry.metrics.range));
+    if fields.len() < header.len() {
+        fields.push(Field::Number(item.value));
+    }
+    fields
+}
+
+/// 词性构成，如 "n 120; v 80"
+fn pos_composition(pos_counts: &[(String, usize)]) -> String {
+    pos_counts
+        .iter()
+        .map(|(pos, count)| format!("{pos} {count}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 将频率词典写为一张表格，band 列标明所在段；截断处的并列说明由调用方另行给出
+pub fn write_dictionary_csv<W: Write>(
+    writer: W,
+    dictionary: &Dictionary,
+    options: &ExportOptions,
+) -> io::Result<()> {
+    let header = dictionary_header(dictionary, options);
+    let mut table = TableWriter::new(writer, options);
+    for name in &header {
+        table.cell(name)?;
+    }
+    table.end_row()?;
+    for item in &dictionary.entries {
+        for field in dictionary_fields(item, &header, options) {
+            match field {
+                Field::Text(text) => table.text(&text)?,
+                Field::Integer(v) => table.integer(v)?,
+                Field::Number(v) => table.number(Some(v))?,
+                Field::Empty => table.cell("")?,
+            }
+        }
+        table.end_row()?;
+    }
+    table.finish()
+}
+
+/// 将频率词典写为 XLSX：summary 表为各段汇总（截断处有并列时其后附说明），其后每段一张 band_<段号> 表
+pub fn write_dictionary_xlsx<W: Write>(
+    writer: W,
+    dictionary: &Dictionary,
+    options: &ExportOptions,
+) -> io::Result<()> {
+    let mut xlsx = XlsxWriter::new(writer);
+    xlsx.start_sheet("summary")?;
+    let mean_value = format!("mean_{}", dictionary.order_by);
+    xlsx.row(&[
+        Cell::Text("band"),
+        Cell::Text("first_rank"),
+        Cell::Text("last_rank"),
+        Cell::Text("entries"),
+        Cell::Text(&mean_value),
+        Cell::Text("mean_juilland_d"),
+        Cell::Text("mean_dp"),
+        Cell::Text("pos_composition"),
+    ])?;
+    for band in &dictionary.bands {
+        let composition = pos_composition(&band.pos_counts);
+        let optional = |v: Option<f64>| v.map_or(Cell::Empty, Cell::Number);
+        xlsx.row(&[
+            Cell::Number(band.band as f64),
+            Cell::Number(band.first_rank as f64),
+            Cell::Number(band.last_rank as f64),
+            Cell::Number(band.entries as f64),
+            Cell::Number(band.mean_value),
+            optional(band.mean_juilland_d),
+            optional(band.mean_dp),
+            Cell::Text(&composition),
+        ])?;
+    }
+    if let Some(note) = &dictionary.tie_note {
+        xlsx.row(&[])?;
+        xlsx.row(&[Cell::Text("note"), Cell::Text(note)])?;
+    }
+
+    let header = dictionary_header(dictionary, options);
+    let header_cells: Vec<Cell> = header.iter().map(|name| Cell::Text(name)).collect();
+    for (band, items) in dictionary
+        .bands
+        .iter()
+        .zip(dictionary.entries.chunk_by(|a, b| a.band == b.band))
+    {
+        xlsx.start_sheet(&format!("band_{}", band.band))?;
+        xlsx.row(&header_cells)?;
+        for item in items {
+            let fields = dictionary_fields(item, &header, options);
+            let cells: Vec<Cell> = fields
+                .iter()
+                .map(|field| match field {
+                    Field::Text(text) => Cell::Text(text),
+                    Field::Integer(v) => Cell::Number(*v as f64),
+                    Field::Number(v) => Cell::Number(*v),
+                    Field::Empty => Cell::Empty,
+                })
+                .collect();
+            xlsx.row(&cells)?;
+        }
+    }
+    xlsx.finish().map(|_| ())
+}
+
+/// 字段转义：含分隔符、引号或换行时加引号，内部引号加倍
+fn escape_field(value: &str, delimiter: char) -> Cow<'_, str> {
+    if value.contains([delimiter, '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
@@ -0,0 +1,263 @@
+// filter.rs
+// 停用词与词性过滤，以及引号内词元的豁免
+//
+// 过滤作用于有序的词元流：先标出成对引号之间的片段，片段内的词元不受停用词/词性过滤影响。
+// 各项过滤移除了哪些词、移除多少，汇总为 FilterReport 记入结果元数据。
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::options::AnalysisOptions;
+
+/// 过滤报告中列出的被移除词型数
+pub const TOP_REMOVED_TYPES: usize = 50;
+
+/// 中文引号：(左引号, 右引号)
+const QUOTE_PAIRS: &[(char, char)] = &[('“', '”'), ('‘', '’'), ('「', '」'), ('『', '』')];
+
+/// 过滤统计
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FilterCounts {
+    /// 因停用词移出词表的词元数
+    pub stopwords: u64,
+    /// 因词性移出词表的词元数
+    pub excluded_pos: u64,
+    /// 作为结构性噪声从词元流中移除的词元数
+    pub structural_noise: u64,
+    /// 位于引号内、本应被过滤而保留的词元数
+    pub quote_exempted: u64,
+}
+
+impl FilterCounts {
+    pub fn add(&mut self, other: &Self) {
+        self.stopwords += other.stopwords;
+        self.excluded_pos += other.excluded_pos;
+        self.structural_noise += other.structural_noise;
+        self.quote_exempted += other.quote_exempted;
+    }
+}
+
+/// 词元被过滤的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterReason {
+    Stopword,
+    ExcludedPos,
+    StructuralNoise,
+}
+
+impl FilterReason {
+    /// 记入过滤报告的名称，与对应的选项同名
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FilterReason::Stopword => "stopwords",
+            FilterReason::ExcludedPos => "excluded_pos",
+            FilterReason::StructuralNoise => "structural_noise",
+        }
+    }
+}
+
+/// 各项过滤移除的词元是否同时从部分词数（分布指标的分母）中扣除；默认与既有行为一致
+///
+/// 标点另由 TokenPolicy::exclude_punctuation_from_sizes 控制；清理时丢弃的空白词元总是扣除；
+/// min_frequency 与目标词表只决定哪些词有条目，从不扣除。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DenominatorPolicy {
+    /// 停用词同时扣除；默认否，只移出词表
+    pub stopwords: bool,
+    /// 按词性过滤的词元同时扣除；默认否，只移出词表
+    pub excluded_pos: bool,
+    /// exclude_symbols 移除的符号词元同时扣除；默认是
+    pub symbols: bool,
+    /// 结构性噪声同时扣除；默认是
+    pub structural_noise: bool,
+}
+
+impl Default for DenominatorPolicy {
+    fn default() -> Self {
+        Self {
+            stopwords: false,
+            excluded_pos: false,
+            symbols: true,
+            structural_noise: true,
+        }
+    }
+}
+
+impl DenominatorPolicy {
+    /// 因 reason 移出词表的词元是否从部分词数中扣除
+    pub fn excludes(self, reason: FilterReason) -> bool {
+        match reason {
+            FilterReason::Stopword => self.stopwords,
+            FilterReason::ExcludedPos => self.excluded_pos,
+            FilterReason::StructuralNoise => self.structural_noise,
+        }
+    }
+
+    /// 按选项实际从部分词数中扣除的词元类别，与各选项同名；为空时部分词数即分词所得的全部词元
+    pub fn size_exclusions(options: &AnalysisOptions) -> Vec<String> {
+        let policy = options.token_policy;
+        let denominator = options.denominator;
+        [
+            ("blank_tokens", policy.sanitize),
+            ("punctuation", policy.exclude_punctuation_from_sizes),
+            ("symbols", policy.exclude_symbols && denominator.symbols),
+            (
+                "structural_noise",
+                options.structural_noise && denominator.structural_noise,
+            ),
+            (
+                "stopwords",
+                !options.stopwords.is_empty() && denominator.stopwords,
+            ),
+            (
+                "excluded_pos",
+                !options.excluded_pos.is_empty() && denominator.excluded_pos,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, excluded)| *excluded)
+        .map(|(name, _)| name.to_string())
+        .collect()
+    }
+}
+
+/// 一个被移除的词型及其被移除的总频次
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct RemovedType {
+    pub word: String,
+    pub pos: String,
+    pub frequency: f64,
+}
+
+/// 一个文件被移除的词元数；逐句切分时按来源文件合计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct FileRemoval {
+    pub file: String,
+    pub tokens: u64,
+}
+
+/// 一项过滤的效果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FilterEffect {
+    /// stopwords / excluded_pos / structural_noise / min_frequency
+    pub filter: String,
+    /// 移出词表的词元数
+    pub tokens_removed: u64,
+    /// 移出词表的词型数
+    pub types_removed: usize,
+    /// 从部分词数中扣除的词元数，即对分布指标分母 total_corpus_words 的影响：denominator 中对应开关开启时
+    /// 等于 tokens_removed（其中已按 exclude_punctuation_from_sizes 扣除的标点不重复扣除），否则为 0；min_frequency 总是 0
+    pub corpus_words_removed: u64,
+    /// 各文件移出的词元数，按语料顺序
+    pub by_file: Vec<FileRemoval>,
+    /// 被移除总频次最高的词型（至多 TOP_REMOVED_TYPES 个），按频次降序；
+    /// structural_noise 中为被移除的序列，pos 为命中的规则，频次为出现次数
+    pub top_removed: Vec<RemovedType>,
+}
+
+/// 过滤报告：各项已启用的过滤移除了多少词元与词型，便于发现误删实词的停用词表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct FilterReport {
+    /// 各部分词数之和，即分布指标的分母
+    pub total_corpus_words: u64,
+    pub filters: Vec<FilterEffect>,
+}
+
+/// 按频次降序（同频按词、词性）保留前 TOP_REMOVED_TYPES 个被移除的词型
+pub fn top_removed(mut removed: Vec<RemovedType>) -> Vec<RemovedType> {
+    removed.sort_by(|a, b| {
+        b.frequency
+            .total_cmp(&a.frequency)
+            .then_with(|| a.word.cmp(&b.word))
+            .then_with(|| a.pos.cmp(&b.pos))
+    });
+    removed.truncate(TOP_REMOVED_TYPES);
+    removed
+}
+
+/// 按分析选项构建的停用词/词性过滤器；过滤移出词表，是否改变部分词数见 DenominatorPolicy
+pub struct TokenFilter<'a> {
+    stopwords: HashSet<&'a str>,
+    excluded_pos: HashSet<&'a str>,
+}
+
+impl<'a> TokenFilter<'a> {
+    pub fn new(options: &'a AnalysisOptions) -> Self {
+        Self {
+            stopwords: options.stopwords.iter().map(String::as_str).collect(),
+            excluded_pos: options.excluded_pos.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// 词元应被过滤时返回原因
+    pub fn check(&self, word: &str, pos: &str) -> Option<FilterReason> {
+        if self.stopwords.contains(word) {
+            Some(FilterReason::Stopword)
+        } else if self.excluded_pos.contains(pos) {
+            Some(FilterReason::ExcludedPos)
+        } else {
+            None
+        }
+    }
+}
+
+/// 标出位于成对中文引号之内的词元（含引号本身）
+///
+/// 支持嵌套；右引号与最近的同类左引号配对，中间未闭合的左引号作废。
+/// 始终未闭合的左引号不形成片段，其后的词元按引号外处理。
+pub fn quoted_mask(word_pos: &[(String, String)]) -> Vec<bool> {
+    // 差分数组：片段 [start, end] 在 start 处 +1、end+1 处 -1
+    let mut delta = vec![0i32; word_pos.len() + 1];
+    let mut open: Vec<(char, usize)> = Vec::new();
+    for (i, (word, _)) in word_pos.iter().enumerate() {
+        let mut chars = word.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        if let Some(&(_, close)) = QUOTE_PAIRS.iter().find(|(left, _)| *left == c) {
+            open.push((close, i));
+        } else if let Some(depth) = open.iter().rposition(|&(close, _)| close == c) {
+            let start = open[depth].1;
+            open.truncate(depth);
+            delta[start] += 1;
+            delta[i + 1] -= 1;
+        }
+    }
+    let mut level = 0;
+    delta[..word_pos.len()]
+        .iter()
+        .map(|d| {
+            level += d;
+            level > 0
+        })
+        .collect()
+}
+
+/// 读取停用词表：每行一个词，忽略空行与以 # 开头的注释行
+pub fn read_stopwords(path: &Path) -> io::Result<Vec<String>> {
+    Ok(parse_word_list(&fs::read_to_string(path)?))
+}
+
+/// 按停用词表的格式解析词表文本；目标词表、短语表与资源包中的词表格式相同
+///
+/// ```
+/// use betawordlist_lib::analysis::filter::parse_word_list;
+///
+/// assert_eq!(parse_word_list("# 虚词\n的\n\n  了 \r\n"), ["的", "了"]);
+/// ```
+pub fn parse_word_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
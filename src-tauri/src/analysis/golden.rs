@@ -0,0 +1,278 @@
+// golden.rs
+// 数值回归基准：以夹具语料跑完整的 analyze_corpus，把选定词的频次与各项分布指标与记录的基准值比较
+//
+// 夹具在 fixtures/golden：raw 为原文，tagged 为同一语料逐词标注的版本（"词/词性"），以 PretaggedTokenizer
+// 读入，不需要模型。基准值存于 fixtures/golden/expected.tsv，表头为 word、pos、frequency 与
+// DispersionMetrics::FIELD_NAMES，数值以最短的往返表示写出，缺失值留空。比较按相对误差 GOLDEN_TOLERANCE。
+// 指标有意变动时以 `betawordlist golden --update` 重写基准值：沿用原有的词，只改数值，变动在提交的差异中逐项可见。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{
+    atomic::write_atomic,
+    corpus_pipeline::{analyze_corpus, AnalysisResult},
+    dispersion_metrics::DispersionMetrics,
+    nlp::PretaggedTokenizer,
+    options::AnalysisOptions,
+    order_invariance::approx_equal,
+    phrases::PHRASE_POS,
+    posgrams::POSGRAM_POS,
+    progress::NoopReporter,
+};
+
+/// 比较时允许的相对误差
+pub const GOLDEN_TOLERANCE: f64 = 1e-9;
+/// 首次生成基准值时选取的词数
+pub const GOLDEN_WORDS: usize = 40;
+/// 基准值文件名
+pub const EXPECTED_FILE: &str = "expected.tsv";
+
+/// 一个词的基准值；metrics 按文件表头的顺序
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenRow {
+    pub word: String,
+    pub pos: String,
+    pub frequency: f64,
+    pub metrics: Vec<(String, Option<f64>)>,
+}
+
+/// 一处与基准值不符；field 为 "frequency"、指标名，词条缺失时为 "entry"
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenMismatch {
+    pub word: String,
+    pub pos: String,
+    pub field: String,
+    pub expected: Option<f64>,
+    pub actual: Option<f64>,
+}
+
+/// 仓库内的夹具目录
+pub fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/golden")
+}
+
+/// 基准分析的选项：默认选项，逐文件切分；改动此处即改变基准，须同时更新基准值
+pub fn golden_options() -> AnalysisOptions {
+    AnalysisOptions::default()
+}
+
+/// 以 PretaggedTokenizer 分析 dir/tagged 下的全部 .txt（按文件名排序）
+pub fn analyze_fixture(dir: &Path) -> Result<AnalysisResult, String> {
+    let tagged = dir.join("tagged");
+    let mut files: Vec<PathBuf> = fs::read_dir(&tagged)
+        .map_err(|e| format!("无法读取夹具目录 {}: {e}", tagged.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+    if files.is_empty() {
+        return Err(format!("夹具目录 {} 中没有 .txt 文件", tagged.display()));
+    }
+    analyze_corpus(
+        &PretaggedTokenizer,
+        &files,
+        &golden_options(),
+        None,
+        &NoopReporter,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// 按频次降序（同频按词、词性）均匀选取 count 个词，兼顾高频与低频词
+pub fn select_keys(result: &AnalysisResult, count: usize) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = result
+        .entries
+        .iter()
+        .filter(|e| e.pos != PHRASE_POS && e.pos != POSGRAM_POS && e.frequency > 0.0)
+        .collect();
+    entries.sort_by(|a, b| {
+        b.frequency
+            .total_cmp(&a.frequency)
+            .then_with(|| a.word.cmp(&b.word))
+            .then_with(|| a.pos.cmp(&b.pos))
+    });
+    let take = count.min(entries.len());
+    (0..take)
+        .map(|i| entries[i * entries.len() / take])
+        .map(|e| (e.word.clone(), e.pos.clone()))
+        .collect()
+}
+
+/// 取结果中各词的当前值；结果中没有的词跳过
+pub fn rows_for(result: &AnalysisResult, keys: &[(String, String)]) -> Vec<GoldenRow> {
+    keys.iter()
+        .filter_map(|(word, pos)| {
+            let entry = result
+                .entries
+                .iter()
+                .find(|e| e.word == *word && e.pos == *pos)?;
+            Some(GoldenRow {
+                word: word.clone(),
+                pos: pos.clone(),
+                frequency: entry.frequency,
+                metrics: entry
+                    .metrics
+                    .fields()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// 写出基准值文件的内容
+///
+/// ```
+/// use betawordlist_lib::analysis::golden::{format_golden, parse_golden, GoldenRow};
+///
+/// let rows = vec![GoldenRow {
+///     word: "学校".into(),
+///     pos: "n".into(),
+///     frequency: 2.0,
+///     metrics: vec![("dp".into(), Some(0.1 + 0.2)), ("carroll_d2".into(), None)],
+/// }];
+/// let text = format_golden(&rows);
+/// assert_eq!(text, "word\tpos\tfrequency\tdp\tcarroll_d2\n学校\tn\t2\t0.30000000000000004\t\n");
+/// assert_eq!(parse_golden(&text).unwrap(), rows);
+/// assert!(parse_golden("word\tpos\tfrequency\tdp\n学校\tn\t2\tabc\n").is_err());
+/// ```
+pub fn format_golden(rows: &[GoldenRow]) -> String {
+    let mut header = vec!["word", "pos", "frequency"];
+    match rows.first() {
+        Some(row) => header.extend(row.metrics.iter().map(|(name, _)| name.as_str())),
+        None => header.extend(DispersionMetrics::FIELD_NAMES),
+    }
+    let mut text = header.join("\t");
+    text.push('\n');
+    for row in rows {
+        let mut cells = vec![row.word.clone(), row.pos.clone(), row.frequency.to_string()];
+        cells.extend(
+            row.metrics
+                .iter()
+                .map(|(_, value)| value.map(|v| v.to_string()).unwrap_or_default()),
+        );
+        text.push_str(&cells.join("\t"));
+        text.push('\n');
+    }
+    text
+}
+
+/// 读取基准值文件的内容
+pub fn parse_golden(text: &str) -> Result<Vec<GoldenRow>, String> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<&str> = lines
+        .next()
+        .ok_or_else(|| "基准值文件为空".to_string())?
+        .split('\t')
+        .collect();
+    if header.get(..3) != Some(&["word", "pos", "frequency"][..]) {
+        return Err("基准值文件的表头应以 word、pos、frequency 开头".to_string());
+    }
+    let number = |cell: &str, line: usize| {
+        cell.parse::<f64>()
+            .map_err(|_| format!("基准值文件第 {line} 行的数值无效: {cell}"))
+    };
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let line_no = i + 2;
+            let cells: Vec<&str> = line.split('\t').collect();
+            if cells.len() != header.len() {
+                return Err(format!(
+                    "基准值文件第 {line_no} 行有 {} 列，表头有 {} 列",
+                    cells.len(),
+                    header.len()
+                ));
+            }
+            let metrics = header[3..]
+                .iter()
+                .zip(&cells[3..])
+                .map(|(name, cell)| {
+                    let value = match *cell {
+                        "" => None,
+                        cell => Some(number(cell, line_no)?),
+                    };
+                    Ok((name.to_string(), value))
+                })
+                .collect::<Result<_, String>>()?;
+            Ok(GoldenRow {
+                word: cells[0].to_string(),
+                pos: cells[1].to_string(),
+                frequency: number(cells[2], line_no)?,
+                metrics,
+            })
+        })
+        .collect()
+}
+
+/// 逐项比较基准值与结果中的当前值
+pub fn compare(expected: &[GoldenRow], result: &AnalysisResult) -> Vec<GoldenMismatch> {
+    let mut mismatches = Vec::new();
+    for row in expected {
+        let mismatch = |field: &str, expected: Option<f64>, actual: Option<f64>| GoldenMismatch {
+            word: row.word.clone(),
+            pos: row.pos.clone(),
+            field: field.to_string(),
+            expected,
+            actual,
+        };
+        let keys = [(row.word.clone(), row.pos.clone())];
+        let Some(actual) = rows_for(result, &keys).pop() else {
+            mismatches.push(mismatch("entry", Some(row.frequency), None));
+            continue;
+        };
+        if !approx_equal(
+            Some(row.frequency),
+            Some(actual.frequency),
+            GOLDEN_TOLERANCE,
+        ) {
+            mismatches.push(mismatch(
+                "frequency",
+                Some(row.frequency),
+                Some(actual.frequency),
+            ));
+        }
+        for (name, value) in &row.metrics {
+            let current = actual
+                .metrics
+                .iter()
+                .find(|(field, _)| field == name)
+                .and_then(|(_, value)| *value);
+            if !approx_equal(*value, current, GOLDEN_TOLERANCE) {
+                mismatches.push(mismatch(name, *value, current));
+            }
+        }
+    }
+    mismatches
+}
+
+/// 分析夹具语料并与 dir 下的基准值比较
+///
+/// 回归测试：任何改变已发布数值的改动都会在此失败，须以 `betawordlist golden --update` 显式更新基准值
+pub fn check(dir: &Path) -> Result<Vec<GoldenMismatch>, String> {
+    let path = dir.join(EXPECTED_FILE);
+    let text =
+        fs::read_to_string(&path).map_err(|e| format!("无法读取基准值 {}: {e}", path.display()))?;
+    let expected = parse_golden(&text)?;
+    let result = analyze_fixture(dir)?;
+    Ok(compare(&expected, &result))
+}
+
+/// 重新分析夹具语料并重写基准值，返回写出的词数。已有基准值时沿用其中的词，否则按 select_keys 选取
+pub fn update(dir: &Path) -> Result<usize, String> {
+    let result = analyze_fixture(dir)?;
+    let path = dir.join(EXPECTED_FILE);
+    let keys = match fs::read_to_string(&path) {
+        Ok(text) => parse_golden(&text)?
+            .into_iter()
+            .map(|row| (row.word, row.pos))
+            .collect(),
+        Err(_) => select_keys(&result, GOLDEN_WORDS),
+    };
+    let rows = rows_for(&result, &keys);
+    let text = format_golden(&rows);
+    write_atomic(&path, |w| w.write_all(text.as_bytes())).map_err(|e| e.to_string())?;
+    Ok(rows.len())
+}
@@ -0,0 +1,230 @@
+// group_keywords.rs
+// 组关键词：把语料部分按来源文件分组，逐组与其余各组合并比较，以对数似然与 log ratio 找出各组的特征词
+//
+// 分组表把来源文件映射到组名，键可以是完整路径或文件名，未列出的部分不参与比较。计算使用保留的各部分词频
+// （keep_parts），组频次与组词数为组内各部分之和；只比较词条，短语与词性序列不参与。对照侧频次为 0 时
+// 以 ZERO_SMOOTHING 代替，使 log ratio 有限；对数似然按原始频次计算。只返回在本组中相对更常见的词。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{corpus_pipeline::PartCounts, metadata::PartInfo};
+
+/// 频次为 0 时代替的值（Hardie 2014 的做法）
+pub const ZERO_SMOOTHING: f64 = 0.5;
+
+/// 组关键词选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupKeywordOptions {
+    /// 每组返回的关键词数；0 表示全部
+    pub top_n: usize,
+    /// 组内频次低于此值的词不参与
+    pub min_frequency: f64,
+}
+
+impl Default for GroupKeywordOptions {
+    fn default() -> Self {
+        Self {
+            top_n: 50,
+            min_frequency: 5.0,
+        }
+    }
+}
+
+/// 一个组的一个关键词
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupKeyword {
+    pub word: String,
+    pub pos: String,
+    /// 组内频次
+    pub frequency: f64,
+    /// 其余各组合计的频次
+    pub rest_frequency: f64,
+    /// 对数似然（G²），自由度 1
+    pub log_likelihood: f64,
+    /// 组内与其余各组相对频率之比的以 2 为底的对数
+    pub log_ratio: f64,
+}
+
+/// 一个组的关键词
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupKeywords {
+    pub group: String,
+    /// 组内的部分数
+    pub parts: usize,
+    /// 组内词数（各部分词数之和）
+    pub tokens: f64,
+    /// 按对数似然降序（同值按词、词性）
+    pub keywords: Vec<GroupKeyword>,
+}
+
+/// 一个组内各部分的合计
+#[derive(Default)]
+struct GroupTotals<'a> {
+    parts: usize,
+    tokens: f64,
+    counts: HashMap<(&'a str, &'a str), f64>,
+}
+
+/// 读取分组表：每行 "文件<TAB>组名"，文件可为完整路径或文件名；忽略空行与 # 注释行
+pub fn read_groups(path: &Path) -> io::Result<HashMap<String, String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(file, group)| (file.trim().to_string(), group.trim().to_string()))
+        .collect())
+}
+
+/// 部分所属的组：先按完整来源路径查找，再按文件名
+fn group_of<'a>(part: &PartInfo, groups: &'a HashMap<String, String>) -> Option<&'a String> {
+    groups.get(&part.source).or_else(|| {
+        let name = Path::new(&part.source).file_name()?.to_str()?;
+        groups.get(name)
+    })
+}
+
+/// 对数似然（G²）：a、b 为两侧频次，c、d 为两侧词数
+///
+/// ```
+/// use betawordlist_lib::analysis::group_keywords::log_likelihood;
+///
+/// // 两侧相对频率相同时为 0
+/// assert_eq!(log_likelihood(10.0, 20.0, 1000.0, 2000.0), 0.0);
+/// let g2 = log_likelihood(30.0, 10.0, 1000.0, 1000.0);
+/// assert!((g2 - 10.465).abs() < 1e-3);
+/// // 一侧为 0 时仍然有限
+/// assert!(log_likelihood(12.0, 0.0, 1000.0, 1000.0).is_finite());
+/// ```
+pub fn log_likelihood(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let expected_a = c * (a + b) / (c + d);
+    let expected_b = d * (a + b) / (c + d);
+    let term = |observed: f64, expected: f64| {
+        if observed > 0.0 {
+            observed * (observed / expected).ln()
+        } else {
+            0.0
+        }
+    };
+    2.0 * (term(a, expected_a) + term(b, expected_b))
+}
+
+/// log ratio：log2((a / c) / (b / d))，频次为 0 时以 ZERO_SMOOTHING 代替
+///
+/// ```
+/// use betawordlist_lib::analysis::group_keywords::log_ratio;
+///
+/// assert_eq!(log_ratio(40.0, 10.0, 1000.0, 1000.0), 2.0);
+/// assert_eq!(log_ratio(8.0, 0.0, 1000.0, 1000.0), 4.0);
+/// ```
+pub fn log_ratio(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let smooth = |v: f64| if v > 0.0 { v } else { ZERO_SMOOTHING };
+    ((smooth(a) / c) / (smooth(b) / d)).log2()
+}
+
+/// 按分组表计算各组对其余各组的关键词；组按组名排序。parts 须为保留的各部分词频，与 part_infos 一一对应
+pub fn group_keywords(
+    part_infos: &[PartInfo],
+    parts: &[PartCounts],
+    groups: &HashMap<String, String>,
+    options: &GroupKeywordOptions,
+) -> Result<Vec<GroupKeywords>, String> {
+    if parts.is_empty() || parts.len() != part_infos.len() {
+        return Err("结果未保留各部分词频，请开启 keep_parts 重新分析".to_string());
+    }
+
+    let mut totals: BTreeMap<&str, GroupTotals> = BTreeMap::new();
+    for (info, part) in part_infos.iter().zip(parts) {
+        let Some(group) = group_of(info, groups) else {
+            continue;
+        };
+        let group = totals.entry(group.as_str()).or_default();
+        group.parts += 1;
+        group.tokens += part.size;
+        for (word, pos, frequency) in &part.counts {
+            *group
+                .counts
+                .entry((word.as_str(), pos.as_str()))
+                .or_insert(0.0) += frequency;
+        }
+    }
+    if totals.len() < 2 {
+        return Err(format!(
+            "分组表中只有 {} 个组对应到语料部分，至少需要两个组才能比较",
+            totals.len()
+        ));
+    }
+    let all_tokens: f64 = totals.values().map(|group| group.tokens).sum();
+    let mut all_counts: HashMap<(&str, &str), f64> = HashMap::new();
+    for group in totals.values() {
+        for (&key, &frequency) in &group.counts {
+            *all_counts.entry(key).or_insert(0.0) += frequency;
+        }
+    }
+
+    Ok(totals
+        .iter()
+        .map(
+            |(
+                &group,
+                GroupTotals {
+                    parts,
+                    tokens,
+                    counts,
+                },
+            )| {
+                let rest_tokens = all_tokens - tokens;
+                // 某一侧没有词元时无从比较
+                let comparable = *tokens > 0.0 && rest_tokens > 0.0;
+                let mut keywords: Vec<GroupKeyword> = counts
+                    .iter()
+                    .filter(|_| comparable)
+                    .filter(|&(_, &frequency)| {
+                        frequency >= options.min_frequency && frequency > 0.0
+                    })
+                    .filter_map(|(&(word, pos), &frequency)| {
+                        let rest_frequency = all_counts[&(word, pos)] - frequency;
+                        // 只保留本组相对频率更高的词
+                        if frequency / tokens <= rest_frequency / rest_tokens {
+                            return None;
+                        }
+                        Some(GroupKeyword {
+                            word: word.to_string(),
+                            pos: pos.to_string(),
+                            frequency,
+                            rest_frequency,
+                            log_likelihood: log_likelihood(
+                                frequency,
+                                rest_frequency,
+                                *tokens,
+                                rest_tokens,
+                            ),
+                            log_ratio: log_ratio(frequency, rest_frequency, *tokens, rest_tokens),
+                        })
+                    })
+                    .collect();
+                keywords.sort_by(|a, b| {
+                    b.log_likelihood
+                        .total_cmp(&a.log_likelihood)
+                        .then_with(|| a.word.cmp(&b.word))
+                        .then_with(|| a.pos.cmp(&b.pos))
+                });
+                if options.top_n > 0 {
+                    keywords.truncate(options.top_n);
+                }
+                GroupKeywords {
+                    group: group.to_string(),
+                    parts: *parts,
+                    tokens: *tokens,
+                    keywords,
+                }
+            },
+        )
+        .collect())
+}
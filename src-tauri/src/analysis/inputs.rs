@@ -0,0 +1,124 @@
+// inputs.rs
+// 语料文件列表校验：分析开始前统一检查，空列表、目录与明显的非文本文件直接报错，而不是得到空结果或逐个读取失败
+//
+// 界面与命令行都经 validate_files 取得实际分析的文件列表。不存在或无法读取的文件不在此拦截，仍按空部分计入。
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::paths;
+
+/// 明显不是纯文本的扩展名（小写）
+const BINARY_EXTENSIONS: &[&str] = &[
+    "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "pdf", "epub", "zip", "rar", "7z",
+    "gz", "bz2", "xz", "tar", "exe", "dll", "so", "dylib", "bin", "png", "jpg", "jpeg", "gif",
+    "bmp", "tif", "tiff", "webp", "mp3", "wav", "flac", "ogg", "mp4", "avi", "mkv", "mov", "db",
+    "sqlite",
+];
+
+/// 文件列表的展开方式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputOptions {
+    /// 递归展开目录；关闭时传入目录即报错
+    pub recursive: bool,
+    /// 不按扩展名排除非文本文件
+    pub allow_all: bool,
+}
+
+/// 文件列表无效的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvalidInput {
+    /// 未指定任何文件
+    Empty,
+    /// 传入了目录但未开启递归
+    Directory(String),
+    /// 目录中没有可分析的文件
+    EmptyDirectory(String),
+    /// 扩展名表明不是纯文本
+    NotText(String),
+}
+
+impl fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "未指定待分析文件"),
+            InvalidInput::Directory(path) => {
+                write!(f, "{path} 是目录；如需分析其中的文件，请开启递归")
+            }
+            InvalidInput::EmptyDirectory(path) => write!(f, "目录 {path} 中没有可分析的文本文件"),
+            InvalidInput::NotText(path) => {
+                write!(f, "{path} 不是纯文本文件；如确需分析，请开启 allow_all")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidInput {}
+
+/// 校验文件列表并展开目录，返回实际分析的文件
+///
+/// 目录按路径排序递归展开，跳过隐藏文件与（未开启 allow_all 时）非文本文件。
+pub fn validate_files(
+    file_paths: &[PathBuf],
+    options: &InputOptions,
+) -> Result<Vec<PathBuf>, InvalidInput> {
+    if file_paths.is_empty() {
+        return Err(InvalidInput::Empty);
+    }
+    let mut files = Vec::new();
+    for path in file_paths {
+        if path.is_dir() {
+            if !options.recursive {
+                return Err(InvalidInput::Directory(paths::display(path)));
+            }
+            let found = list_dir(path, options.allow_all);
+            if found.is_empty() {
+                return Err(InvalidInput::EmptyDirectory(paths::display(path)));
+            }
+            files.extend(found);
+        } else if !options.allow_all && is_binary(path) {
+            return Err(InvalidInput::NotText(paths::display(path)));
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+/// 扩展名是否表明不是纯文本
+fn is_binary(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| BINARY_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// 递归列出目录下的文件，路径有序；无法读取的子目录跳过
+fn list_dir(dir: &Path, allow_all: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(read_dir) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(path);
+            } else if allow_all || !is_binary(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
@@ -0,0 +1,210 @@
+// interop.rs
+// 与其他语料工具交换词表：按 AntConc 与 #LancsBox 导入词表时的列布局写出，免去手工对列
+//
+// 两种布局都按频次降序、同频按词（其次词性）的码位升序排列，列的格式固定，不受 ExportOptions 影响：
+// - antconc：AntConc 词表文件，开头三行注释为词型数、语料词数与检索命中数（恒为 0），之后每行
+//   "排名<TAB>频次<TAB>分布范围<TAB>词"，排名从 1 起逐行递增。AntConc 的词表不区分词性，同形不同词性的条目
+//   合并，频次相加，分布范围按合并后的各部分频次重新计算（结果未保留各部分词频时取各词性中的最大值）
+// - lancsbox：带表头的制表符分隔表 "Type<TAB>PoS<TAB>Freq<TAB>RelFreq<TAB>Range<TAB>CV"，
+//   RelFreq 为每万词的频率，CV 为变异系数（总体标准差 / 均值），均保留两位小数
+//
+// 短语、词性序列与未出现的目标词不写出；语料词数即分布指标的分母 metadata.total_tokens。
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult, export::RowSink, phrases::PHRASE_POS, posgrams::POSGRAM_POS,
+    targets::ABSENT_POS, word_entry::WordEntry,
+};
+
+/// RelFreq 的基数（每万词）
+const LANCSBOX_PER: f64 = 10_000.0;
+
+/// 目标工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteropTool {
+    AntConc,
+    LancsBox,
+}
+
+impl InteropTool {
+    /// 按名称解析，不区分大小写；"#lancsbox" 同 "lancsbox"
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "antconc" => Some(Self::AntConc),
+            "lancsbox" | "#lancsbox" => Some(Self::LancsBox),
+            _ => None,
+        }
+    }
+
+    /// 文件扩展名，接在导出路径之后
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::AntConc => "antconc.txt",
+            Self::LancsBox => "lancsbox.tsv",
+        }
+    }
+}
+
+/// 一行：词、词性（antconc 为空）、频次、分布范围、变异系数
+struct InteropRow {
+    word: String,
+    pos: String,
+    frequency: f64,
+    range: usize,
+    cv: Option<f64>,
+}
+
+/// 按目标工具的布局写出词表；条目先缓存，finish 时排序写出
+pub struct InteropSink<W: Write> {
+    writer: W,
+    tool: InteropTool,
+    tokens: u64,
+    /// antconc：各词形合并词性后的分布范围；结果未保留各部分词频时为 None
+    form_ranges: Option<HashMap<String, usize>>,
+    rows: Vec<InteropRow>,
+}
+
+impl<W: Write> InteropSink<W> {
+    pub fn new(writer: W, tool: InteropTool) -> Self {
+        Self {
+            writer,
+            tool,
+            tokens: 0,
+            form_ranges: None,
+            rows: Vec::new(),
+        }
+    }
+
+    /// antconc 的行：同形不同词性的条目合并
+    fn merged_forms(&mut self) -> Vec<InteropRow> {
+        let mut forms: BTreeMap<String, InteropRow> = BTreeMap::new();
+        for row in self.rows.drain(..) {
+            let form = forms.entry(row.word.clone()).or_insert(InteropRow {
+                word: row.word,
+                pos: String::new(),
+                frequency: 0.0,
+                range: 0,
+                cv: None,
+            });
+            form.frequency += row.frequency;
+            form.range = form.range.max(row.range);
+        }
+        let ranges = self.form_ranges.take().unwrap_or_default();
+        forms
+            .into_values()
+            .map(|mut form| {
+                if let Some(&range) = ranges.get(&form.word) {
+                    form.range = range;
+                }
+                form
+            })
+            .collect()
+    }
+}
+
+impl<W: Write> RowSink for InteropSink<W> {
+    fn begin(&mut self, result: &AnalysisResult) -> io::Result<()> {
+        self.tokens = result.metadata.total_tokens;
+        if self.tool == InteropTool::AntConc {
+            // 同形各词性在各部分的频次之和，按 range_threshold 计数
+            let mut sums: HashMap<String, BTreeMap<usize, f64>> = HashMap::new();
+            let retained = result.for_each_part_count(|part, word, _, v| {
+                *sums
+                    .entry(word.to_string())
+                    .or_default()
+                    .entry(part)
+                    .or_insert(0.0) += v;
+            });
+            let threshold = result
+                .metadata
+                .options
+                .range_threshold
+                .max(f64::MIN_POSITIVE);
+            self.form_ranges = retained.then(|| {
+                sums.into_iter()
+                    .map(|(word, parts)| {
+                        (word, parts.values().filter(|&&v| v >= threshold).count())
+                    })
+                    .collect()
+            });
+        }
+        Ok(())
+    }
+
+    fn row(&mut self, entry: &WordEntry, _ranks: &BTreeMap<String, usize>) -> io::Result<()> {
+        let synthetic = [PHRASE_POS, POSGRAM_POS, ABSENT_POS].contains(&entry.pos.as_str());
+        if !synthetic && entry.frequency > 0.0 {
+            self.rows.push(InteropRow {
+                word: entry.word.clone(),
+                pos: entry.pos.clone(),
+                frequency: entry.frequency,
+                range: entry.metrics.range,
+                cv: entry.metrics.vc_population,
+            });
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let mut rows = match self.tool {
+            InteropTool::AntConc => self.merged_forms(),
+            InteropTool::LancsBox => std::mem::take(&mut self.rows),
+        };
+        rows.sort_by(|a, b| {
+            b.frequency
+                .total_cmp(&a.frequency)
+                .then_with(|| a.word.cmp(&b.word))
+                .then_with(|| a.pos.cmp(&b.pos))
+        });
+        let w = &mut self.writer;
+        match self.tool {
+            InteropTool::AntConc => {
+                writeln!(w, "#Word Types: {}", rows.len())?;
+                writeln!(w, "#Word Tokens: {}", self.tokens)?;
+                writeln!(w, "#Search Hits: 0")?;
+                for (i, row) in rows.iter().enumerate() {
+                    writeln!(
+                        w,
+                        "{}\t{}\t{}\t{}",
+                        i + 1,
+                        frequency(row.frequency),
+                        row.range,
+                        row.word
+                    )?;
+                }
+            }
+            InteropTool::LancsBox => {
+                writeln!(w, "Type\tPoS\tFreq\tRelFreq\tRange\tCV")?;
+                let tokens = self.tokens as f64;
+                for row in &rows {
+                    let relative = if tokens > 0.0 {
+                        row.frequency / tokens * LANCSBOX_PER
+                    } else {
+                        0.0
+                    };
+                    let cv = row.cv.map(|cv| format!("{cv:.2}")).unwrap_or_default();
+                    writeln!(
+                        w,
+                        "{}\t{}\t{}\t{relative:.2}\t{}\t{cv}",
+                        row.word,
+                        row.pos,
+                        frequency(row.frequency),
+                        row.range
+                    )?;
+                }
+            }
+        }
+        w.flush()
+    }
+}
+
+/// 频次：整数不带小数点（导入的计数与加权计数都可读）
+fn frequency(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,900 @@
+// json_export.rs
+// 结果 JSON 导出：供程序读取的稳定格式，字段为 camelCase，附带由类型生成的 JSON Schema
+//
+// 文档结构独立于会话文件与前端使用的结构（两者为 snake_case，且随内部实现变化）。
+// 文档格式变化时递增 SCHEMA_VERSION；新增字段不视为格式变化。
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use schemars::{schema::RootSchema, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult,
+    dispersion_metrics::DispersionMetrics,
+    export::RowSink,
+    filter::{DenominatorPolicy, FilterCounts},
+    language::{LanguageScreen, LowHanFile, ScreenMode, ScreenedFile, ScriptHistogram},
+    metadata::{CorpusMetadata, FileInfo, PartInfo, PartMerges, Truncation},
+    near_duplicates::{DuplicatePair, DuplicatePolicy, NearDuplicateOptions},
+    oov::AdjacentSingles,
+    options::{AnalysisOptions, PartitionMode, QuickLook},
+    pos_histogram::{PosOutlier, PosOutlierOptions},
+    posgrams::PosGramOptions,
+    sampling::{CorpusSample, SampleSize, SamplingOptions},
+    token_policy::{LatinCase, TokenPolicy, TokenPolicyCounts},
+    word_entry::{SurfaceVariant, WordEntry},
+};
+
+/// 文档格式版本
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 写入 "$schema" 的标识
+pub const SCHEMA_ID: &str = "urn:betawordlist:result:1";
+
+/// JSON 导出选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonExportOptions {
+    /// 缩进排版；关闭时输出紧凑的单行 JSON
+    pub pretty: bool,
+}
+
+/// 导出的结果文档：元数据与全部条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDocument {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub schema_version: u32,
+    pub metadata: MetadataRecord,
+    pub entries: Vec<EntryRecord>,
+}
+
+impl ResultDocument {
+    pub fn new(result: &AnalysisResult) -> Self {
+        Self {
+            schema: SCHEMA_ID.to_string(),
+            schema_version: SCHEMA_VERSION,
+            metadata: MetadataRecord::from(&result.metadata),
+            entries: result.entries.iter().map(EntryRecord::from).collect(),
+        }
+    }
+}
+
+/// 词表条目；缺失的指标写为 null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryRecord {
+    pub word: String,
+    pub pos: String,
+    /// 稳定标识：SHA-256("签名\x1f词\x1f词性") 的前 16 个十六进制字符，签名见 metadata.normalizationSignature
+    pub id: String,
+    pub char_length: usize,
+    pub frequency: f64,
+    pub metrics: MetricsRecord,
+    pub gt_adjusted_frequency: Option<f64>,
+    pub quoted_frequency: Option<f64>,
+    /// 同一词形的全部词元中标为该词性的比例（0–1）：由标注分布近似的启发式置信度，
+    /// 并非词性模型的分数；仅开启 pos_confidence 时给出
+    pub pos_tag_share: Option<f64>,
+    pub first_occurrence_part: Option<usize>,
+    pub first_occurrence_fraction: Option<f64>,
+    pub occurrence_span_fraction: Option<f64>,
+    pub max_part_index: Option<usize>,
+    /// 规范化前的各写法，即 WordEntry::surface_forms；沿用首版的字段名
+    pub variants: Vec<VariantRecord>,
+    pub examples: Vec<String>,
+    /// 条目标记，见 entry_flags 模块；新增标记不视为格式变化
+    pub flags: Vec<String>,
+}
+
+impl From<&WordEntry> for EntryRecord {
+    fn from(entry: &WordEntry) -> Self {
+        Self {
+            word: entry.word.clone(),
+            pos: entry.pos.clone(),
+            id: entry.id.clone(),
+            char_length: entry.char_length,
+            frequency: entry.frequency,
+            metrics: MetricsRecord::from(&entry.metrics),
+            gt_adjusted_frequency: entry.gt_adjusted_frequency,
+            quoted_frequency: entry.quoted_frequency,
+            pos_tag_share: entry.pos_tag_share,
+            first_occurrence_part: entry.first_occurrence_part,
+            first_occurrence_fraction: entry.first_occurrence_fraction,
+            occurrence_span_fraction: entry.occurrence_span_fraction,
+            max_part_index: entry.max_part_index,
+            variants: entry
+                .surface_forms
+                .iter()
+                .map(VariantRecord::from)
+                .collect(),
+            examples: entry.examples.clone(),
+            flags: entry.flags.clone(),
+        }
+    }
+}
+
+/// 规范化前的一种写法，含义见 SurfaceVariant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantRecord {
+    pub form: String,
+    pub frequency: f64,
+    pub other: bool,
+}
+
+impl From<&SurfaceVariant> for VariantRecord {
+    fn from(v: &SurfaceVariant) -> Self {
+        Self {
+            form: v.form.clone(),
+            frequency: v.frequency,
+            other: v.other,
+        }
+    }
+}
+
+/// 分布指标，含义见 DispersionMetrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsRecord {
+    pub range: usize,
+    pub sd_population: Option<f64>,
+    pub vc_population: Option<f64>,
+    pub juilland_d: Option<f64>,
+    pub carroll_d2: Option<f64>,
+    pub roschengren_s_adj: Option<f64>,
+    pub roschengren_s: Option<f64>,
+    pub dp: Option<f64>,
+    pub dp_norm: Option<f64>,
+    pub kl_divergence: Option<f64>,
+    pub jsd_dispersion: Option<f64>,
+    pub hellinger_dispersion: Option<f64>,
+    pub mean_text_frequency_ft: Option<f64>,
+    pub mean_text_frequency_ft_pm: Option<f64>,
+    pub pervasiveness_pt: Option<f64>,
+    pub evenness_da: Option<f64>,
+    pub ft_adjusted_by_pt: Option<f64>,
+    pub ft_adjusted_by_da: Option<f64>,
+    pub max_part_share: Option<f64>,
+    pub gini: Option<f64>,
+    pub min_part_rate: Option<f64>,
+    pub max_part_rate: Option<f64>,
+    pub range_at_threshold: usize,
+    pub pervasiveness_pt_at_threshold: Option<f64>,
+    pub within_file_dp: Option<f64>,
+    pub within_file_juilland_d: Option<f64>,
+}
+
+impl From<&DispersionMetrics> for MetricsRecord {
+    fn from(m: &DispersionMetrics) -> Self {
+        Self {
+            range: m.range,
+            sd_population: m.sd_population,
+            vc_population: m.vc_population,
+            juilland_d: m.juilland_d,
+            carroll_d2: m.carroll_d2,
+            roschengren_s_adj: m.roschengren_s_adj,
+            roschengren_s: m.roschengren_s,
+            dp: m.dp,
+            dp_norm: m.dp_norm,
+            kl_divergence: m.kl_divergence,
+            jsd_dispersion: m.jsd_dispersion,
+            hellinger_dispersion: m.hellinger_dispersion,
+            mean_text_frequency_ft: m.mean_text_frequency_ft,
+            mean_text_frequency_ft_pm: m.mean_text_frequency_ft_pm,
+            pervasiveness_pt: m.pervasiveness_pt,
+            evenness_da: m.evenness_da,
+            ft_adjusted_by_pt: m.ft_adjusted_by_pt,
+            ft_adjusted_by_da: m.ft_adjusted_by_da,
+            max_part_share: m.max_part_share,
+            gini: m.gini,
+            min_part_rate: m.min_part_rate,
+            max_part_rate: m.max_part_rate,
+            range_at_threshold: m.range_at_threshold,
+            pervasiveness_pt_at_threshold: m.pervasiveness_pt_at_threshold,
+            within_file_dp: m.within_file_dp,
+            within_file_juilland_d: m.within_file_juilland_d,
+        }
+    }
+}
+
+/// 分析元数据，含义见 CorpusMetadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataRecord {
+    pub app_version: String,
+    pub analyzed_at: String,
+    pub run_id: Option<String>,
+    pub partition_mode: String,
+    pub preset: Option<String>,
+    pub options: OptionsRecord,
+    pub tokenizer: String,
+    /// 规范化签名；两次导出的签名相同时可按条目 id 连接
+    pub normalization_signature: String,
+    pub model_files: Vec<FileRecord>,
+    pub files: Vec<FileRecord>,
+    pub seed: u64,
+    pub sample: Option<SampleRecord>,
+    pub parts: Vec<PartRecord>,
+    pub part_merges: Option<PartMergesRecord>,
+    pub adjacent_singles: Option<AdjacentSinglesRecord>,
+    pub screened_files: Vec<ScreenedFileRecord>,
+    pub scripts: ScriptsRecord,
+    pub low_han_files: Vec<LowHanFileRecord>,
+    pub sentences: Option<u64>,
+    pub paragraphs: Option<u64>,
+    pub near_duplicates: Vec<DuplicatePairRecord>,
+    pub merged_from: Vec<String>,
+    pub warnings: Vec<String>,
+    pub file_errors: Vec<String>,
+    pub resumed_parts: usize,
+    pub total_tokens: u64,
+    pub total_raw_tokens: u64,
+    pub size_exclusions: Vec<String>,
+    pub total_types: usize,
+    pub token_policy_removed: TokenPolicyCountsRecord,
+    pub filter_removed: FilterCountsRecord,
+    pub min_frequency_removed: usize,
+    pub pos_outliers: Vec<PosOutlierRecord>,
+    pub estimated_memory_bytes: u64,
+    pub inconsistent_entries: usize,
+    pub sanitized_values: usize,
+    pub sanitized_by_field: BTreeMap<String, usize>,
+    pub truncation: Option<TruncationRecord>,
+}
+
+impl From<&CorpusMetadata> for MetadataRecord {
+    fn from(m: &CorpusMetadata) -> Self {
+        Self {
+            app_version: m.app_version.clone(),
+            analyzed_at: m.analyzed_at.clone(),
+            run_id: m.run_id.clone(),
+            partition_mode: m.partition_mode.clone(),
+            preset: m.preset.clone(),
+            options: OptionsRecord::from(&m.options),
+            tokenizer: m.tokenizer.clone(),
+            normalization_signature: m.normalization_signature.clone(),
+            model_files: m.model_files.iter().map(FileRecord::from).collect(),
+            files: m.files.iter().map(FileRecord::from).collect(),
+            seed: m.seed,
+            sample: m.sample.as_ref().map(SampleRecord::from),
+            parts: m.parts.iter().map(PartRecord::from).collect(),
+            part_merges: m.part_merges.as_ref().map(PartMergesRecord::from),
+            adjacent_singles: m.adjacent_singles.as_ref().map(AdjacentSinglesRecord::from),
+            screened_files: m
+                .screened_files
+                .iter()
+                .map(ScreenedFileRecord::from)
+                .collect(),
+            scripts: ScriptsRecord::from(&m.scripts),
+            low_han_files: m.low_han_files.iter().map(LowHanFileRecord::from).collect(),
+            sentences: m.sentences,
+            paragraphs: m.paragraphs,
+            near_duplicates: m
+                .near_duplicates
+                .iter()
+                .map(DuplicatePairRecord::from)
+                .collect(),
+            merged_from: m.merged_from.clone(),
+            warnings: m.warnings.clone(),
+            file_errors: m.file_errors.clone(),
+            resumed_parts: m.resumed_parts,
+            total_tokens: m.total_tokens,
+            total_raw_tokens: m.total_raw_tokens,
+            size_exclusions: m.size_exclusions.clone(),
+            total_types: m.total_types,
+            token_policy_removed: TokenPolicyCountsRecord::from(&m.token_policy_removed),
+            filter_removed: FilterCountsRecord::from(&m.filter_removed),
+            min_frequency_removed: m.min_frequency_removed,
+            pos_outliers: m.pos_outliers.iter().map(PosOutlierRecord::from).collect(),
+            estimated_memory_bytes: m.estimated_memory_bytes,
+            inconsistent_entries: m.inconsistent_entries,
+            sanitized_values: m.sanitized_values,
+            sanitized_by_field: m.sanitized_by_field.clone(),
+            truncation: m.truncation.as_ref().map(TruncationRecord::from),
+        }
+    }
+}
+
+/// 分析选项，含义见 AnalysisOptions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionsRecord {
+    pub hash_files: bool,
+    pub checkpoint_every: Option<usize>,
+    pub burstiness_window: Option<usize>,
+    pub token_policy: TokenPolicyRecord,
+    pub latin_case: LatinCase,
+    pub max_surface_forms: usize,
+    pub language_screen: LanguageScreenRecord,
+    pub rate_base: f64,
+    pub range_threshold: f64,
+    pub stopwords: Vec<String>,
+    pub excluded_pos: Vec<String>,
+    pub denominator: DenominatorPolicyRecord,
+    pub segment_batch_size: usize,
+    pub segment_timeout_ms: Option<u64>,
+    pub structural_noise: bool,
+    pub protect_quotes: bool,
+    pub pos_confidence: bool,
+    pub memory_limit_mb: Option<u64>,
+    pub min_frequency: Option<f64>,
+    pub two_pass: bool,
+    pub keep_parts: bool,
+    pub collect_examples: usize,
+    pub example_min_frequency: f64,
+    pub oov_pairs: Option<usize>,
+    pub target_words: Vec<String>,
+    pub phrases: Vec<String>,
+    pub posgrams: Option<PosGramRecord>,
+    pub partition: PartitionMode,
+    pub min_part_tokens: usize,
+    pub merge_small_parts: bool,
+    pub pos_outlier: PosOutlierOptionsRecord,
+    pub near_duplicates: Option<NearDuplicateRecord>,
+    pub sampling: Option<SamplingRecord>,
+    pub seed: Option<u64>,
+    pub track_positions: bool,
+    pub file_counts_top: Option<usize>,
+    pub strict: bool,
+    pub quick_look: Option<QuickLookRecord>,
+}
+
+impl From<&AnalysisOptions> for OptionsRecord {
+    fn from(o: &AnalysisOptions) -> Self {
+        Self {
+            hash_files: o.hash_files,
+            checkpoint_every: o.checkpoint_every,
+            burstiness_window: o.burstiness_window,
+            token_policy: TokenPolicyRecord::from(&o.token_policy),
+            latin_case: o.latin_case,
+            max_surface_forms: o.max_surface_forms,
+            language_screen: LanguageScreenRecord::from(&o.language_screen),
+            rate_base: o.rate_base,
+            range_threshold: o.range_threshold,
+            stopwords: o.stopwords.clone(),
+            excluded_pos: o.excluded_pos.clone(),
+            denominator: DenominatorPolicyRecord::from(&o.denominator),
+            segment_batch_size: o.segment_batch_size,
+            segment_timeout_ms: o.segment_timeout_ms,
+            structural_noise: o.structural_noise,
+            protect_quotes: o.protect_quotes,
+            pos_confidence: o.pos_confidence,
+            memory_limit_mb: o.memory_limit_mb,
+            min_frequency: o.min_frequency,
+            two_pass: o.two_pass,
+            keep_parts: o.keep_parts,
+            collect_examples: o.collect_examples,
+            example_min_frequency: o.example_min_frequency,
+            oov_pairs: o.oov_pairs,
+            target_words: o.target_words.clone(),
+            phrases: o.phrases.clone(),
+            posgrams: o.posgrams.as_ref().map(PosGramRecord::from),
+            partition: o.partition,
+            min_part_tokens: o.min_part_tokens,
+            merge_small_parts: o.merge_small_parts,
+            pos_outlier: PosOutlierOptionsRecord::from(&o.pos_outlier),
+            near_duplicates: o.near_duplicates.as_ref().map(NearDuplicateRecord::from),
+            sampling: o.sampling.as_ref().map(SamplingRecord::from),
+            seed: o.seed,
+            track_positions: o.track_positions,
+            file_counts_top: o.file_counts_top,
+            strict: o.strict,
+            quick_look: o.quick_look.as_ref().map(QuickLookRecord::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPolicyRecord {
+    pub exclude_punctuation_from_list: bool,
+    pub exclude_punctuation_from_sizes: bool,
+    pub exclude_symbols: bool,
+    pub sanitize: bool,
+}
+
+impl From<&TokenPolicy> for TokenPolicyRecord {
+    fn from(p: &TokenPolicy) -> Self {
+        Self {
+            exclude_punctuation_from_list: p.exclude_punctuation_from_list,
+            exclude_punctuation_from_sizes: p.exclude_punctuation_from_sizes,
+            exclude_symbols: p.exclude_symbols,
+            sanitize: p.sanitize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DenominatorPolicyRecord {
+    pub stopwords: bool,
+    pub excluded_pos: bool,
+    pub symbols: bool,
+    pub structural_noise: bool,
+}
+
+impl From<&DenominatorPolicy> for DenominatorPolicyRecord {
+    fn from(d: &DenominatorPolicy) -> Self {
+        Self {
+            stopwords: d.stopwords,
+            excluded_pos: d.excluded_pos,
+            symbols: d.symbols,
+            structural_noise: d.structural_noise,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageScreenRecord {
+    pub mode: ScreenMode,
+    pub min_han_ratio: f64,
+}
+
+impl From<&LanguageScreen> for LanguageScreenRecord {
+    fn from(s: &LanguageScreen) -> Self {
+        Self {
+            mode: s.mode,
+            min_han_ratio: s.min_han_ratio,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NearDuplicateRecord {
+    pub threshold: f64,
+    pub policy: DuplicatePolicy,
+}
+
+impl From<&NearDuplicateOptions> for NearDuplicateRecord {
+    fn from(o: &NearDuplicateOptions) -> Self {
+        Self {
+            threshold: o.threshold,
+            policy: o.policy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplingRecord {
+    pub size: SampleSize,
+    pub seed: u64,
+}
+
+impl From<&SamplingOptions> for SamplingRecord {
+    fn from(o: &SamplingOptions) -> Self {
+        Self {
+            size: o.size,
+            seed: o.seed,
+        }
+    }
+}
+
+/// 实际抽得的样本，含义见 CorpusSample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SampleRecord {
+    pub seed: u64,
+    pub size: SampleSize,
+    pub input_files: usize,
+    pub files: Vec<String>,
+    pub estimated_tokens: Option<u64>,
+}
+
+impl From<&CorpusSample> for SampleRecord {
+    fn from(s: &CorpusSample) -> Self {
+        Self {
+            seed: s.seed,
+            size: s.size,
+            input_files: s.input_files,
+            files: s.files.clone(),
+            estimated_tokens: s.estimated_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickLookRecord {
+    pub top_n: usize,
+    pub min_per_million: f64,
+}
+
+impl From<&QuickLook> for QuickLookRecord {
+    fn from(q: &QuickLook) -> Self {
+        Self {
+            top_n: q.top_n,
+            min_per_million: q.min_per_million,
+        }
+    }
+}
+
+/// 速览模式的截断记录，含义见 Truncation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TruncationRecord {
+    pub top_n: usize,
+    pub types_before: usize,
+    pub min_frequency: f64,
+}
+
+impl From<&Truncation> for TruncationRecord {
+    fn from(t: &Truncation) -> Self {
+        Self {
+            top_n: t.top_n,
+            types_before: t.types_before,
+            min_frequency: t.min_frequency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PosGramRecord {
+    pub n: usize,
+    pub min_frequency: f64,
+}
+
+impl From<&PosGramOptions> for PosGramRecord {
+    fn from(o: &PosGramOptions) -> Self {
+        Self {
+            n: o.n,
+            min_frequency: o.min_frequency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PosOutlierOptionsRecord {
+    pub tag: String,
+    pub z_threshold: f64,
+}
+
+impl From<&PosOutlierOptions> for PosOutlierOptionsRecord {
+    fn from(o: &PosOutlierOptions) -> Self {
+        Self {
+            tag: o.tag.clone(),
+            z_threshold: o.z_threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PosOutlierRecord {
+    pub part: usize,
+    pub label: String,
+    pub tag: String,
+    pub share: f64,
+    pub mean_share: f64,
+    pub z: f64,
+}
+
+impl From<&PosOutlier> for PosOutlierRecord {
+    fn from(o: &PosOutlier) -> Self {
+        Self {
+            part: o.part,
+            label: o.label.clone(),
+            tag: o.tag.clone(),
+            share: o.share,
+            mean_share: o.mean_share,
+            z: o.z,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePairRecord {
+    pub file_a: String,
+    pub file_b: String,
+    pub similarity: f64,
+    pub kept: Option<String>,
+}
+
+impl From<&DuplicatePair> for DuplicatePairRecord {
+    fn from(p: &DuplicatePair) -> Self {
+        Self {
+            file_a: p.file_a.clone(),
+            file_b: p.file_b.clone(),
+            similarity: p.similarity,
+            kept: p.kept.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRecord {
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified: Option<u64>,
+    pub sha256: Option<String>,
+    pub scripts: Option<ScriptsRecord>,
+    pub sentences: Option<u64>,
+    pub paragraphs: Option<u64>,
+}
+
+impl From<&FileInfo> for FileRecord {
+    fn from(f: &FileInfo) -> Self {
+        Self {
+            path: f.path.clone(),
+            size_bytes: f.size_bytes,
+            modified: f.modified,
+            sha256: f.sha256.clone(),
+            scripts: f.scripts.as_ref().map(ScriptsRecord::from),
+            sentences: f.sentences,
+            paragraphs: f.paragraphs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptsRecord {
+    pub han: u64,
+    pub latin: u64,
+    pub kana: u64,
+    pub hangul: u64,
+    pub other_letters: u64,
+    pub digits: u64,
+    pub punctuation: u64,
+    pub whitespace: u64,
+    pub other: u64,
+    pub han_ratio: Option<f64>,
+}
+
+impl From<&ScriptHistogram> for ScriptsRecord {
+    fn from(s: &ScriptHistogram) -> Self {
+        Self {
+            han: s.han,
+            latin: s.latin,
+            kana: s.kana,
+            hangul: s.hangul,
+            other_letters: s.other_letters,
+            digits: s.digits,
+            punctuation: s.punctuation,
+            whitespace: s.whitespace,
+            other: s.other,
+            han_ratio: s.han_ratio(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LowHanFileRecord {
+    pub path: String,
+    pub han_ratio: f64,
+}
+
+impl From<&LowHanFile> for LowHanFileRecord {
+    fn from(f: &LowHanFile) -> Self {
+        Self {
+            path: f.path.clone(),
+            han_ratio: f.han_ratio,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PartRecord {
+    pub index: usize,
+    pub source: String,
+    pub sub_index: Option<usize>,
+    pub tokens: u64,
+    pub raw_tokens: u64,
+    pub pos_counts: BTreeMap<String, u64>,
+    pub units: Vec<usize>,
+}
+
+impl From<&PartInfo> for PartRecord {
+    fn from(p: &PartInfo) -> Self {
+        Self {
+            index: p.index,
+            source: p.source.clone(),
+            sub_index: p.sub_index,
+            tokens: p.tokens,
+            raw_tokens: p.raw_tokens,
+            pos_counts: p.pos_counts.clone(),
+            units: p.units.clone(),
+        }
+    }
+}
+
+/// 合并过小部分的记录，含义见 PartMerges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PartMergesRecord {
+    pub min_tokens: usize,
+    pub units: usize,
+    pub merges: usize,
+    pub merged_parts: usize,
+}
+
+impl From<&PartMerges> for PartMergesRecord {
+    fn from(m: &PartMerges) -> Self {
+        Self {
+            min_tokens: m.min_tokens,
+            units: m.units,
+            merges: m.merges,
+            merged_parts: m.merged_parts,
+        }
+    }
+}
+
+/// 相邻单字对，含义见 AdjacentSingles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdjacentSinglesRecord {
+    pub limit: usize,
+    pub pairs: Vec<AdjacentPairRecord>,
+    pub pruned: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdjacentPairRecord {
+    pub left: String,
+    pub right: String,
+    pub count: u64,
+}
+
+impl From<&AdjacentSingles> for AdjacentSinglesRecord {
+    fn from(a: &AdjacentSingles) -> Self {
+        Self {
+            limit: a.limit,
+            pairs: a
+                .pairs
+                .iter()
+                .map(|p| AdjacentPairRecord {
+                    left: p.left.clone(),
+                    right: p.right.clone(),
+                    count: p.count,
+                })
+                .collect(),
+            pruned: a.pruned,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenedFileRecord {
+    pub path: String,
+    pub han_ratio: f64,
+    pub action: ScreenMode,
+}
+
+impl From<&ScreenedFile> for ScreenedFileRecord {
+    fn from(f: &ScreenedFile) -> Self {
+        Self {
+            path: f.path.clone(),
+            han_ratio: f.han_ratio,
+            action: f.action,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPolicyCountsRecord {
+    pub punctuation_from_list: u64,
+    pub punctuation_from_sizes: u64,
+    pub symbols: u64,
+    pub invisible_chars: u64,
+    pub blank_tokens: u64,
+}
+
+impl From<&TokenPolicyCounts> for TokenPolicyCountsRecord {
+    fn from(c: &TokenPolicyCounts) -> Self {
+        Self {
+            punctuation_from_list: c.punctuation_from_list,
+            punctuation_from_sizes: c.punctuation_from_sizes,
+            symbols: c.symbols,
+            invisible_chars: c.invisible_chars,
+            blank_tokens: c.blank_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterCountsRecord {
+    pub stopwords: u64,
+    pub excluded_pos: u64,
+    pub structural_noise: u64,
+    pub quote_exempted: u64,
+}
+
+impl From<&FilterCounts> for FilterCountsRecord {
+    fn from(c: &FilterCounts) -> Self {
+        Self {
+            stopwords: c.stopwords,
+            excluded_pos: c.excluded_pos,
+            structural_noise: c.structural_noise,
+            quote_exempted: c.quote_exempted,
+        }
+    }
+}
+
+/// 写出结果文档
+pub fn write_json<W: Write>(
+    writer: W,
+    result: &AnalysisResult,
+    options: &JsonExportOptions,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let document = ResultDocument::new(result);
+    if options.pretty {
+        serde_json::to_writer_pretty(&mut writer, &document)?;
+    } else {
+        serde_json::to_writer(&mut writer, &document)?;
+    }
+    writer.flush()
+}
+
+/// 逐条目写出与 write_json（紧凑格式）相同的文档，可与其他格式一起经 export_rows 写出
+pub struct JsonSink<W: Write> {
+    writer: W,
+    first: bool,
+}
+
+impl<W: Write> JsonSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            first: true,
+        }
+    }
+}
+
+impl<W: Write> RowSink for JsonSink<W> {
+    fn begin(&mut self, result: &AnalysisResult) -> io::Result<()> {
+        // 字段顺序同 ResultDocument
+        write!(self.writer, "{{\"$schema\":")?;
+        serde_json::to_writer(&mut self.writer, SCHEMA_ID)?;
+        write!(
+            self.writer,
+            ",\"schemaVersion\":{SCHEMA_VERSION},\"metadata\":"
+        )?;
+        serde_json::to_writer(&mut self.writer, &MetadataRecord::from(&result.metadata))?;
+        write!(self.writer, ",\"entries\":[")
+    }
+
+    fn row(&mut self, entry: &WordEntry, _ranks: &BTreeMap<String, usize>) -> io::Result<()> {
+        if !std::mem::take(&mut self.first) {
+            write!(self.writer, ",")?;
+        }
+        Ok(serde_json::to_writer(
+            &mut self.writer,
+            &EntryRecord::from(entry),
+        )?)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        write!(self.writer, "]}}")?;
+        self.writer.flush()
+    }
+}
+
+/// 读取导出的结果文档
+pub fn read_json(path: &Path) -> io::Result<ResultDocument> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// 结果文档的 JSON Schema，由上述类型生成
+pub fn result_schema() -> RootSchema {
+    schemars::schema_for!(ResultDocument)
+}
@@ -0,0 +1,202 @@
+// language.rs
+// 语言筛查：按汉字占比识别非中文文件，决定跳过、改用空白分词或照常分析；
+// 另在逐文件处理时统计每个文件的字符构成（汉字、拉丁字母、数字、标点与其他文字），供选择筛查阈值
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    paths,
+    text::is_han,
+    token_policy::{is_invisible_char, is_latin_letter},
+};
+
+/// 每个采样点读取的字节数
+const SAMPLE_BYTES: u64 = 4096;
+/// 采样点数：文件开头及均匀分布的若干偏移，避免被开头的长英文摘要误导
+const SAMPLE_POINTS: u64 = 4;
+
+/// 汉字占比低于阈值的文件如何处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenMode {
+    /// 照常分析（不筛查）
+    #[default]
+    Include,
+    /// 跳过，不形成语料部分
+    Skip,
+    /// 改用空白分词器
+    Whitespace,
+}
+
+/// 语言筛查选项
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageScreen {
+    pub mode: ScreenMode,
+    /// 汉字在全部字母类字符中的最低占比
+    pub min_han_ratio: f64,
+}
+
+impl Default for LanguageScreen {
+    fn default() -> Self {
+        Self {
+            mode: ScreenMode::Include,
+            min_han_ratio: 0.5,
+        }
+    }
+}
+
+/// 被筛查命中的文件及其处理方式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenedFile {
+    pub path: String,
+    pub han_ratio: f64,
+    pub action: ScreenMode,
+}
+
+impl LanguageScreen {
+    /// 判断文件的处理方式；未命中（或无法读取、不含字母类字符）时返回 None，照常分析
+    pub fn screen(&self, path: &Path) -> Option<ScreenedFile> {
+        if self.mode == ScreenMode::Include {
+            return None;
+        }
+        let han_ratio = sample_han_ratio(path).ok().flatten()?;
+        (han_ratio < self.min_han_ratio).then(|| ScreenedFile {
+            path: paths::display(path),
+            han_ratio,
+            action: self.mode,
+        })
+    }
+}
+
+/// 从文件多个偏移处采样，估计汉字在字母类字符中的占比；没有字母类字符时为 None
+pub fn sample_han_ratio(path: &Path) -> io::Result<Option<f64>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut han = 0usize;
+    let mut letters = 0usize;
+    // 文件不超过全部采样的总长时直接整体读取
+    let (points, sample) = if len <= SAMPLE_BYTES * SAMPLE_POINTS {
+        (1, len)
+    } else {
+        (SAMPLE_POINTS, SAMPLE_BYTES)
+    };
+    let mut buf = Vec::new();
+    for i in 0..points {
+        file.seek(SeekFrom::Start(len * i / points))?;
+        buf.clear();
+        (&mut file).take(sample).read_to_end(&mut buf)?;
+        // 偏移可能落在多字节字符中间，首尾的残缺字节会变成替换字符，不计入统计
+        for c in String::from_utf8_lossy(&buf).chars() {
+            if c.is_alphabetic() {
+                letters += 1;
+                han += usize::from(is_han(c));
+            }
+        }
+    }
+    Ok((letters > 0).then(|| han as f64 / letters as f64))
+}
+
+/// 文本的字符构成：按文字与字符类别逐字计数。字母类字符分为 han、latin、kana、hangul 与 other_letters，
+/// 其和即 sample_han_ratio 所用的字母类字符数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptHistogram {
+    pub han: u64,
+    /// 拉丁字母，含带附加符号的字母与全角字母
+    pub latin: u64,
+    /// 平假名与片假名
+    pub kana: u64,
+    pub hangul: u64,
+    /// 其余文字的字母（西里尔、希腊、阿拉伯等）
+    pub other_letters: u64,
+    pub digits: u64,
+    /// 标点与符号，含全角标点与表情符号
+    pub punctuation: u64,
+    pub whitespace: u64,
+    /// 控制字符与不可见字符
+    pub other: u64,
+}
+
+impl ScriptHistogram {
+    /// 统计一段文本
+    pub fn of(text: &str) -> Self {
+        let mut scripts = Self::default();
+        for c in text.chars() {
+            let slot = if c.is_whitespace() {
+                &mut scripts.whitespace
+            } else if is_han(c) {
+                &mut scripts.han
+            } else if is_latin_letter(c) {
+                &mut scripts.latin
+            } else if c.is_alphabetic() && is_kana(c) {
+                &mut scripts.kana
+            } else if c.is_alphabetic() && is_hangul(c) {
+                &mut scripts.hangul
+            } else if c.is_alphabetic() {
+                &mut scripts.other_letters
+            } else if c.is_numeric() {
+                &mut scripts.digits
+            } else if is_invisible_char(c) {
+                &mut scripts.other
+            } else {
+                &mut scripts.punctuation
+            };
+            *slot += 1;
+        }
+        scripts
+    }
+
+    /// 累加另一段文本的计数
+    pub fn add(&mut self, other: &Self) {
+        self.han += other.han;
+        self.latin += other.latin;
+        self.kana += other.kana;
+        self.hangul += other.hangul;
+        self.other_letters += other.other_letters;
+        self.digits += other.digits;
+        self.punctuation += other.punctuation;
+        self.whitespace += other.whitespace;
+        self.other += other.other;
+    }
+
+    /// 字母类字符数
+    pub fn letters(&self) -> u64 {
+        self.han + self.latin + self.kana + self.hangul + self.other_letters
+    }
+
+    /// 全部字符数
+    pub fn chars(&self) -> u64 {
+        self.letters() + self.digits + self.punctuation + self.whitespace + self.other
+    }
+
+    /// 汉字在字母类字符中的占比，口径同 sample_han_ratio；没有字母类字符时为 None
+    pub fn han_ratio(&self) -> Option<f64> {
+        let letters = self.letters();
+        (letters > 0).then(|| self.han as f64 / letters as f64)
+    }
+}
+
+/// 汉字占比低于 language_screen.min_han_ratio 的文件（按全文统计，不论筛查方式）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LowHanFile {
+    pub path: String,
+    pub han_ratio: f64,
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}')
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'
+        | '\u{3130}'..='\u{318F}'
+        | '\u{A960}'..='\u{A97F}'
+        | '\u{AC00}'..='\u{D7FF}')
+}
@@ -0,0 +1,91 @@
+// length.rs
+// 词长分布与按词长、条目标记筛选的结果分页
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{profile::ProfileTable, rank, word_entry::WordEntry};
+
+/// 某一词长的类型数与词次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LengthBucket {
+    pub length: usize,
+    pub types: u64,
+    pub tokens: f64,
+}
+
+/// 按词长统计类型数与词次数，词长升序
+pub fn length_distribution<'a>(
+    entries: impl IntoIterator<Item = &'a WordEntry>,
+) -> Vec<LengthBucket> {
+    let mut buckets: BTreeMap<usize, (u64, f64)> = BTreeMap::new();
+    for entry in entries {
+        let bucket = buckets.entry(entry.char_length).or_default();
+        bucket.0 += 1;
+        bucket.1 += entry.frequency;
+    }
+    buckets
+        .into_iter()
+        .map(|(length, (types, tokens))| LengthBucket {
+            length,
+            types,
+            tokens,
+        })
+        .collect()
+}
+
+/// 结果分页查询；未给出的条件不做限制
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct EntryQuery {
+    pub offset: usize,
+    /// 每页条数，None 表示取到末尾
+    pub limit: Option<usize>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    /// 只取带有全部这些标记的条目，见 entry_flags 模块
+    pub flags: Vec<String>,
+    /// 为本页条目填写这些指标的排名（在筛选后的全部条目中计算），见 rank 模块
+    pub ranks: Vec<String>,
+}
+
+impl EntryQuery {
+    /// 条目是否满足筛选条件（不含分页）
+    pub fn matches(&self, entry: &WordEntry) -> bool {
+        self.min_length.is_none_or(|min| entry.char_length >= min)
+            && self.max_length.is_none_or(|max| entry.char_length <= max)
+            && self.flags.iter().all(|flag| entry.flags.contains(flag))
+    }
+}
+
+/// 一页结果；total 为筛选后的总条数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryPage {
+    pub total: usize,
+    pub entries: Vec<WordEntry>,
+    /// 按输出配置渲染的本页表格；仅指定输出配置时给出
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<ProfileTable>,
+}
+
+/// 按查询条件筛选并分页；排名列名须先经 rank::validate_names 校验，标记名须先经 entry_flags::validate_names 校验
+pub fn query_entries(entries: &[WordEntry], query: &EntryQuery) -> EntryPage {
+    let filtered: Vec<&WordEntry> = entries.iter().filter(|e| query.matches(e)).collect();
+    let mut ranks = rank::entry_ranks(&filtered, &query.ranks);
+    let page = filtered
+        .iter()
+        .zip(ranks.iter_mut())
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .map(|(e, ranks)| WordEntry {
+            ranks: std::mem::take(ranks),
+            ..(*e).clone()
+        })
+        .collect();
+    EntryPage {
+        total: filtered.len(),
+        entries: page,
+        table: None,
+    }
+}
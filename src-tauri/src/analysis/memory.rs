@@ -0,0 +1,77 @@
+// memory.rs
+// 计数阶段的粗略内存估算与软上限
+//
+// 估算只覆盖随词表增长的部分（全局词频表及其附属表），按 hashbrown 的槽位布局与分配器的 8 字节对齐折算，
+// 与实际占用的误差一般在 20% 以内；分词器与单个文件的临时数据不计入。
+
+use std::fmt;
+use std::mem::size_of;
+
+use serde::{Deserialize, Serialize};
+
+/// 哈希表槽位的平均放大系数：负载因子 7/8 且容量按 2 的幂增长，平均约 1.3 倍
+const SLOT_OVERHEAD: f64 = 1.3;
+
+/// 字符串堆内存按 8 字节对齐
+fn heap_bytes(len: usize) -> usize {
+    len.div_ceil(8) * 8
+}
+
+/// 一个以 (词, 词性) 为键、值类型为 V 的哈希表条目的估算字节数；extra_heap 为值另占的堆内存
+pub fn entry_bytes<V>(word: &str, pos: &str, extra_heap: usize) -> u64 {
+    let slot = size_of::<((String, String), V)>() as f64 * SLOT_OVERHEAD + 1.0;
+    (slot as usize + heap_bytes(word.len()) + heap_bytes(pos.len()) + extra_heap) as u64
+}
+
+/// 逐句切分时稠密词频表（词型数 × 部分数）的单元上限，约合 800 MB
+pub const DENSE_CELL_LIMIT: usize = 100_000_000;
+
+/// 超出内存软上限时的中止原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryLimitExceeded {
+    pub limit_bytes: u64,
+    pub estimated_bytes: u64,
+    /// 中止时的词表条目数
+    pub vocab_entries: usize,
+    /// 中止前已计入的文件数
+    pub files_done: usize,
+}
+
+impl fmt::Display for MemoryLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "估算内存 {} MB 超过上限 {} MB（已处理 {} 个文件，词表 {} 条）。\
+             请提高内存上限，或设置 min_frequency 并开启两遍计数以缩小词表",
+            self.estimated_bytes / (1 << 20),
+            self.limit_bytes / (1 << 20),
+            self.files_done,
+            self.vocab_entries,
+        )
+    }
+}
+
+impl std::error::Error for MemoryLimitExceeded {}
+
+/// 逐句切分的部分过多，稠密词频表超出 DENSE_CELL_LIMIT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DensePartsExceeded {
+    pub parts: usize,
+    pub vocab_entries: usize,
+    /// 中止前已计入的文件数
+    pub files_done: usize,
+}
+
+impl fmt::Display for DensePartsExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "逐句切分已得到 {} 个部分、{} 个词型（已处理 {} 个文件），超出稠密词频表的上限 {} 个单元。\
+             词频表目前只有稠密实现，逐句切分只适用于较小的语料；请缩小语料、设置 min_frequency 并开启两遍计数，\
+             或改为按文件切分",
+            self.parts, self.vocab_entries, self.files_done, DENSE_CELL_LIMIT,
+        )
+    }
+}
+
+impl std::error::Error for DensePartsExceeded {}
@@ -0,0 +1,86 @@
+// merge.rs
+// 合并分别分析的子语料：拼接各会话保存的部分词频，在并集上重新计算全部指标
+
+use crate::analysis::{
+    corpus_pipeline::{self, AnalysisResult},
+    diff,
+    metadata::CorpusMetadata,
+};
+
+/// 合并多个分析结果，sessions 为 (来源名, 结果)，来源名用于错误提示与元数据
+///
+/// 每个结果都须在开启 keep_parts 时得到；分词引擎、切分方式与影响数值的选项须与第一个一致。
+/// 合并结果沿用第一个结果的选项，各部分按给定顺序拼接。
+pub fn merge_results(sessions: Vec<(String, AnalysisResult)>) -> Result<AnalysisResult, String> {
+    let (first_name, first) = sessions.first().ok_or("没有可合并的会话")?;
+    for (name, result) in &sessions {
+        if !result.metadata.options.keep_parts {
+            return Err(format!(
+                "会话 {name} 未保存各部分词频，请开启 keep_parts 重新分析后再合并"
+            ));
+        }
+        let files = result.metadata.files.len();
+        if result.metadata.partition_mode == "per_file" && result.parts.len() != files {
+            return Err(format!(
+                "会话 {name} 的部分词频数（{}）与文件数（{files}）不符",
+                result.parts.len()
+            ));
+        }
+        let conflicts = diff::setting_warnings(&first.metadata, &result.metadata);
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "会话 {name} 与 {first_name} 的分析设置不兼容：{}",
+                conflicts.join("；")
+            ));
+        }
+    }
+
+    let mut metadata = CorpusMetadata::new(&first.metadata.tokenizer, &first.metadata.options);
+    metadata.partition_mode = first.metadata.partition_mode.clone();
+    metadata.seed = first.metadata.seed;
+    metadata.model_files = first.metadata.model_files.clone();
+    metadata.warnings = first.metadata.options.warnings();
+    let mut parts = Vec::new();
+    let mut sources = Vec::new();
+    for (name, result) in sessions {
+        sources.extend(part_sources(&name, &result));
+        metadata.merged_from.push(name);
+        metadata.files.extend(result.metadata.files);
+        metadata
+            .screened_files
+            .extend(result.metadata.screened_files);
+        metadata.file_errors.extend(result.metadata.file_errors);
+        for (total, count) in [
+            (&mut metadata.sentences, result.metadata.sentences),
+            (&mut metadata.paragraphs, result.metadata.paragraphs),
+        ] {
+            if let Some(count) = count {
+                *total = Some(total.unwrap_or(0) + count);
+            }
+        }
+        parts.extend(result.parts);
+    }
+    corpus_pipeline::aggregate_parts(parts, sources, metadata)
+}
+
+/// 各部分的来源：优先取元数据中的部分信息，旧会话退回文件列表，都不对应时以 "会话#序号" 表示
+fn part_sources(name: &str, result: &AnalysisResult) -> Vec<(String, Option<usize>)> {
+    let count = result.parts.len();
+    if result.metadata.parts.len() == count {
+        result
+            .metadata
+            .parts
+            .iter()
+            .map(|p| (p.source.clone(), p.sub_index))
+            .collect()
+    } else if result.metadata.files.len() == count {
+        result
+            .metadata
+            .files
+            .iter()
+            .map(|f| (f.path.clone(), None))
+            .collect()
+    } else {
+        (0..count).map(|i| (format!("{name}#{i}"), None)).collect()
+    }
+}
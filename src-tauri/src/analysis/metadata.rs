@@ -0,0 +1,288 @@
+// metadata.rs
+// 分析元数据，随结果一并返回与导出，便于追溯结果的来源与异常
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::{
+    filter::{FilterCounts, FilterReport},
+    language::{LowHanFile, ScreenedFile, ScriptHistogram},
+    near_duplicates::DuplicatePair,
+    oov::AdjacentSingles,
+    options::AnalysisOptions,
+    paths,
+    pos_histogram::PosOutlier,
+    sampling::CorpusSample,
+    seed,
+    token_policy::TokenPolicyCounts,
+};
+
+/// 文件指纹：大小、修改时间，以及可选的 SHA-256
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FileInfo {
+    pub path: String,
+    pub size_bytes: u64,
+    /// 修改时间（Unix 秒）
+    pub modified: Option<u64>,
+    /// 未开启 hash_files 或读取失败时为 None
+    pub sha256: Option<String>,
+    /// 全文的字符构成，分词前统计；模型文件、从检查点恢复的文件与读取失败的文件为 None
+    #[serde(default)]
+    pub scripts: Option<ScriptHistogram>,
+    /// 句数，见 text::TextUnits；改用空白分词的文件不按中文句末标点断句，与 scripts 一样未读取的文件同为 None
+    #[serde(default)]
+    pub sentences: Option<u64>,
+    /// 段数（非空行数），见 text::TextUnits；未读取的文件为 None
+    #[serde(default)]
+    pub paragraphs: Option<u64>,
+}
+
+impl FileInfo {
+    /// 读取文件指纹；文件不可访问时大小记为 0
+    pub fn collect(path: &Path, hash: bool) -> Self {
+        let meta = fs::metadata(path).ok();
+        let modified = meta
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        Self {
+            path: paths::display(path),
+            size_bytes: meta.as_ref().map_or(0, |m| m.len()),
+            modified,
+            sha256: if hash { sha256_file(path).ok() } else { None },
+            scripts: None,
+            sentences: None,
+            paragraphs: None,
+        }
+    }
+}
+
+/// 流式计算文件 SHA-256，避免整文件读入内存
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 一个语料部分：来源文件（或文本编号）、块序号与词数；结果中的各部分数据均按 index 引用
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PartInfo {
+    pub index: usize,
+    /// 来源文件路径；直接分析文本时为 "text_<序号>"
+    pub source: String,
+    /// 同一来源切分为多个块或段落时的序号；整个文件为一个部分时为 None
+    pub sub_index: Option<usize>,
+    /// 部分词数，即分布指标所用的分母
+    pub tokens: u64,
+    /// 原始词数：分词所得的全部词元，未经任何扣除
+    #[serde(default)]
+    pub raw_tokens: u64,
+    /// 各词性的词元数，见 pos_histogram 模块
+    #[serde(default)]
+    pub pos_counts: BTreeMap<String, u64>,
+    /// 开启 merge_small_parts 时组成本部分的原始句子序号（文件内，依次相邻）；
+    /// 未合并时为空。合并后的部分的分布指标以整个部分为单位计算，不再区分其中的句子
+    #[serde(default)]
+    pub units: Vec<usize>,
+}
+
+impl PartInfo {
+    /// 导出用的简短标签：文件名，有块序号时加 "#序号"；由多个句子合并而成时为 "#首句-末句"
+    pub fn label(&self) -> String {
+        let name = Path::new(&self.source)
+            .file_name()
+            .map_or_else(|| self.source.clone(), |n| n.to_string_lossy().to_string());
+        match (self.units.as_slice(), self.sub_index) {
+            ([first, .., last], _) => format!("{name}#{first}-{last}"),
+            (_, Some(sub)) => format!("{name}#{sub}"),
+            (_, None) => name,
+        }
+    }
+
+    /// 某词性的词元占比；部分没有词元时为 None
+    pub fn pos_share(&self, tag: &str) -> Option<f64> {
+        let total: u64 = self.pos_counts.values().sum();
+        (total > 0).then(|| self.pos_counts.get(tag).copied().unwrap_or(0) as f64 / total as f64)
+    }
+}
+
+/// 速览模式截断的记录；结果不是完整词表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Truncation {
+    /// 保留的词条数上限
+    pub top_n: usize,
+    /// 截断前（已按最低频次筛选）的词条数
+    pub types_before: usize,
+    /// 实际使用的最低频次：min_frequency 与按语料规模换算的频次中的较大者
+    pub min_frequency: f64,
+}
+
+/// 合并过小部分的记录，见 AnalysisOptions::merge_small_parts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PartMerges {
+    /// 合并门槛，即 options.min_part_tokens
+    pub min_tokens: usize,
+    /// 合并前的句子数
+    pub units: usize,
+    /// 并入其他句子的句子数，即合并前后部分数之差
+    pub merges: usize,
+    /// 由多个句子合并而成的部分数
+    pub merged_parts: usize,
+}
+
+/// 语料分析元数据
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CorpusMetadata {
+    /// 生成结果的应用版本
+    pub app_version: String,
+    /// 分析开始时间（RFC 3339，UTC）
+    pub analyzed_at: String,
+    /// 产生本结果的分析运行 id，同进度事件中的 run_id；命令行与测试中的分析为 None
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// 语料切分方式
+    pub partition_mode: String,
+    /// 所用的分析预设；options 为预设与显式选项合并后的结果
+    #[serde(default)]
+    pub preset: Option<String>,
+    pub options: AnalysisOptions,
+    /// 分词引擎名称（ltp / rule）
+    pub tokenizer: String,
+    /// 规范化签名，见 entry_id::normalization_signature；签名相同的两次导出可按条目 id 连接
+    #[serde(default)]
+    pub normalization_signature: String,
+    /// 分词引擎使用的模型文件
+    pub model_files: Vec<FileInfo>,
+    /// 语料文件
+    pub files: Vec<FileInfo>,
+    /// 本次分析所用的种子：options.seed，未指定时为生成的种子；以它重新分析可复现抽样与例句
+    #[serde(default)]
+    pub seed: u64,
+    /// 开启抽样时的种子与抽中的文件；None 表示分析了全部输入文件
+    #[serde(default)]
+    pub sample: Option<CorpusSample>,
+    /// 语料部分，顺序即部分序号
+    pub parts: Vec<PartInfo>,
+    /// 开启 merge_small_parts 时的合并记录；None 表示未合并
+    #[serde(default)]
+    pub part_merges: Option<PartMerges>,
+    /// 开启 oov_pairs 时统计的相邻单字对；None 表示未统计
+    #[serde(default)]
+    pub adjacent_singles: Option<AdjacentSingles>,
+    /// 被语言筛查命中的文件（跳过的文件不在 files 中）
+    pub screened_files: Vec<ScreenedFile>,
+    /// 本次读取的全部文件（含并入的近重复文件）字符构成之和
+    #[serde(default)]
+    pub scripts: ScriptHistogram,
+    /// 汉字占比低于 options.language_screen.min_han_ratio 的文件，按路径排序
+    #[serde(default)]
+    pub low_han_files: Vec<LowHanFile>,
+    /// 本次读取的全部文件（含并入的近重复文件）的句数之和；没有任何文件计得句数时为 None
+    #[serde(default)]
+    pub sentences: Option<u64>,
+    /// 同上，段数之和
+    #[serde(default)]
+    pub paragraphs: Option<u64>,
+    /// 检测到的近重复文件对；按策略去掉或并入的文件不在 files 中
+    #[serde(default)]
+    pub near_duplicates: Vec<DuplicatePair>,
+    /// 合并而来时，依次为各来源会话
+    pub merged_from: Vec<String>,
+    /// 选项之间相互影响的提示
+    pub warnings: Vec<String>,
+    /// 读取失败的文件（"路径: 原因"），按空部分计入
+    pub file_errors: Vec<String>,
+    /// 从检查点恢复、未重新分词的部分数
+    pub resumed_parts: usize,
+    /// 各部分词数之和，即分布指标的分母
+    pub total_tokens: u64,
+    /// 各部分原始词数之和
+    #[serde(default)]
+    pub total_raw_tokens: u64,
+    /// 部分词数从原始词数中扣除的词元类别（见 DenominatorPolicy::size_exclusions）；为空时分母即原始词数
+    #[serde(default)]
+    pub size_exclusions: Vec<String>,
+    pub total_types: usize,
+    /// token_policy 各开关移除的词元数
+    pub token_policy_removed: TokenPolicyCounts,
+    /// 停用词/词性过滤统计
+    pub filter_removed: FilterCounts,
+    /// 各项过滤移除的词元、词型与各文件的移除数
+    #[serde(default)]
+    pub filter_report: FilterReport,
+    /// 总频次低于 min_frequency 而移出词表的词型数
+    pub min_frequency_removed: usize,
+    /// 按 options.pos_outlier 判定的词性占比异常的部分
+    #[serde(default)]
+    pub pos_outliers: Vec<PosOutlier>,
+    /// 计数阶段词频表的估算内存（字节）
+    pub estimated_memory_bytes: u64,
+    /// 部分频次与部分词数不一致的条目数，其 S / S_adj 记为 None
+    pub inconsistent_entries: usize,
+    /// 被清洗为 None 的非有限指标值（NaN/±Inf）总数
+    pub sanitized_values: usize,
+    /// 按指标字段统计的清洗次数，便于定位产生非有限值的公式
+    pub sanitized_by_field: BTreeMap<String, usize>,
+    /// 速览模式下结果被截断时的记录；None 表示词表完整
+    #[serde(default)]
+    pub truncation: Option<Truncation>,
+}
+
+impl CorpusMetadata {
+    /// 以当前时间、应用版本与选项初始化元数据；未指定种子时生成一个
+    pub fn new(tokenizer: &str, options: &AnalysisOptions) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            analyzed_at: now_rfc3339(),
+            partition_mode: options.partition.as_str().to_string(),
+            options: options.clone(),
+            tokenizer: tokenizer.to_string(),
+            seed: options.seed.unwrap_or_else(seed::generate_seed),
+            ..Default::default()
+        }
+    }
+
+    /// 记录一次清洗结果
+    pub fn record_sanitized(&mut self, fields: &[&'static str]) {
+        self.sanitized_values += fields.len();
+        for field in fields {
+            *self
+                .sanitized_by_field
+                .entry((*field).to_string())
+                .or_insert(0) += 1;
+        }
+    }
+}
+
+/// 当前时间的 RFC 3339 表示（UTC，精确到秒）
+pub(crate) fn now_rfc3339() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // 由 1970-01-01 起的天数推算公历日期（Howard Hinnant 的 civil_from_days 算法）
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,401 @@
+// metric_descriptions.rs
+// 指标说明：每个可比较指标的定义、取值范围、出处与方向，供界面提示与导出附表使用
+//
+// 记号：f 为总频次，n 为部分数，v_i 为第 i 部分的频次，s_i 为第 i 部分词数占全语料的比例，
+// p_i = v_i / 第 i 部分词数。新增 DispersionMetrics 字段时须在此补上说明，见 metric_descriptions 的示例。
+// 供界面着色的字段结构（metric_schema）由同一张说明表生成：上下界取自 range，方向取自 higher_is_more_even。
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+/// 一个指标的说明
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricDescription {
+    /// 字段名，同 DispersionMetrics::FIELD_NAMES 与导出列名
+    pub name: &'static str,
+    /// 简短定义
+    pub definition: &'static str,
+    /// 取值范围；序列化为 "≥ 0"、"[0, 1)" 等写法
+    #[serde(serialize_with = "serialize_range")]
+    pub range: ValueRange,
+    /// 出处；本工具自定义的辅助指标为 None
+    pub citation: Option<&'static str>,
+    /// 值越大是否分布越均匀；不衡量分布均匀程度的指标（如频次）为 None
+    pub higher_is_more_even: Option<bool>,
+    /// 所属分组
+    pub group: MetricGroup,
+    /// 界面显示的小数位数
+    pub precision: u8,
+}
+
+/// 指标分组
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricGroup {
+    /// 频次与频率
+    Frequency,
+    /// 分布指标
+    Dispersion,
+    /// 按分布或频次谱调整后的频次
+    Adjusted,
+}
+
+impl MetricDescription {
+    const fn adjusted(mut self) -> Self {
+        self.group = MetricGroup::Adjusted;
+        self
+    }
+
+    const fn precision(mut self, digits: u8) -> Self {
+        self.precision = digits;
+        self
+    }
+}
+
+const GRIES_2008: &str =
+    "Gries, S. Th. (2008). Dispersions and adjusted frequencies in corpora. IJCL 13(4), 403–437.";
+const JUILLAND_1964: &str =
+    "Juilland, A., & Chang-Rodríguez, E. (1964). Frequency dictionary of Spanish words. Mouton.";
+const ROSENGREN_1971: &str = "Rosengren, I. (1971). The quantitative concept of language and its relation to the structure of frequency dictionaries. Études de linguistique appliquée 1, 103–127.";
+const EGBERT_2020: &str =
+    "Egbert, J., Burch, B., & Biber, D. (2020). Lexical dispersion and corpus design. IJCL 25(1), 89–115.";
+
+const fn describe(
+    name: &'static str,
+    definition: &'static str,
+    range: ValueRange,
+    citation: Option<&'static str>,
+    higher_is_more_even: Option<bool>,
+) -> MetricDescription {
+    MetricDescription {
+        name,
+        definition,
+        range,
+        citation,
+        higher_is_more_even,
+        // 衡量分布均匀程度的归为分布指标，其余为频次；调整频次与显示位数按需另行标注
+        group: match higher_is_more_even {
+            Some(_) => MetricGroup::Dispersion,
+            None => MetricGroup::Frequency,
+        },
+        precision: 4,
+    }
+}
+
+/// ≥ 0
+const NON_NEGATIVE: ValueRange = ValueRange {
+    min: 0.0,
+    max: UpperBound::Unbounded,
+};
+/// 0 – n
+const UP_TO_PARTS: ValueRange = ValueRange {
+    min: 0.0,
+    max: UpperBound::PartCount,
+};
+/// [0, 1]
+const UNIT: ValueRange = ValueRange {
+    min: 0.0,
+    max: UpperBound::Value {
+        max: 1.0,
+        inclusive: true,
+    },
+};
+/// [0, 1)
+const UNIT_EXCLUSIVE: ValueRange = ValueRange {
+    min: 0.0,
+    max: UpperBound::Value {
+        max: 1.0,
+        inclusive: false,
+    },
+};
+
+/// 全部指标说明：frequency、gt_adjusted_frequency，其后按 DispersionMetrics::FIELD_NAMES 的顺序
+const DESCRIPTIONS: &[MetricDescription] = &[
+    describe("frequency", "全语料总频次 f", NON_NEGATIVE, None, None).precision(0),
+    describe(
+        "gt_adjusted_frequency",
+        "按频次谱做 Simple Good-Turing 平滑后的频次",
+        NON_NEGATIVE,
+        Some("Gale, W. A., & Sampson, G. (1995). Good-Turing frequency estimation without tears. JQL 2(3), 217–237."),
+        None,
+    )
+    .adjusted()
+    .precision(2),
+    describe("range", "出现过（v_i > 0）的部分数", UP_TO_PARTS, None, Some(true)).precision(0),
+    describe(
+        "sd_population",
+        "各部分频次 v_i 的总体标准差",
+        NON_NEGATIVE,
+        Some(GRIES_2008),
+        Some(false),
+    ),
+    describe(
+        "vc_population",
+        "各部分频次的总体变异系数：标准差 / 均值",
+        NON_NEGATIVE,
+        Some(GRIES_2008),
+        Some(false),
+    ),
+    describe(
+        "juilland_d",
+        "Juilland D：1 − vc(p) / √(n − 1)，vc(p) 为各部分频率的变异系数",
+        UNIT,
+        Some(JUILLAND_1964),
+        Some(true),
+    ),
+    describe(
+        "carroll_d2",
+        "Carroll D2：各部分频率归一化后的熵 / log n",
+        UNIT,
+        Some("Carroll, J. B. (1970). An alternative to Juilland's usage coefficient for lexical frequencies. ETS Research Bulletin."),
+        Some(true),
+    ),
+    describe(
+        "roschengren_s_adj",
+        "Rosengren S（按部分大小加权）：(Σ √(s_i · v_i))² / f",
+        UNIT,
+        Some(ROSENGREN_1971),
+        Some(true),
+    ),
+    describe(
+        "roschengren_s",
+        "Rosengren S（各部分等权）：(Σ √v_i)² / (n · f)",
+        UNIT,
+        Some(ROSENGREN_1971),
+        Some(true),
+    ),
+    describe(
+        "dp",
+        "Gries DP：½ Σ |v_i / f − s_i|，实际分布与按部分大小的期望分布之差",
+        UNIT_EXCLUSIVE,
+        Some(GRIES_2008),
+        Some(false),
+    ),
+    describe(
+        "dp_norm",
+        "标准化 DP：DP / (1 − min s_i)，使最大值为 1",
+        UNIT,
+        Some("Lijffijt, J., & Gries, S. Th. (2012). Correction to Stefan Th. Gries' \"Dispersions and adjusted frequencies in corpora\". IJCL 17(1), 147–149."),
+        Some(false),
+    ),
+    describe(
+        "kl_divergence",
+        "v_i / f 相对于 s_i 的 KL 散度（以 2 为底）",
+        NON_NEGATIVE,
+        Some("Gries, S. Th. (2020). Analyzing dispersion. In A practical handbook of corpus linguistics, 99–118. Springer."),
+        Some(false),
+    ),
+    describe(
+        "jsd_dispersion",
+        "1 − JSD(v_i / f, s_i)，JSD 为以 2 为底的 Jensen-Shannon 散度",
+        UNIT,
+        Some("Lin, J. (1991). Divergence measures based on the Shannon entropy. IEEE Trans. Inf. Theory 37(1), 145–151."),
+        Some(true),
+    ),
+    describe(
+        "hellinger_dispersion",
+        "1 − H(v_i / f, s_i)，H 为 Hellinger 距离 √(1 − Σ √(v_i / f · s_i))",
+        UNIT,
+        Some("Hellinger, E. (1909). Neue Begründung der Theorie quadratischer Formen von unendlichvielen Veränderlichen. J. reine angew. Math. 136, 210–271."),
+        Some(true),
+    ),
+    describe(
+        "mean_text_frequency_ft",
+        "平均文本频率 FT：各部分频率 p_i 的均值",
+        NON_NEGATIVE,
+        Some(EGBERT_2020),
+        None,
+    )
+    .precision(6),
+    describe(
+        "mean_text_frequency_ft_pm",
+        "FT 按 rate_base 缩放（默认每百万词）",
+        NON_NEGATIVE,
+        Some(EGBERT_2020),
+        None,
+    )
+    .precision(2),
+    describe(
+        "pervasiveness_pt",
+        "普遍度 PT：range / n",
+        UNIT,
+        Some(EGBERT_2020),
+        Some(true),
+    ),
+    describe(
+        "evenness_da",
+        "均匀度 DA：1 − 各部分频率两两之差的平均绝对值 / (2 · 平均频率)",
+        UNIT,
+        Some("Burch, B., Egbert, J., & Biber, D. (2017). Measuring and interpreting lexical dispersion in corpus linguistics. JRDH 1(2), 189–216."),
+        Some(true),
+    ),
+    describe(
+        "ft_adjusted_by_pt",
+        "FT × PT，按普遍度调整的频率",
+        NON_NEGATIVE,
+        Some(EGBERT_2020),
+        None,
+    )
+    .adjusted()
+    .precision(6),
+    describe(
+        "ft_adjusted_by_da",
+        "FT × DA，按均匀度调整的频率",
+        NON_NEGATIVE,
+        Some(EGBERT_2020),
+        None,
+    )
+    .adjusted()
+    .precision(6),
+    describe(
+        "max_part_share",
+        "最高部分占比：max v_i / f",
+        UNIT,
+        None,
+        Some(false),
+    ),
+    describe(
+        "gini",
+        "各部分频率 p_i 的基尼系数",
+        UNIT_EXCLUSIVE,
+        Some("Gini, C. (1912). Variabilità e mutabilità. Tipografia di Paolo Cuppini."),
+        Some(false),
+    ),
+    describe(
+        "min_part_rate",
+        "各部分频率 p_i 的最小值，不含词数为 0 的部分",
+        UNIT,
+        None,
+        None,
+    )
+    .precision(6),
+    describe(
+        "max_part_rate",
+        "各部分频率 p_i 的最大值",
+        UNIT,
+        None,
+        None,
+    )
+    .precision(6),
+    describe(
+        "range_at_threshold",
+        "出现次数不少于 range_threshold 的部分数",
+        UP_TO_PARTS,
+        None,
+        Some(true),
+    )
+    .precision(0),
+    describe(
+        "pervasiveness_pt_at_threshold",
+        "range_at_threshold / n",
+        UNIT,
+        None,
+        Some(true),
+    ),
+    describe(
+        "within_file_dp",
+        "文件内按 burstiness_window 切分滑窗后求得的 DP，在含该词的文件间取均值",
+        UNIT_EXCLUSIVE,
+        Some(GRIES_2008),
+        Some(false),
+    ),
+    describe(
+        "within_file_juilland_d",
+        "文件内滑窗的 Juilland D，在含该词的文件间取均值",
+        UNIT,
+        Some(JUILLAND_1964),
+        Some(true),
+    ),
+];
+
+/// 全部指标说明：frequency、gt_adjusted_frequency，其后按 DispersionMetrics::FIELD_NAMES 的顺序
+pub fn metric_descriptions() -> &'static [MetricDescription] {
+    DESCRIPTIONS
+}
+
+/// 按字段名查找说明
+pub fn description(name: &str) -> Option<&'static MetricDescription> {
+    DESCRIPTIONS.iter().find(|d| d.name == name)
+}
+
+/// 取值范围：下界（含）与上界
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueRange {
+    pub min: f64,
+    pub max: UpperBound,
+}
+
+impl fmt::Display for ValueRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.max {
+            UpperBound::Unbounded => write!(f, "≥ {}", self.min),
+            UpperBound::PartCount => write!(f, "{} – n", self.min),
+            UpperBound::Value { max, inclusive } => {
+                let close = if inclusive { ']' } else { ')' };
+                write!(f, "[{}, {max}{close}", self.min)
+            }
+        }
+    }
+}
+
+/// 取值范围序列化为说明中的写法
+fn serialize_range<S: Serializer>(range: &ValueRange, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(range)
+}
+
+/// 值的上界
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum UpperBound {
+    /// 无上界
+    Unbounded,
+    /// 上界为部分数 n，随语料而定
+    PartCount,
+    /// 固定上界；inclusive 为 false 时取不到
+    Value { max: f64, inclusive: bool },
+}
+
+/// 分布均匀程度的方向，供界面按"好坏"着色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricDirection {
+    /// 值越大分布越均匀
+    HigherIsMoreEven,
+    /// 值越小分布越均匀
+    LowerIsMoreEven,
+    /// 不衡量分布均匀程度
+    Neutral,
+}
+
+/// 一个数值字段的结构，由说明表生成
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSchema {
+    pub name: &'static str,
+    pub group: MetricGroup,
+    /// 下界（含）
+    pub min: f64,
+    pub max: UpperBound,
+    pub direction: MetricDirection,
+    /// 界面显示的小数位数
+    pub precision: u8,
+}
+
+/// 各数值字段的取值范围、方向、显示位数与分组，顺序同 metric_descriptions
+pub fn metric_schema() -> Vec<MetricSchema> {
+    DESCRIPTIONS
+        .iter()
+        .map(|d| MetricSchema {
+            name: d.name,
+            group: d.group,
+            min: d.range.min,
+            max: d.range.max,
+            direction: match d.higher_is_more_even {
+                Some(true) => MetricDirection::HigherIsMoreEven,
+                Some(false) => MetricDirection::LowerIsMoreEven,
+                None => MetricDirection::Neutral,
+            },
+            precision: d.precision,
+        })
+        .collect()
+}
@@ -1,7 +1,72 @@
 // mod.rs
 // analysis 模块入口，统一 re-export
 
+pub mod atomic;
+pub mod batch_export;
+pub mod bootstrap;
+pub mod burstiness;
+pub mod character_families;
+pub mod checkpoint;
 pub mod corpus_pipeline;
+pub mod correlation;
+pub mod count_import;
+pub mod coverage;
+pub mod dictionary;
+pub mod diff;
 pub mod dispersion_metrics;
+pub mod entry_flags;
+pub mod entry_id;
+pub mod event_forwarder;
+pub mod examples;
+pub mod export;
+pub mod filter;
+pub mod golden;
+pub mod group_keywords;
+pub mod inputs;
+pub mod interop;
+pub mod json_export;
+pub mod language;
+pub mod length;
+pub mod memory;
+pub mod merge;
+pub mod metadata;
+pub mod metric_descriptions;
+pub mod model_file;
+pub mod near_duplicates;
 pub mod nlp;
+pub mod oov;
+pub mod options;
+pub mod order_invariance;
+pub mod partition_advice;
+pub mod paths;
+pub mod phrases;
+pub mod pos_histogram;
+pub mod posgrams;
+pub mod presets;
+pub mod profile;
+pub mod progress;
+pub mod rank;
+pub mod reanalyze;
+pub mod resource_bundle;
+pub mod result_slots;
+pub mod romanize;
+pub mod run_report;
+pub mod sampling;
+pub mod seed;
+pub mod segment_watchdog;
+pub mod selection;
+pub mod session;
+pub mod spectrum;
+pub mod structural;
+pub mod tag_confidence;
+pub mod tagset;
+pub mod targets;
+pub mod text;
+pub mod token_policy;
+pub mod vertical;
 pub mod word_analyzer;
+pub mod word_entry;
+pub mod word_sketch;
+pub mod worker;
+pub mod xlsx;
+pub mod zip;
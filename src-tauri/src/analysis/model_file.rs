@@ -0,0 +1,161 @@
+// model_file.rs
+// 模型文件诊断：模型无法解析时检查文件本身，区分选错了文件、下载不完整与模型版本不符，给出对应的建议
+//
+// LTP 模型为 AVRO 对象容器文件：以 "Obj\x01" 开头，随后是元数据（含 avro.codec 与 avro.schema）与 16 字节的
+// 同步标记，之后的每个数据块为 "对象数、字节数、数据、同步标记"。文件不以魔数开头即不是 AVRO 文件；
+// 元数据或某个数据块在文件末尾之前截断、或数据块后的同步标记不符，说明文件不完整；容器完整却无法解析，
+// 多半是较新或较旧版本 LTP 的模型，结构与本程序所用的 ltp-rs 不一致。只检查容器结构，不解压数据块。
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// AVRO 对象容器文件的魔数
+pub const AVRO_MAGIC: &[u8; 4] = b"Obj\x01";
+/// 同步标记的字节数
+const SYNC_LEN: usize = 16;
+
+/// 模型文件无法使用的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFailure {
+    /// 无法打开或读取
+    Unreadable,
+    /// 不是 AVRO 文件
+    NotAvro,
+    /// AVRO 文件不完整
+    Truncated,
+    /// AVRO 文件完整，但结构与本程序支持的模型不符
+    SchemaMismatch,
+    /// 能够解析，但在样例句上的输出不合理
+    Rejected,
+}
+
+impl ModelFailure {
+    /// 给用户的建议
+    pub fn suggestion(self) -> &'static str {
+        match self {
+            ModelFailure::Unreadable => "请确认文件存在且有读取权限",
+            ModelFailure::NotAvro => {
+                "这不是 LTP 模型文件，请检查所选文件（模型通常为 cws_model.bin 与 pos_model.bin）"
+            }
+            ModelFailure::Truncated => "文件不完整，可能下载中断，请重新下载",
+            ModelFailure::SchemaMismatch => {
+                "模型格式与本程序不符，多半来自较新或较旧版本的 LTP，请改用与 ltp-rs 0.1 配套的旧版模型"
+            }
+            ModelFailure::Rejected => "请确认分词与词性模型来自同一版本的 LTP",
+        }
+    }
+}
+
+/// 容器结构的检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvroContainer {
+    /// 元数据中的 avro.codec；未给出时为 "null"
+    pub codec: String,
+    /// 数据块数
+    pub blocks: usize,
+}
+
+/// 检查 AVRO 对象容器文件的结构；返回 Err 时为 NotAvro 或 Truncated
+pub fn read_container(bytes: &[u8]) -> Result<AvroContainer, ModelFailure> {
+    let Some(rest) = bytes.strip_prefix(AVRO_MAGIC) else {
+        return Err(ModelFailure::NotAvro);
+    };
+    let mut reader = Cursor {
+        bytes: rest,
+        pos: 0,
+    };
+
+    let mut codec = "null".to_string();
+    loop {
+        let mut count = reader.long().ok_or(ModelFailure::Truncated)?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            // 负的项数后跟该段的字节数
+            count = count.checked_neg().ok_or(ModelFailure::Truncated)?;
+            reader.long().ok_or(ModelFailure::Truncated)?;
+        }
+        for _ in 0..count {
+            let key = reader.bytes().ok_or(ModelFailure::Truncated)?;
+            let value = reader.bytes().ok_or(ModelFailure::Truncated)?;
+            if key == b"avro.codec" {
+                codec = String::from_utf8_lossy(value).to_string();
+            }
+        }
+    }
+    let sync = reader.take(SYNC_LEN).ok_or(ModelFailure::Truncated)?;
+
+    let mut blocks = 0;
+    while !reader.is_empty() {
+        reader.long().ok_or(ModelFailure::Truncated)?;
+        let size = reader.long().ok_or(ModelFailure::Truncated)?;
+        let size = usize::try_from(size).map_err(|_| ModelFailure::Truncated)?;
+        reader.take(size).ok_or(ModelFailure::Truncated)?;
+        if reader.take(SYNC_LEN).ok_or(ModelFailure::Truncated)? != sync {
+            return Err(ModelFailure::Truncated);
+        }
+        blocks += 1;
+    }
+    Ok(AvroContainer { codec, blocks })
+}
+
+/// 模型无法解析时诊断文件：返回原因与补充说明（如不支持的压缩方式）
+pub fn diagnose(path: &Path) -> (ModelFailure, Option<String>) {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => return (ModelFailure::Unreadable, Some(e.to_string())),
+    };
+    match read_container(&bytes) {
+        Err(failure) => (failure, None),
+        Ok(container) if !matches!(container.codec.as_str(), "null" | "deflate") => (
+            ModelFailure::SchemaMismatch,
+            Some(format!(
+                "压缩方式为 {}，本程序只支持 deflate 与 null",
+                container.codec
+            )),
+        ),
+        Ok(_) => (ModelFailure::SchemaMismatch, None),
+    }
+}
+
+/// 按 AVRO 的二进制编码读取
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(n)?;
+        let slice = self.bytes.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    /// zigzag 编码的变长整数
+    fn long(&mut self) -> Option<i64> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.take(1)?.first()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        None
+    }
+
+    /// 长度前缀的字节串
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = usize::try_from(self.long()?).ok()?;
+        self.take(len)
+    }
+}
@@ -0,0 +1,221 @@
+// near_duplicates.rs
+// 近重复文件检测：同一篇文章换了标题或转载信息后以不同文件出现时，会严重扭曲分布指标
+//
+// 每个文件取去掉空白后的字符 5-gram 集合，计算 MinHash 签名（SIGNATURE_SIZE 个哈希），
+// 再按 LSH 分段（BANDS 段 × ROWS 行）分桶，只比较至少一段落入同一桶的文件对。
+// 整体为 O(n·k)，不做两两比较；相似度为签名估计的 Jaccard 系数。
+// 检测需在分析前额外读取一遍全部文件。
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::paths;
+
+/// MinHash 签名长度，等于 BANDS × ROWS
+pub const SIGNATURE_SIZE: usize = 128;
+/// LSH 分段数与每段行数；相似度约 (1/BANDS)^(1/ROWS) ≈ 0.71 以上的文件对大概率成为候选
+const BANDS: usize = 16;
+const ROWS: usize = 8;
+/// 字符 n-gram 长度
+const SHINGLE_CHARS: usize = 5;
+
+/// 近重复文件的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// 只报告，两个文件都照常分析
+    #[default]
+    KeepBoth,
+    /// 只保留较长的文件
+    KeepLonger,
+    /// 合并为一个语料部分，位置取较长的文件
+    Merge,
+}
+
+/// 近重复检测选项
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NearDuplicateOptions {
+    /// 估计 Jaccard 相似度不低于此值视为近重复
+    pub threshold: f64,
+    pub policy: DuplicatePolicy,
+}
+
+impl Default for NearDuplicateOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            policy: DuplicatePolicy::KeepBoth,
+        }
+    }
+}
+
+/// 一对近重复文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePair {
+    pub file_a: String,
+    pub file_b: String,
+    /// 估计的 Jaccard 相似度
+    pub similarity: f64,
+    /// 按策略保留（或合并后代表两者）的文件；KeepBoth 时为 None
+    pub kept: Option<String>,
+}
+
+/// 检测与处理结果
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// 参与分析的文件组，按原顺序排列：首个为保留的文件，其余为并入的文件（仅 Merge）
+    pub groups: Vec<Vec<usize>>,
+    pub pairs: Vec<DuplicatePair>,
+}
+
+/// 检测近重复文件并按策略分组；无法读取或为空的文件不参与检测
+pub fn resolve(files: &[PathBuf], options: &NearDuplicateOptions) -> Resolution {
+    let signatures: Vec<Option<(Vec<u64>, usize)>> = files
+        .iter()
+        .map(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|text| signature(&text))
+        })
+        .collect();
+    let found = candidate_pairs(&signatures, options.threshold);
+
+    // 近重复关系传递成簇，每簇保留最长的文件（等长时取靠前的）
+    let mut parent: Vec<usize> = (0..files.len()).collect();
+    for &(a, b, _) in &found {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        parent[ra.max(rb)] = ra.min(rb);
+    }
+    let length = |i: usize| signatures[i].as_ref().map_or(0, |(_, len)| *len);
+    let mut representative: HashMap<usize, usize> = HashMap::new();
+    for i in 0..files.len() {
+        let root = find(&mut parent, i);
+        let best = representative.entry(root).or_insert(i);
+        if length(i) > length(*best) {
+            *best = i;
+        }
+    }
+    let kept_of = |parent: &mut Vec<usize>, i: usize| representative[&find(parent, i)];
+
+    let pairs = found
+        .iter()
+        .map(|&(a, b, similarity)| DuplicatePair {
+            file_a: paths::display(&files[a]),
+            file_b: paths::display(&files[b]),
+            similarity,
+            kept: (options.policy != DuplicatePolicy::KeepBoth)
+                .then(|| paths::display(&files[kept_of(&mut parent, a)])),
+        })
+        .collect();
+
+    let groups = match options.policy {
+        DuplicatePolicy::KeepBoth => (0..files.len()).map(|i| vec![i]).collect(),
+        DuplicatePolicy::KeepLonger => (0..files.len())
+            .filter(|&i| kept_of(&mut parent, i) == i)
+            .map(|i| vec![i])
+            .collect(),
+        DuplicatePolicy::Merge => {
+            let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+            for i in 0..files.len() {
+                let kept = kept_of(&mut parent, i);
+                if kept != i {
+                    members.entry(kept).or_default().push(i);
+                }
+            }
+            (0..files.len())
+                .filter(|&i| kept_of(&mut parent, i) == i)
+                .map(|i| {
+                    let mut group = vec![i];
+                    group.extend(members.remove(&i).unwrap_or_default());
+                    group
+                })
+                .collect()
+        }
+    };
+    Resolution { groups, pairs }
+}
+
+/// 文本的 MinHash 签名与去空白后的字符数；空文本返回 None
+fn signature(text: &str) -> Option<(Vec<u64>, usize)> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.is_empty() {
+        return None;
+    }
+    let shingles: HashSet<u64> = chars
+        .windows(SHINGLE_CHARS.min(chars.len()))
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect();
+    let mut signature = vec![u64::MAX; SIGNATURE_SIZE];
+    for shingle in shingles {
+        for (k, slot) in signature.iter_mut().enumerate() {
+            *slot = (*slot).min(mix(shingle ^ mix(k as u64)));
+        }
+    }
+    Some((signature, chars.len()))
+}
+
+/// LSH 分桶取候选对，再以完整签名估计相似度，返回达到阈值的 (a, b, 相似度)，a < b，按 (a, b) 排序
+fn candidate_pairs(
+    signatures: &[Option<(Vec<u64>, usize)>],
+    threshold: f64,
+) -> Vec<(usize, usize, f64)> {
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (i, signature) in signatures.iter().enumerate() {
+        let Some((signature, _)) = signature else {
+            continue;
+        };
+        for (band, rows) in signature.chunks(ROWS).enumerate().take(BANDS) {
+            let mut hasher = DefaultHasher::new();
+            rows.hash(&mut hasher);
+            buckets.entry((band, hasher.finish())).or_default().push(i);
+        }
+    }
+    let mut candidates: HashSet<(usize, usize)> = HashSet::new();
+    for members in buckets.values() {
+        for (x, &a) in members.iter().enumerate() {
+            for &b in &members[x + 1..] {
+                candidates.insert((a, b));
+            }
+        }
+    }
+    let mut pairs: Vec<(usize, usize, f64)> = candidates
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let (sa, _) = signatures[a].as_ref()?;
+            let (sb, _) = signatures[b].as_ref()?;
+            let equal = sa.iter().zip(sb).filter(|(x, y)| x == y).count();
+            let similarity = equal as f64 / SIGNATURE_SIZE as f64;
+            (similarity >= threshold).then_some((a, b, similarity))
+        })
+        .collect();
+    pairs.sort_by_key(|&(a, b, _)| (a, b));
+    pairs
+}
+
+/// 并查集查找（路径减半）
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// splitmix64 混合函数，用作 MinHash 的一族哈希
+fn mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -1,27 +1,246 @@
 // nlp.rs
-// 中文分词、词性标注、命名实体识别模块，基于 ltp-rs
+// 中文分词、词性标注模块：分词器接口、基于 ltp-rs 的实现，以及不依赖模型的规则分词器
 
+use crate::analysis::model_file::ModelFailure;
+use crate::analysis::text::is_han;
+#[cfg(feature = "ltp")]
+use crate::analysis::{model_file, tagset};
+#[cfg(feature = "ltp")]
 use ltp::{CWSModel, Codec, Format, ModelSerde, POSModel};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "ltp")]
 use std::fs::File;
+#[cfg(feature = "ltp")]
+use std::path::Path;
+
+/// 分词器接口，语料流程只依赖该接口
+pub trait Tokenizer: Send + Sync {
+    /// 分词与词性标注，返回 (词, 词性) 二元组
+    fn segment_pos(&self, text: &str) -> Vec<(String, String)>;
+
+    /// 批量分词与词性标注：按输入顺序逐句返回。默认逐句调用 segment_pos；
+    /// 底层模型支持一次预测多句时可覆盖，以省去逐次调用的开销
+    fn segment_pos_batch(&self, texts: &[&str]) -> Vec<Vec<(String, String)>> {
+        texts.iter().map(|text| self.segment_pos(text)).collect()
+    }
+
+    /// 引擎名称，用于元数据记录
+    fn engine(&self) -> &'static str;
+
+    /// 引擎使用的模型文件路径，用于元数据记录
+    fn model_files(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
 
 /// NLP模型结构体，包含分词、词性、实体模型
+#[cfg(feature = "ltp")]
 pub struct LtpNlp {
     pub cws: CWSModel,
     pub pos: POSModel,
+    model_paths: Vec<String>,
+}
+
+/// 模型种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Cws,
+    Pos,
 }
 
+impl fmt::Display for ModelKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModelKind::Cws => write!(f, "分词模型"),
+            ModelKind::Pos => write!(f, "词性模型"),
+        }
+    }
+}
+
+/// 模型加载阶段，按顺序上报；规则分词器不经历这些阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelLoadPhase {
+    OpeningCws,
+    DeserializingCws,
+    OpeningPos,
+    DeserializingPos,
+    Verifying,
+}
+
+impl ModelLoadPhase {
+    /// 阶段总数
+    pub const COUNT: usize = 5;
+
+    /// 阶段序号（从 1 起）
+    pub fn step(self) -> usize {
+        self as usize + 1
+    }
+
+    #[cfg(feature = "ltp")]
+    fn opening(model: ModelKind) -> Self {
+        match model {
+            ModelKind::Cws => ModelLoadPhase::OpeningCws,
+            ModelKind::Pos => ModelLoadPhase::OpeningPos,
+        }
+    }
+
+    #[cfg(feature = "ltp")]
+    fn deserializing(model: ModelKind) -> Self {
+        match model {
+            ModelKind::Cws => ModelLoadPhase::DeserializingCws,
+            ModelKind::Pos => ModelLoadPhase::DeserializingPos,
+        }
+    }
+}
+
+/// 模型文件无法使用：无法读取、格式不符，或在样例句上的输出不合理
+#[derive(Debug, Clone)]
+pub struct ModelIncompatible {
+    pub model: ModelKind,
+    pub path: String,
+    pub reason: String,
+    /// 失败的类别，决定给出的建议
+    pub failure: ModelFailure,
+    /// 两个文件互换后可以通过校验，多半是选反了
+    pub swapped: bool,
+}
+
+impl fmt::Display for ModelIncompatible {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} 不可用: {}", self.model, self.path, self.reason)?;
+        if self.swapped {
+            write!(f, "。分词与词性模型似乎选反了，请交换两个文件后重试")
+        } else {
+            write!(f, "。{}", self.failure.suggestion())
+        }
+    }
+}
+
+impl std::error::Error for ModelIncompatible {}
+
+/// 加载后校验用的样例句
+#[cfg(feature = "ltp")]
+const SAMPLE_SENTENCE: &str = "他叫汤姆去拿外衣。";
+
+#[cfg(feature = "ltp")]
 impl LtpNlp {
-    /// 加载模型
-    pub fn load(cws_path: &str, pos_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let cws_file = File::open(cws_path)?;
-        let cws = ModelSerde::load(cws_file, Format::AVRO(Codec::Deflate))?;
-        let pos_file = File::open(pos_path)?;
-        let pos = ModelSerde::load(pos_file, Format::AVRO(Codec::Deflate))?;
-        Ok(Self { cws, pos })
+    /// 加载并校验模型；失败时若两个文件互换后能通过校验，错误中会提示选反了
+    pub fn load(cws_path: &str, pos_path: &str) -> Result<Self, ModelIncompatible> {
+        Self::load_with_progress(cws_path, pos_path, &|_| {})
+    }
+
+    /// 同 load，每进入一个阶段调用一次 progress；互换文件的重试不上报
+    pub fn load_with_progress(
+        cws_path: &str,
+        pos_path: &str,
+        progress: &dyn Fn(ModelLoadPhase),
+    ) -> Result<Self, ModelIncompatible> {
+        Self::load_verified(cws_path, pos_path, progress).map_err(|mut e| {
+            e.swapped = Self::load_verified(pos_path, cws_path, &|_| {}).is_ok();
+            e
+        })
+    }
+
+    fn load_verified(
+        cws_path: &str,
+        pos_path: &str,
+        progress: &dyn Fn(ModelLoadPhase),
+    ) -> Result<Self, ModelIncompatible> {
+        let cws: CWSModel = load_model(ModelKind::Cws, cws_path, progress)?;
+        let pos: POSModel = load_model(ModelKind::Pos, pos_path, progress)?;
+        let nlp = Self {
+            cws,
+            pos,
+            model_paths: vec![cws_path.to_string(), pos_path.to_string()],
+        };
+        progress(ModelLoadPhase::Verifying);
+        nlp.verify()?;
+        Ok(nlp)
     }
 
+    /// 在样例句上运行两个模型：分词结果须能拼回原句且含多字词，词性须与词一一对应且属于 LTP 标注集
+    fn verify(&self) -> Result<(), ModelIncompatible> {
+        let incompatible = |model, reason: String| ModelIncompatible {
+            path: self.model_paths[if model == ModelKind::Cws { 0 } else { 1 }].clone(),
+            model,
+            reason,
+            failure: ModelFailure::Rejected,
+            swapped: false,
+        };
+        let words = self
+            .cws
+            .predict(SAMPLE_SENTENCE)
+            .map_err(|e| incompatible(ModelKind::Cws, format!("样例句分词失败: {e}")))?;
+        let multi_char = words.iter().any(|w| w.chars().count() > 1);
+        if words.concat() != SAMPLE_SENTENCE || !multi_char {
+            return Err(incompatible(
+                ModelKind::Cws,
+                format!("样例句分词结果异常: {words:?}"),
+            ));
+        }
+        let tags = self
+            .pos
+            .predict(&words)
+            .map_err(|e| incompatible(ModelKind::Pos, format!("样例句词性标注失败: {e}")))?;
+        if tags.len() != words.len() || tags.iter().any(|t| !tagset::describe(t).known) {
+            return Err(incompatible(
+                ModelKind::Pos,
+                format!("样例句词性标注结果异常: {tags:?}"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 读取一个 AVRO 格式的模型文件：先按 deflate 压缩读取，失败时再按未压缩读取（部分发行版附带未压缩的模型）；
+/// 都失败时检查文件本身，区分选错了文件、下载不完整与模型版本不符
+#[cfg(feature = "ltp")]
+fn load_model<M: ModelSerde>(
+    model: ModelKind,
+    path: &str,
+    progress: &dyn Fn(ModelLoadPhase),
+) -> Result<M, ModelIncompatible> {
+    let incompatible = |failure, reason: String| ModelIncompatible {
+        model,
+        path: path.to_string(),
+        reason,
+        failure,
+        swapped: false,
+    };
+    let open = || {
+        File::open(path)
+            .map_err(|e| incompatible(ModelFailure::Unreadable, format!("无法打开: {e}")))
+    };
+    progress(ModelLoadPhase::opening(model));
+    let file = open()?;
+    progress(ModelLoadPhase::deserializing(model));
+    let error = match ModelSerde::load(file, Format::AVRO(Codec::Deflate)) {
+        Ok(loaded) => return Ok(loaded),
+        Err(e) => e,
+    };
+    if let Ok(loaded) = ModelSerde::load(open()?, Format::AVRO(Codec::Null)) {
+        return Ok(loaded);
+    }
+    let (failure, detail) = model_file::diagnose(Path::new(path));
+    let reason = match failure {
+        ModelFailure::Unreadable => format!("无法读取: {}", detail.unwrap_or_default()),
+        ModelFailure::NotAvro => "不是 AVRO 格式的文件".to_string(),
+        ModelFailure::Truncated => "AVRO 文件不完整".to_string(),
+        _ => match detail {
+            Some(detail) => format!("无法解析（{error}）；{detail}"),
+            None => format!("无法解析（{error}）"),
+        },
+    };
+    Err(incompatible(failure, reason))
+}
+
+// 所依赖的 ltp-rs 只提供逐句的 predict，segment_pos_batch 沿用默认的逐句实现
+#[cfg(feature = "ltp")]
+impl Tokenizer for LtpNlp {
     /// 仅分词与词性标注，返回 (词, 词性) 二元组
-    pub fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+    fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
         let words = self.cws.predict(text).unwrap_or_default();
         let pos = self.pos.predict(&words).unwrap_or_default();
         words
@@ -30,4 +249,176 @@ impl LtpNlp {
             .map(|(w, p)| (w.to_string(), p.to_string()))
             .collect()
     }
+
+    fn engine(&self) -> &'static str {
+        "ltp"
+    }
+
+    fn model_files(&self) -> Vec<String> {
+        self.model_paths.clone()
+    }
+}
+
+/// 规则分词器：不依赖模型、输出确定，供测试、CI 及未启用 ltp 特性的构建使用
+/// - 汉字逐字切分，按小词表赋词性，未收录者记为 n
+/// - 连续的拉丁字母、连续的数字各自成词（ws / m）
+/// - 其余非空白字符各自成词（wp），空白仅作分隔
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RuleTokenizer;
+
+/// 规则分词器的单字词性表（LTP 标注集）
+const RULE_POS_TABLE: &[(char, &str)] = &[
+    ('的', "u"),
+    ('了', "u"),
+    ('着', "u"),
+    ('过', "u"),
+    ('地', "u"),
+    ('得', "u"),
+    ('是', "v"),
+    ('有', "v"),
+    ('在', "p"),
+    ('把', "p"),
+    ('被', "p"),
+    ('从', "p"),
+    ('和', "c"),
+    ('与', "c"),
+    ('但', "c"),
+    ('而', "c"),
+    ('我', "r"),
+    ('你', "r"),
+    ('他', "r"),
+    ('她', "r"),
+    ('它', "r"),
+    ('这', "r"),
+    ('那', "r"),
+    ('不', "d"),
+    ('也', "d"),
+    ('都', "d"),
+    ('很', "d"),
+    ('就', "d"),
+    ('一', "m"),
+    ('二', "m"),
+    ('三', "m"),
+    ('十', "m"),
+    ('个', "q"),
+    ('吗', "e"),
+    ('呢', "e"),
+];
+
+fn rule_pos(c: char) -> &'static str {
+    RULE_POS_TABLE
+        .iter()
+        .find(|(ch, _)| *ch == c)
+        .map_or("n", |(_, pos)| pos)
+}
+
+impl Tokenizer for RuleTokenizer {
+    fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+        let mut tokens = Vec::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if is_han(c) {
+                tokens.push((c.to_string(), rule_pos(c).to_string()));
+                continue;
+            }
+            if c.is_alphanumeric() {
+                let numeric = c.is_numeric();
+                let mut word = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() && !is_han(next) && next.is_numeric() == numeric {
+                        word.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let pos = if numeric { "m" } else { "ws" };
+                tokens.push((word, pos.to_string()));
+                continue;
+            }
+            tokens.push((c.to_string(), "wp".to_string()));
+        }
+        tokens
+    }
+
+    fn engine(&self) -> &'static str {
+        "rule"
+    }
+}
+
+/// 空白分词器：按空白切分，去掉词两端的标点（标点单独成词），供非中文文件使用
+/// - 含字母的词记为 ws，纯数字记为 m，纯标点/符号记为 wp
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+        let mut tokens = Vec::new();
+        for raw in text.split_whitespace() {
+            let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+            if word.is_empty() {
+                tokens.push((raw.to_string(), "wp".to_string()));
+                continue;
+            }
+            let start = raw.len() - raw.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+            let end = start + word.len();
+            tokens.extend(
+                raw[..start]
+                    .chars()
+                    .map(|c| (c.to_string(), "wp".to_string())),
+            );
+            let pos = if word.chars().all(char::is_numeric) {
+                "m"
+            } else {
+                "ws"
+            };
+            tokens.push((word.to_string(), pos.to_string()));
+            tokens.extend(
+                raw[end..]
+                    .chars()
+                    .map(|c| (c.to_string(), "wp".to_string())),
+            );
+        }
+        tokens
+    }
+
+    fn engine(&self) -> &'static str {
+        "whitespace"
+    }
+}
+
+/// 已标注文本的分词器：按空白切分，每个词元为 "词/词性"（以最后一个 / 分隔），不含 / 或词性为空时记为 n。
+/// 供回归测试的夹具语料在没有模型时复现固定的分词结果；切句会把 "。/wp" 拆开，须整段调用（segment_batch_size 为 0）
+///
+/// ```
+/// use betawordlist_lib::analysis::nlp::{PretaggedTokenizer, Tokenizer};
+///
+/// let tokens = PretaggedTokenizer.segment_pos("我们/r 去/v 学校/n 。/wp\n1/2/m 书");
+/// let pairs: Vec<(&str, &str)> = tokens.iter().map(|(w, p)| (w.as_str(), p.as_str())).collect();
+/// assert_eq!(
+///     pairs,
+///     [("我们", "r"), ("去", "v"), ("学校", "n"), ("。", "wp"), ("1/2", "m"), ("书", "n")]
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PretaggedTokenizer;
+
+impl Tokenizer for PretaggedTokenizer {
+    fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+        text.split_whitespace()
+            .map(|token| match token.rsplit_once('/') {
+                Some((word, pos)) if !word.is_empty() && !pos.is_empty() => {
+                    (word.to_string(), pos.to_string())
+                }
+                _ => (token.to_string(), "n".to_string()),
+            })
+            .collect()
+    }
+
+    fn engine(&self) -> &'static str {
+        "pretagged"
+    }
 }
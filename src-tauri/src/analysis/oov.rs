@@ -0,0 +1,279 @@
+// oov.rs
+// 未登录词线索：列出疑似被切碎的高频单字与含领域用字的词，按估计影响排序，供复核分词
+//
+// 分词模型没见过的词（新词、人名）常被切成单字，典型特征是两个高频单字在词元流中反复相邻。
+// 开启 oov_pairs 时主流程逐文件统计相邻单字对（两个词元都是单个汉字）的次数：不同的对超过上限的两倍时
+// 只保留次数最多的上限个，并记下去掉的对数，因此低频对的次数可能偏低。
+// 分词引擎不支持用户词典，领域用字取自调用方给出的领域词表；同一词形的不同词性合为一项。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult, phrases::PHRASE_POS, posgrams::POSGRAM_POS, text,
+};
+
+/// 每个候选最多列出的相邻单字与领域词数
+const MAX_LISTED: usize = 5;
+
+/// 相邻的两个单字词元及其出现次数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdjacentPair {
+    pub left: String,
+    pub right: String,
+    pub count: u64,
+}
+
+/// 主流程统计的相邻单字对，记入元数据
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct AdjacentSingles {
+    /// 保留的对数上限，即 options.oov_pairs
+    pub limit: usize,
+    /// 按次数降序
+    pub pairs: Vec<AdjacentPair>,
+    /// 因超过上限而去掉的对数
+    pub pruned: usize,
+}
+
+/// 跨文件的相邻单字对计数器
+pub struct PairCounter {
+    limit: usize,
+    counts: HashMap<(String, String), u64>,
+    pruned: usize,
+}
+
+impl PairCounter {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            counts: HashMap::new(),
+            pruned: 0,
+        }
+    }
+
+    /// 计入一个文件的词元流；相邻关系不跨文件
+    pub fn add_text(&mut self, word_pos: &[(String, String)]) {
+        for pair in word_pos.windows(2) {
+            let (left, right) = (&pair[0].0, &pair[1].0);
+            if is_single_han(left) && is_single_han(right) {
+                *self
+                    .counts
+                    .entry((left.clone(), right.clone()))
+                    .or_default() += 1;
+            }
+        }
+        if self.counts.len() > self.limit.saturating_mul(2) {
+            self.prune();
+        }
+    }
+
+    /// 只保留次数最多的 limit 个对
+    fn prune(&mut self) {
+        let mut pairs: Vec<((String, String), u64)> = self.counts.drain().collect();
+        pairs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        self.pruned += pairs.len().saturating_sub(self.limit);
+        pairs.truncate(self.limit);
+        self.counts = pairs.into_iter().collect();
+    }
+
+    /// 按次数降序列出保留的对
+    pub fn finish(mut self) -> AdjacentSingles {
+        if self.counts.len() > self.limit {
+            self.prune();
+        }
+        let mut pairs: Vec<AdjacentPair> = self
+            .counts
+            .into_iter()
+            .map(|((left, right), count)| AdjacentPair { left, right, count })
+            .collect();
+        pairs.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| (&a.left, &a.right).cmp(&(&b.left, &b.right)))
+        });
+        AdjacentSingles {
+            limit: self.limit,
+            pairs,
+            pruned: self.pruned,
+        }
+    }
+}
+
+/// 词元是否为单个汉字
+fn is_single_han(word: &str) -> bool {
+    let mut chars = word.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if text::is_han(c))
+}
+
+/// 未登录词报告的选项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OovOptions {
+    /// 单字与其相邻单字的总频次都不低于此值时才视为高频
+    pub min_frequency: f64,
+    /// 领域词表；不在表中、但含表中汉字的词列为候选
+    pub domain_words: Vec<String>,
+}
+
+impl Default for OovOptions {
+    fn default() -> Self {
+        Self {
+            min_frequency: 5.0,
+            domain_words: Vec::new(),
+        }
+    }
+}
+
+/// 与候选单字相邻的另一个高频单字
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdjacentPartner {
+    /// 两字按出现顺序连写，即可能被切碎的词
+    pub joined: String,
+    pub count: u64,
+}
+
+/// 一个未登录词线索
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OovCandidate {
+    pub word: String,
+    /// 该词形的各词性，按频次降序
+    pub pos: Vec<String>,
+    /// 各词性的总频次
+    pub frequency: f64,
+    /// 估计影响：可能被错切的词元数，取相邻影响与领域影响中的较大者
+    pub impact: f64,
+    /// 与高频单字相邻的次数之和；不是单字或未统计相邻单字时为 0
+    pub adjacent_count: u64,
+    /// 相邻次数最多的几个高频单字，按次数降序
+    pub adjacent: Vec<AdjacentPartner>,
+    /// 与该词共用汉字的领域词，按领域词表的顺序
+    pub domain_words: Vec<String>,
+}
+
+/// 未登录词报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OovReport {
+    /// 按估计影响降序
+    pub candidates: Vec<OovCandidate>,
+    /// 结果是否统计了相邻单字；未统计时只按领域词表给出候选
+    pub adjacency_counted: bool,
+    /// 因超过上限而去掉的相邻单字对数，见 AdjacentSingles::pruned
+    pub pruned_pairs: usize,
+}
+
+/// 列出估计影响最大的前 top_n 个未登录词线索
+///
+/// 高频单字的相邻影响为它与其他高频单字相邻的次数之和；不在领域词表中的词的领域影响为
+/// 总频次乘以其中领域用字所占的比例。结果既未统计相邻单字、又没有给出领域词表时返回错误。
+pub fn oov_report(
+    result: &AnalysisResult,
+    top_n: usize,
+    options: &OovOptions,
+) -> Result<OovReport, String> {
+    let singles = result.metadata.adjacent_singles.as_ref();
+    if singles.is_none() && options.domain_words.is_empty() {
+        return Err("结果未统计相邻单字，请开启 oov_pairs 重新分析，或给出领域词表".to_string());
+    }
+
+    // 同一词形的不同词性合为一项
+    let mut forms: HashMap<&str, Vec<(&str, f64)>> = HashMap::new();
+    for entry in result
+        .entries
+        .iter()
+        .filter(|e| e.pos != PHRASE_POS && e.pos != POSGRAM_POS && e.frequency > 0.0)
+    {
+        forms
+            .entry(entry.word.as_str())
+            .or_default()
+            .push((entry.pos.as_str(), entry.frequency));
+    }
+    let total = |word: &str| -> f64 {
+        forms
+            .get(word)
+            .map_or(0.0, |tags| tags.iter().map(|(_, f)| f).sum())
+    };
+    let frequent = |word: &str| total(word) >= options.min_frequency;
+
+    let mut adjacent: HashMap<&str, Vec<AdjacentPartner>> = HashMap::new();
+    for pair in singles.map_or(&[][..], |s| &s.pairs) {
+        if !(frequent(&pair.left) && frequent(&pair.right)) {
+            continue;
+        }
+        let partner = AdjacentPartner {
+            joined: format!("{}{}", pair.left, pair.right),
+            count: pair.count,
+        };
+        adjacent
+            .entry(pair.left.as_str())
+            .or_default()
+            .push(partner.clone());
+        if pair.right != pair.left {
+            adjacent
+                .entry(pair.right.as_str())
+                .or_default()
+                .push(partner);
+        }
+    }
+
+    let domain_set: HashSet<&str> = options.domain_words.iter().map(String::as_str).collect();
+    let domain_chars: HashSet<char> = options
+        .domain_words
+        .iter()
+        .flat_map(|w| w.chars())
+        .filter(|&c| text::is_han(c))
+        .collect();
+
+    let mut candidates: Vec<OovCandidate> = forms
+        .iter()
+        .filter_map(|(&word, tags)| {
+            let frequency = total(word);
+            let partners = adjacent.remove(word).unwrap_or_default();
+            let adjacent_count: u64 = partners.iter().map(|p| p.count).sum();
+            let (domain_impact, domain_words) = if domain_set.contains(word) {
+                (0.0, Vec::new())
+            } else {
+                let chars: Vec<char> = word.chars().collect();
+                let shared = chars.iter().filter(|c| domain_chars.contains(c)).count();
+                let words: Vec<String> = options
+                    .domain_words
+                    .iter()
+                    .filter(|w| w.chars().any(|c| text::is_han(c) && chars.contains(&c)))
+                    .take(MAX_LISTED)
+                    .cloned()
+                    .collect();
+                (frequency * shared as f64 / chars.len() as f64, words)
+            };
+            let impact = (adjacent_count as f64).max(domain_impact);
+            if impact <= 0.0 {
+                return None;
+            }
+            let mut tags = tags.clone();
+            tags.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            let mut partners = partners;
+            partners.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.joined.cmp(&b.joined)));
+            partners.truncate(MAX_LISTED);
+            Some(OovCandidate {
+                word: word.to_string(),
+                pos: tags.iter().map(|(pos, _)| pos.to_string()).collect(),
+                frequency,
+                impact,
+                adjacent_count,
+                adjacent: partners,
+                domain_words,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.impact
+            .total_cmp(&a.impact)
+            .then(b.frequency.total_cmp(&a.frequency))
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    candidates.truncate(top_n);
+    Ok(OovReport {
+        candidates,
+        adjacency_counted: singles.is_some(),
+        pruned_pairs: singles.map_or(0, |s| s.pruned),
+    })
+}
@@ -0,0 +1,297 @@
+// options.rs
+// 分析选项：由前端或命令行传入，原样记录在结果元数据中
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    filter::DenominatorPolicy,
+    language::LanguageScreen,
+    near_duplicates::NearDuplicateOptions,
+    partition_advice::MIN_STABLE_PART_TOKENS,
+    pos_histogram::PosOutlierOptions,
+    posgrams::PosGramOptions,
+    sampling::SamplingOptions,
+    token_policy::{LatinCase, TokenPolicy},
+};
+
+/// 不影响结果数值的选项
+pub const IMMATERIAL_OPTIONS: &[&str] = &[
+    "hash_files",
+    "checkpoint_every",
+    "memory_limit_mb",
+    "two_pass",
+    "keep_parts",
+    "file_counts_top",
+    "strict",
+    "min_part_tokens",
+    "pos_outlier",
+    "seed",
+];
+
+/// 只作用于指标计算、改动后可复用已有计数的选项
+pub const METRIC_OPTIONS: &[&str] = &[
+    "rate_base",
+    "range_threshold",
+    "min_frequency",
+    "pos_confidence",
+    "quick_look",
+];
+
+/// 语料切分方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionMode {
+    /// 每个文件为一个部分
+    #[default]
+    PerFile,
+    /// 每个句子为一个部分（按句末标点切分，见 text::split_sentences）
+    PerSentence,
+}
+
+impl PartitionMode {
+    /// 记入元数据的名称
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartitionMode::PerFile => "per_file",
+            PartitionMode::PerSentence => "per_sentence",
+        }
+    }
+}
+
+/// 分析选项，缺省字段取默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisOptions {
+    /// 计算语料与模型文件的 SHA-256；关闭时仅以大小与修改时间作为指纹
+    pub hash_files: bool,
+    /// 每处理多少个文件保存一次检查点；None 表示不保存
+    pub checkpoint_every: Option<usize>,
+    /// 文件内滑窗分析的窗口词数；None 表示不计算 within_file_* 指标
+    pub burstiness_window: Option<usize>,
+    /// 标点与符号词元的处理策略
+    pub token_policy: TokenPolicy,
+    /// 拉丁字母词元的大小写处理；在清理之后、其余处理之前进行，停用词与短语按折叠后的写法匹配
+    pub latin_case: LatinCase,
+    /// 每个条目保留的写法数，见 WordEntry::surface_forms；其余写法的频次合计为一项 other
+    pub max_surface_forms: usize,
+    /// 非中文文件的筛查方式
+    pub language_screen: LanguageScreen,
+    /// 频率缩放基数：每多少词（如 10000、1000000），用于 *_pm 字段
+    pub rate_base: f64,
+    /// range_at_threshold / pervasiveness_pt_at_threshold 要求的部分内最少出现次数；range 与 PT 本身不受影响
+    pub range_threshold: f64,
+    /// 停用词：移出词表，默认仍计入部分词数
+    pub stopwords: Vec<String>,
+    /// 移出词表的词性
+    pub excluded_pos: Vec<String>,
+    /// 各项过滤移除的词元是否同时从部分词数中扣除
+    pub denominator: DenominatorPolicy,
+    /// 分词批大小：0 表示整段文本一次分词；大于 0 时先按句切分（见 text::split_text_sentences），
+    /// 每批这么多句交给 Tokenizer::segment_pos_batch，词元按原文顺序拼接
+    pub segment_batch_size: usize,
+    /// 单批分词的时限（毫秒）：超过时跳过该批并记入 metadata.warnings，分析继续；None 表示不限时。
+    /// segment_batch_size 为 0 时整段文本为一批
+    pub segment_timeout_ms: Option<u64>,
+    /// 分词后、计数前移除章节标题、罗马数字与页码等结构性噪声（见 structural::strip），
+    /// 被移除的词元不计入部分词数
+    pub structural_noise: bool,
+    /// 中文引号内的词元不受停用词/词性过滤，并单独统计 quoted_frequency
+    pub protect_quotes: bool,
+    /// 为每个条目给出 pos_tag_share：同一词形的词元中标为该词性的比例，供标注人员复核低一致度的标注
+    pub pos_confidence: bool,
+    /// 计数阶段的内存软上限（MB）；估算超出时中止分析。None 表示不限制
+    pub memory_limit_mb: Option<u64>,
+    /// 总频次低于此值的词移出词表，但仍计入部分词数；None 表示不限制
+    pub min_frequency: Option<f64>,
+    /// 两遍计数：第一遍只统计总频次，第二遍只为达到 min_frequency 的词建立各部分频次。
+    /// 结果与单遍相同，但每个文件要分词两次
+    pub two_pass: bool,
+    /// 在结果中保留各部分词频，使会话文件可与其他会话合并；词表较大时会话文件会明显变大
+    pub keep_parts: bool,
+    /// 每个词保留的例句数；0 表示不采集。从检查点恢复的文件不采集例句
+    pub collect_examples: usize,
+    /// 第一个文件之后，累计频次达到此值的词才开始采集例句，以限制内存
+    pub example_min_frequency: f64,
+    /// 统计相邻单字对供未登录词报告使用（见 oov 模块），值为保留的对数上限；None 表示不统计。
+    /// 从检查点恢复的文件不计入
+    pub oov_pairs: Option<usize>,
+    /// 目标词表：非空时只为这些词（按词形，不区分词性）建立各部分词频，其余词元仍计入部分词数；
+    /// 从未出现的目标词以频次 0 的条目返回（见 targets 模块）
+    pub target_words: Vec<String>,
+    /// 短语表：单独统计这些多词表达的频次与分布，以词性 PHRASE 的条目返回
+    pub phrases: Vec<String>,
+    /// 词性序列：统计连续 n 个词性构成的模式的分布，以词性 POSGRAM 的条目返回（见 posgrams 模块）；
+    /// None 表示不统计
+    pub posgrams: Option<PosGramOptions>,
+    /// 语料切分方式
+    pub partition: PartitionMode,
+    /// 有部分词数低于此值时在元数据中提示（任何切分方式下都检查）；0 表示不检查
+    pub min_part_tokens: usize,
+    /// 逐句切分时把同一文件内词数低于 min_part_tokens 的相邻句子依次合并，直到达到该值；
+    /// 各部分由哪些句子组成见 PartInfo::units
+    pub merge_small_parts: bool,
+    /// 标出某一词性占比明显偏离其他部分的部分，记入元数据的 pos_outliers（见 pos_histogram 模块）
+    pub pos_outlier: PosOutlierOptions,
+    /// 近重复文件检测；None 表示不检测
+    pub near_duplicates: Option<NearDuplicateOptions>,
+    /// 试点抽样：只分析输入文件的随机样本（见 sampling 模块）；None 表示分析全部文件
+    pub sampling: Option<SamplingOptions>,
+    /// 分析种子：试点抽样、例句采样等用到随机数的功能都由它推出各自的种子（见 seed 模块）；
+    /// None 表示生成一个，实际所用的种子记入元数据的 seed
+    pub seed: Option<u64>,
+    /// 位置模式：按输入顺序给全语料计数词元编号，给出各词首次出现的位置与出现跨度
+    pub track_positions: bool,
+    /// 每个部分计数完成后上报 file-counted 事件，附频次最高的若干个 (词, 词性, 频次)；
+    /// 0 表示全部（至多 progress::FILE_COUNTS_MAX 个），None 表示不上报
+    pub file_counts_top: Option<usize>,
+    /// 严格模式：有文件读取、解码或分词出错时，处理完其余文件后中止分析并列出全部问题，不保留结果与检查点；
+    /// 关闭时出错的文件按空部分计入并记入元数据的 file_errors
+    pub strict: bool,
+    /// 速览模式：按频次排序后只保留前 top_n 个词，另按语料规模提高最低频次，结果标记为截断
+    /// （见 metadata.truncation）；None 表示关闭。经 presets::resolve_options 解析时还会只保留实词、
+    /// 关闭耗时的附加统计
+    pub quick_look: Option<QuickLook>,
+}
+
+/// 速览模式的阈值
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct QuickLook {
+    /// 计算指标后按频次降序（同频按词、词性）保留的词条数
+    pub top_n: usize,
+    /// 每百万词的最低频次；与 min_frequency 取较大者
+    pub min_per_million: f64,
+}
+
+impl Default for QuickLook {
+    fn default() -> Self {
+        Self {
+            top_n: 500,
+            min_per_million: 10.0,
+        }
+    }
+}
+
+impl QuickLook {
+    /// 按语料词数换算的最低频次
+    pub fn min_frequency(&self, total_tokens: f64) -> f64 {
+        self.min_per_million * total_tokens / 1_000_000.0
+    }
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            hash_files: false,
+            checkpoint_every: None,
+            burstiness_window: None,
+            token_policy: TokenPolicy::default(),
+            latin_case: LatinCase::Preserve,
+            max_surface_forms: 10,
+            language_screen: LanguageScreen::default(),
+            rate_base: 1_000_000.0,
+            range_threshold: 1.0,
+            stopwords: Vec::new(),
+            excluded_pos: Vec::new(),
+            denominator: DenominatorPolicy::default(),
+            segment_batch_size: 0,
+            segment_timeout_ms: None,
+            structural_noise: false,
+            protect_quotes: false,
+            pos_confidence: false,
+            memory_limit_mb: None,
+            min_frequency: None,
+            two_pass: false,
+            keep_parts: false,
+            collect_examples: 0,
+            example_min_frequency: 3.0,
+            oov_pairs: None,
+            target_words: Vec::new(),
+            phrases: Vec::new(),
+            posgrams: None,
+            partition: PartitionMode::PerFile,
+            min_part_tokens: MIN_STABLE_PART_TOKENS,
+            merge_small_parts: false,
+            pos_outlier: PosOutlierOptions::default(),
+            near_duplicates: None,
+            sampling: None,
+            seed: None,
+            track_positions: false,
+            file_counts_top: None,
+            strict: false,
+            quick_look: None,
+        }
+    }
+}
+
+impl AnalysisOptions {
+    /// 两遍计数实际生效时返回 min_frequency
+    pub fn two_pass_min_frequency(&self) -> Option<f64> {
+        self.min_frequency.filter(|_| self.two_pass)
+    }
+
+    /// 选项之间相互影响的提示，记入结果元数据
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.two_pass {
+            warnings.push(match self.min_frequency {
+                Some(_) => "两遍计数会对每个文件分词两次，耗时约为单遍的两倍".to_string(),
+                None => "未设置 min_frequency，两遍计数不起作用，已按单遍计数".to_string(),
+            });
+        }
+        if self.partition == PartitionMode::PerSentence && self.checkpoint_every.is_some() {
+            warnings.push("逐句切分不支持检查点，已忽略 checkpoint_every".to_string());
+        }
+        if self.merge_small_parts && self.partition == PartitionMode::PerFile {
+            warnings
+                .push("按文件切分时每个文件只有一个部分，merge_small_parts 不起作用".to_string());
+        }
+        if self.sampling.is_some() {
+            warnings
+                .push("试点抽样：只分析了部分输入文件，见 sample；结果不代表全部语料".to_string());
+        }
+        if let Some(quick) = self.quick_look {
+            warnings.push(format!(
+                "速览模式：结果只含频次最高的 {} 个词，不是完整词表，请勿直接发布",
+                quick.top_n
+            ));
+        }
+        warnings
+    }
+
+    /// 与另一组选项取值不同的选项名（按序列化后的字段比较），按名称排序
+    pub fn changed_options(&self, other: &AnalysisOptions) -> Vec<String> {
+        let a = serde_json::to_value(self).unwrap_or_default();
+        let b = serde_json::to_value(other).unwrap_or_default();
+        let (Some(map_a), Some(map_b)) = (a.as_object(), b.as_object()) else {
+            return Vec::new();
+        };
+        let mut keys: Vec<&String> = map_a.keys().chain(map_b.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter(|key| map_a.get(*key) != map_b.get(*key))
+            .cloned()
+            .collect()
+    }
+
+    /// 与另一组选项取值不同、且影响结果数值的选项名；
+    /// 任一方合并过小部分时，min_part_tokens 决定部分的划分，不再只影响提示；
+    /// 任一方抽样或采集例句时，seed 决定样本与例句
+    pub fn material_changes(&self, other: &AnalysisOptions) -> Vec<String> {
+        let merging = self.merge_small_parts || other.merge_small_parts;
+        let seeded = self.sampling.is_some()
+            || other.sampling.is_some()
+            || self.collect_examples > 0
+            || other.collect_examples > 0;
+        self.changed_options(other)
+            .into_iter()
+            .filter(|key| {
+                !IMMATERIAL_OPTIONS.contains(&key.as_str())
+                    || (merging && key == "min_part_tokens")
+                    || (seeded && key == "seed")
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,214 @@
+// order_invariance.rs
+// 部分顺序无关性自检：把保留的各部分词频向量与部分词数按同一置换重排，重新计算抽样词的分布指标，
+// 确认结果与原顺序一致
+//
+// 所有分布指标都只依赖各部分的 (频次, 词数) 多重集合，与文件的处理顺序无关；并行计数、检查点续算与
+// 合并都依赖这一点。置换包括倒序、循环移位与若干个由固定种子生成的随机置换，结果可复现。
+// 数值按相对误差 ORDER_TOLERANCE 比较（求和顺序不同会带来末位差异）；最大频率所在部分经置换映射回
+// 原序号后须指向频率相同的部分。短语与词性序列不参与。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    bootstrap::SplitMix64,
+    corpus_pipeline::AnalysisResult,
+    phrases::PHRASE_POS,
+    posgrams::POSGRAM_POS,
+    seed,
+    word_analyzer::{AnalyzerConfig, CorpusWordAnalyzer},
+};
+
+/// 比较时允许的相对误差
+pub const ORDER_TOLERANCE: f64 = 1e-12;
+/// 随机置换的个数
+pub const SHUFFLES: u64 = 3;
+/// 未指定抽样数时检查的词数
+pub const DEFAULT_SAMPLE_WORDS: usize = 200;
+
+/// 一处不一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderViolation {
+    pub word: String,
+    pub pos: String,
+    /// 置换名称，如 "reverse"、"shuffle_1"
+    pub permutation: String,
+    /// 指标名，见 DispersionMetrics::FIELD_NAMES；最大频率所在部分为 "max_part_index"
+    pub metric: String,
+    pub original: Option<f64>,
+    pub permuted: Option<f64>,
+}
+
+/// 顺序无关性自检的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderInvarianceReport {
+    /// 参与置换的部分数
+    pub parts: usize,
+    /// 检查的词数
+    pub words_checked: usize,
+    /// 使用的置换名称
+    pub permutations: Vec<String>,
+    /// 比较过的数值个数
+    pub comparisons: usize,
+    pub violations: Vec<OrderViolation>,
+}
+
+impl OrderInvarianceReport {
+    /// 没有任何不一致
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// 两个值在相对误差 ORDER_TOLERANCE 内相等
+fn same(a: Option<f64>, b: Option<f64>) -> bool {
+    approx_equal(a, b, ORDER_TOLERANCE)
+}
+
+/// 两个值在相对误差 tolerance 内相等（绝对值小于 1 时按绝对误差）；都为 None 或都不是有限值（且相同）时视为相等
+pub(crate) fn approx_equal(a: Option<f64>, b: Option<f64>, tolerance: f64) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) if a.is_finite() && b.is_finite() => {
+            (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1.0)
+        }
+        (Some(a), Some(b)) => a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan()),
+        _ => false,
+    }
+}
+
+/// 检查用的置换：order[j] 为置换后第 j 个部分在原顺序中的序号；seed 为结果的分析种子
+fn permutations(parts: usize, seed: u64) -> Vec<(String, Vec<usize>)> {
+    let shuffle_seed = seed::derive_seed(seed, seed::ORDER_INVARIANCE);
+    let identity: Vec<usize> = (0..parts).collect();
+    let mut reverse = identity.clone();
+    reverse.reverse();
+    let mut rotate = identity.clone();
+    rotate.rotate_left(1.min(parts));
+    let mut list = vec![
+        ("reverse".to_string(), reverse),
+        ("rotate".to_string(), rotate),
+    ];
+    for k in 0..SHUFFLES {
+        let mut rng = SplitMix64::new(shuffle_seed, k);
+        let mut order = identity.clone();
+        // Fisher–Yates
+        for i in (1..parts).rev() {
+            order.swap(i, rng.below(i + 1));
+        }
+        list.push((format!("shuffle_{}", k + 1), order));
+    }
+    list
+}
+
+/// 按频次降序（同频按词、词性）均匀抽取 sample_words 个词，兼顾高频与低频词；0 表示全部
+fn sample_keys(result: &AnalysisResult, sample_words: usize) -> Vec<(String, String)> {
+    let mut entries: Vec<_> = result
+        .entries
+        .iter()
+        .filter(|e| e.pos != PHRASE_POS && e.pos != POSGRAM_POS && e.frequency > 0.0)
+        .collect();
+    entries.sort_by(|a, b| {
+        b.frequency
+            .total_cmp(&a.frequency)
+            .then_with(|| a.word.cmp(&b.word))
+            .then_with(|| a.pos.cmp(&b.pos))
+    });
+    let take = if sample_words == 0 {
+        entries.len()
+    } else {
+        sample_words.min(entries.len())
+    };
+    (0..take)
+        .map(|i| entries[i * entries.len() / take])
+        .map(|e| (e.word.clone(), e.pos.clone()))
+        .collect()
+}
+
+/// 对抽样词逐一检查各置换下的指标是否不变；需要结果保留各部分词频（本次分析的计数或 keep_parts）
+pub fn verify_order_invariance(
+    result: &AnalysisResult,
+    sample_words: usize,
+) -> Result<OrderInvarianceReport, String> {
+    let keys = sample_keys(result, sample_words);
+    let index: HashMap<(&str, &str), usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, (word, pos))| ((word.as_str(), pos.as_str()), i))
+        .collect();
+    let sizes: Vec<f64> = result
+        .metadata
+        .parts
+        .iter()
+        .map(|p| p.tokens as f64)
+        .collect();
+    let mut vectors = vec![vec![0.0; sizes.len()]; keys.len()];
+    let retained = result.for_each_part_count(|part, word, pos, v| {
+        if let Some(&i) = index.get(&(word, pos)) {
+            if let Some(slot) = vectors[i].get_mut(part) {
+                *slot += v;
+            }
+        }
+    });
+    if !retained {
+        return Err("结果未保留各部分词频，请重新分析或开启 keep_parts".to_string());
+    }
+
+    let total: f64 = sizes.iter().sum();
+    let config = AnalyzerConfig::from(&result.metadata.options);
+    let analyzer = |v: Vec<f64>, sizes: Vec<f64>| {
+        CorpusWordAnalyzer::with_config(v, sizes, total, config).expect("向量与部分数等长")
+    };
+    let permutations = permutations(sizes.len(), result.metadata.seed);
+    let mut report = OrderInvarianceReport {
+        parts: sizes.len(),
+        words_checked: keys.len(),
+        permutations: permutations.iter().map(|(name, _)| name.clone()).collect(),
+        ..Default::default()
+    };
+    for ((word, pos), v) in keys.iter().zip(&vectors) {
+        let original = analyzer(v.clone(), sizes.clone());
+        let metrics = original.calculate_all_metrics();
+        let max_part = original.get_max_part_rate();
+        let mut violation = |permutation: &str, metric: &str, a: Option<f64>, b: Option<f64>| {
+            report.violations.push(OrderViolation {
+                word: word.clone(),
+                pos: pos.clone(),
+                permutation: permutation.to_string(),
+                metric: metric.to_string(),
+                original: a,
+                permuted: b,
+            })
+        };
+        for (name, order) in &permutations {
+            let permuted = analyzer(
+                order.iter().map(|&i| v[i]).collect(),
+                order.iter().map(|&i| sizes[i]).collect(),
+            );
+            for ((metric, a), (_, b)) in metrics
+                .fields()
+                .into_iter()
+                .zip(permuted.calculate_all_metrics().fields())
+            {
+                report.comparisons += 1;
+                if !same(a, b) {
+                    violation(name, metric, a, b);
+                }
+            }
+            // 最大频率所在部分映射回原序号后，频率须与原结果的最大值一致（并列时可指向另一个部分）
+            report.comparisons += 1;
+            let mapped = permuted.get_max_part_rate().map(|(j, _)| order[j]);
+            let rate_at = |part: Option<usize>| part.map(|i| v[i] / sizes[i]);
+            if !same(rate_at(max_part.map(|(i, _)| i)), rate_at(mapped)) {
+                violation(
+                    name,
+                    "max_part_index",
+                    max_part.map(|(i, _)| i as f64),
+                    mapped.map(|i| i as f64),
+                );
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,196 @@
+// partition_advice.rs
+// 切分建议：不经分词粗估语料词次，给出切成 k 个等长块时每块的词次，并对过小的部分给出提示或将其合并
+//
+// 估算按字符类别进行：连续汉字按每 HAN_CHARS_PER_TOKEN 字一个词，连续的字母数字算一个词，
+// 其余非空白字符（标点、符号）各算一个词元。与实际分词结果通常相差一成以内，只用于挑选块数。
+
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{paths, text};
+
+/// 部分词数低于此值时，多数分布指标在部分之间波动很大
+pub const MIN_STABLE_PART_TOKENS: usize = 1000;
+
+/// 未指定块数时给出建议的候选块数
+pub const CANDIDATE_CHUNK_COUNTS: &[usize] = &[10, 50, 100];
+
+/// 汉字词的平均字数
+const HAN_CHARS_PER_TOKEN: f64 = 1.6;
+
+/// 一种块数下的估算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkCandidate {
+    pub chunks: usize,
+    /// 每块的估算词次
+    pub tokens_per_chunk: f64,
+    /// 每块不少于 MIN_STABLE_PART_TOKENS 词次
+    pub stable: bool,
+}
+
+/// 切分建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionSuggestion {
+    /// 全语料的估算词次
+    pub estimated_tokens: f64,
+    pub files: usize,
+    pub candidates: Vec<ChunkCandidate>,
+    /// 各候选中每块仍足够大的最大块数；语料太小时为 None
+    pub recommended: Option<usize>,
+    /// 块过小与无法读取文件的提示
+    pub warnings: Vec<String>,
+}
+
+/// 不分词估算一段文本的词次
+///
+/// ```
+/// use betawordlist_lib::analysis::partition_advice::estimate_tokens;
+///
+/// // 8 个汉字约 5 个词；"AI"、"2024"、"年" 与 2 个标点各 1 个
+/// assert_eq!(estimate_tokens("今天讨论人工智能AI，2024年。"), 5.0 + 5.0);
+/// assert_eq!(estimate_tokens("  \n"), 0.0);
+/// ```
+pub fn estimate_tokens(content: &str) -> f64 {
+    let mut tokens = 0.0;
+    let mut han_run = 0usize;
+    let mut in_word = false;
+    let flush_han = |han_run: &mut usize, tokens: &mut f64| {
+        if *han_run > 0 {
+            *tokens += (*han_run as f64 / HAN_CHARS_PER_TOKEN).ceil();
+            *han_run = 0;
+        }
+    };
+    for c in content.chars() {
+        if text::is_han(c) {
+            han_run += 1;
+            in_word = false;
+            continue;
+        }
+        flush_han(&mut han_run, &mut tokens);
+        if c.is_alphanumeric() {
+            if !in_word {
+                tokens += 1.0;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+            if !c.is_whitespace() {
+                tokens += 1.0;
+            }
+        }
+    }
+    flush_han(&mut han_run, &mut tokens);
+    tokens
+}
+
+/// 估算语料词次，给出按 chunk_counts（为空时取 CANDIDATE_CHUNK_COUNTS）切分时每块的词次
+pub fn suggest_partition(files: &[PathBuf], chunk_counts: &[usize]) -> PartitionSuggestion {
+    let mut warnings = Vec::new();
+    let mut estimated_tokens = 0.0;
+    for file in files {
+        match fs::read_to_string(file) {
+            Ok(content) => estimated_tokens += estimate_tokens(&content),
+            Err(e) => warnings.push(format!("无法读取 {}: {e}", paths::display(file))),
+        }
+    }
+    let chunk_counts = if chunk_counts.is_empty() {
+        CANDIDATE_CHUNK_COUNTS
+    } else {
+        chunk_counts
+    };
+    let candidates: Vec<ChunkCandidate> = chunk_counts
+        .iter()
+        .filter(|&&chunks| chunks > 0)
+        .map(|&chunks| {
+            let tokens_per_chunk = estimated_tokens / chunks as f64;
+            ChunkCandidate {
+                chunks,
+                tokens_per_chunk,
+                stable: tokens_per_chunk >= MIN_STABLE_PART_TOKENS as f64,
+            }
+        })
+        .collect();
+    for candidate in candidates.iter().filter(|c| !c.stable) {
+        warnings.push(format!(
+            "切成 {} 块时每块约 {:.0} 词次，少于 {MIN_STABLE_PART_TOKENS}，多数分布指标会不稳定",
+            candidate.chunks, candidate.tokens_per_chunk
+        ));
+    }
+    let recommended = candidates
+        .iter()
+        .filter(|c| c.stable)
+        .map(|c| c.chunks)
+        .max();
+    PartitionSuggestion {
+        estimated_tokens,
+        files: files.len(),
+        candidates,
+        recommended,
+        warnings,
+    }
+}
+
+/// 部分词数低于 min_tokens 时的提示；min_tokens 为 0 或没有过小的部分时为 None
+///
+/// ```
+/// use betawordlist_lib::analysis::partition_advice::small_parts_warning;
+///
+/// let warning = small_parts_warning(&[5000.0, 800.0, 120.0], 1000).unwrap();
+/// assert!(warning.starts_with("2 个部分"));
+/// assert!(warning.contains("最小为 120"));
+/// assert!(small_parts_warning(&[5000.0, 800.0], 0).is_none());
+/// assert!(small_parts_warning(&[5000.0], 1000).is_none());
+/// ```
+pub fn small_parts_warning(part_sizes: &[f64], min_tokens: usize) -> Option<String> {
+    if min_tokens == 0 {
+        return None;
+    }
+    let small: Vec<f64> = part_sizes
+        .iter()
+        .copied()
+        .filter(|&size| size < min_tokens as f64)
+        .collect();
+    let smallest = small.iter().copied().reduce(f64::min)?;
+    Some(format!(
+        "{} 个部分的词数低于 {min_tokens}（最小为 {smallest}），这些部分上的分布指标不稳定",
+        small.len()
+    ))
+}
+
+/// 把过小的相邻单元合并为组：依次累加单元，词数达到 min_tokens 即成一组；
+/// 末尾不足 min_tokens 的单元并入前一组（只有一组时保留）。min_tokens 为 0 时每个单元自成一组
+///
+/// ```
+/// use betawordlist_lib::analysis::partition_advice::merge_groups;
+///
+/// assert_eq!(merge_groups(&[3, 4, 10, 2, 6, 1], 6), [0..2, 2..3, 3..6]);
+/// // 末尾的小单元并入前一组
+/// assert_eq!(merge_groups(&[8, 3], 6), [0..2]);
+/// // 门槛高于全部词数时整体为一组
+/// assert_eq!(merge_groups(&[1, 2, 3], usize::MAX), [0..3]);
+/// assert_eq!(merge_groups(&[1, 2], 0), [0..1, 1..2]);
+/// assert!(merge_groups(&[], 6).is_empty());
+/// ```
+pub fn merge_groups(sizes: &[usize], min_tokens: usize) -> Vec<Range<usize>> {
+    let mut groups: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0usize;
+    for (i, &size) in sizes.iter().enumerate() {
+        tokens = tokens.saturating_add(size);
+        if tokens >= min_tokens {
+            groups.push(start..i + 1);
+            start = i + 1;
+            tokens = 0;
+        }
+    }
+    if start < sizes.len() {
+        match groups.last_mut() {
+            Some(last) => last.end = sizes.len(),
+            None => groups.push(start..sizes.len()),
+        }
+    }
+    groups
+}
@@ -0,0 +1,54 @@
+// paths.rs
+// 语料路径处理：Windows 长路径（\\?\ 前缀）与面向用户的路径显示
+//
+// 路径在分析流程中始终以 Path/PathBuf 传递，不经 String 转换，非 UTF-8 路径也不会丢失信息。
+
+use std::path::{Path, PathBuf};
+
+/// Windows 传统 API 的路径长度上限（含结尾的 NUL）；目录还须为 8.3 文件名留出 12 个字符
+#[cfg(windows)]
+const MAX_PATH: usize = 260;
+
+/// 规范化待读取的路径：Windows 上接近或超过 MAX_PATH 的路径转为绝对路径并加 \\?\ 前缀，
+/// 已带前缀或较短的路径原样返回；其他平台原样返回
+pub fn normalize(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        use std::ffi::OsString;
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+        let lossy = path.to_string_lossy();
+        if lossy.starts_with(r"\\?\") || path.as_os_str().encode_wide().count() < MAX_PATH - 12 {
+            return path.to_path_buf();
+        }
+        let Ok(absolute) = std::path::absolute(path) else {
+            return path.to_path_buf();
+        };
+        let wide: Vec<u16> = absolute.as_os_str().encode_wide().collect();
+        let mut verbatim = OsString::from(r"\\?\");
+        // UNC 路径 \\server\share → \\?\UNC\server\share
+        if wide.starts_with(&[u16::from(b'\\'), u16::from(b'\\')]) {
+            verbatim.push(r"UNC\");
+            verbatim.push(OsString::from_wide(&wide[2..]));
+        } else {
+            verbatim.push(absolute.as_os_str());
+        }
+        PathBuf::from(verbatim)
+    }
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// 面向用户与元数据的路径字符串：去掉 \\?\ 前缀，非 UTF-8 部分以替换字符表示
+pub fn display(path: &Path) -> String {
+    let lossy = path.to_string_lossy();
+    if let Some(rest) = lossy.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{rest}")
+    } else if let Some(rest) = lossy.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        lossy.into_owned()
+    }
+}
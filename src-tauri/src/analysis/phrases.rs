@@ -0,0 +1,93 @@
+// phrases.rs
+// 短语表：统计用户指定的多词表达在各部分的出现次数，不受分词切分方式影响
+//
+// 匹配在拼回的词元流上进行（见 text::join_tokens），从左到右扫描，每个位置取最长的匹配短语，
+// 匹配到的片段不再参与其他短语的匹配（不重叠计数）。短语计数单独成表，不计入常规词频统计。
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+use crate::analysis::{filter, text};
+
+/// 短语条目的词性标记
+pub const PHRASE_POS: &str = "PHRASE";
+
+/// 短语匹配器
+pub struct PhraseMatcher {
+    phrases: HashSet<String>,
+    /// 最长短语的字数（按 text::char_length 计）
+    max_chars: usize,
+}
+
+impl PhraseMatcher {
+    /// 由短语表构建；去掉首尾空白与空短语
+    pub fn new(phrases: &[String]) -> Self {
+        let phrases: HashSet<String> = phrases
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let max_chars = phrases
+            .iter()
+            .map(|p| text::char_length(p))
+            .max()
+            .unwrap_or(0);
+        Self { phrases, max_chars }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    /// 统计词元流中各短语的不重叠出现次数，最长匹配优先
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::phrases::PhraseMatcher;
+    ///
+    /// let matcher = PhraseMatcher::new(&["人工智能".to_string(), "智能".to_string()]);
+    /// let tokens = [("人工", "n"), ("智能", "n"), ("与", "c"), ("智能", "n"), ("手机", "n")]
+    ///     .map(|(w, p)| (w.to_string(), p.to_string()));
+    /// let counts = matcher.count(&tokens);
+    /// assert_eq!(counts["人工智能"], 1.0);
+    /// assert_eq!(counts["智能"], 1.0);
+    /// ```
+    pub fn count(&self, word_pos: &[(String, String)]) -> HashMap<String, f64> {
+        let mut counts = HashMap::new();
+        if self.is_empty() {
+            return counts;
+        }
+        let joined = text::join_tokens(word_pos.iter().map(|(w, _)| w.as_str()), usize::MAX);
+        // 按字（见 text::graphemes）的边界匹配，不会在 emoji 序列中间切开
+        let starts: Vec<usize> = text::graphemes(&joined)
+            .iter()
+            .scan(0, |offset, grapheme| {
+                let start = *offset;
+                *offset += grapheme.len();
+                Some(start)
+            })
+            .chain([joined.len()])
+            .collect();
+        let mut i = 0;
+        while i + 1 < starts.len() {
+            let longest = (1..=self.max_chars.min(starts.len() - 1 - i))
+                .rev()
+                .find(|&n| self.phrases.contains(&joined[starts[i]..starts[i + n]]));
+            match longest {
+                Some(n) => {
+                    *counts
+                        .entry(joined[starts[i]..starts[i + n]].to_string())
+                        .or_insert(0.0) += 1.0;
+                    i += n;
+                }
+                None => i += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// 读取短语表，格式同停用词表：每行一个，忽略空行与 # 注释行
+pub fn read_phrases(path: &Path) -> io::Result<Vec<String>> {
+    filter::read_stopwords(path)
+}
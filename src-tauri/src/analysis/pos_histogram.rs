@@ -0,0 +1,103 @@
+// pos_histogram.rs
+// 各部分的词性分布：计数时顺带统计每个部分各词性的词元数，并标出某一词性占比明显偏离其他部分的部分
+//
+// 标点（wp）或外文（ws）占比异常高的文件多为 OCR 垃圾或编码错误。分布在词元清理与大小写折叠之后、
+// 停用词与词性过滤之前统计，含标点与被过滤的词元，不受 token_policy 的标点开关影响。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{metadata::PartInfo, token_policy::PUNCTUATION_POS};
+
+/// 词性占比异常的判定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PosOutlierOptions {
+    /// 检查占比的词性
+    pub tag: String,
+    /// |z| 不低于此值的部分视为异常；0 表示不检查
+    pub z_threshold: f64,
+}
+
+impl Default for PosOutlierOptions {
+    fn default() -> Self {
+        Self {
+            tag: PUNCTUATION_POS.to_string(),
+            z_threshold: 3.0,
+        }
+    }
+}
+
+/// 词性占比异常的部分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PosOutlier {
+    /// 部分下标，对应 metadata.parts
+    pub part: usize,
+    /// 部分标签，见 PartInfo::label
+    pub label: String,
+    pub tag: String,
+    /// 该词性在此部分的占比
+    pub share: f64,
+    /// 各部分该占比的均值
+    pub mean_share: f64,
+    /// 相对各部分占比的 z 分数（总体标准差）
+    pub z: f64,
+}
+
+/// 一个部分中各词性的占比，按占比降序（同占比按词性）
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use betawordlist_lib::analysis::pos_histogram::shares;
+///
+/// let counts = BTreeMap::from([("n".to_string(), 6), ("wp".to_string(), 2), ("v".to_string(), 2)]);
+/// assert_eq!(
+///     shares(&counts),
+///     [("n".to_string(), 0.6), ("v".to_string(), 0.2), ("wp".to_string(), 0.2)]
+/// );
+/// assert!(shares(&BTreeMap::new()).is_empty());
+/// ```
+pub fn shares(pos_counts: &BTreeMap<String, u64>) -> Vec<(String, f64)> {
+    let total: u64 = pos_counts.values().sum();
+    let mut shares: Vec<(String, f64)> = pos_counts
+        .iter()
+        .map(|(tag, &count)| (tag.clone(), count as f64 / total as f64))
+        .collect();
+    shares.sort_by(|a, b| b.1.total_cmp(&a.1));
+    shares
+}
+
+/// 按 options 找出词性占比异常的部分；没有词元的部分不参与，少于 3 个部分或占比完全相同时不判定
+pub fn outliers(parts: &[PartInfo], options: &PosOutlierOptions) -> Vec<PosOutlier> {
+    if options.z_threshold <= 0.0 {
+        return Vec::new();
+    }
+    let shares: Vec<(&PartInfo, f64)> = parts
+        .iter()
+        .filter_map(|part| part.pos_share(&options.tag).map(|share| (part, share)))
+        .collect();
+    if shares.len() < 3 {
+        return Vec::new();
+    }
+    let n = shares.len() as f64;
+    let mean = shares.iter().map(|(_, s)| s).sum::<f64>() / n;
+    let sd = (shares.iter().map(|(_, s)| (s - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if sd == 0.0 {
+        return Vec::new();
+    }
+    shares
+        .into_iter()
+        .filter_map(|(part, share)| {
+            let z = (share - mean) / sd;
+            (z.abs() >= options.z_threshold).then(|| PosOutlier {
+                part: part.index,
+                label: part.label(),
+                tag: options.tag.clone(),
+                share,
+                mean_share: mean,
+                z,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,54 @@
+// posgrams.rs
+// 词性序列：统计各部分中连续 n 个词性构成的模式（如 "v+n"、"p+n+v"），以与词条相同的指标衡量其分布
+//
+// 序列不跨句（见 text::split_sentences），标点词元也视为边界，不进入模式。统计在词元清理与
+// 大小写折叠之后、停用词与词性过滤之前进行，与短语表一样不受过滤影响；部分词数不变。
+// 条目以连接后的词性串为词形、POSGRAM_POS 为词性返回，排序、比较与导出照常进行。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{text, token_policy::PUNCTUATION_POS};
+
+/// 词性序列条目的词性标记
+pub const POSGRAM_POS: &str = "POSGRAM";
+
+/// 连接序列中各词性的分隔符
+pub const POSGRAM_SEPARATOR: &str = "+";
+
+/// 词性序列选项
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PosGramOptions {
+    /// 序列长度；0 不统计
+    pub n: usize,
+    /// 全语料总频次低于此值的模式不返回；三元以上的模式往往有数千种，多数只出现寥寥几次
+    pub min_frequency: f64,
+}
+
+impl Default for PosGramOptions {
+    fn default() -> Self {
+        Self {
+            n: 2,
+            min_frequency: 5.0,
+        }
+    }
+}
+
+/// 统计词元流中各词性序列的出现次数；序列可重叠
+pub fn count(word_pos: &[(String, String)], n: usize) -> HashMap<String, f64> {
+    let mut counts = HashMap::new();
+    if n == 0 {
+        return counts;
+    }
+    for sentence in text::split_sentences(word_pos) {
+        for run in sentence.split(|(_, pos)| pos == PUNCTUATION_POS) {
+            for window in run.windows(n) {
+                let tags: Vec<&str> = window.iter().map(|(_, pos)| pos.as_str()).collect();
+                *counts.entry(tags.join(POSGRAM_SEPARATOR)).or_insert(0.0) += 1.0;
+            }
+        }
+    }
+    counts
+}
@@ -0,0 +1,258 @@
+// presets.rs
+// 分析预设：为常见用途打包的一组分析选项，可与显式传入的选项合并
+//
+// 内置 frequency_dictionary（词频词典）、pedagogical_list（教学词表）、dispersion_research（分布研究）
+// 与 quick_look（速览）；
+// 自定义预设写在 settings.json 的 presets 中，与内置预设同名时优先使用自定义预设。
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    options::{AnalysisOptions, PartitionMode, QuickLook},
+};
+
+/// 教学词表排除的功能词词性：连词、叹词、拟声词、介词、助词、标点、前后缀与非语素字
+const FUNCTION_POS: [&str; 9] = ["c", "e", "o", "p", "u", "wp", "h", "k", "x"];
+
+/// 命名的分析预设
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisPreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub options: AnalysisOptions,
+    /// 建议搭配的输出配置名
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+impl AnalysisPreset {
+    /// 词频词典：按文件切分、不过滤停用词，并计算文件内突发性与位置指标
+    pub fn frequency_dictionary() -> Self {
+        Self {
+            name: "frequency_dictionary".to_string(),
+            description: "按文件切分，不过滤停用词，计算全部指标".to_string(),
+            options: AnalysisOptions {
+                partition: PartitionMode::PerFile,
+                burstiness_window: Some(1000),
+                track_positions: true,
+                ..Default::default()
+            },
+            profile: Some("research".to_string()),
+        }
+    }
+
+    /// 教学词表：只保留实词词性、总频次不低于 5，导出时用带覆盖段的 teaching 配置；
+    /// 停用词表需另行载入（见 load_stopwords）并作为显式选项传入
+    pub fn pedagogical_list() -> Self {
+        Self {
+            name: "pedagogical_list".to_string(),
+            description: "只保留实词，总频次不低于 5，按覆盖段导出".to_string(),
+            options: AnalysisOptions {
+                excluded_pos: FUNCTION_POS.map(String::from).to_vec(),
+                min_frequency: Some(5.0),
+                ..Default::default()
+            },
+            profile: Some("teaching".to_string()),
+        }
+    }
+
+    /// 分布研究：计算全部指标并保留各部分词频，便于合并会话与按部分查询
+    pub fn dispersion_research() -> Self {
+        Self {
+            name: "dispersion_research".to_string(),
+            description: "计算全部指标并保留各部分词频".to_string(),
+            options: AnalysisOptions {
+                burstiness_window: Some(1000),
+                track_positions: true,
+                keep_parts: true,
+                ..Default::default()
+            },
+            profile: Some("research".to_string()),
+        }
+    }
+
+    /// 速览：只看频次最高的 500 个实词，按语料规模提高最低频次，关闭耗时的附加统计；结果标记为截断
+    pub fn quick_look() -> Self {
+        Self {
+            name: "quick_look".to_string(),
+            description: "快速查看频次最高的 500 个实词，结果不完整".to_string(),
+            options: AnalysisOptions {
+                excluded_pos: FUNCTION_POS.map(String::from).to_vec(),
+                quick_look: Some(QuickLook::default()),
+                ..Default::default()
+            },
+            profile: None,
+        }
+    }
+
+    /// 内置预设
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            Self::frequency_dictionary(),
+            Self::pedagogical_list(),
+            Self::dispersion_research(),
+            Self::quick_look(),
+        ]
+    }
+}
+
+/// settings.json 中的预设部分
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PresetSettings {
+    /// 自定义分析预设
+    pub presets: Vec<AnalysisPreset>,
+}
+
+impl PresetSettings {
+    /// 读取设置文件；文件不存在时返回默认设置
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 内置与自定义预设；同名时自定义预设替换内置预设
+    pub fn all_presets(&self) -> Vec<AnalysisPreset> {
+        let mut presets: Vec<AnalysisPreset> = AnalysisPreset::builtin()
+            .into_iter()
+            .filter(|builtin| self.presets.iter().all(|p| p.name != builtin.name))
+            .collect();
+        presets.extend(self.presets.iter().cloned());
+        presets
+    }
+
+    /// 按名称查找预设
+    pub fn find(&self, name: &str) -> Result<AnalysisPreset, String> {
+        self.all_presets()
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("未知的分析预设: {name}"))
+    }
+}
+
+/// 把自定义预设写入设置文件，替换同名预设；设置文件中的其余项原样保留
+pub fn save_preset(path: &Path, preset: AnalysisPreset) -> Result<(), String> {
+    if preset.name.trim().is_empty() {
+        return Err("预设名称不能为空".to_string());
+    }
+    let mut settings = read_settings(path)?;
+    let mut presets: Vec<AnalysisPreset> = match settings.remove("presets") {
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("设置中的 presets 无法解析: {e}"))?
+        }
+        None => Vec::new(),
+    };
+    match presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+    let presets = serde_json::to_value(&presets).map_err(|e| e.to_string())?;
+    settings.insert("presets".to_string(), presets);
+    write_settings(path, &settings)
+}
+
+/// 把设置文件中的一项替换为 value；其余项原样保留
+pub fn save_setting(path: &Path, key: &str, value: serde_json::Value) -> Result<(), String> {
+    let mut settings = read_settings(path)?;
+    settings.insert(key.to_string(), value);
+    write_settings(path, &settings)
+}
+
+fn read_settings(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| format!("设置读取失败 {}: {e}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Default::default()),
+        Err(e) => Err(format!("设置读取失败 {}: {e}", path.display())),
+    }
+}
+
+fn write_settings(
+    path: &Path,
+    settings: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| WriteError::new(path, e).to_string())?;
+    }
+    write_atomic(path, |w| Ok(serde_json::to_writer_pretty(w, settings)?))
+        .map_err(|e| e.to_string())
+}
+
+/// 开启速览模式时只保留实词，并关闭耗时的附加统计（滑窗、位置、例句、词性置信度、词性序列、
+/// 近重复检测、文件哈希与保留各部分词频）；未开启时不做改动
+pub fn apply_quick_look(options: &mut AnalysisOptions) {
+    if options.quick_look.is_none() {
+        return;
+    }
+    for pos in FUNCTION_POS {
+        if !options.excluded_pos.iter().any(|p| p == pos) {
+            options.excluded_pos.push(pos.to_string());
+        }
+    }
+    options.burstiness_window = None;
+    options.track_positions = false;
+    options.collect_examples = 0;
+    options.pos_confidence = false;
+    options.posgrams = None;
+    options.near_duplicates = None;
+    options.hash_files = false;
+    options.keep_parts = false;
+}
+
+/// 以预设（或默认选项）为底，逐字段合并显式传入的选项；对象按键递归合并，其余取值直接替换；
+/// 结果开启速览模式时再经 apply_quick_look 调整
+///
+/// ```
+/// use betawordlist_lib::analysis::presets::{resolve_options, AnalysisPreset};
+///
+/// let preset = AnalysisPreset::pedagogical_list();
+/// let overrides = serde_json::json!({ "min_frequency": 3.0, "stopwords": ["的"] });
+/// let options = resolve_options(Some(&preset), Some(overrides)).unwrap();
+/// assert_eq!(options.min_frequency, Some(3.0));
+/// assert_eq!(options.stopwords, ["的"]);
+/// assert_eq!(options.excluded_pos, preset.options.excluded_pos);
+///
+/// assert_eq!(resolve_options(None, None).unwrap().min_frequency, None);
+/// ```
+pub fn resolve_options(
+    preset: Option<&AnalysisPreset>,
+    overrides: Option<serde_json::Value>,
+) -> Result<AnalysisOptions, String> {
+    let base = preset.map(|p| p.options.clone()).unwrap_or_default();
+    let mut options = match overrides.filter(|v| !v.is_null()) {
+        None => base,
+        Some(overrides) if overrides.is_object() => {
+            let mut merged = serde_json::to_value(&base).map_err(|e| e.to_string())?;
+            merge_json(&mut merged, overrides);
+            serde_json::from_value(merged).map_err(|e| format!("分析选项无效: {e}"))?
+        }
+        Some(_) => return Err("分析选项须为对象".to_string()),
+    };
+    apply_quick_look(&mut options);
+    Ok(options)
+}
+
+fn merge_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+    match (base.as_object_mut(), overrides) {
+        (Some(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(slot) => merge_json(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (_, overrides) => *base = overrides,
+    }
+}
@@ -0,0 +1,383 @@
+// profile.rs
+// 输出配置：命名的列选择与顺序及各列格式，供导出与分页使用
+//
+// 内置 research（全部研究指标）与 teaching（教学用精简列：拼音、词性名称、每百万词频、覆盖段与等级）；
+// 自定义配置写在应用配置目录的 settings.json 中，与内置配置同名时优先使用自定义配置。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult,
+    diff,
+    dispersion_metrics::DispersionMetrics,
+    entry_flags,
+    export::ExportOptions,
+    metadata::PartInfo,
+    phrases::PHRASE_POS,
+    posgrams::POSGRAM_POS,
+    rank, romanize,
+    tagset::{self, LabelLanguage},
+    word_entry::{WordEntry, POSITION_FIELDS},
+};
+
+/// 覆盖段上限（百分比）：按频次降序累计，词所在位置的累计词次占比落入的第一个上限
+const COVERAGE_BANDS: [u32; 5] = [80, 90, 95, 98, 100];
+
+/// 除指标名与 rank_<指标> 外可用的列
+const FIELDS: &[&str] = &[
+    "word",
+    "surface_form",
+    "pos",
+    "pos_label",
+    "char_length",
+    "frequency",
+    "frequency_pm",
+    "coverage_band",
+    "pinyin",
+    "pinyin_ambiguous",
+    "level",
+    "gt_adjusted_frequency",
+    "quoted_frequency",
+    "pos_tag_share",
+    "first_occurrence_part",
+    "first_occurrence_fraction",
+    "occurrence_span_fraction",
+    "max_part_index",
+    "max_part_file",
+    "id",
+    "flags",
+    "surface_forms",
+    // surface_forms 的旧名
+    "variants",
+    "examples",
+];
+
+/// 一列：字段名、表头与小数位数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileColumn {
+    pub field: String,
+    /// 表头，缺省为字段名
+    #[serde(default)]
+    pub header: Option<String>,
+    /// 覆盖导出格式中的小数位数
+    #[serde(default)]
+    pub precision: Option<usize>,
+}
+
+impl ProfileColumn {
+    fn new(field: &str, header: Option<&str>, precision: Option<usize>) -> Self {
+        Self {
+            field: field.to_string(),
+            header: header.map(str::to_string),
+            precision,
+        }
+    }
+
+    pub fn header(&self) -> &str {
+        self.header.as_deref().unwrap_or(&self.field)
+    }
+}
+
+/// 输出配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputProfile {
+    pub name: String,
+    pub columns: Vec<ProfileColumn>,
+}
+
+impl OutputProfile {
+    /// 全部研究指标，列同默认 CSV 导出，另加例句
+    pub fn research() -> Self {
+        let mut fields = vec!["word", "pos", "char_length", "frequency"];
+        fields.extend(DispersionMetrics::FIELD_NAMES);
+        fields.extend(["gt_adjusted_frequency", "quoted_frequency", "pos_tag_share"]);
+        fields.extend(POSITION_FIELDS);
+        fields.extend(["max_part_index", "max_part_file", "id", "flags"]);
+        fields.push("examples");
+        Self {
+            name: "research".to_string(),
+            columns: fields
+                .into_iter()
+                .map(|field| ProfileColumn::new(field, None, None))
+                .collect(),
+        }
+    }
+
+    /// 教学用精简列
+    pub fn teaching() -> Self {
+        Self {
+            name: "teaching".to_string(),
+            columns: vec![
+                ProfileColumn::new("word", Some("词语"), None),
+                ProfileColumn::new("pinyin", Some("拼音"), None),
+                ProfileColumn::new("pinyin_ambiguous", Some("多音字"), None),
+                ProfileColumn::new("pos_label", Some("词性"), None),
+                ProfileColumn::new("frequency_pm", Some("每百万词频"), Some(2)),
+                ProfileColumn::new("coverage_band", Some("覆盖段"), None),
+                ProfileColumn::new("level", Some("等级"), None),
+            ],
+        }
+    }
+
+    /// 校验列名与小数位数
+    pub fn validate(&self) -> Result<(), String> {
+        for column in &self.columns {
+            let field = column.field.as_str();
+            let known = FIELDS.contains(&field)
+                || DispersionMetrics::FIELD_NAMES.contains(&field)
+                || field
+                    .strip_prefix("rank_")
+                    .is_some_and(diff::is_known_metric);
+            if !known {
+                return Err(format!("输出配置 {} 含未知列: {field}", self.name));
+            }
+            if column.precision.is_some_and(|p| p > 17) {
+                return Err(format!("输出配置 {} 的列 {field} 小数位数过大", self.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// 配置中 rank_<指标> 列所需的排名指标
+    pub fn rank_metrics(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter_map(|c| c.field.strip_prefix("rank_"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn headers(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|c| c.header().to_string())
+            .collect()
+    }
+}
+
+/// 应用设置中与输出配置相关的部分
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    /// 自定义输出配置
+    pub profiles: Vec<OutputProfile>,
+    /// 词语等级表（如 HSK），每行 "词<TAB>等级"，供 level 列使用
+    pub level_list: Option<PathBuf>,
+    /// 最近一次导出条目表所用的列（ExportOptions::columns）
+    pub export_columns: Vec<String>,
+}
+
+impl ProfileSettings {
+    /// 读取设置文件；文件不存在时返回默认设置
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 内置与自定义输出配置；同名时自定义配置替换内置配置
+    pub fn all_profiles(&self) -> Vec<OutputProfile> {
+        let mut profiles: Vec<OutputProfile> =
+            [OutputProfile::research(), OutputProfile::teaching()]
+                .into_iter()
+                .filter(|builtin| self.profiles.iter().all(|p| p.name != builtin.name))
+                .collect();
+        profiles.extend(self.profiles.iter().cloned());
+        profiles
+    }
+
+    /// 按名称查找并校验输出配置
+    pub fn find(&self, name: &str) -> Result<OutputProfile, String> {
+        let profile = self
+            .all_profiles()
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("未知的输出配置: {name}"))?;
+        profile.validate()?;
+        Ok(profile)
+    }
+}
+
+/// 读取词语等级表：每行 "词<TAB>等级"，忽略空行与 # 注释行
+pub fn read_levels(path: &Path) -> io::Result<HashMap<String, String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(word, level)| (word.trim().to_string(), level.trim().to_string()))
+        .collect())
+}
+
+/// 分页查询中按输出配置渲染的表格
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// 渲染所需的全结果信息：总词次与覆盖段在完整词表上计算，不受筛选影响
+pub struct ProfileContext {
+    total_tokens: f64,
+    coverage: HashMap<(String, String), usize>,
+    levels: HashMap<String, String>,
+    pos_language: LabelLanguage,
+    /// 各部分的标签，供 max_part_file 列
+    part_labels: Vec<String>,
+}
+
+impl ProfileContext {
+    /// pos_label 列使用导出格式中的语言，未指定时为中文
+    pub fn new(
+        result: &AnalysisResult,
+        levels: HashMap<String, String>,
+        options: &ExportOptions,
+    ) -> Self {
+        Self {
+            total_tokens: result.metadata.total_tokens as f64,
+            coverage: coverage_bands(&result.entries),
+            levels,
+            pos_language: options.pos_label.unwrap_or(LabelLanguage::Zh),
+            part_labels: result.metadata.parts.iter().map(PartInfo::label).collect(),
+        }
+    }
+
+    /// 按配置渲染一行；ranks 为该条目的排名（见 rank 模块）
+    pub fn render_row(
+        &self,
+        profile: &OutputProfile,
+        entry: &WordEntry,
+        ranks: &BTreeMap<String, usize>,
+        options: &ExportOptions,
+    ) -> Vec<String> {
+        let mut pinyin = None;
+        profile
+            .columns
+            .iter()
+            .map(|column| {
+                let number = |value: Option<f64>| match (value, column.precision) {
+                    (None, _) => String::new(),
+                    (Some(v), None) => options.format_number(v),
+                    (Some(v), Some(precision)) => ExportOptions {
+                        precision,
+                        decimal_separator: options.decimal_separator,
+                        ..ExportOptions::default()
+                    }
+                    .format_number(v),
+                };
+                let mut pinyin = || {
+                    pinyin
+                        .get_or_insert_with(|| romanize::word_pinyin(&entry.word))
+                        .clone()
+                };
+                match column.field.as_str() {
+                    "word" => entry.word.clone(),
+                    "surface_form" => entry.top_surface_form().to_string(),
+                    "pos" => entry.pos.clone(),
+                    "id" => entry.id.clone(),
+                    "flags" => entry_flags::display(&entry.flags),
+                    "pos_label" => tagset::label(&entry.pos, self.pos_language),
+                    "char_length" => entry.char_length.to_string(),
+                    "frequency" => number(Some(entry.frequency)),
+                    "frequency_pm" => number(
+                        (self.total_tokens > 0.0)
+                            .then(|| entry.frequency / self.total_tokens * 1e6),
+                    ),
+                    "coverage_band" => self
+                        .coverage
+                        .get(&(entry.word.clone(), entry.pos.clone()))
+                        .map(|&band| format!("{}%", COVERAGE_BANDS[band]))
+                        .unwrap_or_default(),
+                    "pinyin" => pinyin().pinyin,
+                    "pinyin_ambiguous" => if pinyin().ambiguous { "是" } else { "" }.to_string(),
+                    "level" => self.levels.get(&entry.word).cloned().unwrap_or_default(),
+                    "first_occurrence_part" => entry
+                        .first_occurrence_part
+                        .map(|part| part.to_string())
+                        .unwrap_or_default(),
+                    "pos_tag_share" => number(entry.pos_tag_share),
+                    "first_occurrence_fraction" => number(entry.first_occurrence_fraction),
+                    "occurrence_span_fraction" => number(entry.occurrence_span_fraction),
+                    "max_part_index" => entry
+                        .max_part_index
+                        .map(|part| part.to_string())
+                        .unwrap_or_default(),
+                    "max_part_file" => entry
+                        .max_part_index
+                        .and_then(|part| self.part_labels.get(part))
+                        .cloned()
+                        .unwrap_or_default(),
+                    "surface_forms" | "variants" => entry
+                        .surface_forms
+                        .iter()
+                        .map(|v| format!("{}:{}", v.form, v.frequency))
+                        .collect::<Vec<_>>()
+                        .join(" | "),
+                    "examples" => entry.examples.join(" | "),
+                    field => match field.strip_prefix("rank_") {
+                        Some(metric) => {
+                            ranks.get(metric).map(|r| r.to_string()).unwrap_or_default()
+                        }
+                        None => number(diff::metric_value(entry, field).flatten()),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// 按配置渲染一组条目；排名在这些条目中计算
+    pub fn render_table(
+        &self,
+        profile: &OutputProfile,
+        entries: &[&WordEntry],
+        options: &ExportOptions,
+    ) -> ProfileTable {
+        let ranks = rank::entry_ranks(entries, &profile.rank_metrics());
+        ProfileTable {
+            headers: profile.headers(),
+            rows: entries
+                .iter()
+                .zip(&ranks)
+                .map(|(entry, ranks)| self.render_row(profile, entry, ranks, options))
+                .collect(),
+        }
+    }
+}
+
+/// 各词的覆盖段下标：按频次降序（同频按词、词性）累计词次占比；短语与词性序列不参与
+fn coverage_bands(entries: &[WordEntry]) -> HashMap<(String, String), usize> {
+    let mut words: Vec<&WordEntry> = entries
+        .iter()
+        .filter(|e| e.pos != PHRASE_POS && e.pos != POSGRAM_POS)
+        .collect();
+    words.sort_by(|a, b| {
+        b.frequency
+            .total_cmp(&a.frequency)
+            .then_with(|| (&a.word, &a.pos).cmp(&(&b.word, &b.pos)))
+    });
+    let total: f64 = words.iter().map(|e| e.frequency).sum();
+    let mut cumulative = 0.0;
+    words
+        .into_iter()
+        .map(|entry| {
+            cumulative += entry.frequency;
+            let percent = if total > 0.0 {
+                cumulative / total * 100.0
+            } else {
+                100.0
+            };
+            let band = COVERAGE_BANDS
+                .iter()
+                .position(|&upper| percent <= f64::from(upper) + 1e-9)
+                .unwrap_or(COVERAGE_BANDS.len() - 1);
+            ((entry.word.clone(), entry.pos.clone()), band)
+        })
+        .collect()
+}
@@ -0,0 +1,607 @@
+// progress.rs
+// 分析进度上报：统一的事件类型与上报接口，前端经 Tauri 事件接收，测试与命令行可替换实现
+
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::analysis::{metadata::PartInfo, nlp::ModelLoadPhase};
+
+/// file-counted 事件中 (词, 词性, 频次) 的条数上限，file_counts_top 为 0 或更大时同样截断
+pub const FILE_COUNTS_MAX: usize = 100_000;
+
+/// 文件数不超过此值时每个文件一个进度事件；超过时见 ProgressCadence
+pub const PER_FILE_EVENTS_MAX: usize = 1000;
+
+/// 计算指标阶段每多完成这么多条目才再上报一次
+pub const METRIC_PROGRESS_EVERY: usize = 4096;
+
+/// 计算指标阶段取样的间隔
+const METRIC_SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 平滑文件吞吐量时新测得的值所占权重
+const THROUGHPUT_SMOOTHING: f64 = 0.3;
+
+/// 未完成任何文件时估算词次所用的每词元字节数：UTF-8 汉字 3 字节，词平均约 1.6 字，计入单字节的标点与空白后约 4.5
+const BYTES_PER_TOKEN: f64 = 4.5;
+
+/// 进度事件；current / total 按文件计，tokens_done / tokens_total_estimate 按词次计，
+/// 文件大小悬殊时后者更能反映进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisProgress {
+    pub current: usize,
+    pub total: usize,
+    pub file: String,
+    /// 当前词表条目数，即已发现的 (词, 词性) 类型数；min_frequency 尚未生效
+    pub vocab_entries: usize,
+    /// 词频表的估算内存（字节）
+    pub estimated_bytes: u64,
+    /// 已并入的部分的词次之和，口径同 metadata.total_tokens
+    pub tokens_done: u64,
+    /// 全语料词次的估算，见 TokenEstimate；全部文件完成时等于 tokens_done
+    pub tokens_total_estimate: u64,
+    /// 自上一个进度事件以来完成的文件数；文件较少时每个事件为 1，file 为其中最后一个
+    pub files_in_event: usize,
+    /// 平滑后的吞吐量（文件 / 秒）；仅合并上报时给出
+    pub files_per_second: Option<f64>,
+    #[serde(default)]
+    pub phase: ProgressPhase,
+    /// 计算指标阶段已完成的词表条目数；逐文件阶段为 0
+    #[serde(default)]
+    pub types_done: usize,
+    /// 计算指标阶段需计算的词表条目数（min_frequency 已生效）；逐文件阶段为 0
+    #[serde(default)]
+    pub types_total: usize,
+}
+
+/// 进度所处的阶段；两个阶段都由 analysis-complete 或 analysis-error 事件结束
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressPhase {
+    /// 逐文件分词计数，进度按 current / total 与 tokens_done
+    #[default]
+    Files,
+    /// 全部文件完成后逐条目计算分布指标，进度按 types_done / types_total
+    Metrics,
+}
+
+/// 计算指标阶段的计数：计算端逐条目累加，上报端在另一线程取样，计算本身从不等待上报
+#[derive(Debug, Default)]
+pub struct MetricProgress {
+    total: AtomicUsize,
+    done: AtomicUsize,
+}
+
+impl MetricProgress {
+    /// 开始计算 total 个条目
+    pub fn start(&self, total: usize) {
+        self.done.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// 又完成了一个条目；可在多个线程中并行调用
+    pub fn entry_done(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// (已完成, 总数)
+    pub fn snapshot(&self) -> (usize, usize) {
+        (
+            self.done.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// 在当前线程运行 work（计算指标），同时由取样线程每隔约 50 毫秒读取计数，总数已知后先报一次，
+/// 之后每多完成 METRIC_PROGRESS_EVERY 个条目报一次；work 结束后再报一次，最后一个事件的
+/// types_done 等于 types_total。事件以 base 为底，phase 为 Metrics
+pub fn report_metric_progress<T>(
+    reporter: &dyn ProgressReporter,
+    base: &AnalysisProgress,
+    work: impl FnOnce(&MetricProgress) -> T,
+) -> T {
+    let counter = MetricProgress::default();
+    let finished = AtomicBool::new(false);
+    let event = |(types_done, types_total)| AnalysisProgress {
+        phase: ProgressPhase::Metrics,
+        types_done,
+        types_total,
+        ..base.clone()
+    };
+    thread::scope(|scope| {
+        let sampler = scope.spawn(|| {
+            let mut reported: Option<usize> = None;
+            loop {
+                thread::park_timeout(METRIC_SAMPLE_INTERVAL);
+                if finished.load(Ordering::Acquire) {
+                    break;
+                }
+                let (done, total) = counter.snapshot();
+                let due = match reported {
+                    None => total > 0,
+                    Some(last) => done >= last + METRIC_PROGRESS_EVERY,
+                };
+                if due {
+                    reporter.progress(&event((done, total)));
+                    reported = Some(done);
+                }
+            }
+        });
+        let output = work(&counter);
+        finished.store(true, Ordering::Release);
+        sampler.thread().unpark();
+        sampler.join().ok();
+        reporter.progress(&event(counter.snapshot()));
+        output
+    })
+}
+
+/// 一个进度事件合并的文件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressBatch {
+    pub files: usize,
+    pub files_per_second: Option<f64>,
+}
+
+/// 进度事件的频度：文件数不超过 PER_FILE_EVENTS_MAX 时每个文件一个事件；更多时每 every 个文件合为一个，
+/// every 按平滑后的吞吐量取为 target 时长内约完成的文件数，使事件间隔接近 target。
+/// 距上一个事件已过 target 或进度到达终点时不等凑满 every，立即上报
+#[derive(Debug, Clone)]
+pub struct ProgressCadence {
+    /// 进度的终点（AnalysisProgress::total）
+    total: usize,
+    aggregated: bool,
+    target: Duration,
+    /// 合并上报时每个事件合并的文件数
+    every: usize,
+    /// 上一个事件之后完成的文件数
+    files_since: usize,
+    /// 上一个事件的时刻（自分析开始）
+    last_event: Duration,
+    files_per_second: Option<f64>,
+}
+
+impl ProgressCadence {
+    /// files 为文件数，决定是否合并上报；total 为进度的终点（两遍计数时为文件数的两倍）
+    pub fn new(files: usize, total: usize, target: Duration) -> Self {
+        Self {
+            total,
+            aggregated: files > PER_FILE_EVENTS_MAX,
+            target,
+            every: 1,
+            files_since: 0,
+            last_event: Duration::ZERO,
+            files_per_second: None,
+        }
+    }
+
+    /// 是否合并上报
+    pub fn aggregated(&self) -> bool {
+        self.aggregated
+    }
+
+    /// 又完成了 files 个文件，进度到达 current，elapsed 为自分析开始的时长；
+    /// 应发出进度事件时返回其合并的文件数与吞吐量
+    pub fn files_done(
+        &mut self,
+        files: usize,
+        current: usize,
+        elapsed: Duration,
+    ) -> Option<ProgressBatch> {
+        self.files_since += files;
+        let since = elapsed.saturating_sub(self.last_event);
+        if self.aggregated {
+            let due = self.files_since >= self.every || since >= self.target;
+            if !due && current < self.total {
+                return None;
+            }
+            self.adapt(since);
+        }
+        self.last_event = elapsed;
+        Some(ProgressBatch {
+            files: std::mem::take(&mut self.files_since),
+            files_per_second: self.files_per_second,
+        })
+    }
+
+    /// 按本批的吞吐量更新平滑吞吐量与 every
+    fn adapt(&mut self, since: Duration) {
+        let seconds = since.as_secs_f64();
+        if seconds <= 0.0 {
+            // 时钟分辨率内完成了整批，吞吐量无法测得
+            self.every = self.every.saturating_mul(2);
+            return;
+        }
+        let rate = self.files_since as f64 / seconds;
+        let smoothed = match self.files_per_second {
+            Some(previous) => previous + THROUGHPUT_SMOOTHING * (rate - previous),
+            None => rate,
+        };
+        self.files_per_second = Some(smoothed);
+        self.every = ((smoothed * self.target.as_secs_f64()).round() as usize).max(1);
+    }
+}
+
+/// 全语料词次的估算：开始时按文件字节数与 BYTES_PER_TOKEN 估算，之后按已完成文件实际的词次 / 字节比
+/// 推算其余文件，随文件完成逐步修正
+///
+/// ```
+/// use betawordlist_lib::analysis::progress::TokenEstimate;
+///
+/// let mut estimate = TokenEstimate::new(vec![900, 9000]);
+/// assert_eq!(estimate.estimate(0), 2200);
+/// estimate.file_done(0);
+/// // 第一个文件 900 字节实为 300 词次，其余 9000 字节按同一比例
+/// assert_eq!(estimate.estimate(300), 3300);
+/// estimate.file_done(1);
+/// assert_eq!(estimate.estimate(3100), 3100);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TokenEstimate {
+    sizes: Vec<u64>,
+    bytes_total: u64,
+    bytes_done: u64,
+}
+
+impl TokenEstimate {
+    /// sizes 为各文件的字节数
+    pub fn new(sizes: Vec<u64>) -> Self {
+        Self {
+            bytes_total: sizes.iter().sum(),
+            sizes,
+            bytes_done: 0,
+        }
+    }
+
+    /// 按文件路径取字节数；无法读取元数据的文件按 0 字节计
+    pub fn for_files<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Self {
+        Self::new(
+            paths
+                .into_iter()
+                .map(|path| fs::metadata(path).map_or(0, |m| m.len()))
+                .collect(),
+        )
+    }
+
+    /// 第 index 个文件已完成
+    pub fn file_done(&mut self, index: usize) {
+        self.bytes_done += self.sizes.get(index).copied().unwrap_or(0);
+    }
+
+    /// 已完成 tokens_done 词次时全语料的估算词次
+    pub fn estimate(&self, tokens_done: u64) -> u64 {
+        let remaining = self.bytes_total.saturating_sub(self.bytes_done) as f64;
+        let tokens_per_byte = if self.bytes_done > 0 {
+            tokens_done as f64 / self.bytes_done as f64
+        } else {
+            1.0 / BYTES_PER_TOKEN
+        };
+        tokens_done + (remaining * tokens_per_byte).round() as u64
+    }
+}
+
+/// 一个语料部分计数完成：部分信息与频次最高的 (词, 词性, 频次)，按频次降序（同频按词、词性）；
+/// 仅设置 file_counts_top 时上报。停用词等过滤已生效，min_frequency 尚未生效
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCounted {
+    pub part: PartInfo,
+    /// 该部分的词型数
+    pub types: usize,
+    /// counts 少于 types 时为 true
+    pub truncated: bool,
+    pub counts: Vec<(String, String, u64)>,
+}
+
+impl FileCounted {
+    /// 取 counts 中频次最高的 top 个（0 表示全部），至多 FILE_COUNTS_MAX 个
+    pub fn new(part: PartInfo, counts: &[(String, String, f64)], top: usize) -> Self {
+        let limit = if top == 0 {
+            FILE_COUNTS_MAX
+        } else {
+            top.min(FILE_COUNTS_MAX)
+        };
+        let mut sorted: Vec<&(String, String, f64)> = counts.iter().collect();
+        sorted.sort_by(|a, b| {
+            b.2.total_cmp(&a.2)
+                .then_with(|| a.0.cmp(&b.0))
+                .then_with(|| a.1.cmp(&b.1))
+        });
+        sorted.truncate(limit);
+        Self {
+            part,
+            types: counts.len(),
+            truncated: sorted.len() < counts.len(),
+            counts: sorted
+                .into_iter()
+                .map(|(w, p, v)| (w.clone(), p.clone(), *v as u64))
+                .collect(),
+        }
+    }
+}
+
+/// 分析完成事件；types 与 tokens 即结果的 metadata.total_types 与 metadata.total_tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisComplete {
+    pub types: usize,
+    pub tokens: u64,
+    pub duration_ms: u64,
+    /// 读取失败的文件（"路径: 原因"），这些文件按空部分计入
+    pub errors: Vec<String>,
+}
+
+/// 分析失败事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisError {
+    pub message: String,
+}
+
+/// 模型加载进度的事件名
+pub const MODEL_LOAD_PROGRESS_EVENT: &str = "model-load-progress";
+
+/// 模型加载进度事件：进入 current / total 个阶段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelLoadProgress {
+    pub phase: ModelLoadPhase,
+    pub current: usize,
+    pub total: usize,
+}
+
+impl From<ModelLoadPhase> for ModelLoadProgress {
+    fn from(phase: ModelLoadPhase) -> Self {
+        Self {
+            phase,
+            current: phase.step(),
+            total: ModelLoadPhase::COUNT,
+        }
+    }
+}
+
+/// 事件通道名，便于前端区分不同功能的进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventChannels {
+    pub progress: String,
+    pub complete: String,
+    pub error: String,
+    pub file_counted: String,
+}
+
+impl Default for EventChannels {
+    fn default() -> Self {
+        Self {
+            progress: "progress".to_string(),
+            complete: "analysis-complete".to_string(),
+            error: "analysis-error".to_string(),
+            file_counted: "file-counted".to_string(),
+        }
+    }
+}
+
+/// 进度上报接口；每次分析以 complete 或 error 之一结束
+pub trait ProgressReporter: Send + Sync {
+    fn progress(&self, event: &AnalysisProgress);
+    fn file_counted(&self, event: &FileCounted);
+    fn complete(&self, event: &AnalysisComplete);
+    fn error(&self, event: &AnalysisError);
+
+    /// 是否已请求取消；分析在处理下一个文件前检查
+    fn cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// 生成随机的 UUID（第 4 版）字符串，不依赖外部库
+///
+/// ```
+/// use betawordlist_lib::analysis::progress::new_run_id;
+///
+/// let id = new_run_id();
+/// assert_eq!(id.len(), 36);
+/// assert_eq!(id.matches('-').count(), 4);
+/// assert_eq!(&id[14..15], "4");
+/// assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+/// assert_ne!(id, new_run_id());
+/// ```
+pub fn new_run_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    // 每个 RandomState 的密钥各不相同，以此代替随机数源
+    let half = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        u128::from(hasher.finish())
+    };
+    let bits = (half() << 64) | half();
+    let bits = (bits & !(0xf << 76)) | (0x4 << 76);
+    let bits = (bits & !(0x3 << 62)) | (0x2 << 62);
+    let hex = format!("{bits:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// 一次分析运行：run_id 随事件与结果元数据上报，供前端丢弃已取消或已被取代的运行的事件
+#[derive(Debug, Clone)]
+pub struct AnalysisRun {
+    pub run_id: String,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Default for AnalysisRun {
+    fn default() -> Self {
+        Self {
+            run_id: new_run_id(),
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl AnalysisRun {
+    /// 请求取消；分析在处理下一个文件前中止
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+/// 不上报（命令行、脚本）
+pub struct NoopReporter;
+
+impl ProgressReporter for NoopReporter {
+    fn progress(&self, _event: &AnalysisProgress) {}
+    fn file_counted(&self, _event: &FileCounted) {}
+    fn complete(&self, _event: &AnalysisComplete) {}
+    fn error(&self, _event: &AnalysisError) {}
+}
+
+/// 分析状态，供前端按 run_id 过滤事件
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisStatus {
+    /// 最近启动且尚未结束的运行；没有正在进行的分析时为 None
+    pub run_id: Option<String>,
+}
+
+/// 送往前端的事件：事件本身的字段之外另有 run_id
+#[derive(Clone, Serialize)]
+struct RunEvent<'a, T> {
+    run_id: &'a str,
+    #[serde(flatten)]
+    event: &'a T,
+}
+
+/// 以 Tauri 事件上报到前端，每个事件附上所属运行的 run_id
+pub struct TauriReporter {
+    handle: AppHandle,
+    channels: EventChannels,
+    run: AnalysisRun,
+}
+
+impl TauriReporter {
+    pub fn new(handle: AppHandle, channels: EventChannels, run: AnalysisRun) -> Self {
+        Self {
+            handle,
+            channels,
+            run,
+        }
+    }
+
+    fn emit<T: Serialize + Clone>(&self, channel: &str, event: &T) {
+        let event = RunEvent {
+            run_id: &self.run.run_id,
+            event,
+        };
+        self.handle.emit(channel, event).ok();
+    }
+}
+
+impl ProgressReporter for TauriReporter {
+    fn progress(&self, event: &AnalysisProgress) {
+        self.emit(&self.channels.progress, event);
+    }
+
+    fn file_counted(&self, event: &FileCounted) {
+        self.emit(&self.channels.file_counted, event);
+    }
+
+    fn complete(&self, event: &AnalysisComplete) {
+        self.emit(&self.channels.complete, event);
+    }
+
+    fn error(&self, event: &AnalysisError) {
+        self.emit(&self.channels.error, event);
+    }
+
+    fn cancelled(&self) -> bool {
+        self.run.is_cancelled()
+    }
+}
+
+/// 上报过的事件
+#[derive(Debug, Clone)]
+pub enum ReportedEvent {
+    Progress(AnalysisProgress),
+    FileCounted(FileCounted),
+    Complete(AnalysisComplete),
+    Error(AnalysisError),
+}
+
+/// 记录全部事件，供测试断言
+#[derive(Default)]
+pub struct RecordingReporter {
+    pub events: Mutex<Vec<ReportedEvent>>,
+}
+
+impl ProgressReporter for RecordingReporter {
+    fn progress(&self, event: &AnalysisProgress) {
+        let event = ReportedEvent::Progress(event.clone());
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn file_counted(&self, event: &FileCounted) {
+        let event = ReportedEvent::FileCounted(event.clone());
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn complete(&self, event: &AnalysisComplete) {
+        let event = ReportedEvent::Complete(event.clone());
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn error(&self, event: &AnalysisError) {
+        let event = ReportedEvent::Error(event.clone());
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+/// 保证终止事件：未调用 complete 就被丢弃（提前返回或 panic）时补发 error
+pub struct TerminalGuard<'a> {
+    reporter: &'a dyn ProgressReporter,
+    done: bool,
+}
+
+impl<'a> TerminalGuard<'a> {
+    pub fn new(reporter: &'a dyn ProgressReporter) -> Self {
+        Self {
+            reporter,
+            done: false,
+        }
+    }
+
+    pub fn complete(mut self, event: &AnalysisComplete) {
+        self.done = true;
+        self.reporter.complete(event);
+    }
+
+    pub fn error(mut self, message: String) {
+        self.done = true;
+        self.reporter.error(&AnalysisError { message });
+    }
+}
+
+impl Drop for TerminalGuard<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.reporter.error(&AnalysisError {
+                message: "分析意外中止".to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// rank.rs
+// 频次与指标的密集排名：并列的词共用名次，下一名次紧接其后（1, 2, 2, 3）
+//
+// 排名在导出与分页时对实际输出的条目计算，筛选掉的词不占名次。
+
+use std::collections::BTreeMap;
+
+use crate::analysis::{diff, word_entry::WordEntry};
+
+/// 数值越大分布越集中的指标，按升序排名，使名次 1 总是分布最均匀的词；其余按降序排名
+pub const CONCENTRATION_METRICS: &[&str] = &[
+    "sd_population",
+    "vc_population",
+    "dp",
+    "dp_norm",
+    "kl_divergence",
+    "max_part_share",
+    "gini",
+    "within_file_dp",
+];
+
+/// 校验排名列名：frequency、gt_adjusted_frequency 或 DispersionMetrics 的字段
+pub fn validate_names(names: &[String]) -> Result<(), String> {
+    match names.iter().find(|name| !diff::is_known_metric(name)) {
+        Some(name) => Err(format!("无法排名的指标: {name}")),
+        None => Ok(()),
+    }
+}
+
+/// 密集排名；ascending 为 true 时最小值为第 1 名。缺失值没有名次
+///
+/// ```
+/// use betawordlist_lib::analysis::rank::dense_ranks;
+///
+/// let values = [Some(5.0), Some(3.0), None, Some(5.0), Some(1.0)];
+/// assert_eq!(
+///     dense_ranks(&values, false),
+///     [Some(1), Some(2), None, Some(1), Some(3)]
+/// );
+/// assert_eq!(
+///     dense_ranks(&values, true),
+///     [Some(3), Some(2), None, Some(3), Some(1)]
+/// );
+/// ```
+pub fn dense_ranks(values: &[Option<f64>], ascending: bool) -> Vec<Option<usize>> {
+    let mut order: Vec<(usize, f64)> = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| v.map(|v| (i, v)))
+        .collect();
+    order.sort_by(|a, b| {
+        let ord = a.1.total_cmp(&b.1);
+        if ascending {
+            ord
+        } else {
+            ord.reverse()
+        }
+    });
+    let mut ranks = vec![None; values.len()];
+    let mut rank = 0;
+    let mut previous = None;
+    for (i, value) in order {
+        if previous != Some(value) {
+            rank += 1;
+            previous = Some(value);
+        }
+        ranks[i] = Some(rank);
+    }
+    ranks
+}
+
+/// 各条目在给定指标下的名次，与 entries 一一对应；指标名须先经 validate_names 校验
+pub fn entry_ranks(entries: &[&WordEntry], names: &[String]) -> Vec<BTreeMap<String, usize>> {
+    let mut ranks = vec![BTreeMap::new(); entries.len()];
+    for name in names {
+        let values: Vec<Option<f64>> = entries
+            .iter()
+            .map(|entry| diff::metric_value(entry, name).flatten())
+            .collect();
+        let ascending = CONCENTRATION_METRICS.contains(&name.as_str());
+        for (entry_ranks, rank) in ranks.iter_mut().zip(dense_ranks(&values, ascending)) {
+            if let Some(rank) = rank {
+                entry_ranks.insert(name.clone(), rank);
+            }
+        }
+    }
+    ranks
+}
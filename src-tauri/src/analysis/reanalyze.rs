@@ -0,0 +1,159 @@
+// reanalyze.rs
+// 选择性重新分析：只对指定文件重新分词，在保留的各部分词频中原位替换，再在全部部分上重算指标
+//
+// 依赖 keep_parts 保留的各部分词频；文件按规范路径对应到原结果的部分，符号链接与相对路径指向同一文件时视为同一文件。
+// 原结果中没有的文件追加在末尾，磁盘上已不存在的文件移出分析并记入 warnings。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{
+    corpus_pipeline::{self, AnalysisResult, PartCounts},
+    metadata::{CorpusMetadata, FileInfo},
+    nlp::Tokenizer,
+    paths,
+};
+
+/// 比较用的规范路径：canonicalize 失败（如文件已删除）时取绝对路径
+fn canonical_key(path: &Path) -> PathBuf {
+    fs::canonicalize(paths::normalize(path))
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// 对 file_paths 重新分词并替换其在原结果中的部分，其余部分沿用保留的词频
+///
+/// 替换的部分留在原位置，新文件的部分追加在末尾；沿用原结果的分析选项，分词引擎须与原结果一致。
+pub fn reanalyze_files(
+    nlp: &dyn Tokenizer,
+    previous: &AnalysisResult,
+    file_paths: &[PathBuf],
+) -> Result<AnalysisResult, String> {
+    let old = &previous.metadata;
+    if !old.options.keep_parts || previous.parts.len() != old.parts.len() {
+        return Err("结果未保留各部分词频，请开启 keep_parts 重新分析".to_string());
+    }
+    if nlp.engine() != old.tokenizer {
+        return Err(format!(
+            "当前分词引擎 {} 与上次分析的 {} 不同，请完整重新分析",
+            nlp.engine(),
+            old.tokenizer
+        ));
+    }
+    let options = &old.options;
+    let mut warnings = Vec::new();
+    let mut files = old.files.clone();
+    let mut entries: Vec<(String, Option<usize>, PartCounts)> = old
+        .parts
+        .iter()
+        .zip(&previous.parts)
+        .map(|(info, part)| (info.source.clone(), info.sub_index, part.clone()))
+        .collect();
+    let mut keys: HashMap<String, PathBuf> = HashMap::new();
+    let mut key_of = |source: &str| {
+        keys.entry(source.to_string())
+            .or_insert_with(|| canonical_key(Path::new(source)))
+            .clone()
+    };
+
+    let mut done: Vec<PathBuf> = Vec::new();
+    for path in file_paths {
+        let key = canonical_key(path);
+        if done.contains(&key) {
+            continue;
+        }
+        done.push(key.clone());
+        let position = entries
+            .iter()
+            .position(|(source, _, _)| key_of(source) == key);
+        let source = position.map_or_else(|| paths::display(path), |i| entries[i].0.clone());
+        entries.retain(|(s, _, _)| key_of(s) != key);
+        let file_index = files.iter().position(|f| key_of(&f.path) == key);
+
+        let readable = paths::normalize(path);
+        let counted = fs::metadata(&readable)
+            .is_ok()
+            .then(|| corpus_pipeline::count_file_parts(nlp, &readable, options));
+        let parts = match counted {
+            None => {
+                warnings.push(format!("文件已不存在，已移出分析: {source}"));
+                None
+            }
+            Some(None) => {
+                warnings.push(format!("文件被语言筛查跳过，已移出分析: {source}"));
+                None
+            }
+            Some(Some(parts)) => Some(parts),
+        };
+        let Some(parts) = parts else {
+            if let Some(i) = file_index {
+                files.remove(i);
+            }
+            continue;
+        };
+        let at = position.unwrap_or(entries.len());
+        entries.splice(
+            at..at,
+            parts
+                .into_iter()
+                .map(|(sub_index, part)| (source.clone(), sub_index, part)),
+        );
+        let mut info = FileInfo::collect(path, options.hash_files);
+        info.path = source.clone();
+        match file_index {
+            Some(i) => files[i] = info,
+            None => files.push(info),
+        }
+    }
+
+    let mut metadata = CorpusMetadata::new(&old.tokenizer, options);
+    // 未指定种子时沿用原结果的种子
+    metadata.seed = options.seed.unwrap_or(old.seed);
+    metadata.partition_mode = old.partition_mode.clone();
+    metadata.preset = old.preset.clone();
+    metadata.model_files = old.model_files.clone();
+    metadata.screened_files = old.screened_files.clone();
+    metadata.near_duplicates = old.near_duplicates.clone();
+    metadata.merged_from = old.merged_from.clone();
+    metadata.file_errors = old.file_errors.clone();
+    metadata.warnings = options.warnings();
+    metadata.warnings.extend(warnings);
+    metadata.files = files;
+    let (sources, parts) = entries
+        .into_iter()
+        .map(|(source, sub_index, part)| ((source, sub_index), part))
+        .unzip();
+    corpus_pipeline::aggregate_parts(parts, sources, metadata)
+}
+
+/// 与分析时的指纹（大小、修改时间，记录了 SHA-256 时也比较哈希）不同或已不存在的文件，按元数据中的顺序
+pub fn changed_files(metadata: &CorpusMetadata) -> Vec<PathBuf> {
+    metadata
+        .files
+        .iter()
+        .filter(|recorded| {
+            let path = Path::new(&recorded.path);
+            if fs::metadata(paths::normalize(path)).is_err() {
+                return true;
+            }
+            let current = FileInfo::collect(path, recorded.sha256.is_some());
+            current.size_bytes != recorded.size_bytes
+                || current.modified != recorded.modified
+                || current.sha256 != recorded.sha256
+        })
+        .map(|f| PathBuf::from(&f.path))
+        .collect()
+}
+
+/// 按文件指纹找出变化的文件并重新分析；没有变化时返回 None
+pub fn refresh_analysis(
+    nlp: &dyn Tokenizer,
+    previous: &AnalysisResult,
+) -> Result<Option<AnalysisResult>, String> {
+    let changed = changed_files(&previous.metadata);
+    if changed.is_empty() {
+        return Ok(None);
+    }
+    reanalyze_files(nlp, previous, &changed).map(Some)
+}
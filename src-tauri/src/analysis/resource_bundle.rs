@@ -0,0 +1,356 @@
+// resource_bundle.rs
+// 资源包：把停用词表、目标词表、短语表与分析选项打成一个 ZIP，供在另一台机器上导入同样的设置
+//
+// 包内每项资源一个文件，manifest.json 记录包格式版本以及各资源的版本、字节数与 SHA-256。导入时逐项校验，
+// 缺失、损坏或版本过新的资源不安装，在报告中注明；ZIP 结构或 CRC 损坏时整个包拒收。词表每行一个，
+// 格式同停用词表（见 filter::parse_word_list），以 # 开头的词不能经资源包传递；options.json 中不重复这三份词表。
+// 安装目录与包内布局相同，只是 manifest.json 仅列出实际安装的资源，启动后可经 load_installed 读回。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    filter,
+    options::AnalysisOptions,
+    result_slots::StaleResult,
+    zip::{self, ZipWriter},
+};
+
+/// 资源包格式版本；包的 format 高于此值时拒绝导入
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// 清单文件名
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// 资源种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    /// 分析选项（不含三份词表）
+    Options,
+    Stopwords,
+    TargetWords,
+    Phrases,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 4] = [
+        ResourceKind::Options,
+        ResourceKind::Stopwords,
+        ResourceKind::TargetWords,
+        ResourceKind::Phrases,
+    ];
+
+    /// 包内文件名
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ResourceKind::Options => "options.json",
+            ResourceKind::Stopwords => "stopwords.txt",
+            ResourceKind::TargetWords => "target_words.txt",
+            ResourceKind::Phrases => "phrases.txt",
+        }
+    }
+
+    /// 选项中对应的词表；Options 不是词表，为 None
+    pub fn word_list(self, options: &AnalysisOptions) -> Option<&[String]> {
+        match self {
+            ResourceKind::Options => None,
+            ResourceKind::Stopwords => Some(&options.stopwords),
+            ResourceKind::TargetWords => Some(&options.target_words),
+            ResourceKind::Phrases => Some(&options.phrases),
+        }
+    }
+
+    /// 本版本写出与能读取的资源版本
+    pub fn version(self) -> u32 {
+        1
+    }
+}
+
+/// 清单中的一项资源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub kind: ResourceKind,
+    pub file: String,
+    pub version: u32,
+    /// 字节数
+    pub size: u64,
+    /// 文件内容的 SHA-256（十六进制）
+    pub sha256: String,
+}
+
+/// 资源包清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    /// 写出资源包的程序版本
+    pub app_version: String,
+    pub resources: Vec<ManifestEntry>,
+}
+
+/// 一项资源的校验结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceStatus {
+    Ok,
+    /// 清单中没有，或清单列出但包内缺少文件
+    Missing,
+    /// 大小或 SHA-256 与清单不符，或内容无法解析
+    Corrupt,
+    /// 资源版本高于本程序支持的版本
+    UnsupportedVersion,
+}
+
+/// 一项资源的导入报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceReport {
+    pub kind: ResourceKind,
+    pub file: String,
+    /// 清单记录的版本；清单中没有时为 None
+    pub version: Option<u32>,
+    pub status: ResourceStatus,
+    /// 词表的条数；选项为 None
+    pub items: Option<usize>,
+    /// 状态不为 ok 时的说明
+    pub message: Option<String>,
+}
+
+/// 校验后的资源包；序列化为导入报告（清单、各项资源报告与拼成的选项）
+#[derive(Debug, Clone, Serialize)]
+pub struct Bundle {
+    pub manifest: Manifest,
+    /// 按 ResourceKind::ALL 顺序的各项资源报告
+    pub resources: Vec<ResourceReport>,
+    /// 校验通过的资源内容
+    #[serde(skip)]
+    verified: Vec<(ManifestEntry, Vec<u8>)>,
+    /// 由通过校验的资源拼成的选项；缺少 options.json 时其余选项取默认值
+    options: AnalysisOptions,
+}
+
+/// import_resource_bundle 的返回值：导入报告，以及所用词表与资源包不同的已有结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedBundle {
+    #[serde(flatten)]
+    pub bundle: Bundle,
+    pub stale_results: Vec<StaleResult>,
+}
+
+/// 写出资源包：options 中的三份词表各成一个文件，其余选项写入 options.json，返回底层写出端
+pub fn write_bundle<W: Write>(writer: W, options: &AnalysisOptions) -> io::Result<W> {
+    let lists = AnalysisOptions {
+        stopwords: Vec::new(),
+        target_words: Vec::new(),
+        phrases: Vec::new(),
+        ..options.clone()
+    };
+    let files: Vec<(ResourceKind, Vec<u8>)> = ResourceKind::ALL
+        .into_iter()
+        .map(|kind| {
+            let data = match kind {
+                ResourceKind::Options => serde_json::to_vec_pretty(&lists)?,
+                ResourceKind::Stopwords => word_list(&options.stopwords),
+                ResourceKind::TargetWords => word_list(&options.target_words),
+                ResourceKind::Phrases => word_list(&options.phrases),
+            };
+            Ok((kind, data))
+        })
+        .collect::<io::Result<_>>()?;
+    let manifest = Manifest {
+        format: BUNDLE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        resources: files
+            .iter()
+            .map(|(kind, data)| manifest_entry(*kind, data))
+            .collect(),
+    };
+
+    let mut zip = ZipWriter::new(writer);
+    zip.add(MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+    for (kind, data) in &files {
+        zip.add(kind.file_name(), data)?;
+    }
+    zip.finish()
+}
+
+/// 读取并校验资源包；ZIP 损坏、缺少清单或包格式过新时报错，单项资源的问题记在报告中
+pub fn read_bundle(bytes: &[u8]) -> Result<Bundle, String> {
+    let mut files: HashMap<String, Vec<u8>> = zip::read_entries(bytes)
+        .map_err(|e| format!("资源包已损坏: {e}"))?
+        .into_iter()
+        .collect();
+    let manifest = files
+        .remove(MANIFEST_FILE)
+        .ok_or_else(|| format!("不是资源包：缺少 {MANIFEST_FILE}"))?;
+    Bundle::verify(&manifest, |file| files.remove(file))
+}
+
+/// 读取 install 安装到 dir 的资源；目录中没有清单时为 None
+pub fn load_installed(dir: &Path) -> Result<Option<Bundle>, String> {
+    let manifest = match fs::read(dir.join(MANIFEST_FILE)) {
+        Ok(manifest) => manifest,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("无法读取已安装的资源清单: {e}")),
+    };
+    Bundle::verify(&manifest, |file| fs::read(dir.join(file)).ok()).map(Some)
+}
+
+impl Bundle {
+    /// 按清单校验各项资源；take 按文件名取出内容，不存在时为 None
+    fn verify(
+        manifest: &[u8],
+        mut take: impl FnMut(&str) -> Option<Vec<u8>>,
+    ) -> Result<Self, String> {
+        let manifest: Manifest =
+            serde_json::from_slice(manifest).map_err(|e| format!("资源清单无法解析: {e}"))?;
+        if manifest.format > BUNDLE_FORMAT {
+            return Err(format!(
+                "资源包格式版本 {} 高于本程序支持的 {BUNDLE_FORMAT}（由 {} 版写出），请升级后再导入",
+                manifest.format, manifest.app_version
+            ));
+        }
+
+        // ALL 中 Options 在前，随后的词表填入其中
+        let mut options = AnalysisOptions::default();
+        let mut resources = Vec::new();
+        let mut verified = Vec::new();
+        for kind in ResourceKind::ALL {
+            let entry = manifest.resources.iter().find(|entry| entry.kind == kind);
+            let mut report = ResourceReport {
+                kind,
+                file: entry.map_or(kind.file_name(), |e| &e.file).to_string(),
+                version: entry.map(|e| e.version),
+                status: ResourceStatus::Ok,
+                items: None,
+                message: None,
+            };
+            let checked = match entry {
+                None => Err((ResourceStatus::Missing, "清单中没有此资源".to_string())),
+                // 文件名来自外部，只接受固定的名称，安装时不会写到目录之外
+                Some(entry) if entry.file != kind.file_name() => Err((
+                    ResourceStatus::Corrupt,
+                    format!("文件名应为 {}", kind.file_name()),
+                )),
+                Some(entry) => check(entry, take(&entry.file)),
+            };
+            let parsed = checked.and_then(|data| {
+                if kind == ResourceKind::Options {
+                    options = serde_json::from_slice(&data)
+                        .map_err(|e| (ResourceStatus::Corrupt, format!("选项无法解析: {e}")))?;
+                } else {
+                    let text = String::from_utf8(data.clone()).map_err(|_| {
+                        (ResourceStatus::Corrupt, "词表不是 UTF-8 文本".to_string())
+                    })?;
+                    let words = filter::parse_word_list(&text);
+                    report.items = Some(words.len());
+                    match kind {
+                        ResourceKind::Stopwords => options.stopwords = words,
+                        ResourceKind::TargetWords => options.target_words = words,
+                        ResourceKind::Phrases => options.phrases = words,
+                        ResourceKind::Options => unreachable!(),
+                    }
+                }
+                Ok(data)
+            });
+            match parsed {
+                Ok(data) => verified.push((entry.cloned().expect("已校验的资源必有清单项"), data)),
+                Err((status, message)) => {
+                    report.status = status;
+                    report.message = Some(message);
+                }
+            }
+            resources.push(report);
+        }
+
+        Ok(Self {
+            manifest,
+            resources,
+            verified,
+            options,
+        })
+    }
+
+    /// 所有资源都通过了校验
+    pub fn is_complete(&self) -> bool {
+        self.resources
+            .iter()
+            .all(|r| r.status == ResourceStatus::Ok)
+    }
+
+    /// 由通过校验的资源拼成的分析选项
+    pub fn options(&self) -> &AnalysisOptions {
+        &self.options
+    }
+
+    /// 把通过校验的资源写入 dir，并写出只列出这些资源的清单；dir 中其余文件不动
+    pub fn install(&self, dir: &Path) -> Result<(), WriteError> {
+        fs::create_dir_all(dir).map_err(|e| WriteError::new(dir, e))?;
+        for (entry, data) in &self.verified {
+            write_atomic(&dir.join(&entry.file), |w| w.write_all(data))?;
+        }
+        let manifest = Manifest {
+            resources: self
+                .verified
+                .iter()
+                .map(|(entry, _)| entry.clone())
+                .collect(),
+            ..self.manifest.clone()
+        };
+        write_atomic(&dir.join(MANIFEST_FILE), |w| {
+            Ok(serde_json::to_writer_pretty(w, &manifest)?)
+        })
+    }
+}
+
+/// 核对一项资源的版本、大小与 SHA-256
+fn check(
+    entry: &ManifestEntry,
+    data: Option<Vec<u8>>,
+) -> Result<Vec<u8>, (ResourceStatus, String)> {
+    if entry.version > entry.kind.version() {
+        return Err((
+            ResourceStatus::UnsupportedVersion,
+            format!(
+                "资源版本 {} 高于本程序支持的 {}",
+                entry.version,
+                entry.kind.version()
+            ),
+        ));
+    }
+    let data = data.ok_or((ResourceStatus::Missing, "包内缺少此文件".to_string()))?;
+    if data.len() as u64 != entry.size || sha256_hex(&data) != entry.sha256 {
+        return Err((
+            ResourceStatus::Corrupt,
+            "大小或校验和与清单不符，文件不完整或已被改动".to_string(),
+        ));
+    }
+    Ok(data)
+}
+
+fn manifest_entry(kind: ResourceKind, data: &[u8]) -> ManifestEntry {
+    ManifestEntry {
+        kind,
+        file: kind.file_name().to_string(),
+        version: kind.version(),
+        size: data.len() as u64,
+        sha256: sha256_hex(data),
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+fn word_list(words: &[String]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| [word.as_str(), "\n"])
+        .collect::<String>()
+        .into_bytes()
+}
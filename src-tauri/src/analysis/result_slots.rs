@@ -0,0 +1,176 @@
+// result_slots.rs
+// 后端保留的分析结果：若干个具名的结果槽，可同时保留修改选项前后的两次结果，分别分页、导出或比较
+//
+// 每次分析、合并、导入与重新分析写入 LATEST_SLOT；pin 把某个结果复制到另一个名字下，之后的分析不会覆盖它。
+// 命令不指定槽名时取最近写入的槽。槽数以 MAX_SLOTS 为限（LATEST_SLOT 不受限），每个槽都是完整的结果，
+// 大语料的结果较占内存，不用的槽应及时移除。
+//
+// 读入新的停用词表等资源只影响之后的分析；stale_results 列出仍以此前的资源得出的结果，
+// 停用词与词性过滤可经 corpus_pipeline::apply_filters 在保留的计数上事后施加，存为新的槽。
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    corpus_pipeline::{AnalysisResult, AnalysisSummary},
+    resource_bundle::ResourceKind,
+};
+
+/// 分析结果默认写入的槽
+pub const LATEST_SLOT: &str = "latest";
+/// 最多保留的槽数
+pub const MAX_SLOTS: usize = 8;
+
+/// 一个槽的概况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultSlotInfo {
+    pub name: String,
+    /// 是否为不指定槽名时使用的槽（最近写入）
+    pub current: bool,
+    pub summary: AnalysisSummary,
+    /// 条目数
+    pub entries: usize,
+}
+
+/// 以不同于新读入词表的资源得出的结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaleResult {
+    pub slot: String,
+    /// 产生该结果的分析运行 id；载入自会话文件等时为 None
+    pub run_id: Option<String>,
+    /// 与新读入的不同的词表
+    pub lists: Vec<ResourceKind>,
+}
+
+/// 读入的资源词表，及仍以此前的资源得出的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadedResource {
+    pub items: Vec<String>,
+    pub stale_results: Vec<StaleResult>,
+}
+
+/// 具名的结果槽，按写入先后排列，最近写入的在末尾
+#[derive(Default)]
+pub struct ResultSlots {
+    slots: Vec<(String, AnalysisResult)>,
+}
+
+impl ResultSlots {
+    /// 写入 name 槽并使其成为最近写入的槽；已有同名槽时替换
+    pub fn store(&mut self, name: &str, result: AnalysisResult) {
+        self.slots.retain(|(slot, _)| slot != name);
+        self.slots.push((name.to_string(), result));
+    }
+
+    /// 写入新的具名槽：检查槽名与槽数上限
+    pub fn insert(&mut self, name: &str, result: AnalysisResult) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("结果名称不能为空".to_string());
+        }
+        let exists = self.slots.iter().any(|(slot, _)| slot == name);
+        let limited = self
+            .slots
+            .iter()
+            .filter(|(slot, _)| slot != LATEST_SLOT)
+            .count();
+        if !exists && name != LATEST_SLOT && limited >= MAX_SLOTS - 1 {
+            return Err(format!(
+                "最多保留 {MAX_SLOTS} 个结果（含 {LATEST_SLOT}），请先移除不再使用的结果"
+            ));
+        }
+        self.store(name, result);
+        Ok(())
+    }
+
+    /// 把 from 槽（缺省为最近写入的槽）复制到 name 槽；复制不改变最近写入的槽
+    pub fn pin(&mut self, name: &str, from: Option<&str>) -> Result<(), String> {
+        let current = self.resolve(None)?;
+        let result = self.get(from)?.clone();
+        self.insert(name, result)?;
+        if let Some(index) = self.slots.iter().position(|(slot, _)| *slot == current) {
+            let slot = self.slots.remove(index);
+            self.slots.push(slot);
+        }
+        Ok(())
+    }
+
+    /// 移除 name 槽，返回是否存在
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.slots.len();
+        self.slots.retain(|(slot, _)| slot != name);
+        self.slots.len() != before
+    }
+
+    /// name 槽的结果；name 为 None 时取最近写入的槽
+    pub fn get(&self, name: Option<&str>) -> Result<&AnalysisResult, String> {
+        let index = self.index(name)?;
+        Ok(&self.slots[index].1)
+    }
+
+    /// 同 get，可修改
+    pub fn get_mut(&mut self, name: Option<&str>) -> Result<&mut AnalysisResult, String> {
+        let index = self.index(name)?;
+        Ok(&mut self.slots[index].1)
+    }
+
+    /// 实际使用的槽名；name 为 None 时为最近写入的槽
+    pub fn resolve(&self, name: Option<&str>) -> Result<String, String> {
+        let index = self.index(name)?;
+        Ok(self.slots[index].0.clone())
+    }
+
+    /// 各槽的概况，按写入先后
+    pub fn list(&self) -> Vec<ResultSlotInfo> {
+        let last = self.slots.len().saturating_sub(1);
+        self.slots
+            .iter()
+            .enumerate()
+            .map(|(i, (name, result))| ResultSlotInfo {
+                name: name.clone(),
+                current: i == last,
+                summary: result.summary(0),
+                entries: result.entries.len(),
+            })
+            .collect()
+    }
+
+    /// 所用词表与 lists 中给出的新词表不同（按集合比较）的各槽，按写入先后；lists 中的 Options 项不参与比较
+    pub fn stale_results(&self, lists: &[(ResourceKind, &[String])]) -> Vec<StaleResult> {
+        let as_set = |items: &[String]| -> BTreeSet<String> { items.iter().cloned().collect() };
+        self.slots
+            .iter()
+            .filter_map(|(name, result)| {
+                let differs: Vec<ResourceKind> = lists
+                    .iter()
+                    .filter(|(list, items)| {
+                        list.word_list(&result.metadata.options)
+                            .is_some_and(|used| as_set(used) != as_set(items))
+                    })
+                    .map(|(list, _)| *list)
+                    .collect();
+                (!differs.is_empty()).then(|| StaleResult {
+                    slot: name.clone(),
+                    run_id: result.metadata.run_id.clone(),
+                    lists: differs,
+                })
+            })
+            .collect()
+    }
+
+    fn index(&self, name: Option<&str>) -> Result<usize, String> {
+        match name {
+            None => self
+                .slots
+                .len()
+                .checked_sub(1)
+                .ok_or_else(|| "暂无分析结果".to_string()),
+            Some(name) => self
+                .slots
+                .iter()
+                .position(|(slot, _)| slot == name)
+                .ok_or_else(|| format!("没有名为 {name} 的分析结果")),
+        }
+    }
+}
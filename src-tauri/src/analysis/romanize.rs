@@ -0,0 +1,29 @@
+// romanize.rs
+// 词语的带调拼音：多音字取最常用读音并标记，非汉字原样保留
+
+use ::pinyin::{ToPinyin, ToPinyinMulti};
+
+/// 一个词的拼音
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordPinyin {
+    /// 各字拼音连写，如 "yínháng"
+    pub pinyin: String,
+    /// 含多音字，给出的读音可能不对
+    pub ambiguous: bool,
+}
+
+/// 生成词的拼音；多音字取拼音库列出的第一个（最常用）读音
+pub fn word_pinyin(word: &str) -> WordPinyin {
+    let mut pinyin = String::new();
+    let mut ambiguous = false;
+    for c in word.chars() {
+        match c.to_pinyin() {
+            Some(reading) => {
+                pinyin.push_str(reading.with_tone());
+                ambiguous |= c.to_pinyin_multi().is_some_and(|multi| multi.count() > 1);
+            }
+            None => pinyin.push(c),
+        }
+    }
+    WordPinyin { pinyin, ambiguous }
+}
@@ -0,0 +1,243 @@
+// run_report.rs
+// 运行报告：一次分析运行的结构化 JSON 报告，供 Snakemake 等流程工具判断运行结果、收集产物
+//
+// 报告含退出状态、计数、警告、文件错误、耗时、选项哈希与写出的文件路径，字段为 camelCase，附带由类型生成的 JSON Schema。
+// 命令行以 --report 写出；界面在批量导出时开启 BatchOptions::report，写入 <path_base>.report.json。
+// 各处已记录的提示（选项提示、汉字占比、语言筛查跳过的文件、过滤移除的词元、清洗的非有限值等）都汇入 warnings。
+// 报告格式变化时递增 REPORT_SCHEMA_VERSION；新增字段不视为格式变化。
+
+use std::path::Path;
+
+use schemars::{schema::RootSchema, JsonSchema};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    corpus_pipeline::AnalysisResult,
+    language::ScreenMode,
+    metadata::{self, CorpusMetadata},
+    options::AnalysisOptions,
+};
+
+/// 报告格式版本
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// 写入 "$schema" 的标识
+pub const REPORT_SCHEMA_ID: &str = "urn:betawordlist:run-report:1";
+
+/// 运行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Succeeded,
+    Failed,
+}
+
+/// 一次运行的报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunReport {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub schema_version: u32,
+    pub status: RunStatus,
+    /// 进程退出码：0 成功，1 运行失败，2 参数错误
+    pub exit_code: i32,
+    /// 失败原因
+    pub error: Option<String>,
+    pub app_version: String,
+    pub run_id: Option<String>,
+    pub tokenizer: Option<String>,
+    /// 分析选项的哈希，见 options_hash；参数解析失败时为 null
+    pub options_hash: Option<String>,
+    pub timing: RunTiming,
+    /// 语料与词表的计数；分析未完成时为 null
+    pub counts: Option<RunCounts>,
+    pub warnings: Vec<String>,
+    pub file_errors: Vec<String>,
+    /// 写出文件的绝对路径（不含报告本身）
+    pub outputs: Vec<String>,
+}
+
+/// 时间信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTiming {
+    /// 分析开始的时间（RFC 3339），同元数据的 analyzed_at
+    pub analyzed_at: Option<String>,
+    /// 运行耗时（毫秒）；界面导出时不可得，为 null
+    pub elapsed_ms: Option<u64>,
+    /// 报告生成的时间（RFC 3339）
+    pub reported_at: String,
+}
+
+/// 语料与词表的计数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunCounts {
+    /// 参与分析的文件数
+    pub files: usize,
+    /// 语言筛查跳过的文件数
+    pub skipped_files: usize,
+    /// 无法读取或分词的文件数
+    pub file_errors: usize,
+    pub parts: usize,
+    pub total_tokens: u64,
+    pub total_raw_tokens: u64,
+    pub total_types: usize,
+    /// 结果中的条目数
+    pub entries: usize,
+    /// 清洗为空的非有限指标值个数
+    pub sanitized_values: usize,
+}
+
+impl RunReport {
+    /// 分析成功：计数、警告与文件错误取自结果元数据
+    pub fn succeeded(
+        result: &AnalysisResult,
+        elapsed_ms: Option<u64>,
+        outputs: Vec<String>,
+    ) -> Self {
+        let metadata = &result.metadata;
+        Self {
+            run_id: metadata.run_id.clone(),
+            tokenizer: Some(metadata.tokenizer.clone()),
+            options_hash: Some(options_hash(&metadata.options)),
+            timing: RunTiming {
+                analyzed_at: Some(metadata.analyzed_at.clone()),
+                elapsed_ms,
+                reported_at: metadata::now_rfc3339(),
+            },
+            counts: Some(RunCounts {
+                files: metadata.files.len(),
+                skipped_files: skipped_files(metadata),
+                file_errors: metadata.file_errors.len(),
+                parts: metadata.parts.len(),
+                total_tokens: metadata.total_tokens,
+                total_raw_tokens: metadata.total_raw_tokens,
+                total_types: metadata.total_types,
+                entries: result.entries.len(),
+                sanitized_values: metadata.sanitized_values,
+            }),
+            warnings: collect_warnings(metadata),
+            file_errors: metadata.file_errors.clone(),
+            outputs,
+            ..Self::new(RunStatus::Succeeded, 0, None)
+        }
+    }
+
+    /// 运行失败；options 为已解析出的分析选项
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::run_report::{RunReport, RunStatus};
+    ///
+    /// let report = RunReport::failed(2, "未知参数: --bogus", None, Some(3));
+    /// assert_eq!((report.status, report.exit_code), (RunStatus::Failed, 2));
+    /// assert_eq!(report.error.as_deref(), Some("未知参数: --bogus"));
+    /// assert!(report.counts.is_none() && report.options_hash.is_none());
+    /// ```
+    pub fn failed(
+        exit_code: i32,
+        error: &str,
+        options: Option<&AnalysisOptions>,
+        elapsed_ms: Option<u64>,
+    ) -> Self {
+        let mut report = Self::new(RunStatus::Failed, exit_code, Some(error.to_string()));
+        report.options_hash = options.map(options_hash);
+        report.warnings = options.map(AnalysisOptions::warnings).unwrap_or_default();
+        report.timing.elapsed_ms = elapsed_ms;
+        report
+    }
+
+    fn new(status: RunStatus, exit_code: i32, error: Option<String>) -> Self {
+        Self {
+            schema: REPORT_SCHEMA_ID.to_string(),
+            schema_version: REPORT_SCHEMA_VERSION,
+            status,
+            exit_code,
+            error,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            run_id: None,
+            tokenizer: None,
+            options_hash: None,
+            timing: RunTiming {
+                analyzed_at: None,
+                elapsed_ms: None,
+                reported_at: metadata::now_rfc3339(),
+            },
+            counts: None,
+            warnings: Vec::new(),
+            file_errors: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// 以缩进排版的 JSON 原子写出
+    pub fn write(&self, path: &Path) -> Result<(), WriteError> {
+        write_atomic(path, |w| Ok(serde_json::to_writer_pretty(w, self)?))
+    }
+}
+
+/// 分析选项的哈希：序列化后的 SHA-256（十六进制），选项相同的两次运行哈希相同
+pub fn options_hash(options: &AnalysisOptions) -> String {
+    let bytes = serde_json::to_vec(options).unwrap_or_default();
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn skipped_files(metadata: &CorpusMetadata) -> usize {
+    metadata
+        .screened_files
+        .iter()
+        .filter(|f| f.action == ScreenMode::Skip)
+        .count()
+}
+
+/// 汇集元数据中各处记录的提示：元数据的 warnings，其后是语言筛查、过滤、清洗的非有限值与不一致的条目
+pub fn collect_warnings(metadata: &CorpusMetadata) -> Vec<String> {
+    let mut warnings = metadata.warnings.clone();
+    let skipped = skipped_files(metadata);
+    if skipped > 0 {
+        warnings.push(format!(
+            "语言筛查跳过了 {skipped} 个文件，见 screened_files"
+        ));
+    }
+    let whitespace = metadata.screened_files.len() - skipped;
+    if whitespace > 0 {
+        warnings.push(format!(
+            "{whitespace} 个文件经语言筛查改用空白分词，见 screened_files"
+        ));
+    }
+    for effect in &metadata.filter_report.filters {
+        if effect.tokens_removed > 0 {
+            warnings.push(format!(
+                "过滤 {} 移除了 {} 个词元（{} 个词型），见 filter_report",
+                effect.filter, effect.tokens_removed, effect.types_removed
+            ));
+        }
+    }
+    if metadata.inconsistent_entries > 0 {
+        warnings.push(format!(
+            "{} 个条目的各部分词频与总频次不一致，见 inconsistent_entries",
+            metadata.inconsistent_entries
+        ));
+    }
+    if metadata.sanitized_values > 0 {
+        let fields: Vec<String> = metadata
+            .sanitized_by_field
+            .iter()
+            .map(|(field, n)| format!("{field}: {n}"))
+            .collect();
+        warnings.push(format!(
+            "{} 个指标值不是有限数，已写为空（{}）",
+            metadata.sanitized_values,
+            fields.join(", ")
+        ));
+    }
+    warnings
+}
+
+/// 运行报告的 JSON Schema，由上述类型生成
+pub fn report_schema() -> RootSchema {
+    schemars::schema_for!(RunReport)
+}
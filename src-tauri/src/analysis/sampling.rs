@@ -0,0 +1,111 @@
+// sampling.rs
+// 语料抽样：方法试点时只分析语料的随机样本，按文件比例、文件数或词次预算抽取
+//
+// 抽样在分析开始前完成，进度与语料部分都只涉及样本。输入文件先按路径排序（与输入顺序、目录展开顺序无关），
+// 再以分析种子推出的抽样种子的 SplitMix64 洗牌（SamplingOptions::seed 选择其中的序列），按比例或文件数取前若干个；词次预算则按洗牌后的顺序逐个读取文件、以
+// partition_advice::estimate_tokens 估算词次，累计达到预算为止（最后一个文件使总数略超预算）。
+// 样本仍按原输入顺序分析；序列编号与抽中的文件记入 metadata.sample，分析种子记入 metadata.seed，
+// 同一组文件与种子在任何平台上抽出相同的样本。
+
+use std::fs;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{bootstrap::SplitMix64, partition_advice::estimate_tokens, paths};
+
+/// 样本大小
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleSize {
+    /// 文件数的比例，取值 (0, 1]；四舍五入后至少一个文件
+    Fraction(f64),
+    /// 文件数
+    Files(usize),
+    /// 估算词次的预算
+    Tokens(u64),
+}
+
+/// 抽样选项
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingOptions {
+    pub size: SampleSize,
+    /// 序列编号：同一分析种子下换一个编号即抽出另一个样本
+    pub seed: u64,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        Self {
+            size: SampleSize::Fraction(0.1),
+            seed: 0,
+        }
+    }
+}
+
+/// 实际抽得的样本，记入元数据
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusSample {
+    pub seed: u64,
+    pub size: SampleSize,
+    /// 抽样前的文件数
+    pub input_files: usize,
+    /// 抽中的文件，按分析顺序
+    pub files: Vec<String>,
+    /// 按词次预算抽样时样本的估算词次
+    pub estimated_tokens: Option<u64>,
+}
+
+/// 按选项抽取文件，返回样本（按原输入顺序）与抽样记录；seed 为抽样种子（分析种子经 seed::derive_seed 推出）
+pub fn sample_files(
+    file_paths: &[PathBuf],
+    options: &SamplingOptions,
+    seed: u64,
+) -> (Vec<PathBuf>, CorpusSample) {
+    let mut order: Vec<usize> = (0..file_paths.len()).collect();
+    order.sort_by(|&a, &b| file_paths[a].cmp(&file_paths[b]));
+    let mut rng = SplitMix64::new(seed, options.seed);
+    // Fisher–Yates
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+
+    let total = file_paths.len();
+    let mut estimated_tokens = None;
+    let take = match options.size {
+        SampleSize::Fraction(fraction) => {
+            let count = (fraction.clamp(0.0, 1.0) * total as f64).round() as usize;
+            count.max(1).min(total)
+        }
+        SampleSize::Files(count) => count.min(total),
+        SampleSize::Tokens(budget) => {
+            let mut tokens = 0.0;
+            let mut count = 0;
+            for &i in &order {
+                if tokens >= budget as f64 {
+                    break;
+                }
+                // 无法读取的文件照常抽入，由分析记入 file_errors
+                if let Ok(content) = fs::read_to_string(&file_paths[i]) {
+                    tokens += estimate_tokens(&content);
+                }
+                count += 1;
+            }
+            estimated_tokens = Some(tokens as u64);
+            count
+        }
+    };
+    let mut chosen = order[..take].to_vec();
+    chosen.sort_unstable();
+    let sample: Vec<PathBuf> = chosen.iter().map(|&i| file_paths[i].clone()).collect();
+    let record = CorpusSample {
+        seed: options.seed,
+        size: options.size,
+        input_files: total,
+        files: sample.iter().map(|path| paths::display(path)).collect(),
+        estimated_tokens,
+    };
+    (sample, record)
+}
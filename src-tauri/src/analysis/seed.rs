@@ -0,0 +1,62 @@
+// seed.rs
+// 分析种子：每次分析一个种子并记入元数据，各项用到随机数的功能由它与功能标签推出各自的种子
+//
+// 未指定 options.seed 时生成一个种子。以元数据中的种子、相同的输入与选项重新分析，试点抽样与例句采样的
+// 结果相同；结果上的自助法与顺序自检未指定种子时也由该种子推出，导出逐字节一致。
+// 生成的种子不超过 2^53，前端以 JSON 数字往返时不丢精度。
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 试点抽样
+pub const SAMPLING: &str = "sampling";
+/// 例句的蓄水池抽样
+pub const EXAMPLES: &str = "examples";
+/// 语料层面的自助法
+pub const BOOTSTRAP: &str = "bootstrap";
+/// 部分顺序无关性自检的随机置换
+pub const ORDER_INVARIANCE: &str = "order_invariance";
+
+/// 生成的种子的上限（不含）
+const GENERATED_LIMIT: u64 = 1 << 53;
+
+/// 生成一个新的分析种子
+///
+/// ```
+/// use betawordlist_lib::analysis::seed::generate_seed;
+///
+/// let seed = generate_seed();
+/// assert!(seed < 1 << 53);
+/// assert_ne!(seed, generate_seed());
+/// ```
+pub fn generate_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    // 每个 RandomState 的密钥各不相同，以此代替随机数源
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    hasher.finish() % GENERATED_LIMIT
+}
+
+/// 由分析种子与功能标签推出该功能的种子：标签按 FNV-1a 哈希后与种子混合，再经 SplitMix64 的终结函数打散
+///
+/// ```
+/// use betawordlist_lib::analysis::seed::{derive_seed, BOOTSTRAP, SAMPLING};
+///
+/// assert_eq!(derive_seed(42, SAMPLING), derive_seed(42, SAMPLING));
+/// assert_ne!(derive_seed(42, SAMPLING), derive_seed(42, BOOTSTRAP));
+/// assert_ne!(derive_seed(42, SAMPLING), derive_seed(43, SAMPLING));
+/// ```
+pub fn derive_seed(seed: u64, feature: &str) -> u64 {
+    let tag = feature
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let mut z = seed.wrapping_add(tag.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
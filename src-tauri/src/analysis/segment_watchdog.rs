@@ -0,0 +1,85 @@
+// segment_watchdog.rs
+// 分词看门狗：在作用域线程中执行分词，单次调用超过时限或分析被取消时不再等待，流水线继续处理后续文本
+//
+// ltp 的 predict 无法中途打断，被放弃的调用仍在作用域线程中运行到自行结束。线程只持有分词器的共享引用
+// 与自己的文本副本，结果发往已无人接收的通道即被丢弃，不触及计数等共享状态；分词中 panic 同样只丢弃该批。
+// 作用域在 analyze_corpus 返回前汇合这些线程，因此返回可能晚于最后一个文件处理完毕。
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::Scope;
+use std::time::{Duration, Instant};
+
+use crate::analysis::nlp::Tokenizer;
+
+/// 等待期间检查取消的间隔
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+/// 一批文本的分词结果
+#[derive(Debug)]
+pub enum Segmented {
+    /// 按输入顺序逐句的 (词, 词性)
+    Done(Vec<Vec<(String, String)>>),
+    /// 超过时限，已放弃
+    TimedOut,
+    /// 分词中 panic，已放弃
+    Failed,
+    /// 等待期间分析被取消
+    Cancelled,
+}
+
+/// 分词看门狗
+pub struct SegmentWatchdog<'scope, 'env> {
+    scope: &'scope Scope<'scope, 'env>,
+    nlp: &'env dyn Tokenizer,
+    timeout: Duration,
+}
+
+impl<'scope, 'env> SegmentWatchdog<'scope, 'env> {
+    pub fn new(
+        scope: &'scope Scope<'scope, 'env>,
+        nlp: &'env dyn Tokenizer,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            scope,
+            nlp,
+            timeout,
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// 在工作线程中调用 segment_pos_batch，至多等待 timeout；cancelled 返回 true 时提前放弃
+    pub fn segment_pos_batch(&self, texts: &[&str], cancelled: &dyn Fn() -> bool) -> Segmented {
+        let (sender, receiver) = mpsc::channel();
+        let texts: Vec<String> = texts.iter().map(|text| text.to_string()).collect();
+        let nlp = self.nlp;
+        self.scope.spawn(move || {
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| nlp.segment_pos_batch(&texts)));
+            if let Ok(sentences) = result {
+                // 已被放弃时接收端不在，结果直接丢弃
+                sender.send(sentences).ok();
+            }
+        });
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_timeout(remaining.min(CANCEL_POLL)) {
+                Ok(sentences) => return Segmented::Done(sentences),
+                Err(RecvTimeoutError::Disconnected) => return Segmented::Failed,
+                Err(RecvTimeoutError::Timeout) => {
+                    if cancelled() {
+                        return Segmented::Cancelled;
+                    }
+                    if Instant::now() >= deadline {
+                        return Segmented::TimedOut;
+                    }
+                }
+            }
+        }
+    }
+}
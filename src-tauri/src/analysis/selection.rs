@@ -0,0 +1,184 @@
+// selection.rs
+// 按选择导出：只导出界面中筛选或勾选的条目，沿用批量导出的各格式
+//
+// 所选条目以稳定 id 或 (词形, 词性) 给出，按给出的顺序导出；结果中找不到的标识逐一报告，不会被悄悄略去。
+// 也可以直接给出分页所用的筛选条件（EntryQuery），由后端导出筛选后的全部条目，前端无需传送成千上万个 id。
+// 元数据仍描述整个语料，另在 warnings 中注明只导出了所选条目；排名列在导出的条目中计算。
+// 竖排格式按元数据重新分词、写出整个语料，不适用于所选条目：不交给批量导出，直接在报告末尾注明拒绝。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    batch_export::{self, BatchOptions, FormatReport},
+    corpus_pipeline::AnalysisResult,
+    entry_flags,
+    length::EntryQuery,
+    word_entry::WordEntry,
+};
+
+/// 条目的标识：稳定 id（见 entry_id），或词形与词性
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EntryKey {
+    Id(String),
+    WordPos { word: String, pos: String },
+}
+
+/// 要导出的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Selection {
+    /// 按给出的顺序导出这些条目；重复的标识只导出一次
+    Keys(Vec<EntryKey>),
+    /// 导出满足筛选条件的全部条目，顺序同结果；offset、limit 与 ranks 不起作用
+    Query(EntryQuery),
+}
+
+impl Selection {
+    /// 校验筛选条件中的标记名
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Selection::Keys(_) => Ok(()),
+            Selection::Query(query) => entry_flags::validate_names(&query.flags),
+        }
+    }
+}
+
+/// 选出的条目：只含所选条目的结果，以及结果中找不到的标识
+#[derive(Debug, Clone)]
+pub struct Selected {
+    pub result: AnalysisResult,
+    pub unknown: Vec<EntryKey>,
+}
+
+/// 选出条目，组成只含这些条目的结果
+pub fn select(result: &AnalysisResult, selection: &Selection) -> Selected {
+    let (entries, unknown) = match selection {
+        Selection::Keys(keys) => select_keys(&result.entries, keys),
+        Selection::Query(query) => (
+            result
+                .entries
+                .iter()
+                .filter(|e| query.matches(e))
+                .cloned()
+                .collect(),
+            Vec::new(),
+        ),
+    };
+    let mut metadata = result.metadata.clone();
+    metadata.warnings.push(format!(
+        "仅导出所选的 {} 个条目（结果共 {} 个）",
+        entries.len(),
+        result.entries.len()
+    ));
+    Selected {
+        // 各部分词频与计数对应全部条目，不随所选条目保留
+        result: AnalysisResult {
+            entries,
+            metadata,
+            parts: Vec::new(),
+            counts: None,
+        },
+        unknown,
+    }
+}
+
+fn select_keys(entries: &[WordEntry], keys: &[EntryKey]) -> (Vec<WordEntry>, Vec<EntryKey>) {
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    let mut by_word_pos: HashMap<(&str, &str), usize> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        by_id.entry(entry.id.as_str()).or_insert(i);
+        by_word_pos
+            .entry((entry.word.as_str(), entry.pos.as_str()))
+            .or_insert(i);
+    }
+    let mut chosen = vec![false; entries.len()];
+    let mut selected = Vec::new();
+    let mut unknown = Vec::new();
+    for key in keys {
+        let found = match key {
+            EntryKey::Id(id) => by_id.get(id.as_str()),
+            EntryKey::WordPos { word, pos } => by_word_pos.get(&(word.as_str(), pos.as_str())),
+        };
+        match found {
+            Some(&i) => {
+                if !std::mem::replace(&mut chosen[i], true) {
+                    selected.push(entries[i].clone());
+                }
+            }
+            None => unknown.push(key.clone()),
+        }
+    }
+    (selected, unknown)
+}
+
+/// 按选择导出的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionReport {
+    /// 导出的条目数
+    pub exported: usize,
+    /// 结果中找不到、未导出的标识
+    pub unknown: Vec<EntryKey>,
+    /// 各格式的导出结果，同 export_results
+    pub formats: Vec<FormatReport>,
+}
+
+/// 只导出所选条目到 <path_base>.<扩展名>，格式与选项同 batch_export::export_batch；
+/// 没有可导出的条目时不写出任何文件
+pub fn export_selection(
+    result: &AnalysisResult,
+    selection: &Selection,
+    path_base: &Path,
+    formats: &[String],
+    options: &BatchOptions,
+) -> Result<SelectionReport, String> {
+    selection.validate()?;
+    let Selected { result, unknown } = select(result, selection);
+    if result.entries.is_empty() {
+        return Err(if unknown.is_empty() {
+            "没有可导出的条目".to_string()
+        } else {
+            format!("所选条目均不在结果中: {}", describe(&unknown))
+        });
+    }
+    let (vertical, formats): (Vec<String>, Vec<String>) =
+        formats.iter().cloned().partition(|name| {
+            matches!(
+                name.trim().to_ascii_lowercase().as_str(),
+                "vertical" | "vrt"
+            )
+        });
+    let mut formats_report =
+        batch_export::export_batch(&result, None, path_base, &formats, options);
+    if let Some(name) = vertical.first() {
+        formats_report.push(FormatReport {
+            format: name.trim().to_ascii_lowercase(),
+            written: Vec::new(),
+            error: Some("竖排格式写出整个语料，不适用于所选条目".to_string()),
+        });
+    }
+    Ok(SelectionReport {
+        exported: result.entries.len(),
+        unknown,
+        formats: formats_report,
+    })
+}
+
+/// 标识列表的说明，最多列出前 10 个
+fn describe(keys: &[EntryKey]) -> String {
+    let mut shown: Vec<String> = keys
+        .iter()
+        .take(10)
+        .map(|key| match key {
+            EntryKey::Id(id) => id.clone(),
+            EntryKey::WordPos { word, pos } => format!("{word}/{pos}"),
+        })
+        .collect();
+    if keys.len() > 10 {
+        shown.push(format!("等 {} 个", keys.len()));
+    }
+    shown.join(", ")
+}
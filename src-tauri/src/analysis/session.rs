@@ -0,0 +1,26 @@
+// session.rs
+// 会话文件：将完整分析结果（词表与元数据）保存为 JSON，便于日后比较或重新导出
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+use crate::analysis::atomic::{write_atomic, WriteError};
+use crate::analysis::corpus_pipeline::AnalysisResult;
+use crate::analysis::{entry_flags, entry_id};
+
+/// 保存分析结果为会话文件（原子写入，保存中途失败不会损坏已有的会话文件）
+pub fn save(path: &Path, result: &AnalysisResult) -> Result<(), WriteError> {
+    write_atomic(path, |w| Ok(serde_json::to_writer(w, result)?))
+}
+
+/// 读取会话文件；早于条目 id 的会话文件在载入时补上 id 与规范化签名，条目标记在载入时重新计算
+pub fn load(path: &Path) -> io::Result<AnalysisResult> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut result: AnalysisResult = serde_json::from_reader(reader)?;
+    if result.metadata.normalization_signature.is_empty() {
+        entry_id::assign(&mut result);
+    }
+    entry_flags::assign(&mut result);
+    Ok(result)
+}
@@ -0,0 +1,146 @@
+// spectrum.rs
+// 频次谱 V(m) 与 Simple Good-Turing 频次平滑（Gale & Sampson, 1995）
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// 频次谱：恰好出现 m 次的类型数 V(m)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FrequencySpectrum {
+    /// (m, V(m))，仅列出 m ≤ cap 且 V(m) > 0 的项，按 m 升序
+    pub spectrum: Vec<(u64, u64)>,
+    /// 频次大于 cap 的类型数
+    pub tail_types: u64,
+    /// 频次大于 cap 的类型的词次总和，使 Σ m·V(m) + tail_tokens 等于总词次
+    pub tail_tokens: u64,
+    pub total_types: u64,
+    pub total_tokens: u64,
+}
+
+/// 按 (m, V(m)) 统计全部频次，m 升序
+fn frequency_counts(frequencies: impl IntoIterator<Item = f64>) -> BTreeMap<u64, u64> {
+    let mut counts = BTreeMap::new();
+    for f in frequencies {
+        let m = f.round() as u64;
+        if m > 0 {
+            *counts.entry(m).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// 计算频次谱，m > cap 的类型归入尾部桶
+pub fn frequency_spectrum(
+    frequencies: impl IntoIterator<Item = f64>,
+    cap: u64,
+) -> FrequencySpectrum {
+    let mut result = FrequencySpectrum::default();
+    for (m, v) in frequency_counts(frequencies) {
+        result.total_types += v;
+        result.total_tokens += m * v;
+        if m <= cap {
+            result.spectrum.push((m, v));
+        } else {
+            result.tail_types += v;
+            result.tail_tokens += m * v;
+        }
+    }
+    result
+}
+
+/// Simple Good-Turing：返回 频次 r → 平滑后频次 的映射
+///
+/// 平滑后频次已归一化，使所有已见类型的总量为 (1 - P0)·N，其中 P0 = V(1)/N 为未见类型的概率质量。
+/// 不同频次少于两种、或不存在 hapax 时无法拟合，返回 None。
+///
+/// log Z 对 log r 的拟合斜率不小于 -1 时拟合值不可用（Gale & Sampson 要求斜率 < -1），
+/// 全部改用 Turing 估计 (r+1)·V(r+1)/V(r)；V(r+1) 为 0 的 r 无法估计，保留原频次。
+pub fn simple_good_turing(frequencies: impl IntoIterator<Item = f64>) -> Option<HashMap<u64, f64>> {
+    let counts: Vec<(u64, u64)> = frequency_counts(frequencies).into_iter().collect();
+    if counts.len() < 2 {
+        return None;
+    }
+    let n_of = |r: u64| {
+        counts
+            .binary_search_by_key(&r, |&(m, _)| m)
+            .map_or(0.0, |i| counts[i].1 as f64)
+    };
+    let big_n: f64 = counts.iter().map(|&(r, n)| (r * n) as f64).sum();
+    let p0 = n_of(1) / big_n;
+    if p0 <= 0.0 {
+        return None;
+    }
+
+    // Z_r = n_r / (0.5·(t - q))，q、t 为相邻的非零频次
+    let log_points: Vec<(f64, f64)> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &(r, n))| {
+            let q = if i == 0 { 0.0 } else { counts[i - 1].0 as f64 };
+            let t = counts
+                .get(i + 1)
+                .map_or(2.0 * r as f64 - q, |&(next, _)| next as f64);
+            let z = n as f64 / (0.5 * (t - q));
+            ((r as f64).ln(), z.ln())
+        })
+        .collect();
+
+    // 最小二乘拟合 log Z = a + b·log r
+    let k = log_points.len() as f64;
+    let mean_x = log_points.iter().map(|p| p.0).sum::<f64>() / k;
+    let mean_y = log_points.iter().map(|p| p.1).sum::<f64>() / k;
+    let sxx: f64 = log_points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = log_points
+        .iter()
+        .map(|p| (p.0 - mean_x) * (p.1 - mean_y))
+        .sum();
+    if sxx <= 0.0 {
+        return None;
+    }
+    let b = sxy / sxx;
+    let a = mean_y - b * mean_x;
+    let smoothed = |r: f64| (a + b * r.ln()).exp();
+    let turing = |r: u64, n: u64| {
+        let n_next = n_of(r + 1);
+        (n_next > 0.0).then(|| (r as f64 + 1.0) * n_next / n as f64)
+    };
+
+    let mut r_star: Vec<(u64, f64)> = Vec::with_capacity(counts.len());
+    if b >= -1.0 {
+        r_star.extend(
+            counts
+                .iter()
+                .map(|&(r, n)| (r, turing(r, n).unwrap_or(r as f64))),
+        );
+    } else {
+        // 先用 Turing 估计，二者差异不再显著后切换到拟合值（LGT），且不再切回
+        let mut use_lgt = false;
+        for &(r, n) in &counts {
+            let rf = r as f64;
+            let lgt = (rf + 1.0) * smoothed(rf + 1.0) / smoothed(rf);
+            if let Some(turing) = turing(r, n).filter(|_| !use_lgt) {
+                let (nf, n_next) = (n as f64, n_of(r + 1));
+                let sd = ((rf + 1.0).powi(2) * (n_next / nf.powi(2)) * (1.0 + n_next / nf)).sqrt();
+                if (lgt - turing).abs() > 1.96 * sd {
+                    r_star.push((r, turing));
+                    continue;
+                }
+            }
+            use_lgt = true;
+            r_star.push((r, lgt));
+        }
+    }
+
+    // 归一化：已见类型的概率和为 1 - P0，再换算回频次
+    let n_prime: f64 = r_star.iter().map(|&(r, rs)| n_of(r) * rs).sum();
+    if n_prime <= 0.0 || !n_prime.is_finite() {
+        return None;
+    }
+    Some(
+        r_star
+            .into_iter()
+            .map(|(r, rs)| (r, (1.0 - p0) * rs / n_prime * big_n))
+            .collect(),
+    )
+}
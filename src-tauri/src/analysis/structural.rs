@@ -0,0 +1,202 @@
+// structural.rs
+// 结构性噪声过滤：章节标题、罗马数字与页码等排版成分，在小说类语料中会挤占中频段
+//
+// 规则作用于分词后的有序词元流，跨词元匹配（"第一章" 可能被切成 第 / 一 / 章），须在计数之前进行。
+// 行首信息由词元在原文中的位置还原；被移除的词元不计入部分词数。
+
+use crate::analysis::{text, token_policy::PUNCTUATION_POS};
+
+/// 章节标题最多跨越的词元数（第 / 十 / 二 / 章）
+const HEADING_MAX_TOKENS: usize = 6;
+
+/// 章节序号中的数字：中文小写与大写数字、半角与全角阿拉伯数字
+const HEADING_NUMERALS: &str =
+    "零〇一二两三四五六七八九十百千万壹贰叁肆伍陆柒捌玖拾佰仟0123456789０１２３４５６７８９";
+
+/// 章节标题的结尾；"第一回" 也有 "第一次" 的意思，以 回 结尾的只在行首时视为标题
+const HEADING_SUFFIXES: [char; 3] = ['章', '节', '回'];
+
+/// 一段被移除的结构性噪声
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseSequence {
+    /// 拼接后的文本，如 "第一章"
+    pub text: String,
+    /// 命中的规则：chapter_heading / roman_numeral / page_number
+    pub rule: &'static str,
+    /// 移除的词元数
+    pub tokens: usize,
+}
+
+/// 移除结构性噪声，返回余下的词元流与被移除的序列（按出现顺序）
+///
+/// - 第 + 数字 + 章/节/回，可跨词元；以 回 结尾的须位于行首
+/// - 罗马数字：Ⅳ 等专用字符随处移除；拉丁字母写成的（IV）须独占一行，两侧可带标点
+/// - 页码：独占一行的数字，两侧可带标点（如 "- 12 -"）
+pub fn strip(
+    text: &str,
+    word_pos: Vec<(String, String)>,
+) -> (Vec<(String, String)>, Vec<NoiseSequence>) {
+    let starts = line_starts(text, &word_pos);
+    let mut spans: Vec<(usize, usize, &'static str)> = Vec::new();
+    let mut i = 0;
+    while i < word_pos.len() {
+        let found = heading_end(&word_pos, i, starts[i])
+            .map(|end| (end, "chapter_heading"))
+            .or_else(|| is_roman_symbol(&word_pos[i].0).then_some((i + 1, "roman_numeral")))
+            .or_else(|| {
+                starts[i]
+                    .then(|| numbered_line(&word_pos, &starts, i))
+                    .flatten()
+            });
+        match found {
+            Some((end, rule)) => {
+                spans.push((i, end, rule));
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+    if spans.is_empty() {
+        return (word_pos, Vec::new());
+    }
+
+    let removed = spans
+        .iter()
+        .map(|&(start, end, rule)| NoiseSequence {
+            text: text::join_tokens(
+                word_pos[start..end].iter().map(|(w, _)| w.as_str()),
+                usize::MAX,
+            ),
+            rule,
+            tokens: end - start,
+        })
+        .collect();
+    let mut spans = spans.into_iter().peekable();
+    let kept = word_pos
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| {
+            while spans.next_if(|&(_, end, _)| end <= *i).is_some() {}
+            spans.peek().is_none_or(|&(start, _, _)| *i < start)
+        })
+        .map(|(_, token)| token)
+        .collect();
+    (kept, removed)
+}
+
+/// 各词元是否位于行首：按顺序在原文中定位词元，与上一词元之间有换行即为行首；
+/// 在原文中找不到的词元（分词器改写过）视为不在行首
+pub fn line_starts(text: &str, word_pos: &[(String, String)]) -> Vec<bool> {
+    let mut cursor = 0;
+    let mut starts = Vec::with_capacity(word_pos.len());
+    for (i, (word, _)) in word_pos.iter().enumerate() {
+        match text[cursor..].find(word.as_str()) {
+            Some(offset) => {
+                let gap = &text[cursor..cursor + offset];
+                starts.push(i == 0 || gap.contains('\n'));
+                cursor += offset + word.len();
+            }
+            None => starts.push(i == 0),
+        }
+    }
+    starts
+}
+
+/// 从 start 起的章节标题的结束位置（不含）
+fn heading_end(word_pos: &[(String, String)], start: usize, line_start: bool) -> Option<usize> {
+    if !word_pos[start].0.starts_with('第') {
+        return None;
+    }
+    let mut joined = String::new();
+    for (end, (word, _)) in word_pos
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(HEADING_MAX_TOKENS)
+    {
+        joined.push_str(word);
+        let Some(suffix) = joined
+            .chars()
+            .last()
+            .filter(|c| HEADING_SUFFIXES.contains(c))
+        else {
+            continue;
+        };
+        let numeral = &joined['第'.len_utf8()..joined.len() - suffix.len_utf8()];
+        let is_heading = !numeral.is_empty()
+            && numeral.chars().all(|c| HEADING_NUMERALS.contains(c))
+            && (suffix != '回' || line_start);
+        return is_heading.then_some(end + 1);
+    }
+    None
+}
+
+/// 从行首 start 起独占一行的页码或罗马数字：除标点外只有一个数字或罗马数字词元，返回 (行尾, 规则)
+fn numbered_line(
+    word_pos: &[(String, String)],
+    starts: &[bool],
+    start: usize,
+) -> Option<(usize, &'static str)> {
+    let end = (start + 1..word_pos.len())
+        .find(|&j| starts[j])
+        .unwrap_or(word_pos.len());
+    let mut content = word_pos[start..end]
+        .iter()
+        .filter(|(word, pos)| pos != PUNCTUATION_POS && word.chars().any(char::is_alphanumeric));
+    let (word, _) = content.next()?;
+    if content.next().is_some() {
+        return None;
+    }
+    if word
+        .chars()
+        .all(|c| c.is_ascii_digit() || ('０'..='９').contains(&c))
+    {
+        Some((end, "page_number"))
+    } else if is_roman_numeral(word) {
+        Some((end, "roman_numeral"))
+    } else {
+        None
+    }
+}
+
+/// 全由罗马数字专用字符（Ⅰ–ↈ）组成
+fn is_roman_symbol(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| ('\u{2160}'..='\u{2188}').contains(&c))
+}
+
+/// 规范写法的大写罗马数字（1–3999），如 IV、XII、MCMXC
+fn is_roman_numeral(word: &str) -> bool {
+    const DIGITS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut value = 0;
+    let mut rest = word;
+    while let Some(&(v, digit)) = DIGITS.iter().find(|(_, digit)| rest.starts_with(digit)) {
+        value += v;
+        rest = &rest[digit.len()..];
+    }
+    if !rest.is_empty() || value == 0 || value > 3999 {
+        return false;
+    }
+    // 以数值重新书写，与原文一致才是规范写法（排除 IIII、VX 等）
+    let mut canonical = String::new();
+    for (v, digit) in DIGITS {
+        while value >= v {
+            canonical.push_str(digit);
+            value -= v;
+        }
+    }
+    canonical == word
+}
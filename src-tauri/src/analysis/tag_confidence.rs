@@ -0,0 +1,89 @@
+// tag_confidence.rs
+// 词性标注一致度：以同一词形在各词性间的分布近似标注置信度，并列出一致度最低的高频条目供复核
+//
+// ltp-rs 的 POSModel::predict 只返回标签、不给出分数，因此不是模型置信度：一个词形的词元 55/45 分属两个词性时，
+// 两个条目的一致度分别为 0.55 与 0.45。份额按计入词表的词元计算，停用词/词性过滤移除的词元不计入；两遍计数时只含保留下来的词。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{phrases::PHRASE_POS, posgrams::POSGRAM_POS, word_entry::WordEntry};
+
+/// 同一词形的另一种词性标注
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagAlternative {
+    pub pos: String,
+    pub frequency: f64,
+}
+
+/// 一个标注一致度较低的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowConfidenceItem {
+    pub word: String,
+    pub pos: String,
+    pub frequency: f64,
+    /// 见 WordEntry::pos_tag_share
+    pub pos_tag_share: f64,
+    /// 词表中同一词形的其他词性，按频次降序
+    pub alternatives: Vec<TagAlternative>,
+}
+
+/// 各词形在全部词性上的总频次
+pub(crate) fn word_totals(vocab: &HashMap<(String, String), Vec<f64>>) -> HashMap<&str, f64> {
+    let mut totals: HashMap<&str, f64> = HashMap::new();
+    for ((word, _), freqs) in vocab {
+        *totals.entry(word.as_str()).or_default() += freqs.iter().sum::<f64>();
+    }
+    totals
+}
+
+/// 总频次不低于 min_frequency、且不总是标为同一词性的条目，按一致度升序（同一致度按频次降序）取前 limit 个；
+/// 结果未开启 pos_confidence 时返回错误
+pub fn low_confidence_report(
+    entries: &[WordEntry],
+    min_frequency: f64,
+    limit: usize,
+) -> Result<Vec<LowConfidenceItem>, String> {
+    if entries.iter().all(|e| e.pos_tag_share.is_none()) {
+        return Err("结果不含词性标注一致度，请开启 pos_confidence 重新分析".to_string());
+    }
+    let mut by_word: HashMap<&str, Vec<&WordEntry>> = HashMap::new();
+    for entry in entries
+        .iter()
+        .filter(|e| e.pos != PHRASE_POS && e.pos != POSGRAM_POS)
+    {
+        by_word.entry(entry.word.as_str()).or_default().push(entry);
+    }
+    let mut items: Vec<LowConfidenceItem> = entries
+        .iter()
+        .filter(|e| e.frequency >= min_frequency)
+        .filter_map(|e| {
+            let share = e.pos_tag_share.filter(|&share| share < 1.0)?;
+            let mut alternatives: Vec<TagAlternative> = by_word[e.word.as_str()]
+                .iter()
+                .filter(|other| other.pos != e.pos)
+                .map(|other| TagAlternative {
+                    pos: other.pos.clone(),
+                    frequency: other.frequency,
+                })
+                .collect();
+            alternatives.sort_by(|a, b| b.frequency.total_cmp(&a.frequency));
+            Some(LowConfidenceItem {
+                word: e.word.clone(),
+                pos: e.pos.clone(),
+                frequency: e.frequency,
+                pos_tag_share: share,
+                alternatives,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        a.pos_tag_share
+            .total_cmp(&b.pos_tag_share)
+            .then(b.frequency.total_cmp(&a.frequency))
+            .then_with(|| (&a.word, &a.pos).cmp(&(&b.word, &b.pos)))
+    });
+    items.truncate(limit);
+    Ok(items)
+}
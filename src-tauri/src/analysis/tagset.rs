@@ -0,0 +1,100 @@
+// tagset.rs
+// 词性标注集说明：LTP 词性标记对应的中英文名称，供界面提示、过滤选项与导出使用
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::token_policy::EMOJI_POS;
+
+/// LTP 词性标记：(标记, 中文名, 英文名)
+const LTP_TAGS: &[(&str, &str, &str)] = &[
+    ("a", "形容词", "adjective"),
+    ("b", "区别词", "other noun-modifier"),
+    ("c", "连词", "conjunction"),
+    ("d", "副词", "adverb"),
+    ("e", "叹词", "exclamation"),
+    ("g", "语素", "morpheme"),
+    ("h", "前缀", "prefix"),
+    ("i", "成语", "idiom"),
+    ("j", "简称", "abbreviation"),
+    ("k", "后缀", "suffix"),
+    ("m", "数词", "number"),
+    ("n", "普通名词", "general noun"),
+    ("nd", "方位名词", "direction noun"),
+    ("nh", "人名", "person name"),
+    ("ni", "机构名", "organization name"),
+    ("nl", "处所名词", "location noun"),
+    ("ns", "地名", "geographical name"),
+    ("nt", "时间名词", "temporal noun"),
+    ("nz", "其他专名", "other proper noun"),
+    ("o", "拟声词", "onomatopoeia"),
+    ("p", "介词", "preposition"),
+    ("q", "量词", "quantity"),
+    ("r", "代词", "pronoun"),
+    ("u", "助词", "auxiliary"),
+    ("v", "动词", "verb"),
+    ("wp", "标点", "punctuation"),
+    ("ws", "外文", "foreign word"),
+    ("x", "非语素字", "non-lexeme"),
+    ("z", "状态词", "descriptive word"),
+];
+
+/// 分析流程自行标注的伪词性：(标记, 中文名, 英文名)
+const PSEUDO_TAGS: &[(&str, &str, &str)] = &[(EMOJI_POS, "表情符号", "emoji")];
+
+/// 名称语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelLanguage {
+    Zh,
+    En,
+}
+
+/// 一个词性标记的说明
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagInfo {
+    pub tag: String,
+    pub zh: String,
+    pub en: String,
+    /// 是否为标注集中的已知标记
+    pub known: bool,
+}
+
+/// 完整标注集，顺序同 LTP 文档，其后为伪词性
+pub fn tagset() -> Vec<TagInfo> {
+    LTP_TAGS
+        .iter()
+        .chain(PSEUDO_TAGS)
+        .map(|&(tag, _, _)| describe(tag))
+        .collect()
+}
+
+/// 标记说明；未知标记（如新版模型引入的）原样保留，名称记为 "未知 (xx)" / "unknown (xx)"
+pub fn describe(tag: &str) -> TagInfo {
+    match LTP_TAGS
+        .iter()
+        .chain(PSEUDO_TAGS)
+        .find(|(t, _, _)| *t == tag)
+    {
+        Some(&(_, zh, en)) => TagInfo {
+            tag: tag.to_string(),
+            zh: zh.to_string(),
+            en: en.to_string(),
+            known: true,
+        },
+        None => TagInfo {
+            tag: tag.to_string(),
+            zh: format!("未知 ({tag})"),
+            en: format!("unknown ({tag})"),
+            known: false,
+        },
+    }
+}
+
+/// 指定语言的标记名称
+pub fn label(tag: &str, language: LabelLanguage) -> String {
+    let info = describe(tag);
+    match language {
+        LabelLanguage::Zh => info.zh,
+        LabelLanguage::En => info.en,
+    }
+}
@@ -0,0 +1,62 @@
+// targets.rs
+// 目标词模式：只为目标词表中的词建立各部分词频，其余词元仍计入部分词数，分布指标的分母不变
+//
+// 目标词按词形匹配（不区分词性），匹配在词元清理与拉丁字母大小写折叠之后进行，目标词本身也按 latin_case 折叠。
+// 从未出现的目标词以词性 ABSENT_POS、频次 0 的条目返回，指标取各指标在频次为 0 时的定义值。
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use crate::analysis::{filter, options::AnalysisOptions, token_policy};
+
+/// 未出现的目标词条目的词性
+pub const ABSENT_POS: &str = "";
+
+/// 按分析选项构建的目标词表；未设置 target_words 时不限制
+pub struct TargetWords {
+    words: HashSet<String>,
+}
+
+impl TargetWords {
+    /// 未设置目标词表时返回 None
+    pub fn new(options: &AnalysisOptions) -> Option<Self> {
+        if options.target_words.is_empty() {
+            return None;
+        }
+        Some(Self {
+            words: normalized(options).collect(),
+        })
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+/// 按 latin_case 折叠后的目标词，按目标词表顺序，不去重
+fn normalized(options: &AnalysisOptions) -> impl Iterator<Item = String> + '_ {
+    options
+        .target_words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(|word| token_policy::fold_latin(word, options.latin_case).into_owned())
+}
+
+/// 目标词表中不在 counted（已计数的词形）之内的词（折叠后去重），按目标词表顺序
+pub fn absent_targets<'a>(
+    options: &AnalysisOptions,
+    counted: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let present: HashSet<&str> = counted.into_iter().collect();
+    let mut seen = HashSet::new();
+    normalized(options)
+        .filter(|word| !present.contains(word.as_str()) && seen.insert(word.clone()))
+        .collect()
+}
+
+/// 读取目标词表，格式同停用词表：每行一个词，忽略空行与 # 注释行
+pub fn read_target_words(path: &Path) -> io::Result<Vec<String>> {
+    filter::read_stopwords(path)
+}
@@ -0,0 +1,236 @@
+// text.rs
+// 文本与字符分类等通用工具
+
+use crate::analysis::token_policy::PUNCTUATION_POS;
+
+/// 是否为汉字（CJK 统一表意文字及扩展区、兼容表意文字）
+pub fn is_han(c: char) -> bool {
+    matches!(c,
+        '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+        | '\u{30000}'..='\u{323AF}')
+}
+
+/// 是否为附着在前一字符上的扩展字符（组合附加符号、变体选择符、零宽连接符、肤色修饰符、标签字符）
+pub(crate) fn is_extender(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200D}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{E0020}'..='\u{E007F}'
+        | '\u{E0100}'..='\u{E01EF}')
+}
+
+/// 是否为区域指示符（两两组成旗帜）
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+/// 是否为 emoji 的基字符（近似 Unicode 的 Extended_Pictographic：杂项符号、装饰符号、
+/// 技术符号、箭头与星形，以及 U+1F000 起的各图形符号区与区域指示符）；肤色修饰符等扩展字符不算
+pub fn is_emoji(c: char) -> bool {
+    !is_extender(c)
+        && matches!(c,
+            '\u{2300}'..='\u{23FF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// 是否为 emoji 词元：非空，且每个字（见 graphemes）都以 emoji 基字符开头
+///
+/// ```
+/// use betawordlist_lib::analysis::text::is_emoji_token;
+///
+/// assert!(is_emoji_token("😀"));
+/// assert!(is_emoji_token("👍🏽"));
+/// assert!(is_emoji_token("👩\u{200D}💻❤\u{FE0F}"));
+/// assert!(is_emoji_token("🇨🇳"));
+/// assert!(!is_emoji_token("好😀"));
+/// assert!(!is_emoji_token("。"));
+/// assert!(!is_emoji_token(""));
+/// ```
+pub fn is_emoji_token(word: &str) -> bool {
+    word.chars().next().is_some_and(is_emoji)
+        && graphemes(word)
+            .iter()
+            .all(|g| g.chars().next().is_some_and(is_emoji))
+}
+
+/// 各字的起始字节位置：扩展字符并入前一字符，ZWJ 连接其后的字符，两个区域指示符组成一面旗帜；
+/// 开头的扩展字符自成一字
+fn grapheme_starts(word: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut has_base = false;
+    let mut joined = false;
+    let mut open_flag = false;
+    word.char_indices().filter_map(move |(i, c)| {
+        if has_base && is_extender(c) {
+            joined = c == '\u{200D}';
+            return None;
+        }
+        let regional = is_regional_indicator(c);
+        let paired = regional && open_flag;
+        open_flag = regional && !paired;
+        let continues = joined || paired;
+        joined = false;
+        has_base = true;
+        (!continues).then_some(i)
+    })
+}
+
+/// 词长（字数）：纯汉字即字符数；混合文本中组合符号、变体选择符并入前一字符，ZWJ 连接的序列与旗帜计为一个
+pub fn char_length(word: &str) -> usize {
+    grapheme_starts(word).count()
+}
+
+/// 按 char_length 的规则切出的各字：扩展字符并入前一字符，ZWJ 连接的序列与旗帜为一个字
+///
+/// ```
+/// use betawordlist_lib::analysis::text::{char_length, graphemes};
+///
+/// assert_eq!(graphemes("人工智能"), ["人", "工", "智", "能"]);
+/// // 变体选择符、ZWJ 序列、肤色修饰符与旗帜不拆开；扩展 B 区汉字为一个字
+/// let word = "葛\u{E0100}家👩\u{200D}💻👋🏿🇨🇳🇯🇵𠀀";
+/// assert_eq!(
+///     graphemes(word),
+///     ["葛\u{E0100}", "家", "👩\u{200D}💻", "👋🏿", "🇨🇳", "🇯🇵", "𠀀"]
+/// );
+/// assert_eq!(graphemes(word).len(), char_length(word));
+/// assert_eq!(graphemes("\u{FE0F}甲"), ["\u{FE0F}", "甲"]);
+/// assert!(graphemes("").is_empty());
+/// ```
+pub fn graphemes(word: &str) -> Vec<&str> {
+    let starts: Vec<usize> = grapheme_starts(word).chain([word.len()]).collect();
+    starts.windows(2).map(|w| &word[w[0]..w[1]]).collect()
+}
+
+/// 不超过 max_units 个 UTF-16 码元的最长前缀，只在字的边界截断（Excel 按 UTF-16 码元计长度）
+///
+/// ```
+/// use betawordlist_lib::analysis::text::truncate_utf16;
+///
+/// assert_eq!(truncate_utf16("甲乙丙", 2), "甲乙");
+/// // 扩展 B 区汉字与 emoji 各占两个码元，ZWJ 序列整体保留或整体截去
+/// assert_eq!(truncate_utf16("𠀀𠀁", 3), "𠀀");
+/// assert_eq!(truncate_utf16("甲👩\u{200D}💻", 5), "甲");
+/// assert_eq!(truncate_utf16("甲👩\u{200D}💻", 6), "甲👩\u{200D}💻");
+/// ```
+pub fn truncate_utf16(text: &str, max_units: usize) -> &str {
+    let mut units = 0;
+    let mut end = 0;
+    for grapheme in graphemes(text) {
+        units += grapheme.encode_utf16().count();
+        if units > max_units {
+            return &text[..end];
+        }
+        end += grapheme.len();
+    }
+    text
+}
+
+/// 句末标点：其后开始新句
+const SENTENCE_END: &[&str] = &["。", "！", "？", "!", "?", "；", ";", "…", "……"];
+
+/// 按句末标点把词元流切成句子，句末标点归入前一句；末尾未结束的词元自成一句
+pub fn split_sentences(word_pos: &[(String, String)]) -> impl Iterator<Item = &[(String, String)]> {
+    word_pos.split_inclusive(|(word, pos)| {
+        pos == PUNCTUATION_POS && SENTENCE_END.contains(&word.as_str())
+    })
+}
+
+/// 分词前切句用的句末字符
+const TEXT_SENTENCE_END: &[char] = &['。', '！', '？', '!', '?', '；', ';', '…', '\n'];
+
+/// 紧随句末字符时归入前一句的闭引号与闭括号
+const SENTENCE_CLOSERS: &[char] = &['”', '’', '」', '』', '）', ')'];
+
+/// 分词前按句末字符把原文切成句子：连续的句末字符及其后的闭引号、闭括号归入前一句；
+/// 各句依次相接即为原文，不丢弃任何字符
+pub fn split_text_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut ended = false;
+    for (i, c) in text.char_indices() {
+        if ended && !TEXT_SENTENCE_END.contains(&c) && !SENTENCE_CLOSERS.contains(&c) {
+            sentences.push(&text[start..i]);
+            start = i;
+            ended = false;
+        }
+        ended |= TEXT_SENTENCE_END.contains(&c);
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+/// 句数与段数：分词前由原文统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextUnits {
+    /// 按 split_text_sentences 切分、含非空白字符的句子数；换行亦结束一句
+    pub sentences: u64,
+    /// 含非空白字符的行数，每行为一段
+    pub paragraphs: u64,
+}
+
+impl TextUnits {
+    /// 统计一段文本的句数与段数
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::text::TextUnits;
+    ///
+    /// let units = TextUnits::of("他说：“好。”我们走吧！！\n\n  \n第二段……结束\n");
+    /// assert_eq!((units.sentences, units.paragraphs), (4, 2));
+    /// assert_eq!(TextUnits::of(" \n\t"), TextUnits::default());
+    /// // 没有句末标点的一行为一句
+    /// assert_eq!(TextUnits::of("标题\n正文。").sentences, 2);
+    /// ```
+    pub fn of(text: &str) -> Self {
+        let non_blank = |s: &str| !s.trim().is_empty();
+        Self {
+            sentences: split_text_sentences(text)
+                .into_iter()
+                .filter(|s| non_blank(s))
+                .count() as u64,
+            paragraphs: text.lines().filter(|s| non_blank(s)).count() as u64,
+        }
+    }
+}
+
+/// 把词元拼回文本：相邻两个词元在交界处都是 ASCII 字母或数字时以空格分隔，其余直接相连；
+/// 超过 max_chars 个字（按 char_length 计）时在字的边界截断并加省略号
+///
+/// ```
+/// use betawordlist_lib::analysis::text::join_tokens;
+///
+/// assert_eq!(join_tokens(["我", "用", "AI", "写", "code"], 100), "我用AI写code");
+/// assert_eq!(join_tokens(["a", "b"], 100), "a b");
+/// // 不会截断在 ZWJ 序列或肤色修饰符中间
+/// let words = ["好", "👩\u{200D}💻", "👍🏽", "𠀀"];
+/// assert_eq!(join_tokens(words, 2), "好👩\u{200D}💻…");
+/// assert_eq!(join_tokens(words, 3), "好👩\u{200D}💻👍🏽…");
+/// ```
+pub fn join_tokens<'a>(words: impl IntoIterator<Item = &'a str>, max_chars: usize) -> String {
+    let mut text = String::new();
+    for word in words {
+        let needs_space = text.ends_with(|c: char| c.is_ascii_alphanumeric())
+            && word.starts_with(|c: char| c.is_ascii_alphanumeric());
+        if needs_space {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+    let cut = grapheme_starts(&text).nth(max_chars);
+    if let Some(cut) = cut {
+        text.truncate(cut);
+        text.push('…');
+    }
+    text
+}
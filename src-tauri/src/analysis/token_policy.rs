@@ -0,0 +1,226 @@
+// token_policy.rs
+// 标点、符号与空白词元的处理策略
+//
+// 清理（sanitize）先于其余开关与计数：删去词元中的控制字符与零宽字符，
+// 清理后为空或全为空白（如 U+3000）的词元丢弃，不计入部分词数。
+// 拉丁字母词元的大小写折叠（latin_case）紧随清理之后。
+//
+// emoji 在分词之后、任何开关之前先经 join_emoji 拼合：分词器拆开的 ZWJ 序列、肤色修饰符、变体选择符
+// 与旗帜重新连成一个词元，全由 emoji 组成的词元标为伪词性 emo（EMOJI_POS），可按词性过滤；
+// emo 不是标点，不受标点开关影响，但属于 exclude_symbols 所指的符号词元。清理时 emoji 之间的 ZWJ 保留。
+
+use std::borrow::Cow;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::text;
+
+/// 标点的词性标记（LTP 与规则分词器一致）
+pub const PUNCTUATION_POS: &str = "wp";
+/// emoji 词元的伪词性
+pub const EMOJI_POS: &str = "emo";
+
+/// 词元过滤策略，各开关相互独立；默认全部关闭，与既有结果保持一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TokenPolicy {
+    /// 标点不进入词表
+    pub exclude_punctuation_from_list: bool,
+    /// 标点不计入各部分词数与语料总词数
+    pub exclude_punctuation_from_sizes: bool,
+    /// 未标为标点、但全由符号/emoji/空白组成的词元既不进入词表，也不计入词数
+    pub exclude_symbols: bool,
+    /// 删去词元中的控制字符与零宽字符，丢弃清理后为空或全为空白的词元
+    pub sanitize: bool,
+}
+
+impl TokenPolicy {
+    /// 推荐预设：全部开关开启
+    pub const RECOMMENDED: Self = Self {
+        exclude_punctuation_from_list: true,
+        exclude_punctuation_from_sizes: true,
+        exclude_symbols: true,
+        sanitize: true,
+    };
+
+    /// 按名称取预设（default / recommended）
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "recommended" => Some(Self::RECOMMENDED),
+            _ => None,
+        }
+    }
+}
+
+/// 各开关移除的词元数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TokenPolicyCounts {
+    pub punctuation_from_list: u64,
+    pub punctuation_from_sizes: u64,
+    pub symbols: u64,
+    /// 清理时删去的不可见字符数
+    pub invisible_chars: u64,
+    /// 清理后为空或全为空白而丢弃的词元数
+    pub blank_tokens: u64,
+}
+
+impl TokenPolicyCounts {
+    pub fn add(&mut self, other: &Self) {
+        self.punctuation_from_list += other.punctuation_from_list;
+        self.punctuation_from_sizes += other.punctuation_from_sizes;
+        self.symbols += other.symbols;
+        self.invisible_chars += other.invisible_chars;
+        self.blank_tokens += other.blank_tokens;
+    }
+}
+
+/// 是否为清理时删去的不可见字符：控制字符、零宽字符与方向标记、BOM（U+FEFF）
+pub fn is_invisible_char(c: char) -> bool {
+    c.is_control() || matches!(c, '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}')
+}
+
+/// 拼合分词器拆开的 emoji：以扩展字符（ZWJ、变体选择符、肤色修饰符、组合符号等）开头的词元并入前一词元，
+/// 前一词元以 ZWJ 结尾时下一个 emoji 并入，未成对的区域指示符与下一个区域指示符并成旗帜；
+/// 之后全由 emoji 组成的词元标为 EMOJI_POS
+pub fn join_emoji(word_pos: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut joined: Vec<(String, String)> = Vec::with_capacity(word_pos.len());
+    for (word, pos) in word_pos {
+        match joined.last_mut() {
+            Some((previous, _)) if continues_token(previous, &word) => previous.push_str(&word),
+            _ => joined.push((word, pos)),
+        }
+    }
+    for (word, pos) in &mut joined {
+        if pos != EMOJI_POS && text::is_emoji_token(word) {
+            *pos = EMOJI_POS.to_string();
+        }
+    }
+    joined
+}
+
+/// next 是否整体并入 previous 的最后一个字
+fn continues_token(previous: &str, next: &str) -> bool {
+    // 绝大多数词元不以扩展字符或 emoji 开头，不必切字
+    let starts_joinable = next
+        .chars()
+        .next()
+        .is_some_and(|c| text::is_extender(c) || text::is_emoji(c));
+    if !starts_joinable || previous.is_empty() {
+        return false;
+    }
+    let last = text::graphemes(previous).pop().unwrap_or_default();
+    text::char_length(&format!("{last}{next}")) == 1
+}
+
+/// 清理词元流：删去不可见字符，丢弃清理后为空或全为空白的词元，删去的字符与词元计入 counts；
+/// 连接两个 emoji 的 ZWJ 属于 emoji 序列，不删去
+pub fn sanitize(
+    word_pos: Vec<(String, String)>,
+    counts: &mut TokenPolicyCounts,
+) -> Vec<(String, String)> {
+    word_pos
+        .into_iter()
+        .filter_map(|(w, p)| {
+            let chars: Vec<char> = w.chars().collect();
+            let removed = |i: usize| {
+                let c = chars[i];
+                let in_emoji = c == '\u{200D}'
+                    && i > 0
+                    && chars.get(i + 1).is_some_and(|&next| text::is_emoji(next))
+                    && chars[..i]
+                        .iter()
+                        .rev()
+                        .find(|&&c| !text::is_extender(c))
+                        .is_some_and(|&c| text::is_emoji(c));
+                is_invisible_char(c) && !in_emoji
+            };
+            let invisible = (0..chars.len()).filter(|&i| removed(i)).count();
+            let w = if invisible == 0 {
+                w
+            } else {
+                counts.invisible_chars += invisible as u64;
+                (0..chars.len())
+                    .filter(|&i| !removed(i))
+                    .map(|i| chars[i])
+                    .collect()
+            };
+            if w.trim().is_empty() {
+                counts.blank_tokens += 1;
+                return None;
+            }
+            Some((w, p))
+        })
+        .collect()
+}
+
+/// 是否为纯符号词元：不含任何字母、数字或汉字（空白亦视为符号）
+pub fn is_symbol_token(word: &str) -> bool {
+    !word.is_empty() && !word.chars().any(char::is_alphanumeric)
+}
+
+/// 拉丁字母词元（见 is_latin_token）的大小写处理；汉字词与中英混合词元不受影响
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LatinCase {
+    /// 保留原样，"iPhone" 与 "iphone" 为不同条目
+    #[default]
+    Preserve,
+    /// 转为小写后计数
+    Lower,
+    /// 转为小写后计数，并在条目的 surface_forms 中保留各原始写法及其频次
+    Fold,
+}
+
+/// 是否为拉丁字母词元：全由拉丁字母（含带附加符号的字母与全角字母）和数字组成
+pub fn is_latin_token(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| is_latin_letter(c) || c.is_ascii_digit() || matches!(c, '０'..='９'))
+}
+
+/// 是否为拉丁字母，含带附加符号的字母与全角字母
+pub fn is_latin_letter(c: char) -> bool {
+    c.is_ascii_alphabetic()
+        || matches!(c, '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}') && c.is_alphabetic()
+        || matches!(c, 'Ａ'..='Ｚ' | 'ａ'..='ｚ')
+}
+
+/// 按 latin_case 折叠后的写法；非拉丁字母词元与 Preserve 时原样返回。
+/// 全角字母折叠为全角小写，不转为半角，"ＩＰＨＯＮＥ" 与 "iphone" 仍为不同条目
+pub fn fold_latin(word: &str, case: LatinCase) -> Cow<'_, str> {
+    if case == LatinCase::Preserve || !word.chars().any(char::is_uppercase) || !is_latin_token(word)
+    {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Owned(word.to_lowercase())
+    }
+}
+
+/// 折叠词元流中的拉丁字母词元；Fold 时另返回各词元折叠前的写法（非拉丁字母词元为 None），否则为空
+pub fn fold_latin_case(
+    word_pos: Vec<(String, String)>,
+    case: LatinCase,
+) -> (Vec<(String, String)>, Vec<Option<String>>) {
+    if case == LatinCase::Preserve {
+        return (word_pos, Vec::new());
+    }
+    let mut surfaces = Vec::new();
+    let folded = word_pos
+        .into_iter()
+        .map(|(w, p)| {
+            let lowered = match fold_latin(&w, case) {
+                Cow::Owned(lowered) => Some(lowered),
+                Cow::Borrowed(_) => None,
+            };
+            if case == LatinCase::Fold {
+                surfaces.push(is_latin_token(&w).then(|| w.clone()));
+            }
+            (lowered.unwrap_or(w), p)
+        })
+        .collect();
+    (folded, surfaces)
+}
@@ -0,0 +1,131 @@
+// vertical.rs
+// 竖排格式（CQPweb / Sketch Engine 的 .vrt）导出：每行一个词元 word<TAB>pos
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    atomic::{write_atomic, WriteError},
+    corpus_pipeline,
+    metadata::CorpusMetadata,
+    nlp::Tokenizer,
+    paths, text,
+};
+
+/// 竖排导出选项
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct VerticalOptions {
+    /// 每个输入文件写一个 .vrt（path 为目录）；否则全部写入 path 一个文件
+    pub per_file: bool,
+    /// 以 <s> … </s> 标出句子
+    pub sentences: bool,
+    /// 以 <text id="…"> … </text> 标出文件
+    pub texts: bool,
+}
+
+/// 写出一个文本的词元流
+pub fn write_text<W: Write + ?Sized>(
+    writer: &mut W,
+    text_id: &str,
+    word_pos: &[(String, String)],
+    options: &VerticalOptions,
+) -> io::Result<()> {
+    if options.texts {
+        writeln!(writer, "<text id=\"{}\">", escape(text_id, true))?;
+    }
+    let sentences: Vec<&[(String, String)]> = if options.sentences {
+        text::split_sentences(word_pos).collect()
+    } else {
+        vec![word_pos]
+    };
+    for sentence in sentences {
+        if options.sentences {
+            writeln!(writer, "<s>")?;
+        }
+        for (word, pos) in sentence {
+            writeln!(writer, "{}\t{}", escape(word, false), escape(pos, false))?;
+        }
+        if options.sentences {
+            writeln!(writer, "</s>")?;
+        }
+    }
+    if options.texts {
+        writeln!(writer, "</text>")?;
+    }
+    Ok(())
+}
+
+/// 按元数据中记录的文件与选项重新分词并导出，返回写出的文件路径
+///
+/// 分词结果与原分析一致：同一分词器、同一语言筛查，跳过的文件不导出。各文件均原子写入。
+pub fn export(
+    nlp: &dyn Tokenizer,
+    metadata: &CorpusMetadata,
+    path: &Path,
+    options: &VerticalOptions,
+) -> Result<Vec<PathBuf>, WriteError> {
+    // 元数据中的路径已去掉长路径前缀，读取前重新规范化
+    let texts = metadata.files.iter().filter_map(|file| {
+        let source = paths::normalize(Path::new(&file.path));
+        let word_pos = corpus_pipeline::tokenize_file(nlp, &source, &metadata.options)?;
+        let text_id = Path::new(&file.path)
+            .file_stem()
+            .map_or_else(|| file.path.clone(), |s| s.to_string_lossy().to_string());
+        Some((text_id, word_pos))
+    });
+
+    if !options.per_file {
+        write_atomic(path, |w| {
+            for (text_id, word_pos) in texts {
+                write_text(w, &text_id, &word_pos, options)?;
+            }
+            Ok(())
+        })?;
+        return Ok(vec![path.to_path_buf()]);
+    }
+    fs::create_dir_all(path).map_err(|e| WriteError::new(path, e))?;
+    let mut written = Vec::new();
+    for (text_id, word_pos) in texts {
+        let target = unique_path(path, &text_id, &written);
+        write_atomic(&target, |w| write_text(w, &text_id, &word_pos, options))?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// 目录下 <stem>.vrt；不同目录下的同名文件依次加 _2、_3 后缀
+fn unique_path(dir: &Path, stem: &str, taken: &[PathBuf]) -> PathBuf {
+    let mut candidate = dir.join(format!("{stem}.vrt"));
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = dir.join(format!("{stem}_{n}.vrt"));
+        n += 1;
+    }
+    candidate
+}
+
+/// 转义：制表符与换行替换为空格，&、<、> 写为实体，避免与结构标记混淆；属性值另转义引号
+fn escape(value: &str, attribute: bool) -> Cow<'_, str> {
+    let special =
+        |c: char| matches!(c, '\t' | '\n' | '\r' | '&' | '<' | '>') || (attribute && c == '"');
+    if !value.contains(special) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len() + 8);
+    for c in value.chars() {
+        match c {
+            '\t' | '\n' | '\r' => escaped.push(' '),
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
@@ -1,9 +1,77 @@
 // word_analyzer.rs
 // 单词/词性分布指标计算核心，参考 word_analyzer_ref.rs 进行全面实现与注释
+//
+// 各指标的取值约定（由本模块保证）：
+// - Juilland D、Carroll D2、Roschengren S / S_adj、DP、DP_norm、JSD、Hellinger、DA、PT、最高部分占比、基尼系数 ∈ [0, 1]
+// - KL ≥ 0，FT ≥ 0，range ≤ n
+// 部分占比 s_i 按各部分词数之和归一，s 总是一个分布，上述范围由公式本身保证；浮点运算可能带来
+// 1.0000000000000002 或 -2e-16 之类的越界，只把不超过 ROUNDING 的越界收回，更大的越界原样保留，
+// 由 calculate_all_metrics 示例中的性质测试发现。
+// 部分频次与部分词数长度不同时构造即失败；某部分词数为 0、小于频次却有频次，或给出的总词数与
+// 部分词数之和不符时，部分不一致，依赖部分权重的 S / S_adj 返回 None，而不是悄悄丢掉这些项。
 
-use crate::analysis::dispersion_metrics::DispersionMetrics;
+use std::fmt;
+
+use crate::analysis::{dispersion_metrics::DispersionMetrics, options::AnalysisOptions};
 use std::f64::consts::LN_2;
 
+/// 浮点舍入允许的越界量
+const ROUNDING: f64 = 1e-9;
+
+/// 将浮点舍入导致的轻微越界（不超过 ROUNDING）收回 [0, 1]；更大的越界说明公式有误，原样返回
+fn snap_unit(x: f64) -> f64 {
+    if (-ROUNDING..0.0).contains(&x) {
+        0.0
+    } else if x > 1.0 && x <= 1.0 + ROUNDING {
+        1.0
+    } else {
+        x
+    }
+}
+
+/// 逐词计算指标时用到的选项，取自 AnalysisOptions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyzerConfig {
+    /// range_at_threshold 与 pervasiveness_pt_at_threshold 要求的部分内最少出现次数
+    pub range_threshold: f64,
+    /// mean_text_frequency_ft_pm 的缩放基数
+    pub rate_base: f64,
+}
+
+impl Default for AnalyzerConfig {
+    fn default() -> Self {
+        Self::from(&AnalysisOptions::default())
+    }
+}
+
+impl From<&AnalysisOptions> for AnalyzerConfig {
+    fn from(options: &AnalysisOptions) -> Self {
+        Self {
+            range_threshold: options.range_threshold,
+            rate_base: options.rate_base,
+        }
+    }
+}
+
+/// 部分频次向量与部分词数的长度不同
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartLengthMismatch {
+    pub frequencies: usize,
+    pub part_sizes: usize,
+}
+
+impl fmt::Display for PartLengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "部分频次数（{}）与部分词数的个数（{}）不一致",
+            self.frequencies, self.part_sizes
+        )
+    }
+}
+
+impl std::error::Error for PartLengthMismatch {}
+
 /// 语料库单词分布指标分析器
 pub struct CorpusWordAnalyzer {
     pub v: Vec<f64>,
@@ -11,18 +79,56 @@ pub struct CorpusWordAnalyzer {
     f: f64,
     s: Vec<f64>,
     p: Vec<f64>,
+    consistent: bool,
+    config: AnalyzerConfig,
 }
 
 impl CorpusWordAnalyzer {
-    /// 构造函数，预计算 s（各部分占比）和 p（各部分归一化频率）
-    pub fn new(v: Vec<f64>, corpus_part_sizes_words: Vec<f64>, total_corpus_words: f64) -> Self {
+    /// 以默认配置构造
+    ///
+    /// 长度不同时不按较短者截断，而是返回错误：
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::word_analyzer::{CorpusWordAnalyzer, PartLengthMismatch};
+    ///
+    /// let error = CorpusWordAnalyzer::new(vec![1.0, 2.0, 0.0], vec![100.0; 2], 200.0).err();
+    /// assert_eq!(error, Some(PartLengthMismatch { frequencies: 3, part_sizes: 2 }));
+    /// ```
+    pub fn new(
+        v: Vec<f64>,
+        corpus_part_sizes_words: Vec<f64>,
+        total_corpus_words: f64,
+    ) -> Result<Self, PartLengthMismatch> {
+        Self::with_config(
+            v,
+            corpus_part_sizes_words,
+            total_corpus_words,
+            AnalyzerConfig::default(),
+        )
+    }
+
+    /// 构造函数，预计算 s（各部分占全部部分词数的比例）和 p（各部分归一化频率）；v 与 corpus_part_sizes_words 须等长。
+    /// total_corpus_words 应为部分词数之和，不符时 parts_consistent 为 false
+    pub fn with_config(
+        v: Vec<f64>,
+        corpus_part_sizes_words: Vec<f64>,
+        total_corpus_words: f64,
+        config: AnalyzerConfig,
+    ) -> Result<Self, PartLengthMismatch> {
+        if v.len() != corpus_part_sizes_words.len() {
+            return Err(PartLengthMismatch {
+                frequencies: v.len(),
+                part_sizes: corpus_part_sizes_words.len(),
+            });
+        }
         let n = v.len();
         let f = v.iter().sum();
+        let parts_total: f64 = corpus_part_sizes_words.iter().sum();
         let s: Vec<f64> = corpus_part_sizes_words
             .iter()
             .map(|&size| {
-                if total_corpus_words > 0.0 {
-                    size / total_corpus_words
+                if parts_total > 0.0 {
+                    size / parts_total
                 } else {
                     0.0
                 }
@@ -33,7 +139,25 @@ impl CorpusWordAnalyzer {
             .zip(corpus_part_sizes_words.iter())
             .map(|(&freq, &size)| if size > 0.0 { freq / size } else { 0.0 })
             .collect();
-        Self { v, n, f, s, p }
+        let consistent = (total_corpus_words - parts_total).abs()
+            <= ROUNDING * parts_total.max(1.0)
+            && v.iter()
+                .zip(corpus_part_sizes_words.iter())
+                .all(|(&freq, &size)| freq <= size + 1e-9);
+        Ok(Self {
+            v,
+            n,
+            f,
+            s,
+            p,
+            consistent,
+            config,
+        })
+    }
+
+    /// 部分频次与部分词数是否一致：没有词数为 0 或小于频次的部分
+    pub fn parts_consistent(&self) -> bool {
+        self.consistent
     }
 
     /// 范围：出现次数大于0的文本部分数量
@@ -41,6 +165,28 @@ impl CorpusWordAnalyzer {
         self.v.iter().filter(|&&x| x > 1e-9).count()
     }
 
+    /// 出现次数不少于 range_threshold 的文本部分数量；阈值不大于 1 时同 get_range
+    ///
+    /// ```
+    /// use betawordlist_lib::analysis::word_analyzer::{AnalyzerConfig, CorpusWordAnalyzer};
+    ///
+    /// let config = AnalyzerConfig { range_threshold: 2.0, ..Default::default() };
+    /// let v = vec![3.0, 1.0, 2.0, 0.0];
+    /// let analyzer = CorpusWordAnalyzer::with_config(v, vec![100.0; 4], 400.0, config).unwrap();
+    /// assert_eq!((analyzer.get_range(), analyzer.get_range_at_threshold()), (3, 2));
+    /// assert_eq!(analyzer.get_pervasiveness_pt_at_threshold(), Some(0.5));
+    /// let metrics = analyzer.calculate_all_metrics();
+    /// assert_eq!((metrics.range, metrics.range_at_threshold), (3, 2));
+    /// assert_eq!(metrics.pervasiveness_pt, Some(0.75));
+    /// ```
+    pub fn get_range_at_threshold(&self) -> usize {
+        let threshold = self.config.range_threshold;
+        self.v
+            .iter()
+            .filter(|&&x| x > 1e-9 && x >= threshold - 1e-9)
+            .count()
+    }
+
     /// 频次总体标准差
     pub fn get_sd_population(&self) -> Option<f64> {
         if self.n == 0 {
@@ -56,6 +202,9 @@ impl CorpusWordAnalyzer {
 
     /// 频次总体变异系数
     pub fn get_vc_population(&self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
         let mean_v = self.f / self.n as f64;
         if mean_v.abs() < 1e-12 {
             return Some(0.0);
@@ -78,7 +227,7 @@ impl CorpusWordAnalyzer {
         let variance_p = self.p.iter().map(|&x| (x - mean_p).powi(2)).sum::<f64>() / self.n as f64;
         let sd_p = variance_p.sqrt();
         let vc_p = sd_p / mean_p;
-        Some(1.0 - vc_p / ((self.n - 1) as f64).sqrt())
+        Some(snap_unit(1.0 - vc_p / ((self.n - 1) as f64).sqrt()))
     }
 
     /// Carroll's D2（基于熵）
@@ -103,11 +252,26 @@ impl CorpusWordAnalyzer {
             })
             .sum::<f64>();
         let log2_n = (self.n as f64).ln() / LN_2;
-        Some(entropy / (log2_n * LN_2))
+        Some(snap_unit(entropy / (log2_n * LN_2)))
+    }
+
+    /// Roschengren's S（各部分等权 1/n，未按部分大小调整）
+    pub fn get_roschengren_s(&self) -> Option<f64> {
+        if !self.consistent {
+            return None;
+        }
+        if self.f == 0.0 {
+            return Some(0.0);
+        }
+        let sum_sqrt = self.v.iter().map(|&v_i| v_i.sqrt()).sum::<f64>();
+        Some(snap_unit((sum_sqrt * sum_sqrt) / (self.n as f64 * self.f)))
     }
 
     /// Roschengren's S_adj
     pub fn get_roschengren_s_adj(&self) -> Option<f64> {
+        if !self.consistent {
+            return None;
+        }
         if self.f == 0.0 {
             return Some(0.0);
         }
@@ -117,7 +281,7 @@ impl CorpusWordAnalyzer {
             .zip(self.v.iter())
             .map(|(&s_i, &v_i)| (s_i * v_i).sqrt())
             .sum::<f64>();
-        Some((sum_sqrt * sum_sqrt) / self.f)
+        Some(snap_unit((sum_sqrt * sum_sqrt) / self.f))
     }
 
     /// DP（比例偏离度）
@@ -131,7 +295,7 @@ impl CorpusWordAnalyzer {
             .zip(self.s.iter())
             .map(|(&v_i, &s_i)| (v_i / self.f - s_i).abs())
             .sum::<f64>();
-        Some(0.5 * sum_abs_diff)
+        Some(snap_unit(0.5 * sum_abs_diff))
     }
 
     /// DP_norm（标准化DP）
@@ -142,7 +306,7 @@ impl CorpusWordAnalyzer {
         if denom.abs() < 1e-12 {
             return Some(0.0);
         }
-        Some(dp / denom)
+        Some(snap_unit(dp / denom))
     }
 
     /// KL 散度
@@ -164,6 +328,10 @@ impl CorpusWordAnalyzer {
                 }
             })
             .sum::<f64>();
+        // Gibbs 不等式保证 KL ≥ 0，只收回舍入造成的微小负值
+        if (-ROUNDING..0.0).contains(&kl) {
+            return Some(0.0);
+        }
         Some(kl)
     }
 
@@ -192,7 +360,7 @@ impl CorpusWordAnalyzer {
             .fold((0.0, 0.0), |(acc_p, acc_q), (kl_p, kl_q)| (acc_p + kl_p, acc_q + kl_q));
 
         let jsd = 0.5 * (kl_pm + kl_qm);
-        Some(1.0 - (jsd / LN_2).min(1.0))
+        Some(snap_unit(1.0 - jsd / LN_2))
     }
 
     /// Hellinger 分布度
@@ -207,8 +375,8 @@ impl CorpusWordAnalyzer {
             .iter()
             .zip(q_dist.iter())
             .map(|(&p, &q)| (p * q).sqrt())
-            .sum::<f64>()
-            .clamp(0.0, 1.0);
+            .sum::<f64>();
+        let bc = snap_unit(bc);
 
         let hellinger_distance = (1.0 - bc).sqrt();
         Some(1.0 - hellinger_distance)
@@ -231,9 +399,7 @@ impl CorpusWordAnalyzer {
             return Some(if all_same { 1.0 } else { 0.0 });
         }
 
-        let sum_abs_diff = (0..self.n)
-            .flat_map(|i| ((i + 1)..self.n).map(move |j| (self.p[i] - self.p[j]).abs()))
-            .sum::<f64>();
+        let sum_abs_diff = self.sum_abs_rate_diffs();
 
         let num_pairs = (self.n * (self.n - 1)) / 2;
         if num_pairs == 0 {
@@ -241,7 +407,65 @@ impl CorpusWordAnalyzer {
         }
         let avg_abs_diff = sum_abs_diff / num_pairs as f64;
         let da = 1.0 - (avg_abs_diff / (2.0 * mean_p));
-        Some(da.clamp(0.0, 1.0))
+        Some(snap_unit(da))
+    }
+
+    /// 各部分频率两两之差的绝对值之和 Σ_{i<j} |p_i - p_j|
+    ///
+    /// 排序后等于 Σ_k p_(k)·(2k - n + 1)，为 O(n log n)；逐句切分时部分数可达数十万
+    fn sum_abs_rate_diffs(&self) -> f64 {
+        let mut sorted = self.p.clone();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(k, &p)| p * (2.0 * k as f64 - self.n as f64 + 1.0))
+            .sum::<f64>()
+    }
+
+    /// 最高部分占比：频次最高的部分占总频次的比例 max(v_i) / f
+    pub fn get_max_part_share(&self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
+        if self.f == 0.0 {
+            return Some(0.0);
+        }
+        let max = self.v.iter().cloned().fold(0.0, f64::max);
+        Some(snap_unit(max / self.f))
+    }
+
+    /// 各部分频率的基尼系数：Σ_{i<j} |p_i - p_j| / (n · Σp)，0 为完全均匀
+    pub fn get_gini(&self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
+        let sum_p = self.p.iter().sum::<f64>();
+        if self.f == 0.0 || self.n == 1 || sum_p.abs() < 1e-12 {
+            return Some(0.0);
+        }
+        Some(snap_unit(self.sum_abs_rate_diffs() / (self.n as f64 * sum_p)))
+    }
+
+    /// 各部分频率的最小值，不含词数为 0 的部分（读取失败或为空的文件按空部分保留）；没有非空部分时为 None
+    pub fn get_min_part_rate(&self) -> Option<f64> {
+        self.nonempty_rates().map(|(_, p)| p).reduce(f64::min)
+    }
+
+    /// 各部分频率的最大值及其部分序号，同为最大时取靠前的部分；没有非空部分时为 None
+    pub fn get_max_part_rate(&self) -> Option<(usize, f64)> {
+        self.nonempty_rates()
+            .reduce(|best, next| if next.1 > best.1 { next } else { best })
+    }
+
+    /// 词数大于 0 的部分的 (序号, 频率)
+    fn nonempty_rates(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.p
+            .iter()
+            .zip(&self.s)
+            .enumerate()
+            .filter(|(_, (_, &s_i))| s_i > 0.0)
+            .map(|(i, (&p_i, _))| (i, p_i))
     }
 
     /// 平均文本频率（FT）
@@ -260,7 +484,23 @@ impl CorpusWordAnalyzer {
         Some(self.get_range() as f64 / self.n as f64)
     }
 
+    /// 按 range_threshold 计算的普遍度
+    pub fn get_pervasiveness_pt_at_threshold(&self) -> Option<f64> {
+        if self.n == 0 {
+            return None;
+        }
+        Some(self.get_range_at_threshold() as f64 / self.n as f64)
+    }
+
+    /// 各部分的频率按 base 缩放（如每百万词），空部分为 0
+    pub fn get_part_rates(&self, base: f64) -> Vec<f64> {
+        self.p.iter().map(|&p_i| p_i * base).collect()
+    }
+
     /// 计算所有分布指标，返回 DispersionMetrics 结构体
+    ///
+    /// 性质测试：对随机生成的一致输入（频次不超过部分词数），每个指标都落在 metric_schema 给出的取值范围内，
+    /// 且 S / S_adj 不为 None；部分数为 0 的输入丢弃。
     pub fn calculate_all_metrics(&self) -> DispersionMetrics {
         let ft = self.get_mean_text_frequency_ft();
         let pt = self.get_pervasiveness_pt();
@@ -272,12 +512,14 @@ impl CorpusWordAnalyzer {
             juilland_d: self.get_juilland_d(),
             carroll_d2: self.get_carroll_d2(),
             roschengren_s_adj: self.get_roschengren_s_adj(),
+            roschengren_s: self.get_roschengren_s(),
             dp: self.get_dp(),
             dp_norm: self.get_dp_norm(),
             kl_divergence: self.get_kl_divergence(),
             jsd_dispersion: self.get_jsd_dispersion(),
             hellinger_dispersion: self.get_hellinger_dispersion(),
             mean_text_frequency_ft: ft,
+            mean_text_frequency_ft_pm: ft.map(|ft| ft * self.config.rate_base),
             pervasiveness_pt: pt,
             evenness_da: da,
             ft_adjusted_by_pt: match (ft, pt) {
@@ -288,6 +530,15 @@ impl CorpusWordAnalyzer {
                 (Some(f), Some(d)) => Some(f * d),
                 _ => None,
             },
+            max_part_share: self.get_max_part_share(),
+            gini: self.get_gini(),
+            min_part_rate: self.get_min_part_rate(),
+            max_part_rate: self.get_max_part_rate().map(|(_, rate)| rate),
+            range_at_threshold: self.get_range_at_threshold(),
+            pervasiveness_pt_at_threshold: self.get_pervasiveness_pt_at_threshold(),
+            // 文件内滑窗指标需要词序，由 corpus_pipeline 另行填充
+            within_file_dp: None,
+            within_file_juilland_d: None,
         }
     }
 }
@@ -0,0 +1,94 @@
+// word_entry.rs
+// 词表条目：词、词性、频次及分布指标
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::dispersion_metrics::DispersionMetrics;
+
+/// 位置模式的字段名，导出列按此顺序
+pub const POSITION_FIELDS: [&str; 3] = [
+    "first_occurrence_part",
+    "first_occurrence_fraction",
+    "occurrence_span_fraction",
+];
+
+/// 超出 max_surface_forms 的写法合计为一项，写法记为 other
+pub const OTHER_SURFACE_FORM: &str = "other";
+
+/// 规范化前的一种写法及其频次
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceVariant {
+    pub form: String,
+    pub frequency: f64,
+    /// 超出 max_surface_forms 的其余写法之和；此时 form 为 OTHER_SURFACE_FORM
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub other: bool,
+}
+
+/// 词表中的一个 (词, 词性) 条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordEntry {
+    pub word: String,
+    pub pos: String,
+    /// 稳定标识，由词形、词性与 metadata.normalization_signature 确定，见 entry_id 模块
+    #[serde(default)]
+    pub id: String,
+    /// 词长（字数），见 text::char_length
+    pub char_length: usize,
+    /// 全语料总频次
+    pub frequency: f64,
+    pub metrics: DispersionMetrics,
+    /// Simple Good-Turing 平滑后的频次；频次谱不足以拟合时为 None
+    pub gt_adjusted_frequency: Option<f64>,
+    /// 引号内的频次；仅开启 protect_quotes 时给出
+    pub quoted_frequency: Option<f64>,
+    /// 同一词形的全部词元中标为该词性的比例（0–1），以标注分布近似标注置信度，并非模型给出的分数；
+    /// 仅开启 pos_confidence 时给出，短语不给出
+    #[serde(default)]
+    pub pos_tag_share: Option<f64>,
+    /// 首次出现的语料部分序号；以下三项仅开启 track_positions 时给出，短语不给出
+    pub first_occurrence_part: Option<usize>,
+    /// 首次出现位置占全语料词数的比例（0–1），位置按输入文件顺序、只对计入词数的词元编号
+    pub first_occurrence_fraction: Option<f64>,
+    /// 首次与末次出现之间的词数占全语料词数的比例
+    pub occurrence_span_fraction: Option<f64>,
+    /// 频率（metrics.max_part_rate）最高的语料部分序号，对应 metadata.parts
+    #[serde(default)]
+    pub max_part_index: Option<usize>,
+    /// 规范化前观察到的各写法，按频次降序，至多 max_surface_forms 项，其余合计为最后一项 other；
+    /// 仅 latin_case 为 fold 时给出，只有一种与条目相同的写法时为空
+    #[serde(default, alias = "variants", skip_serializing_if = "Vec::is_empty")]
+    pub surface_forms: Vec<SurfaceVariant>,
+    /// 例句；仅开启 collect_examples 时采集
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    /// 条目标记，见 entry_flags 模块
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    /// 各指标下的密集排名，见 rank 模块；仅分页查询指定 ranks 时填写
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ranks: BTreeMap<String, usize>,
+}
+
+impl WordEntry {
+    /// 频次最高的写法；没有记录写法时为条目本身
+    pub fn top_surface_form(&self) -> &str {
+        self.surface_forms
+            .iter()
+            .find(|v| !v.other)
+            .map_or(&self.word, |v| &v.form)
+    }
+
+    /// 在原文中查找该条目时应匹配的写法：条目本身与各记录的写法（不含 other）
+    pub fn search_forms(&self) -> Vec<&str> {
+        let mut forms = vec![self.word.as_str()];
+        for v in self.surface_forms.iter().filter(|v| !v.other) {
+            if !forms.contains(&v.form.as_str()) {
+                forms.push(&v.form);
+            }
+        }
+        forms
+    }
+}
@@ -0,0 +1,348 @@
+// word_sketch.rs
+// 词语素描：在带词性的词元流上按关系模式收集节点词的搭配词（如名词前的动词、形容词修饰语，动词后的宾语），按 logDice 排序
+//
+// v1 不做句法分析，每种关系只是一条邻接 / 窗口模式：从节点词向前或向后至多 window 个词元内，
+// 跳过词性属于 gap_pos 的词元，遇到的第一个词性属于 collocate_pos 的词元即为搭配词，遇到其他词元则停止。
+// 模式不跨句（见 text::split_sentences）。内置关系见 builtin_relations；settings.json 的 sketch_relations
+// 可增补或按名称替换内置关系，无需改动代码。词性均按前缀匹配，"n" 包括 nh、ns、nz 等。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::text;
+
+/// 一种语法关系的模式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SketchRelation {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// 节点词的词性前缀
+    pub node_pos: Vec<String>,
+    /// 搭配词的词性前缀
+    pub collocate_pos: Vec<String>,
+    /// 搭配词在节点词之后；否则在之前
+    #[serde(default)]
+    pub after: bool,
+    /// 搭配词与节点词的最大距离（词元数），1 为紧邻
+    pub window: usize,
+    /// 二者之间允许出现的词性前缀；为空时只能紧邻
+    #[serde(default)]
+    pub gap_pos: Vec<String>,
+}
+
+impl SketchRelation {
+    fn new(
+        name: &str,
+        description: &str,
+        node_pos: &[&str],
+        collocate_pos: &[&str],
+        after: bool,
+        window: usize,
+        gap_pos: &[&str],
+    ) -> Self {
+        let strings = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect();
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            node_pos: strings(node_pos),
+            collocate_pos: strings(collocate_pos),
+            after,
+            window,
+            gap_pos: strings(gap_pos),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("关系名称不能为空".to_string());
+        }
+        if self.node_pos.is_empty() || self.collocate_pos.is_empty() {
+            return Err(format!("关系 {} 须指定节点词与搭配词的词性", self.name));
+        }
+        if self.window == 0 {
+            return Err(format!("关系 {} 的窗口须大于 0", self.name));
+        }
+        Ok(())
+    }
+
+    /// 节点词的词性是否适用此关系
+    pub fn applies_to(&self, pos: &str) -> bool {
+        matches_pos(pos, &self.node_pos)
+    }
+
+    /// 节点词位于 sentence[node] 时此关系的搭配词位置
+    fn collocate(&self, sentence: &[(String, String)], node: usize) -> Option<usize> {
+        for distance in 1..=self.window {
+            let i = if self.after {
+                node + distance
+            } else {
+                node.checked_sub(distance)?
+            };
+            let (_, pos) = sentence.get(i)?;
+            if matches_pos(pos, &self.collocate_pos) {
+                return Some(i);
+            }
+            if !matches_pos(pos, &self.gap_pos) {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+fn matches_pos(pos: &str, prefixes: &[String]) -> bool {
+    prefixes
+        .iter()
+        .any(|prefix| pos.starts_with(prefix.as_str()))
+}
+
+/// 内置关系（LTP 词性标注集）
+pub fn builtin_relations() -> Vec<SketchRelation> {
+    // 数词、量词、区别词、形容词与助词（的、了）可出现在动词与宾语之间
+    let object_gap = ["m", "q", "b", "a", "u"];
+    vec![
+        SketchRelation::new(
+            "object_of",
+            "以该名词为宾语的动词",
+            &["n"],
+            &["v"],
+            false,
+            4,
+            &object_gap,
+        ),
+        SketchRelation::new(
+            "modifier",
+            "修饰该名词的形容词",
+            &["n"],
+            &["a"],
+            false,
+            2,
+            &["u"],
+        ),
+        SketchRelation::new(
+            "object",
+            "该动词的宾语",
+            &["v"],
+            &["n"],
+            true,
+            4,
+            &object_gap,
+        ),
+        SketchRelation::new("subject", "该动词的主语", &["v"], &["n"], false, 2, &["d"]),
+        SketchRelation::new("adverb", "修饰该动词的副词", &["v"], &["d"], false, 1, &[]),
+        SketchRelation::new(
+            "modifies",
+            "该形容词修饰的名词",
+            &["a"],
+            &["n"],
+            true,
+            2,
+            &["u"],
+        ),
+    ]
+}
+
+/// settings.json 中的词语素描部分
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SketchSettings {
+    /// 自定义关系
+    pub sketch_relations: Vec<SketchRelation>,
+}
+
+impl SketchSettings {
+    /// 读取设置文件；文件不存在时返回默认设置
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 内置与自定义关系；同名时自定义关系替换内置关系
+    pub fn all_relations(&self) -> Result<Vec<SketchRelation>, String> {
+        for relation in &self.sketch_relations {
+            relation.validate()?;
+        }
+        let mut relations: Vec<SketchRelation> = builtin_relations()
+            .into_iter()
+            .filter(|builtin| self.sketch_relations.iter().all(|r| r.name != builtin.name))
+            .collect();
+        relations.extend(self.sketch_relations.iter().cloned());
+        Ok(relations)
+    }
+}
+
+/// 每种关系返回的搭配词数与最低共现频次
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SketchOptions {
+    pub limit: usize,
+    pub min_frequency: f64,
+}
+
+impl Default for SketchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 25,
+            min_frequency: 2.0,
+        }
+    }
+}
+
+/// 一个搭配词
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SketchCollocate {
+    pub word: String,
+    pub pos: String,
+    /// 在此关系中与节点词的共现次数
+    pub frequency: f64,
+    /// 搭配词在语料中的总频次
+    pub collocate_frequency: f64,
+    pub log_dice: f64,
+}
+
+/// 一种关系下的搭配词，按 logDice 降序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SketchRelationResult {
+    pub name: String,
+    pub description: String,
+    /// 此关系的共现总次数（含低于最低频次而未列出的搭配词）
+    pub total: f64,
+    pub collocates: Vec<SketchCollocate>,
+}
+
+/// 词语素描
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordSketch {
+    pub word: String,
+    pub pos: String,
+    /// 节点词在语料中的频次
+    pub frequency: f64,
+    /// 适用于节点词词性的各关系，顺序同关系表
+    pub relations: Vec<SketchRelationResult>,
+}
+
+/// logDice = 14 + log2(2 · f_xy / (f_x + f_y))，最大值 14；与语料大小无关，适合比较不同语料中的搭配强度
+///
+/// ```
+/// use betawordlist_lib::analysis::word_sketch::log_dice;
+///
+/// assert_eq!(log_dice(10.0, 10.0, 10.0), 14.0);
+/// assert_eq!(log_dice(5.0, 10.0, 10.0), 13.0);
+/// ```
+pub fn log_dice(f_xy: f64, f_x: f64, f_y: f64) -> f64 {
+    14.0 + (2.0 * f_xy / (f_x + f_y)).log2()
+}
+
+/// 逐文本累计一个节点词的关系共现
+pub struct SketchCounter {
+    word: String,
+    pos: String,
+    /// 在词元流中匹配节点词的写法，缺省只有 word，见 with_forms
+    forms: Vec<String>,
+    relations: Vec<SketchRelation>,
+    node_frequency: f64,
+    /// 各 (词, 词性) 的总频次，用作 f_y
+    frequencies: HashMap<(String, String), f64>,
+    /// 与 relations 一一对应的共现次数
+    pairs: Vec<HashMap<(String, String), f64>>,
+}
+
+impl SketchCounter {
+    /// 只保留适用于 pos 的关系
+    pub fn new(word: &str, pos: &str, relations: Vec<SketchRelation>) -> Self {
+        let relations: Vec<SketchRelation> = relations
+            .into_iter()
+            .filter(|r| r.applies_to(pos))
+            .collect();
+        Self {
+            word: word.to_string(),
+            pos: pos.to_string(),
+            forms: vec![word.to_string()],
+            pairs: vec![HashMap::new(); relations.len()],
+            relations,
+            node_frequency: 0.0,
+            frequencies: HashMap::new(),
+        }
+    }
+
+    /// 节点词在原文中的各写法（见 WordEntry::search_forms）；词元流为规范化前的原文分词，
+    /// 开启规范化时须匹配全部写法才能找到节点词的每次出现
+    pub fn with_forms(mut self, forms: Vec<String>) -> Self {
+        self.forms = forms;
+        self
+    }
+
+    pub fn add_text(&mut self, word_pos: &[(String, String)]) {
+        for sentence in text::split_sentences(word_pos) {
+            for (i, (word, pos)) in sentence.iter().enumerate() {
+                *self
+                    .frequencies
+                    .entry((word.clone(), pos.clone()))
+                    .or_insert(0.0) += 1.0;
+                if *pos != self.pos || !self.forms.contains(word) {
+                    continue;
+                }
+                self.node_frequency += 1.0;
+                for (relation, pairs) in self.relations.iter().zip(&mut self.pairs) {
+                    if let Some(j) = relation.collocate(sentence, i) {
+                        *pairs.entry(sentence[j].clone()).or_insert(0.0) += 1.0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 计算 logDice 并排序：logDice 降序，其次共现次数降序、词形升序
+    pub fn finish(self, options: &SketchOptions) -> WordSketch {
+        let f_x = self.node_frequency;
+        let relations = self
+            .relations
+            .into_iter()
+            .zip(self.pairs)
+            .map(|(relation, pairs)| {
+                let total = pairs.values().sum();
+                let mut collocates: Vec<SketchCollocate> = pairs
+                    .into_iter()
+                    .filter(|&(_, f_xy)| f_xy >= options.min_frequency)
+                    .map(|((word, pos), f_xy)| {
+                        let f_y = self.frequencies[&(word.clone(), pos.clone())];
+                        SketchCollocate {
+                            log_dice: log_dice(f_xy, f_x, f_y),
+                            word,
+                            pos,
+                            frequency: f_xy,
+                            collocate_frequency: f_y,
+                        }
+                    })
+                    .collect();
+                collocates.sort_by(|a, b| {
+                    b.log_dice
+                        .total_cmp(&a.log_dice)
+                        .then(b.frequency.total_cmp(&a.frequency))
+                        .then_with(|| (&a.word, &a.pos).cmp(&(&b.word, &b.pos)))
+                });
+                collocates.truncate(options.limit);
+                SketchRelationResult {
+                    name: relation.name,
+                    description: relation.description,
+                    total,
+                    collocates,
+                }
+            })
+            .collect();
+        WordSketch {
+            word: self.word,
+            pos: self.pos,
+            frequency: f_x,
+            relations,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// worker.rs
+// 结果工作线程：一个常驻线程独占后端保留的分析结果，命令经通道依次送达，对结果的读写不会交错
+//
+// 分析、重新计算、导入、合并与各类导出都读写同一组结果槽。各命令不再直接加锁，而是向工作线程发送
+// WorkerCommand，附带一个应答通道（tokio oneshot），在线程上按到达顺序执行后取回应答。写入、复制、移除与列出各有专门的消息；
+// 其余读写以 Run 消息携带闭包执行，闭包在工作线程上运行时独占结果槽。
+// 异步命令 await 应答，等待期间不占用运行时的线程；阻塞线程（分析线程、目录监视）用 blocking_ 开头的方法。
+// 耗时的计算（分析本身、自助法等）与文件读写应在工作线程之外完成，只把写入或必要的读取（复制出所需的数据）
+// 交给工作线程，以免阻塞分页等命令。
+// 闭包内不可再向同一工作线程发送消息，否则会互相等待。闭包 panic 时工作线程照常运行，panic 在调用方重新抛出。
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use tokio::sync::oneshot;
+
+use crate::analysis::{
+    corpus_pipeline::AnalysisResult,
+    result_slots::{ResultSlotInfo, ResultSlots},
+};
+
+/// 在工作线程上执行的闭包
+pub type WorkerTask = Box<dyn FnOnce(&mut ResultSlots) + Send>;
+
+/// 发往工作线程的消息；reply 为应答通道
+pub enum WorkerCommand {
+    /// 写入 slot 槽，见 ResultSlots::store
+    Store {
+        slot: String,
+        result: Box<AnalysisResult>,
+        reply: oneshot::Sender<()>,
+    },
+    /// 写入新的具名槽，见 ResultSlots::insert
+    Insert {
+        slot: String,
+        result: Box<AnalysisResult>,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    /// 复制结果，见 ResultSlots::pin
+    Pin {
+        name: String,
+        from: Option<String>,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    /// 移除槽，见 ResultSlots::remove
+    Remove {
+        name: String,
+        reply: oneshot::Sender<bool>,
+    },
+    /// 各槽的概况
+    List {
+        reply: oneshot::Sender<Vec<ResultSlotInfo>>,
+    },
+    /// 执行闭包，应答由闭包自行发送
+    Run(WorkerTask),
+}
+
+/// 工作线程的句柄，可复制后在各线程中使用；全部句柄都释放后工作线程退出
+#[derive(Clone)]
+pub struct ResultWorker {
+    sender: Sender<WorkerCommand>,
+}
+
+impl ResultWorker {
+    /// 启动工作线程
+    pub fn spawn() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("result-worker".to_string())
+            .spawn(move || serve(receiver))
+            .expect("无法启动结果工作线程");
+        Self { sender }
+    }
+
+    /// 发送一条消息，不等待应答
+    pub fn send(&self, command: WorkerCommand) {
+        self.sender.send(command).expect("结果工作线程已退出");
+    }
+
+    /// 写入 slot 槽并使其成为最近写入的槽
+    pub async fn store(&self, slot: &str, result: AnalysisResult) {
+        self.request(store(slot, result)).await
+    }
+
+    /// 同 store，供不在异步运行时中的线程调用
+    pub fn blocking_store(&self, slot: &str, result: AnalysisResult) {
+        self.blocking_request(store(slot, result))
+    }
+
+    /// 写入新的具名槽，检查槽名与槽数上限
+    pub async fn insert(&self, slot: &str, result: AnalysisResult) -> Result<(), String> {
+        self.request(|reply| WorkerCommand::Insert {
+            slot: slot.to_string(),
+            result: Box::new(result),
+            reply,
+        })
+        .await
+    }
+
+    /// 把 from 槽（缺省为最近写入的槽）复制到 name 槽
+    pub async fn pin(&self, name: &str, from: Option<&str>) -> Result<(), String> {
+        self.request(|reply| WorkerCommand::Pin {
+            name: name.to_string(),
+            from: from.map(str::to_string),
+            reply,
+        })
+        .await
+    }
+
+    /// 移除 name 槽，返回是否存在
+    pub async fn remove(&self, name: &str) -> bool {
+        self.request(|reply| WorkerCommand::Remove {
+            name: name.to_string(),
+            reply,
+        })
+        .await
+    }
+
+    /// 各槽的概况
+    pub async fn list(&self) -> Vec<ResultSlotInfo> {
+        self.request(|reply| WorkerCommand::List { reply }).await
+    }
+
+    /// 在工作线程上执行 task 并等待其返回值
+    pub async fn run<T, F>(&self, task: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut ResultSlots) -> T + Send + 'static,
+    {
+        self.request(run(task)).await
+    }
+
+    /// 同 run，供不在异步运行时中的线程调用
+    pub fn blocking_run<T, F>(&self, task: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut ResultSlots) -> T + Send + 'static,
+    {
+        self.blocking_request(run(task))
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> WorkerCommand) -> T {
+        let (reply, response) = oneshot::channel();
+        self.send(command(reply));
+        response.await.unwrap_or_else(|_| failed())
+    }
+
+    fn blocking_request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> WorkerCommand) -> T {
+        let (reply, response) = oneshot::channel();
+        self.send(command(reply));
+        response.blocking_recv().unwrap_or_else(|_| failed())
+    }
+}
+
+fn store(slot: &str, result: AnalysisResult) -> impl FnOnce(oneshot::Sender<()>) -> WorkerCommand {
+    let slot = slot.to_string();
+    move |reply| WorkerCommand::Store {
+        slot,
+        result: Box::new(result),
+        reply,
+    }
+}
+
+fn run<T, F>(task: F) -> impl FnOnce(oneshot::Sender<T>) -> WorkerCommand
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ResultSlots) -> T + Send + 'static,
+{
+    |reply| {
+        WorkerCommand::Run(Box::new(move |slots| {
+            reply.send(task(slots)).ok();
+        }))
+    }
+}
+
+/// 应答通道在应答前关闭：闭包 panic，或工作线程已退出
+fn failed() -> ! {
+    panic!("结果工作线程未能完成请求")
+}
+
+/// 工作线程的主循环：按到达顺序处理消息，通道关闭后退出
+fn serve(receiver: Receiver<WorkerCommand>) {
+    let mut slots = ResultSlots::default();
+    for command in receiver {
+        // 应答通道的另一端可能已放弃等待，发送失败时忽略
+        match command {
+            WorkerCommand::Store {
+                slot,
+                result,
+                reply,
+            } => {
+                slots.store(&slot, *result);
+                reply.send(()).ok();
+            }
+            WorkerCommand::Insert {
+                slot,
+                result,
+                reply,
+            } => {
+                reply.send(slots.insert(&slot, *result)).ok();
+            }
+            WorkerCommand::Pin { name, from, reply } => {
+                reply.send(slots.pin(&name, from.as_deref())).ok();
+            }
+            WorkerCommand::Remove { name, reply } => {
+                reply.send(slots.remove(&name)).ok();
+            }
+            WorkerCommand::List { reply } => {
+                reply.send(slots.list()).ok();
+            }
+            WorkerCommand::Run(task) => {
+                // panic 时应答通道随闭包释放，调用方在等待应答时得知失败
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| task(&mut slots)));
+            }
+        }
+    }
+}
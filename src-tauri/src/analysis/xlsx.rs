@@ -0,0 +1,198 @@
+// xlsx.rs
+// 不依赖外部库的最小 XLSX 写出：若干工作表，单元格为数值或内联字符串，不含样式
+//
+// 文件为未压缩（stored）的 ZIP（见 zip::ZipWriter）。工作表边写边计算 CRC，不必在内存中缓存整张表；
+// 不支持 ZIP64，整个文件超过 4 GiB 时报错。
+
+use std::io::{self, Write};
+
+use crate::analysis::{text, zip::ZipWriter};
+
+/// 单元格
+#[derive(Debug, Clone, Copy)]
+pub enum Cell<'a> {
+    Text(&'a str),
+    /// 非有限值写为空单元格
+    Number(f64),
+    Empty,
+}
+
+/// 工作表行数上限（含表头）
+pub const MAX_ROWS: usize = 1_048_576;
+/// 单元格文本长度上限（UTF-16 码元，与 Excel 一致），超出部分在字的边界截去
+const MAX_TEXT_CHARS: usize = 32_767;
+/// XLSX 写出器：依次 start_sheet、row，最后 finish
+pub struct XlsxWriter<W: Write> {
+    zip: ZipWriter<W>,
+    sheets: Vec<String>,
+    rows: usize,
+}
+
+impl<W: Write> XlsxWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            zip: ZipWriter::new(writer),
+            sheets: Vec::new(),
+            rows: 0,
+        }
+    }
+
+    /// 结束当前工作表（如有）并开始新工作表；name 不超过 31 个 UTF-16 码元，不含 []:*?/\
+    pub fn start_sheet(&mut self, name: &str) -> io::Result<()> {
+        if name.is_empty()
+            || name.encode_utf16().count() > 31
+            || name.contains(['[', ']', ':', '*', '?', '/', '\\'])
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("无效的工作表名: {name:?}"),
+            ));
+        }
+        self.end_sheet()?;
+        self.sheets.push(name.to_string());
+        self.rows = 0;
+        self.zip
+            .start_entry(&format!("xl/worksheets/sheet{}.xml", self.sheets.len()))?;
+        self.data(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#
+        ))
+    }
+
+    /// 在当前工作表追加一行
+    pub fn row(&mut self, cells: &[Cell]) -> io::Result<()> {
+        if self.sheets.is_empty() {
+            return Err(io::Error::other("尚未开始工作表"));
+        }
+        if self.rows >= MAX_ROWS {
+            return Err(io::Error::other(format!(
+                "超过 XLSX 工作表行数上限 {MAX_ROWS}"
+            )));
+        }
+        self.rows += 1;
+        let mut xml = String::from("<row>");
+        for cell in cells {
+            match *cell {
+                Cell::Text(text) => {
+                    let preserve = text.starts_with(char::is_whitespace)
+                        || text.ends_with(char::is_whitespace);
+                    xml.push_str(if preserve {
+                        r#"<c t="inlineStr"><is><t xml:space="preserve">"#
+                    } else {
+                        r#"<c t="inlineStr"><is><t>"#
+                    });
+                    escape_into(&mut xml, text);
+                    xml.push_str("</t></is></c>");
+                }
+                Cell::Number(v) if v.is_finite() => {
+                    xml.push_str(&format!("<c><v>{v}</v></c>"));
+                }
+                Cell::Number(_) | Cell::Empty => xml.push_str("<c/>"),
+            }
+        }
+        xml.push_str("</row>");
+        self.data(&xml)
+    }
+
+    /// 写出工作簿结构与 ZIP 中央目录，返回底层写出端；没有工作表时补一张空表
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.sheets.is_empty() {
+            self.start_sheet("Sheet1")?;
+        }
+        self.end_sheet()?;
+        let parts = [
+            ("[Content_Types].xml", self.content_types()),
+            ("_rels/.rels", ROOT_RELS.to_string()),
+            ("xl/workbook.xml", self.workbook()),
+            ("xl/_rels/workbook.xml.rels", self.workbook_rels()),
+        ];
+        for (name, xml) in parts {
+            self.zip.add(name, xml.as_bytes())?;
+        }
+        self.zip.finish()
+    }
+
+    fn end_sheet(&mut self) -> io::Result<()> {
+        if self.zip.in_entry() {
+            self.data("</sheetData></worksheet>")?;
+            self.zip.end_entry()?;
+        }
+        Ok(())
+    }
+
+    fn data(&mut self, text: &str) -> io::Result<()> {
+        self.zip.write(text.as_bytes())
+    }
+
+    fn content_types(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+            r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+            r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+            r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#
+        ));
+        for i in 1..=self.sheets.len() {
+            xml.push_str(&format!(
+                r#"<Override PartName="/xl/worksheets/sheet{i}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#
+            ));
+        }
+        xml.push_str("</Types>");
+        xml
+    }
+
+    fn workbook(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>"#
+        ));
+        for (i, name) in self.sheets.iter().enumerate() {
+            xml.push_str("<sheet name=\"");
+            escape_into(&mut xml, name);
+            xml.push_str(&format!("\" sheetId=\"{0}\" r:id=\"rId{0}\"/>", i + 1));
+        }
+        xml.push_str("</sheets></workbook>");
+        xml
+    }
+
+    fn workbook_rels(&self) -> String {
+        let mut xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#
+        ));
+        for i in 1..=self.sheets.len() {
+            xml.push_str(&format!(
+                r#"<Relationship Id="rId{i}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{i}.xml"/>"#
+            ));
+        }
+        xml.push_str("</Relationships>");
+        xml
+    }
+}
+
+const ROOT_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    "\n",
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    "</Relationships>"
+);
+
+/// XML 转义；去掉 XML 1.0 不允许的字符，超长文本截断
+fn escape_into(xml: &mut String, text: &str) {
+    for c in text::truncate_utf16(text, MAX_TEXT_CHARS).chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\t' | '\n' | '\r' => xml.push(c),
+            c if c.is_control() || matches!(c, '\u{FFFE}' | '\u{FFFF}') => {}
+            c => xml.push(c),
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// zip.rs
+// 不依赖外部库的最小 ZIP：写出与读回未压缩（stored）的条目，供 XLSX 导出与资源包使用
+//
+// 写出时条目边写边计算 CRC，大小与 CRC 写在数据描述符中，不必在内存中缓存整个条目；不支持 ZIP64，
+// 整个文件超过 4 GiB 时报错。读取按中央目录定位条目，只接受 stored 条目并校验 CRC。
+
+use std::io::{self, Write};
+
+/// 文件内时间戳：1980-01-01 00:00（DOS 格式）
+const DOS_DATE: u16 = (1 << 5) | 1;
+/// 通用标志：CRC 与大小写在数据描述符中
+const FLAG_DATA_DESCRIPTOR: u16 = 0x0008;
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const DATA_DESCRIPTOR: u32 = 0x0807_4b50;
+
+/// 已写完的 ZIP 条目，用于中央目录
+struct ZipEntry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// 正在写的 ZIP 条目
+struct OpenEntry {
+    name: String,
+    crc: u32,
+    size: u64,
+    offset: u32,
+}
+
+/// ZIP 写出器：依次 start_entry、write、end_entry（或一次 add），最后 finish
+pub struct ZipWriter<W: Write> {
+    writer: W,
+    /// 已写出的字节数，即下一个条目的偏移
+    written: u64,
+    entries: Vec<ZipEntry>,
+    open: Option<OpenEntry>,
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            written: 0,
+            entries: Vec::new(),
+            open: None,
+        }
+    }
+
+    /// 是否有正在写的条目
+    pub fn in_entry(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// 结束当前条目（如有）并写出新条目的本地文件头；CRC 与大小留待数据描述符
+    pub fn start_entry(&mut self, name: &str) -> io::Result<()> {
+        self.end_entry()?;
+        let offset = self.offset()?;
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend(LOCAL_HEADER.to_le_bytes());
+        header.extend(20u16.to_le_bytes());
+        header.extend(FLAG_DATA_DESCRIPTOR.to_le_bytes());
+        header.extend(0u16.to_le_bytes()); // 不压缩
+        header.extend(0u16.to_le_bytes());
+        header.extend(DOS_DATE.to_le_bytes());
+        header.extend([0u8; 12]); // CRC 与两个大小
+        header.extend((name.len() as u16).to_le_bytes());
+        header.extend(0u16.to_le_bytes());
+        header.extend(name.as_bytes());
+        self.raw(&header)?;
+        self.open = Some(OpenEntry {
+            name: name.to_string(),
+            crc: !0,
+            size: 0,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// 向当前条目追加数据
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let Some(entry) = &mut self.open else {
+            return Err(io::Error::other("尚未开始 ZIP 条目"));
+        };
+        entry.crc = crc32_update(entry.crc, bytes);
+        entry.size += bytes.len() as u64;
+        self.raw(bytes)
+    }
+
+    /// 写出当前条目的数据描述符；没有正在写的条目时什么也不做
+    pub fn end_entry(&mut self) -> io::Result<()> {
+        let Some(entry) = self.open.take() else {
+            return Ok(());
+        };
+        let size = u32::try_from(entry.size).map_err(|_| too_large())?;
+        let crc = !entry.crc;
+        let mut descriptor = Vec::with_capacity(16);
+        descriptor.extend(DATA_DESCRIPTOR.to_le_bytes());
+        descriptor.extend(crc.to_le_bytes());
+        descriptor.extend(size.to_le_bytes());
+        descriptor.extend(size.to_le_bytes());
+        self.raw(&descriptor)?;
+        self.entries.push(ZipEntry {
+            name: entry.name,
+            crc,
+            size,
+            offset: entry.offset,
+        });
+        Ok(())
+    }
+
+    /// 写出一个完整条目
+    pub fn add(&mut self, name: &str, bytes: &[u8]) -> io::Result<()> {
+        self.start_entry(name)?;
+        self.write(bytes)?;
+        self.end_entry()
+    }
+
+    /// 结束当前条目并写出中央目录，返回底层写出端
+    pub fn finish(mut self) -> io::Result<W> {
+        self.end_entry()?;
+        let directory_offset = self.offset()?;
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend(CENTRAL_HEADER.to_le_bytes());
+            directory.extend(20u16.to_le_bytes()); // 创建版本
+            directory.extend(20u16.to_le_bytes()); // 所需版本
+            directory.extend(FLAG_DATA_DESCRIPTOR.to_le_bytes());
+            directory.extend(0u16.to_le_bytes()); // 不压缩
+            directory.extend(0u16.to_le_bytes());
+            directory.extend(DOS_DATE.to_le_bytes());
+            directory.extend(entry.crc.to_le_bytes());
+            directory.extend(entry.size.to_le_bytes());
+            directory.extend(entry.size.to_le_bytes());
+            directory.extend((entry.name.len() as u16).to_le_bytes());
+            directory.extend([0u8; 12]); // 扩展字段、注释、磁盘号、内部与外部属性
+            directory.extend(entry.offset.to_le_bytes());
+            directory.extend(entry.name.as_bytes());
+        }
+        let count = self.entries.len() as u16;
+        let directory_size = directory.len() as u32;
+        directory.extend(END_OF_DIRECTORY.to_le_bytes());
+        directory.extend([0u8; 4]); // 磁盘号
+        directory.extend(count.to_le_bytes());
+        directory.extend(count.to_le_bytes());
+        directory.extend(directory_size.to_le_bytes());
+        directory.extend(directory_offset.to_le_bytes());
+        directory.extend(0u16.to_le_bytes());
+        self.raw(&directory)?;
+        self.offset()?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.writer.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn offset(&self) -> io::Result<u32> {
+        u32::try_from(self.written).map_err(|_| too_large())
+    }
+}
+
+/// 按中央目录顺序读出全部条目 (名称, 内容)；只接受未压缩条目，CRC 不符、越界或结构损坏时报错
+pub fn read_entries(bytes: &[u8]) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let end = (0..=bytes.len().saturating_sub(22))
+        .rev()
+        .find(|&at| read_u32(bytes, at) == Some(END_OF_DIRECTORY))
+        .ok_or_else(|| invalid("找不到 ZIP 中央目录，文件可能不完整".to_string()))?;
+    let count = read_u16(bytes, end + 10).unwrap_or(0) as usize;
+    let mut at = read_u32(bytes, end + 16).unwrap_or(0) as usize;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if read_u32(bytes, at) != Some(CENTRAL_HEADER) {
+            return Err(invalid("ZIP 中央目录已损坏".to_string()));
+        }
+        let field = |offset: usize| read_u16(bytes, at + offset).unwrap_or(0) as usize;
+        let method = field(10);
+        let crc = read_u32(bytes, at + 16).unwrap_or(0);
+        let size = read_u32(bytes, at + 20).unwrap_or(0) as usize;
+        let (name_len, extra_len, comment_len) = (field(28), field(30), field(32));
+        let local = read_u32(bytes, at + 42).unwrap_or(0) as usize;
+        let name = bytes
+            .get(at + 46..at + 46 + name_len)
+            .map(|name| String::from_utf8_lossy(name).to_string())
+            .ok_or_else(|| invalid("ZIP 中央目录已损坏".to_string()))?;
+        at += 46 + name_len + extra_len + comment_len;
+
+        if method != 0 {
+            return Err(invalid(format!("{name} 为压缩条目，仅支持未压缩的 ZIP")));
+        }
+        if read_u32(bytes, local) != Some(LOCAL_HEADER) {
+            return Err(invalid(format!("{name} 的本地文件头已损坏")));
+        }
+        let start = local
+            + 30
+            + read_u16(bytes, local + 26).unwrap_or(0) as usize
+            + read_u16(bytes, local + 28).unwrap_or(0) as usize;
+        let data = bytes
+            .get(start..start + size)
+            .ok_or_else(|| invalid(format!("{name} 的数据不完整")))?;
+        if !crc32_update(!0, data) != crc {
+            return Err(invalid(format!("{name} 的 CRC 校验失败，文件已损坏")));
+        }
+        entries.push((name, data.to_vec()));
+    }
+    Ok(entries)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn too_large() -> io::Error {
+    io::Error::other("ZIP 文件超过 4 GiB（不支持 ZIP64）")
+}
+
+/// CRC-32（IEEE）查找表
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+/// 累加 CRC-32；初值为 !0，结束时取反
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc = CRC_TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
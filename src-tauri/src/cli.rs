@@ -0,0 +1,538 @@
+// cli.rs
+// 命令行无界面模式，供 CI 与脚本跑通 分词 → 指标 → 导出 全流程
+//
+// --report 写出 JSON 运行报告（退出状态、计数、警告、耗时、选项哈希与写出的文件），参数错误或分析失败时同样写出，见 run_report。
+//
+// 开发用：betawordlist golden [--update] [夹具目录]，与数值回归基准比较，或在指标有意变动时重写基准值
+//
+// 用法：betawordlist analyze [--engine ltp|rule|pretagged] [--cws 模型] [--pos 模型] [--hash] [--window 窗口词数] [--segment-batch 句数] [--segment-timeout 毫秒] [--token-policy default|recommended] [--latin-case preserve|lower|fold] [--max-surface-forms 写法数] [--screen skip|whitespace] [--min-han 比例] [--rate-base 基数] [--range-threshold 次数] [--memory-limit MB] [--min-freq 频次] [--two-pass] [--strict] [--oov-pairs 对数] [--quick-look 词数] [--per-sentence] [--min-part-tokens 词数] [--merge-small-parts] [--pos-outlier 词性] [--pos-outlier-z z 分数] [--positions] [--near-duplicates keep_both|keep_longer|merge] [--near-threshold 相似度] [--sample-fraction 比例|--sample-files 文件数|--sample-tokens 词数] [--sample-seed 序列编号] [--seed 种子] [--examples 句数] [--stopwords 文件] [--size-excludes 类别,...] [--protect-quotes] [--pos-confidence] [--structural-noise] [--posgrams 长度] [--posgram-min 频次] [--phrases 文件] [--targets 文件] [--recursive] [--allow-all] [--precision 位数] [--decimal .|,] [--delimiter 分隔符|tab] [--surface-form] [--pos-label zh|en] [--rank 指标,...] [--columns 列,...] [--definitions] [--profile research|teaching] [--levels 等级表] [--out 结果.csv] [--report 报告.json] 文件...
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use betawordlist_lib::analysis::{
+    atomic::write_atomic,
+    corpus_pipeline,
+    export::{self, ExportOptions},
+    filter::{self, DenominatorPolicy},
+    golden,
+    inputs::{self, InputOptions},
+    language::ScreenMode,
+    near_duplicates::DuplicatePolicy,
+    nlp::{PretaggedTokenizer, RuleTokenizer, Tokenizer},
+    options::{AnalysisOptions, PartitionMode, QuickLook},
+    paths, phrases, presets,
+    profile::{self, OutputProfile, ProfileContext, ProfileSettings},
+    progress::NoopReporter,
+    run_report::RunReport,
+    sampling::SampleSize,
+    tagset::LabelLanguage,
+    targets,
+    token_policy::{LatinCase, TokenPolicy},
+};
+
+const GOLDEN_USAGE: &str = "用法: betawordlist golden [--update] [夹具目录]";
+
+const USAGE: &str = "用法: betawordlist analyze [--engine ltp|rule|pretagged] [--cws 模型] [--pos 模型] [--hash] [--window 窗口词数] [--segment-batch 句数] [--segment-timeout 毫秒] [--token-policy default|recommended] [--latin-case preserve|lower|fold] [--max-surface-forms 写法数] [--screen skip|whitespace] [--min-han 比例] [--rate-base 基数] [--range-threshold 次数] [--memory-limit MB] [--min-freq 频次] [--two-pass] [--strict] [--oov-pairs 对数] [--quick-look 词数] [--per-sentence] [--min-part-tokens 词数] [--merge-small-parts] [--pos-outlier 词性] [--pos-outlier-z z 分数] [--positions] [--near-duplicates keep_both|keep_longer|merge] [--near-threshold 相似度] [--sample-fraction 比例|--sample-files 文件数|--sample-tokens 词数] [--sample-seed 序列编号] [--seed 种子] [--examples 句数] [--stopwords 文件] [--size-excludes 类别,...] [--protect-quotes] [--pos-confidence] [--structural-noise] [--posgrams 长度] [--posgram-min 频次] [--phrases 文件] [--targets 文件] [--recursive] [--allow-all] [--precision 位数] [--decimal .|,] [--delimiter 分隔符|tab] [--surface-form] [--pos-label zh|en] [--rank 指标,...] [--columns 列,...] [--definitions] [--profile research|teaching] [--levels 等级表] [--out 结果.csv] [--report 报告.json] 文件...";
+
+/// 命令行参数
+struct CliArgs {
+    engine: String,
+    cws: String,
+    pos: String,
+    out: Option<String>,
+    format: ExportOptions,
+    /// 输出配置（内置）与等级表
+    profile: Option<OutputProfile>,
+    levels: Option<String>,
+    options: AnalysisOptions,
+    files: Vec<PathBuf>,
+}
+
+/// 首个参数为 analyze 或 golden 时以命令行模式运行并返回退出码，否则返回 None 继续启动界面
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = args.first().map(String::as_str);
+    if !matches!(command, Some("golden" | "analyze")) {
+        return None;
+    }
+    attach_parent_console();
+    if command == Some("golden") {
+        return Some(run_golden(&args[1..]));
+    }
+    let started = Instant::now();
+    let elapsed = || Some(started.elapsed().as_millis() as u64);
+    let report = match parse_args(&args[1..]) {
+        Ok(cli_args) => match analyze(&cli_args, &started) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("{e}");
+                RunReport::failed(1, &e, Some(&cli_args.options), elapsed())
+            }
+        },
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            RunReport::failed(2, &e, None, elapsed())
+        }
+    };
+    // 参数解析失败时也要写出报告，单独查找 --report
+    let report_path = args.windows(2).find(|w| w[0] == "--report").map(|w| &w[1]);
+    if let Some(path) = report_path {
+        if let Err(e) = report.write(Path::new(path)) {
+            eprintln!("运行报告写入失败: {e}");
+            return Some(report.exit_code.max(1));
+        }
+    }
+    Some(report.exit_code)
+}
+
+/// Windows 发行版以 windows_subsystem = "windows" 构建，进程没有控制台，println!/eprintln! 的输出无处可去；
+/// 命令行模式下附加到启动它的终端。输出已重定向到文件或管道时标准句柄保持不变；没有父控制台时附加失败，忽略即可
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: AttachConsole 只读取进程编号，失败时返回 0，不涉及调用方的内存
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// 与数值回归基准比较，或以 --update 重写基准值；有不符时退出码为 1
+fn run_golden(args: &[String]) -> i32 {
+    let mut update = false;
+    let mut dir = None;
+    for arg in args {
+        match arg.as_str() {
+            "--update" => update = true,
+            flag if flag.starts_with("--") => {
+                eprintln!("未知参数: {flag}\n{GOLDEN_USAGE}");
+                return 2;
+            }
+            path => dir = Some(PathBuf::from(path)),
+        }
+    }
+    let dir = dir.unwrap_or_else(golden::fixture_dir);
+    if update {
+        return match golden::update(&dir) {
+            Ok(words) => {
+                println!("已重写 {words} 个词的基准值");
+                0
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                1
+            }
+        };
+    }
+    match golden::check(&dir) {
+        Ok(mismatches) if mismatches.is_empty() => {
+            println!("与基准值一致");
+            0
+        }
+        Ok(mismatches) => {
+            for m in &mismatches {
+                eprintln!(
+                    "{}/{} {}: 基准 {:?}，当前 {:?}",
+                    m.word, m.pos, m.field, m.expected, m.actual
+                );
+            }
+            eprintln!(
+                "{} 处与基准值不符；指标有意变动时以 --update 重写",
+                mismatches.len()
+            );
+            1
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            1
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut cli_args = CliArgs {
+        engine: if cfg!(feature = "ltp") { "ltp" } else { "rule" }.to_string(),
+        cws: "cws_model.bin".to_string(),
+        pos: "pos_model.bin".to_string(),
+        out: None,
+        format: ExportOptions::default(),
+        profile: None,
+        levels: None,
+        options: AnalysisOptions::default(),
+        files: Vec::new(),
+    };
+    let mut input = InputOptions::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("参数 {arg} 缺少取值"))
+        };
+        match arg.as_str() {
+            "--engine" => cli_args.engine = value()?,
+            "--cws" => cli_args.cws = value()?,
+            "--pos" => cli_args.pos = value()?,
+            "--out" => cli_args.out = Some(value()?),
+            "--hash" => cli_args.options.hash_files = true,
+            // 已在 run 中读取
+            "--report" => {
+                value()?;
+            }
+            "--window" => {
+                let window = value()?;
+                let window = window
+                    .parse()
+                    .map_err(|_| format!("窗口词数无效: {window}"))?;
+                cli_args.options.burstiness_window = Some(window);
+            }
+            "--segment-batch" => {
+                let size = value()?;
+                cli_args.options.segment_batch_size = size
+                    .parse()
+                    .map_err(|_| format!("分词批大小无效: {size}"))?;
+            }
+            "--segment-timeout" => {
+                let ms = value()?;
+                cli_args.options.segment_timeout_ms =
+                    Some(ms.parse().map_err(|_| format!("分词时限无效: {ms}"))?);
+            }
+            "--token-policy" => {
+                let name = value()?;
+                cli_args.options.token_policy =
+                    TokenPolicy::preset(&name).ok_or_else(|| format!("未知的词元策略: {name}"))?;
+            }
+            "--latin-case" => {
+                cli_args.options.latin_case = match value()?.as_str() {
+                    "preserve" => LatinCase::Preserve,
+                    "lower" => LatinCase::Lower,
+                    "fold" => LatinCase::Fold,
+                    other => return Err(format!("未知的大小写处理方式: {other}")),
+                };
+            }
+            "--max-surface-forms" => {
+                let forms = value()?;
+                cli_args.options.max_surface_forms =
+                    forms.parse().map_err(|_| format!("写法数无效: {forms}"))?;
+            }
+            "--screen" => {
+                cli_args.options.language_screen.mode = match value()?.as_str() {
+                    "include" => ScreenMode::Include,
+                    "skip" => ScreenMode::Skip,
+                    "whitespace" => ScreenMode::Whitespace,
+                    other => return Err(format!("未知的筛查方式: {other}")),
+                };
+            }
+            "--near-duplicates" => {
+                let near = cli_args
+                    .options
+                    .near_duplicates
+                    .get_or_insert_with(Default::default);
+                near.policy = match value()?.as_str() {
+                    "keep_both" => DuplicatePolicy::KeepBoth,
+                    "keep_longer" => DuplicatePolicy::KeepLonger,
+                    "merge" => DuplicatePolicy::Merge,
+                    other => return Err(format!("未知的近重复处理方式: {other}")),
+                };
+            }
+            "--near-threshold" => {
+                let threshold = value()?;
+                cli_args
+                    .options
+                    .near_duplicates
+                    .get_or_insert_with(Default::default)
+                    .threshold = threshold
+                    .parse()
+                    .map_err(|_| format!("相似度阈值无效: {threshold}"))?;
+            }
+            "--sample-fraction" | "--sample-files" | "--sample-tokens" => {
+                let n = value()?;
+                let invalid = || format!("样本大小无效: {n}");
+                let size = match arg.as_str() {
+                    "--sample-fraction" => SampleSize::Fraction(
+                        n.parse()
+                            .ok()
+                            .filter(|&f: &f64| f > 0.0 && f <= 1.0)
+                            .ok_or_else(invalid)?,
+                    ),
+                    "--sample-files" => SampleSize::Files(n.parse().map_err(|_| invalid())?),
+                    _ => SampleSize::Tokens(n.parse().map_err(|_| invalid())?),
+                };
+                cli_args
+                    .options
+                    .sampling
+                    .get_or_insert_with(Default::default)
+                    .size = size;
+            }
+            "--sample-seed" => {
+                let seed = value()?;
+                cli_args
+                    .options
+                    .sampling
+                    .get_or_insert_with(Default::default)
+                    .seed = seed
+                    .parse()
+                    .map_err(|_| format!("抽样序列编号无效: {seed}"))?;
+            }
+            "--seed" => {
+                let seed = value()?;
+                cli_args.options.seed =
+                    Some(seed.parse().map_err(|_| format!("种子无效: {seed}"))?);
+            }
+            "--min-han" => {
+                let ratio = value()?;
+                cli_args.options.language_screen.min_han_ratio = ratio
+                    .parse()
+                    .map_err(|_| format!("汉字占比无效: {ratio}"))?;
+            }
+            "--stopwords" => {
+                let path = value()?;
+                cli_args.options.stopwords = filter::read_stopwords(Path::new(&path))
+                    .map_err(|e| format!("无法读取停用词表 {path}: {e}"))?;
+            }
+            "--size-excludes" => {
+                let mut denominator = DenominatorPolicy {
+                    stopwords: false,
+                    excluded_pos: false,
+                    symbols: false,
+                    structural_noise: false,
+                };
+                for name in value()?.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                    match name {
+                        "stopwords" => denominator.stopwords = true,
+                        "excluded_pos" => denominator.excluded_pos = true,
+                        "symbols" => denominator.symbols = true,
+                        "structural_noise" => denominator.structural_noise = true,
+                        other => return Err(format!("未知的词数扣除类别: {other}")),
+                    }
+                }
+                cli_args.options.denominator = denominator;
+            }
+            "--protect-quotes" => cli_args.options.protect_quotes = true,
+            "--pos-confidence" => cli_args.options.pos_confidence = true,
+            "--structural-noise" => cli_args.options.structural_noise = true,
+            "--posgrams" => {
+                let n = value()?;
+                cli_args
+                    .options
+                    .posgrams
+                    .get_or_insert_with(Default::default)
+                    .n = n.parse().map_err(|_| format!("词性序列长度无效: {n}"))?;
+            }
+            "--posgram-min" => {
+                let min = value()?;
+                cli_args
+                    .options
+                    .posgrams
+                    .get_or_insert_with(Default::default)
+                    .min_frequency = min
+                    .parse()
+                    .map_err(|_| format!("词性序列最低频次无效: {min}"))?;
+            }
+            "--phrases" => {
+                let path = value()?;
+                cli_args.options.phrases = phrases::read_phrases(Path::new(&path))
+                    .map_err(|e| format!("无法读取短语表 {path}: {e}"))?;
+            }
+            "--targets" => {
+                let path = value()?;
+                cli_args.options.target_words = targets::read_target_words(Path::new(&path))
+                    .map_err(|e| format!("无法读取目标词表 {path}: {e}"))?;
+            }
+            "--precision" => {
+                let precision = value()?;
+                cli_args.format.precision = precision
+                    .parse()
+                    .map_err(|_| format!("小数位数无效: {precision}"))?;
+            }
+            "--definitions" => cli_args.format.definitions = true,
+            "--surface-form" => cli_args.format.surface_form = true,
+            "--decimal" => cli_args.format.decimal_separator = single_char(&value()?)?,
+            "--delimiter" => {
+                cli_args.format.delimiter = match value()?.as_str() {
+                    "tab" => '\t',
+                    other => single_char(other)?,
+                }
+            }
+            "--pos-label" => {
+                cli_args.format.pos_label = match value()?.as_str() {
+                    "zh" => Some(LabelLanguage::Zh),
+                    "en" => Some(LabelLanguage::En),
+                    other => return Err(format!("未知的词性名称语言: {other}")),
+                }
+            }
+            "--rank" => {
+                cli_args.format.ranks = value()?.split(',').map(|m| m.trim().to_string()).collect();
+            }
+            "--columns" => {
+                cli_args.format.columns =
+                    value()?.split(',').map(|c| c.trim().to_string()).collect();
+            }
+            "--profile" => {
+                let name = value()?;
+                cli_args.profile = Some(ProfileSettings::default().find(&name)?);
+            }
+            "--levels" => cli_args.levels = Some(value()?),
+            "--rate-base" => {
+                let base = value()?;
+                cli_args.options.rate_base = base
+                    .parse()
+                    .ok()
+                    .filter(|b: &f64| *b > 0.0)
+                    .ok_or_else(|| format!("缩放基数无效: {base}"))?;
+            }
+            "--range-threshold" => {
+                let threshold = value()?;
+                cli_args.options.range_threshold = threshold
+                    .parse()
+                    .ok()
+                    .filter(|t: &f64| t.is_finite() && *t >= 0.0)
+                    .ok_or_else(|| format!("range 阈值无效: {threshold}"))?;
+            }
+            "--memory-limit" => {
+                let mb = value()?;
+                cli_args.options.memory_limit_mb = Some(
+                    mb.parse()
+                        .ok()
+                        .filter(|&mb: &u64| mb > 0)
+                        .ok_or_else(|| format!("内存上限无效: {mb}"))?,
+                );
+            }
+            "--min-freq" => {
+                let freq = value()?;
+                cli_args.options.min_frequency = Some(
+                    freq.parse()
+                        .ok()
+                        .filter(|f: &f64| *f > 0.0)
+                        .ok_or_else(|| format!("最低频次无效: {freq}"))?,
+                );
+            }
+            "--two-pass" => cli_args.options.two_pass = true,
+            "--quick-look" => {
+                let n = value()?;
+                cli_args.options.quick_look = Some(QuickLook {
+                    top_n: n
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or_else(|| format!("速览词数无效: {n}"))?,
+                    ..Default::default()
+                });
+            }
+            "--strict" => cli_args.options.strict = true,
+            "--oov-pairs" => {
+                let n = value()?;
+                cli_args.options.oov_pairs = Some(
+                    n.parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or_else(|| format!("相邻单字对上限无效: {n}"))?,
+                );
+            }
+            "--min-part-tokens" => {
+                let min = value()?;
+                cli_args.options.min_part_tokens = min
+                    .parse()
+                    .map_err(|_| format!("部分最少词数无效: {min}"))?;
+            }
+            "--merge-small-parts" => cli_args.options.merge_small_parts = true,
+            "--pos-outlier" => cli_args.options.pos_outlier.tag = value()?,
+            "--pos-outlier-z" => {
+                let z = value()?;
+                cli_args.options.pos_outlier.z_threshold =
+                    z.parse().map_err(|_| format!("z 分数无效: {z}"))?;
+            }
+            "--per-sentence" => cli_args.options.partition = PartitionMode::PerSentence,
+            "--positions" => cli_args.options.track_positions = true,
+            "--recursive" => input.recursive = true,
+            "--allow-all" => input.allow_all = true,
+            "--examples" => {
+                let k = value()?;
+                cli_args.options.collect_examples =
+                    k.parse().map_err(|_| format!("例句数无效: {k}"))?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("未知参数: {flag}")),
+            file => cli_args.files.push(paths::normalize(Path::new(file))),
+        }
+    }
+    presets::apply_quick_look(&mut cli_args.options);
+    cli_args.files = inputs::validate_files(&cli_args.files, &input).map_err(|e| e.to_string())?;
+    cli_args.format.validate()?;
+    Ok(cli_args)
+}
+
+/// 分析并导出，返回成功时的运行报告
+fn analyze(cli_args: &CliArgs, started: &Instant) -> Result<RunReport, String> {
+    let nlp: Box<dyn Tokenizer> = match cli_args.engine.as_str() {
+        "rule" => Box::new(RuleTokenizer),
+        "pretagged" => Box::new(PretaggedTokenizer),
+        "ltp" => crate::load_tokenizer(&cli_args.cws, &cli_args.pos, &|_| {})?,
+        other => return Err(format!("未知分词引擎: {other}")),
+    };
+
+    let mut result = corpus_pipeline::analyze_corpus(
+        nlp.as_ref(),
+        &cli_args.files,
+        &cli_args.options,
+        None,
+        &NoopReporter,
+    )
+    .map_err(|e| e.to_string())?;
+    // 命令行输出按 (词, 词性) 排序，保证结果可复现、可比对
+    result
+        .entries
+        .sort_by(|a, b| (&a.word, &a.pos).cmp(&(&b.word, &b.pos)));
+
+    let levels = match &cli_args.levels {
+        Some(path) => profile::read_levels(Path::new(path))
+            .map_err(|e| format!("无法读取等级表 {path}: {e}"))?,
+        None => Default::default(),
+    };
+    let context = ProfileContext::new(&result, levels, &cli_args.format);
+    let write = |writer: &mut dyn Write| match &cli_args.profile {
+        Some(profile) => {
+            export::write_profile_csv(writer, &result, profile, &context, &cli_args.format)
+        }
+        None => export::write_csv(writer, &result, &cli_args.format),
+    };
+    let outputs = match &cli_args.out {
+        Some(path) => {
+            let sidecar = write_atomic(Path::new(path), write)
+                .and_then(|()| {
+                    export::write_metadata_sidecar(
+                        Path::new(path),
+                        &result.metadata,
+                        cli_args.format.definitions,
+                    )
+                })
+                .map_err(|e| e.to_string())?;
+            [Path::new(path), sidecar.as_path()]
+                .map(|p| {
+                    paths::display(&std::path::absolute(p).unwrap_or_else(|_| p.to_path_buf()))
+                })
+                .to_vec()
+        }
+        None => {
+            write(&mut io::stdout().lock()).map_err(|e| format!("导出失败: {e}"))?;
+            Vec::new()
+        }
+    };
+    let elapsed = started.elapsed().as_millis() as u64;
+    Ok(RunReport::succeeded(&result, Some(elapsed), outputs))
+}
+
+/// 解析单个字符的参数值
+fn single_char(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("应为单个字符: {value}")),
+    }
+}
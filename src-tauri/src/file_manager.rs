@@ -0,0 +1,83 @@
+// file_manager.rs
+// 在系统文件管理器中定位文件：Windows 资源管理器 / macOS 访达 / Linux 默认文件管理器
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 在文件管理器中显示 path：文件会被选中，目录则直接打开
+///
+/// 只接受已存在的绝对路径；路径只作为参数传给固定的系统程序，从不被执行
+pub fn reveal(path: &str) -> Result<(), String> {
+    let path = validate(path)?;
+    open_in_file_manager(&path).map_err(|e| format!("无法打开文件管理器 {}: {e}", path.display()))
+}
+
+/// 校验并规范化路径
+fn validate(path: &str) -> Result<PathBuf, String> {
+    let raw = Path::new(path);
+    if !raw.is_absolute() {
+        return Err(format!("路径必须为绝对路径: {path}"));
+    }
+    let canonical = raw
+        .canonicalize()
+        .map_err(|e| format!("路径不存在或无法访问 {path}: {e}"))?;
+    Ok(strip_verbatim_prefix(canonical))
+}
+
+/// Windows 下 canonicalize 返回 \\?\ 前缀路径，资源管理器无法识别，需还原为普通形式
+#[cfg(windows)]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let Some(s) = path.to_str() else {
+        return path;
+    };
+    if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{rest}"))
+    } else if let Some(rest) = s.strip_prefix(r"\\?\") {
+        PathBuf::from(rest)
+    } else {
+        path
+    }
+}
+
+#[cfg(not(windows))]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    path
+}
+
+#[cfg(windows)]
+fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    use std::os::windows::process::CommandExt;
+
+    // explorer 自行解析命令行，须写成 /select,"路径" 才能正确处理空格；
+    // Windows 路径不可能包含双引号，无需转义
+    let mut command = Command::new("explorer");
+    if path.is_dir() {
+        command.arg(path);
+    } else {
+        command.raw_arg(format!("/select,\"{}\"", path.display()));
+    }
+    // explorer 即使成功也常返回非零退出码，只关心能否启动
+    command.spawn().map(|_| ())
+}
+
+#[cfg(target_os = "macos")]
+fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    let status = Command::new("open").arg("-R").arg(path).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("open -R 退出码 {status}")))
+    }
+}
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    // 只打开目录，避免 xdg-open 按文件类型启动其他程序
+    let dir = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    Command::new("xdg-open").arg(dir).spawn().map(|_| ())
+}
@@ -1,59 +1,1938 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod analysis;
+mod cli;
+mod file_manager;
+mod watcher;
+#[cfg(feature = "ltp")]
 use std::env::current_exe;
-use std::path::PathBuf;
 
-use analysis::{corpus_pipeline, nlp::LtpNlp};
+#[cfg(feature = "ltp")]
+use betawordlist_lib::analysis::nlp::LtpNlp;
+#[cfg(not(feature = "ltp"))]
+use betawordlist_lib::analysis::nlp::RuleTokenizer;
+use betawordlist_lib::analysis::{
+    atomic::write_atomic,
+    batch_export::{self, BatchOptions, FormatReport},
+    bootstrap::{self, CorpusBootstrap},
+    character_families::{self, CharacterFamilies, CharacterFamilyOptions},
+    checkpoint::{Checkpoint, CheckpointInfo},
+    corpus_pipeline::{
+        self, AnalysisResponse, AnalysisResult, AnalysisSummary, CorpusTotals, EntryDetail,
+    },
+    correlation::{self, MetricCorrelation, RankDisagreement},
+    count_import::{self, MatrixFormat},
+    coverage::{self, ListCoverage},
+    dictionary::{self, DictionaryExport, DictionaryOptions},
+    diff::{self, ResultDiff},
+    entry_flags::{self, EntryFlag},
+    event_forwarder::ForwardingReporter,
+    export::{self, ExportOptions},
+    filter::{self, FilterReport},
+    group_keywords::{self, GroupKeywordOptions, GroupKeywords},
+    inputs::{self, InputOptions},
+    json_export::{self, JsonExportOptions},
+    length::{self, EntryPage, EntryQuery, LengthBucket},
+    merge,
+    metric_descriptions::{self, MetricDescription, MetricSchema},
+    nlp::{ModelLoadPhase, Tokenizer},
+    oov::{self, OovOptions, OovReport},
+    options::{AnalysisOptions, PartitionMode},
+    order_invariance::{self, OrderInvarianceReport},
+    partition_advice::{self, PartitionSuggestion},
+    paths, phrases,
+    presets::{self, AnalysisPreset, PresetSettings},
+    profile::{self, OutputProfile, ProfileContext, ProfileSettings, ProfileTable},
+    progress::{
+        AnalysisError, AnalysisRun, AnalysisStatus, EventChannels, ModelLoadProgress,
+        ProgressReporter, TauriReporter, MODEL_LOAD_PROGRESS_EVENT,
+    },
+    rank, reanalyze,
+    resource_bundle::{self, ImportedBundle, ResourceKind},
+    result_slots::{LoadedResource, ResultSlotInfo, LATEST_SLOT},
+    run_report,
+    selection::{self, Selection, SelectionReport},
+    session,
+    spectrum::{self, FrequencySpectrum},
+    tag_confidence::{self, LowConfidenceItem},
+    tagset::{self, TagInfo},
+    targets,
+    token_policy::TokenPolicy,
+    vertical::{self, VerticalOptions},
+    word_sketch::{SketchCounter, SketchOptions, SketchSettings, WordSketch},
+    worker::ResultWorker,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, State};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+use watcher::Watcher;
 
 /// 应用状态
+///
+/// 只有结果槽经结果工作线程；其余状态各有自己的锁，只在内存中短暂持有，不跨 await，也不在持锁时读写文件：
+/// 运行登记须在工作线程忙于长时间的请求时也能立即取消，分词器由分析线程在整个分词期间持有，
+/// 目录监视持有自己的线程，导入资源只是一份选项
 struct AppState {
-    nlp: Arc<Mutex<Option<LtpNlp>>>,
+    nlp: Arc<Mutex<Option<Box<dyn Tokenizer>>>>,
+    /// 后端保留的分析结果槽，由结果工作线程独占，见 worker 与 result_slots；
+    /// 分页、导出等命令的 slot 缺省时取最近写入的槽
+    results: ResultWorker,
+    /// 正在运行的目录监视
+    watcher: Mutex<Option<Watcher>>,
+    /// 是否有 load_models 正在进行
+    loading_models: AtomicBool,
+    /// 正在进行的分析运行，最近启动的在末尾
+    runs: Arc<Mutex<Vec<AnalysisRun>>>,
+    /// 已导入资源包拼成的分析选项；None 时首次取用从应用数据目录中已安装的资源读回
+    resources: Mutex<Option<AnalysisOptions>>,
 }
 
-/// 启动分析任务
+impl AppState {
+    fn new() -> Self {
+        Self {
+            nlp: Arc::new(Mutex::new(None)),
+            results: ResultWorker::spawn(),
+            watcher: Mutex::new(None),
+            loading_models: AtomicBool::new(false),
+            runs: Arc::new(Mutex::new(Vec::new())),
+            resources: Mutex::new(None),
+        }
+    }
+
+    /// 取消 run_id 对应的运行（缺省为最近启动的运行），返回是否有运行被取消
+    fn cancel_run(&self, run_id: Option<&str>) -> bool {
+        let runs = self.runs.lock().unwrap();
+        let run = match run_id {
+            Some(id) => runs.iter().find(|run| run.run_id == id),
+            None => runs.last(),
+        };
+        run.inspect(|run| run.cancel()).is_some()
+    }
+
+    /// 最近启动且仍在进行的运行
+    fn latest_run(&self) -> Option<String> {
+        self.runs
+            .lock()
+            .unwrap()
+            .last()
+            .map(|run| run.run_id.clone())
+    }
+
+    /// 在结果工作线程上以 slot 中的结果（缺省为最近写入的槽）执行 task；task 只作读取与复制，不读写文件
+    async fn with_result<T, F>(&self, slot: Option<String>, task: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AnalysisResult) -> Result<T, String> + Send + 'static,
+    {
+        self.results
+            .run(move |slots| task(slots.get(slot.as_deref())?))
+            .await
+    }
+
+    /// slot 中结果的副本，供耗时的计算在工作线程之外进行，不阻塞其他命令
+    async fn clone_result(&self, slot: Option<String>) -> Result<AnalysisResult, String> {
+        self.with_result(slot, |result| Ok(result.clone())).await
+    }
+
+    /// 以 slot 中结果的副本在阻塞线程池中执行 task，供导出等读写文件的命令
+    async fn with_result_copy<T, F>(&self, slot: Option<String>, task: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AnalysisResult) -> Result<T, String> + Send + 'static,
+    {
+        let result = self.clone_result(slot).await?;
+        blocking(move || task(&result)).await
+    }
+}
+
+/// 在阻塞线程池中执行 task：文件读写、持有模型锁的分词等同步操作不占用异步运行时的线程
+async fn blocking<T, F>(task: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(task)
+        .await
+        .map_err(|e| format!("后台任务失败: {e}"))?
+}
+
+/// 启动分析任务；若设置了 checkpoint_every，会丢弃同一任务的旧检查点并重新开始。
+/// input 控制目录展开与扩展名检查，见 inputs::validate_files；
+/// preset 为分析预设名，options 中给出的字段覆盖预设中的同名选项。
+/// 只返回结果摘要，条目留在后端，经 get_results_page / get_entries 分页或 get_all_results 一次取回；
+/// return_entries 为 true 时照旧返回完整结果；开启速览模式（quick_look）时结果已截断，直接返回完整结果
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn start_analysis(
     app_handle: AppHandle,
     state: State<'_, AppState>,
-    file_paths: Vec<String>,
-) -> Result<
-    Vec<(
-        String,
-        String,
-        analysis::dispersion_metrics::DispersionMetrics,
-    )>,
-    String,
-> {
-    let nlp_guard = state.nlp.lock().unwrap();
-    let nlp = nlp_guard.as_ref().ok_or("NLP模型未加载")?;
+    file_paths: Vec<PathBuf>,
+    input: Option<InputOptions>,
+    preset: Option<String>,
+    options: Option<serde_json::Value>,
+    channels: Option<EventChannels>,
+    return_entries: Option<bool>,
+) -> Result<AnalysisResponse, String> {
+    let request = AnalysisRequest {
+        file_paths,
+        input: input.unwrap_or_default(),
+        preset,
+        options,
+        channels,
+        resume: false,
+    };
+    spawn_analysis(app_handle, &state, request, return_entries.unwrap_or(false)).await
+}
+
+/// 从检查点继续上次中断的分析；文件列表、预设与选项须与中断时一致，返回值同 start_analysis
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn resume_analysis(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    file_paths: Vec<PathBuf>,
+    input: Option<InputOptions>,
+    preset: Option<String>,
+    options: Option<serde_json::Value>,
+    channels: Option<EventChannels>,
+    return_entries: Option<bool>,
+) -> Result<AnalysisResponse, String> {
+    let request = AnalysisRequest {
+        file_paths,
+        input: input.unwrap_or_default(),
+        preset,
+        options,
+        channels,
+        resume: true,
+    };
+    spawn_analysis(app_handle, &state, request, return_entries.unwrap_or(false)).await
+}
+
+/// 取消分析；给出 run_id 时只取消该运行，不会误取消之后启动的运行，缺省时取消最近启动的运行。
+/// 返回是否有运行被取消；分析在处理下一个文件前中止，并推送 error 事件
+#[tauri::command]
+fn cancel_analysis(state: State<'_, AppState>, run_id: Option<String>) -> Result<bool, String> {
+    Ok(state.cancel_run(run_id.as_deref()))
+}
 
-    Ok(corpus_pipeline::analyze_corpus(
-        nlp,
+/// 当前分析状态
+#[tauri::command]
+fn get_analysis_status(state: State<'_, AppState>) -> AnalysisStatus {
+    AnalysisStatus {
+        run_id: state.latest_run(),
+    }
+}
+
+/// 不经分词粗估语料词次，给出切成 chunk_counts 中各块数（缺省为 10、50、100）时每块的词次，
+/// 每块少于 1000 词次时提示指标不稳定
+#[tauri::command]
+async fn suggest_partition(
+    file_paths: Vec<PathBuf>,
+    input: Option<InputOptions>,
+    chunk_counts: Option<Vec<usize>>,
+) -> Result<PartitionSuggestion, String> {
+    let file_paths = resolve_files(&file_paths, &input.unwrap_or_default())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        partition_advice::suggest_partition(&file_paths, &chunk_counts.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("估算任务失败: {e}"))
+}
+
+/// 查询同一文件列表与选项是否留有可续跑的检查点
+#[tauri::command]
+async fn find_checkpoint(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    file_paths: Vec<PathBuf>,
+    input: Option<InputOptions>,
+    preset: Option<String>,
+    options: Option<serde_json::Value>,
+) -> Result<Option<CheckpointInfo>, String> {
+    let options = analysis_options(&app_handle, preset.as_deref(), options)?;
+    let nlp_guard = state.nlp.lock().unwrap();
+    let nlp = nlp_guard.as_deref().ok_or("NLP模型未加载")?;
+    let file_paths = resolve_files(&file_paths, &input.unwrap_or_default())?;
+    let checkpoint = Checkpoint::new(
+        &checkpoint_root(&app_handle)?,
         &file_paths,
-        Some(&app_handle),
-    ))
+        &options,
+        nlp.engine(),
+    );
+    Ok(checkpoint.info(file_paths.len()))
+}
+
+/// start_analysis / resume_analysis 的参数
+struct AnalysisRequest {
+    file_paths: Vec<PathBuf>,
+    input: InputOptions,
+    /// 预设名；options 中给出的字段覆盖预设，合并结果记入元数据
+    preset: Option<String>,
+    options: Option<serde_json::Value>,
+    channels: Option<EventChannels>,
+    resume: bool,
+}
+
+/// 在阻塞线程池中执行分析，命令所在的异步运行时不被占用；运行期间登记在 AppState.runs 中。
+/// 结果存入 AppState.results 的 LATEST_SLOT，return_entries 为 false 时只返回摘要
+async fn spawn_analysis(
+    app_handle: AppHandle,
+    state: &AppState,
+    request: AnalysisRequest,
+    return_entries: bool,
+) -> Result<AnalysisResponse, String> {
+    let started = Instant::now();
+    let nlp = state.nlp.clone();
+    let results = state.results.clone();
+    let run = AnalysisRun::default();
+    let run_id = run.run_id.clone();
+    state.runs.lock().unwrap().push(run.clone());
+    let outcome = tauri::async_runtime::spawn_blocking(move || {
+        run_analysis(&app_handle, &nlp, &results, run, request)
+    })
+    .await
+    .map_err(|e| format!("分析任务失败: {e}"));
+    state
+        .runs
+        .lock()
+        .unwrap()
+        .retain(|run| run.run_id != run_id);
+    let result = outcome??;
+    Ok(if return_entries || result.metadata.truncation.is_some() {
+        AnalysisResponse::Full(Box::new(result))
+    } else {
+        AnalysisResponse::Summary(result.summary(started.elapsed().as_millis() as u64))
+    })
+}
+
+/// 执行分析并推送事件；channels 缺省为 progress / analysis-complete / analysis-error，
+/// 开始前即失败时同样推送 error 事件。事件经转发线程合并、限速后送往前端，分析线程不等待界面。
+/// 每个事件与结果元数据都带有 run.run_id
+fn run_analysis(
+    app_handle: &AppHandle,
+    nlp: &Mutex<Option<Box<dyn Tokenizer>>>,
+    results: &ResultWorker,
+    run: AnalysisRun,
+    request: AnalysisRequest,
+) -> Result<AnalysisResult, String> {
+    let AnalysisRequest {
+        file_paths,
+        input,
+        preset,
+        options,
+        channels,
+        resume,
+    } = request;
+    let run_id = run.run_id.clone();
+    let reporter = ForwardingReporter::spawn(Arc::new(TauriReporter::new(
+        app_handle.clone(),
+        channels.unwrap_or_default(),
+        run,
+    )));
+    let report = |message: &String| {
+        reporter.error(&AnalysisError {
+            message: message.clone(),
+        })
+    };
+    let options = analysis_options(app_handle, preset.as_deref(), options).inspect_err(report)?;
+    let file_paths = &resolve_files(&file_paths, &input).inspect_err(report)?;
+    let nlp_guard = nlp.lock().unwrap();
+    let (nlp, options, checkpoint) = prepare_analysis(
+        app_handle,
+        nlp_guard.as_deref(),
+        file_paths,
+        options,
+        resume,
+    )
+    .inspect_err(report)?;
+
+    // 分析过程中的中止已由 analyze_corpus 上报
+    let mut result =
+        corpus_pipeline::analyze_corpus(nlp, file_paths, &options, checkpoint.as_ref(), &reporter)
+            .map_err(|e| e.to_string())?;
+    result.metadata.preset = preset;
+    result.metadata.run_id = Some(run_id);
+    results.blocking_store(LATEST_SLOT, result.clone());
+    Ok(result)
+}
+
+/// 检查模型并准备检查点；非续跑时清除旧检查点
+fn prepare_analysis<'a>(
+    app_handle: &AppHandle,
+    nlp: Option<&'a dyn Tokenizer>,
+    file_paths: &[PathBuf],
+    options: AnalysisOptions,
+    resume: bool,
+) -> Result<(&'a dyn Tokenizer, AnalysisOptions, Option<Checkpoint>), String> {
+    let nlp = nlp.ok_or("NLP模型未加载")?;
+    let checkpoint = match options.checkpoint_every {
+        Some(_) => Some(Checkpoint::new(
+            &checkpoint_root(app_handle)?,
+            file_paths,
+            &options,
+            nlp.engine(),
+        )),
+        None => None,
+    };
+    if let (Some(cp), false) = (&checkpoint, resume) {
+        cp.clear().map_err(|e| format!("无法清除旧检查点: {e}"))?;
+    }
+    Ok((nlp, options, checkpoint))
+}
+
+/// 规范化前端传入的语料路径（Windows 长路径加 \\?\ 前缀），校验并展开目录
+fn resolve_files(file_paths: &[PathBuf], input: &InputOptions) -> Result<Vec<PathBuf>, String> {
+    let file_paths: Vec<PathBuf> = file_paths.iter().map(|p| paths::normalize(p)).collect();
+    inputs::validate_files(&file_paths, input).map_err(|e| e.to_string())
+}
+
+/// 监视目录，文件新增、修改或删除后增量重新分析并推送 results-updated 事件；
+/// extensions 缺省为 ["txt"]，已有监视会先停止
+#[tauri::command]
+async fn start_watching(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    dir: PathBuf,
+    extensions: Option<Vec<String>>,
+    options: Option<AnalysisOptions>,
+) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err(format!("目录不存在: {}", dir.display()));
+    }
+    let options = options.unwrap_or_default();
+    // 增量重算按文件缓存部分计数
+    if options.partition != PartitionMode::PerFile {
+        return Err("目录监视只支持按文件切分".to_string());
+    }
+    if options.near_duplicates.is_some() {
+        return Err("目录监视不支持近重复检测".to_string());
+    }
+    let mut watcher_guard = state.watcher.lock().unwrap();
+    watcher_guard.take();
+    *watcher_guard = Some(Watcher::start(
+        app_handle,
+        state.nlp.clone(),
+        state.results.clone(),
+        paths::normalize(&dir),
+        extensions.unwrap_or_else(|| vec!["txt".to_string()]),
+        options,
+    ));
+    Ok(())
+}
+
+/// 停止目录监视
+#[tauri::command]
+async fn stop_watching(state: State<'_, AppState>) -> Result<(), String> {
+    state.watcher.lock().unwrap().take();
+    Ok(())
+}
+
+/// 应用配置目录下的 settings.json
+fn settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("无法定位应用配置目录: {e}"))?
+        .join("settings.json"))
+}
+
+/// 读取应用配置目录下的 settings.json
+fn profile_settings(app_handle: &AppHandle) -> Result<ProfileSettings, String> {
+    let path = settings_path(app_handle)?;
+    ProfileSettings::load(&path).map_err(|e| format!("设置读取失败 {}: {e}", path.display()))
+}
+
+/// 读取应用配置目录下 settings.json 中的分析预设
+fn preset_settings(app_handle: &AppHandle) -> Result<PresetSettings, String> {
+    let path = settings_path(app_handle)?;
+    PresetSettings::load(&path).map_err(|e| format!("设置读取失败 {}: {e}", path.display()))
+}
+
+/// 以预设为底合并显式传入的选项；未指定预设时以默认选项为底
+fn analysis_options(
+    app_handle: &AppHandle,
+    preset: Option<&str>,
+    options: Option<serde_json::Value>,
+) -> Result<AnalysisOptions, String> {
+    let preset = match preset {
+        Some(name) => Some(preset_settings(app_handle)?.find(name)?),
+        None => None,
+    };
+    presets::resolve_options(preset.as_ref(), options)
+}
+
+/// 记下本次导出条目表所用的列，写入 settings.json 的 export_columns
+fn remember_columns(app_handle: &AppHandle, columns: &[String]) -> Result<(), String> {
+    let columns = serde_json::to_value(columns).map_err(|e| e.to_string())?;
+    presets::save_setting(&settings_path(app_handle)?, "export_columns", columns)
+}
+
+/// 按名称查找输出配置，连同所在的设置一并返回
+fn find_profile(
+    app_handle: &AppHandle,
+    name: &str,
+) -> Result<(ProfileSettings, OutputProfile), String> {
+    let settings = profile_settings(app_handle)?;
+    let profile = settings.find(name)?;
+    Ok((settings, profile))
+}
+
+/// 输出配置的渲染上下文；设置中指定了等级表时一并读取
+fn profile_context(
+    settings: &ProfileSettings,
+    result: &AnalysisResult,
+    format: &ExportOptions,
+) -> Result<ProfileContext, String> {
+    let levels = match &settings.level_list {
+        Some(path) => profile::read_levels(path)
+            .map_err(|e| format!("无法读取等级表 {}: {e}", path.display()))?,
+        None => Default::default(),
+    };
+    Ok(ProfileContext::new(result, levels, format))
+}
+
+/// 检查点保存在应用数据目录下
+fn checkpoint_root(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("checkpoints"))
+        .map_err(|e| format!("无法定位应用数据目录: {e}"))
+}
+
+/// 从资源包安装的词表与选项保存在应用数据目录下
+fn resource_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("resources"))
+        .map_err(|e| format!("无法定位应用数据目录: {e}"))
+}
+
+/// 将最近一次分析结果导出为 CSV（附带 .meta.json 元数据），返回 CSV 的绝对路径；
+/// format 指定小数位数、小数点与分隔符，profile 指定输出配置（缺省为默认列）
+#[tauri::command]
+async fn export_csv(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<ExportOptions>,
+    profile: Option<String>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format_given = format.is_some();
+    let format = export_format(format)?;
+    let profile = profile
+        .map(|name| find_profile(&app_handle, &name))
+        .transpose()?;
+    if format_given && profile.is_none() {
+        remember_columns(&app_handle, &format.columns)?;
+    }
+    state
+        .with_result_copy(slot, move |result| {
+            match &profile {
+                Some((settings, profile)) => {
+                    let context = profile_context(settings, result, &format)?;
+                    write_atomic(Path::new(&path), |w| {
+                        export::write_profile_csv(w, result, profile, &context, &format)
+                    })
+                }
+                None => write_atomic(Path::new(&path), |w| export::write_csv(w, result, &format)),
+            }
+            .map_err(|e| e.to_string())?;
+            export::write_metadata_sidecar(Path::new(&path), &result.metadata, format.definitions)
+                .map_err(|e| e.to_string())?;
+            absolute_path(&path)
+        })
+        .await
+}
+
+/// 将最近一次分析结果导出为频率词典（附带 .meta.json 元数据）：路径以 .xlsx 结尾时每段一张表另加 summary 表，
+/// 否则为带 band 列的单张表格；返回绝对路径、各段汇总与截断处的并列说明
+#[tauri::command]
+async fn export_dictionary(
+    state: State<'_, AppState>,
+    path: String,
+    options: Option<DictionaryOptions>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<DictionaryExport, String> {
+    let format = export_format(format)?;
+    state
+        .with_result_copy(slot, move |result| {
+            let dictionary = dictionary::build(result, &options.unwrap_or_default())?;
+            let xlsx = Path::new(&path)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"));
+            write_atomic(Path::new(&path), |w| {
+                if xlsx {
+                    export::write_dictionary_xlsx(w, &dictionary, &format)
+                } else {
+                    export::write_dictionary_csv(w, &dictionary, &format)
+                }
+            })
+            .map_err(|e| e.to_string())?;
+            export::write_metadata_sidecar(Path::new(&path), &result.metadata, format.definitions)
+                .map_err(|e| e.to_string())?;
+            Ok(DictionaryExport {
+                path: absolute_path(&path)?,
+                bands: dictionary.bands,
+                tie_note: dictionary.tie_note,
+            })
+        })
+        .await
+}
+
+/// 将最近一次分析结果导出为 JSON（元数据与全部条目，结构见 get_result_schema），返回绝对路径
+#[tauri::command]
+async fn export_results_json(
+    state: State<'_, AppState>,
+    path: String,
+    options: Option<JsonExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    state
+        .with_result_copy(slot, move |result| {
+            write_atomic(Path::new(&path), |w| {
+                json_export::write_json(w, result, &options)
+            })
+            .map_err(|e| e.to_string())?;
+            absolute_path(&path)
+        })
+        .await
+}
+
+/// 结果 JSON 的 JSON Schema，供外部程序校验导出文件
+#[tauri::command]
+async fn get_result_schema() -> Result<schemars::schema::RootSchema, String> {
+    Ok(json_export::result_schema())
+}
+
+/// 运行报告（BatchOptions::report 与命令行 --report）的 JSON Schema
+#[tauri::command]
+async fn get_run_report_schema() -> Result<schemars::schema::RootSchema, String> {
+    Ok(run_report::report_schema())
 }
 
-/// 加载NLP模型
+/// 最近一次分析结果对词表的覆盖率：各文件与全语料的词次、词型覆盖率，以及频次最高的未覆盖词（默认前 100 个）
+#[tauri::command]
+async fn compute_list_coverage(
+    state: State<'_, AppState>,
+    list_path: String,
+    top: Option<usize>,
+    slot: Option<String>,
+) -> Result<ListCoverage, String> {
+    list_coverage(&state, &list_path, top, slot).await
+}
+
+/// 将词表覆盖率导出为 CSV（各文件一行，末行为全语料），未覆盖的词另存为 .uncovered.csv；返回 CSV 的绝对路径
+#[tauri::command]
+async fn export_list_coverage(
+    state: State<'_, AppState>,
+    list_path: String,
+    path: String,
+    top: Option<usize>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let coverage = list_coverage(&state, &list_path, top, slot).await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_coverage_csv(w, &coverage.corpus, &coverage.files, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        let uncovered_path = Path::new(&path).with_extension("uncovered.csv");
+        write_atomic(&uncovered_path, |w| {
+            export::write_uncovered_csv(w, &coverage.uncovered, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+async fn list_coverage(
+    state: &AppState,
+    list_path: &str,
+    top: Option<usize>,
+    slot: Option<String>,
+) -> Result<ListCoverage, String> {
+    let list_path = list_path.to_string();
+    let list = blocking(move || {
+        coverage::read_word_list(Path::new(&list_path))
+            .map_err(|e| format!("无法读取词表 {list_path}: {e}"))
+    })
+    .await?;
+    state
+        .with_result(slot, move |result| {
+            coverage::list_coverage(result, &list, top.unwrap_or(100))
+        })
+        .await
+}
+
+/// 将最近一次分析检测到的近重复文件对导出为 CSV，供人工复核；返回绝对路径
+#[tauri::command]
+async fn export_near_duplicates(
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let pairs = state
+        .with_result(slot, |result| Ok(result.metadata.near_duplicates.clone()))
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_near_duplicates_csv(w, &pairs, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 将最近一次分析中各部分的词性分布导出为 CSV（part, label, pos, tokens, share, z），z 标出占比异常的部分；返回绝对路径
+#[tauri::command]
+async fn export_pos_histogram(
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let metadata = state
+        .with_result(slot, |result| Ok(result.metadata.clone()))
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_pos_histogram_csv(w, &metadata, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 将最近一次分析的词频矩阵（词 × 语料部分）导出为 CSV，返回绝对路径；需以 keep_parts 分析
+#[tauri::command]
+async fn export_frequency_matrix(
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    state
+        .with_result_copy(slot, move |result| {
+            if result.parts.len() != result.metadata.parts.len() || result.parts.is_empty() {
+                return Err("结果未保留各部分词频，请开启 keep_parts 重新分析".to_string());
+            }
+            write_atomic(Path::new(&path), |w| {
+                export::write_frequency_matrix_csv(w, result, &format)
+            })
+            .map_err(|e| e.to_string())?;
+            absolute_path(&path)
+        })
+        .await
+}
+
+/// 按分组表计算最近一次分析结果中各组对其余各组的关键词（对数似然与 log ratio）；需以 keep_parts 分析。
+/// 分组表由 groups（文件 → 组名）给出，或由 group_file 指定的 "文件<TAB>组名" 文件读取，文件可为完整路径或文件名
+#[tauri::command]
+async fn get_group_keywords(
+    state: State<'_, AppState>,
+    groups: Option<HashMap<String, String>>,
+    group_file: Option<String>,
+    top_n: Option<usize>,
+    min_freq: Option<f64>,
+    slot: Option<String>,
+) -> Result<Vec<GroupKeywords>, String> {
+    let groups = group_mapping(groups, group_file)?;
+    let defaults = GroupKeywordOptions::default();
+    let options = GroupKeywordOptions {
+        top_n: top_n.unwrap_or(defaults.top_n),
+        min_frequency: min_freq.unwrap_or(defaults.min_frequency),
+    };
+    state
+        .with_result(slot, move |result| {
+            group_keywords::group_keywords(&result.metadata.parts, &result.parts, &groups, &options)
+        })
+        .await
+}
+
+/// 将各组关键词导出，返回绝对路径：路径以 .xlsx 结尾时每组一张表另加 summary 表，否则为带 group 列的单张表格
+#[tauri::command]
+async fn export_group_keywords(
+    state: State<'_, AppState>,
+    path: String,
+    groups: Option<HashMap<String, String>>,
+    group_file: Option<String>,
+    options: Option<GroupKeywordOptions>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let groups = group_mapping(groups, group_file)?;
+    let keywords = state
+        .with_result(slot, move |result| {
+            group_keywords::group_keywords(
+                &result.metadata.parts,
+                &result.parts,
+                &groups,
+                &options.unwrap_or_default(),
+            )
+        })
+        .await?;
+    let xlsx = Path::new(&path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"));
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            if xlsx {
+                export::write_group_keywords_xlsx(w, &keywords, &format)
+            } else {
+                export::write_group_keywords_csv(w, &keywords, &format)
+            }
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 分组表：显式给出的映射优先，否则读取分组文件
+fn group_mapping(
+    groups: Option<HashMap<String, String>>,
+    group_file: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    match (groups, group_file) {
+        (Some(groups), _) => Ok(groups),
+        (None, Some(path)) => group_keywords::read_groups(Path::new(&path))
+            .map_err(|e| format!("无法读取分组表 {path}: {e}")),
+        (None, None) => Err("请提供分组表（groups 或 group_file）".to_string()),
+    }
+}
+
+/// 一次导出多种格式（csv、tsv、xlsx、json、vertical）到 <path_base>.<扩展名>，
+/// 各格式共用一次对结果的遍历；某一格式失败不影响其余格式，按格式逐一报告
+#[tauri::command]
+async fn export_results(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    path_base: String,
+    formats: Vec<String>,
+    options: Option<BatchOptions>,
+    slot: Option<String>,
+) -> Result<Vec<FormatReport>, String> {
+    let options_given = options.is_some();
+    let options = options.unwrap_or_default();
+    options.table.validate()?;
+    if options_given {
+        remember_columns(&app_handle, &options.table.columns)?;
+    }
+    // 竖排格式需要重新分词，在工作线程之外导出，以免等待模型时阻塞其他命令
+    let result = state.clone_result(slot).await?;
+    let nlp = Arc::clone(&state.nlp);
+    blocking(move || {
+        let nlp_guard = nlp.lock().unwrap();
+        Ok(batch_export::export_batch(
+            &result,
+            nlp_guard.as_deref(),
+            Path::new(&path_base),
+            &formats,
+            &options,
+        ))
+    })
+    .await
+}
+
+/// 只导出所选的条目：selection 为条目 id 或 (词形, 词性) 的列表（按给出的顺序导出），或与 get_entries 相同的筛选条件
+/// （导出筛选后的全部条目）；格式与选项同 export_results，结果中找不到的条目在报告中列出
+#[tauri::command]
+async fn export_selection(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    selection: Selection,
+    path_base: String,
+    formats: Vec<String>,
+    options: Option<BatchOptions>,
+    slot: Option<String>,
+) -> Result<SelectionReport, String> {
+    let options_given = options.is_some();
+    let options = options.unwrap_or_default();
+    options.table.validate()?;
+    selection.validate()?;
+    if options_given {
+        remember_columns(&app_handle, &options.table.columns)?;
+    }
+    let result = state.clone_result(slot).await?;
+    blocking(move || {
+        selection::export_selection(
+            &result,
+            &selection,
+            Path::new(&path_base),
+            &formats,
+            &options,
+        )
+    })
+    .await
+}
+
+/// 将最近一次分析的语料以竖排格式（word<TAB>pos）导出，返回写出文件的绝对路径
+#[tauri::command]
+async fn export_vertical(
+    state: State<'_, AppState>,
+    path: String,
+    options: Option<VerticalOptions>,
+    slot: Option<String>,
+) -> Result<Vec<String>, String> {
+    let metadata = state
+        .with_result(slot, |result| Ok(result.metadata.clone()))
+        .await?;
+    let nlp = Arc::clone(&state.nlp);
+    blocking(move || {
+        let nlp_guard = nlp.lock().unwrap();
+        let nlp = nlp_guard.as_deref().ok_or("NLP模型未加载")?;
+        let written = vertical::export(
+            nlp,
+            &metadata,
+            Path::new(&path),
+            &options.unwrap_or_default(),
+        )
+        .map_err(|e| e.to_string())?;
+        written
+            .iter()
+            .map(|p| absolute_path(&p.to_string_lossy()))
+            .collect()
+    })
+    .await
+}
+
+/// 最近一次分析中某个词的词语素描：按记录的文件与选项重新分词，按条目的全部原文写法（见 WordEntry::search_forms）匹配节点词，
+/// 返回适用于该词性的各关系下按 logDice 排序的搭配词；
+/// 关系表为内置关系与 settings.json 中的 sketch_relations
+#[tauri::command]
+async fn get_word_sketch(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    word: String,
+    pos: String,
+    options: Option<SketchOptions>,
+    slot: Option<String>,
+) -> Result<WordSketch, String> {
+    let path = settings_path(&app_handle)?;
+    let relations = SketchSettings::load(&path)
+        .map_err(|e| format!("设置读取失败 {}: {e}", path.display()))?
+        .all_relations()?;
+    if !relations.iter().any(|r| r.applies_to(&pos)) {
+        return Err(format!("没有适用于词性 {pos} 的关系"));
+    }
+    // 原文分词未经规范化，按条目的全部写法匹配节点词
+    let (metadata, forms) = state
+        .with_result(slot, {
+            let (word, pos) = (word.clone(), pos.clone());
+            move |result| {
+                let forms = match result
+                    .entries
+                    .iter()
+                    .find(|e| e.word == word && e.pos == pos)
+                {
+                    Some(entry) => entry.search_forms().into_iter().map(String::from).collect(),
+                    None => vec![word],
+                };
+                Ok((result.metadata.clone(), forms))
+            }
+        })
+        .await?;
+    let nlp = Arc::clone(&state.nlp);
+    blocking(move || {
+        let nlp_guard = nlp.lock().unwrap();
+        let nlp = nlp_guard.as_deref().ok_or("NLP模型未加载")?;
+        let mut counter = SketchCounter::new(&word, &pos, relations).with_forms(forms);
+        for file in &metadata.files {
+            let source = paths::normalize(Path::new(&file.path));
+            if let Some(word_pos) = corpus_pipeline::tokenize_file(nlp, &source, &metadata.options)
+            {
+                counter.add_text(&word_pos);
+            }
+        }
+        Ok(counter.finish(&options.unwrap_or_default()))
+    })
+    .await
+}
+
+/// 最近一次分析的过滤报告：各项已启用的过滤移除的词元数、词型数、各文件的移除数与移除最多的词型
+#[tauri::command]
+async fn get_filter_report(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<FilterReport, String> {
+    state
+        .with_result(slot, |result| Ok(result.metadata.filter_report.clone()))
+        .await
+}
+
+/// 最近一次分析结果的频次谱，频次大于 cap（默认 50）的类型归入尾部桶
+#[tauri::command]
+async fn get_frequency_spectrum(
+    state: State<'_, AppState>,
+    cap: Option<u64>,
+    slot: Option<String>,
+) -> Result<FrequencySpectrum, String> {
+    state
+        .with_result(slot, move |result| {
+            Ok(spectrum::frequency_spectrum(
+                result.entries.iter().map(|e| e.frequency),
+                cap.unwrap_or(50),
+            ))
+        })
+        .await
+}
+
+/// 最近一次分析结果的词长分布（各词长的类型数与词次数）
+#[tauri::command]
+async fn get_length_distribution(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<Vec<LengthBucket>, String> {
+    state
+        .with_result(slot, |result| {
+            Ok(length::length_distribution(&result.entries))
+        })
+        .await
+}
+
+/// 最近一次分析结果中各字的字族（含该字的多字词数与总频次）与各多字词的组成字；由词表直接计算，不重新分词
+#[tauri::command]
+async fn get_character_families(
+    state: State<'_, AppState>,
+    options: Option<CharacterFamilyOptions>,
+    slot: Option<String>,
+) -> Result<CharacterFamilies, String> {
+    state
+        .with_result(slot, move |result| {
+            Ok(character_families::character_families(
+                &result.entries,
+                &options.unwrap_or_default(),
+            ))
+        })
+        .await
+}
+
+/// 将最近一次分析结果的字族导出为 CSV（character, family_size, family_frequency, hapax_members, members），返回绝对路径
+#[tauri::command]
+async fn export_character_families(
+    state: State<'_, AppState>,
+    path: String,
+    options: Option<CharacterFamilyOptions>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let families = state
+        .with_result(slot, move |result| {
+            Ok(character_families::character_families(
+                &result.entries,
+                &options.unwrap_or_default(),
+            ))
+        })
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_character_families_csv(w, &families.characters, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 分页获取最近一次分析结果，可按词长与条目标记筛选，并为本页条目附上筛选结果内的排名；
+/// 指定 profile 时另按输出配置渲染本页表格，format 为其数值格式
+#[tauri::command]
+async fn get_entries(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    query: Option<EntryQuery>,
+    profile: Option<String>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<EntryPage, String> {
+    let mut query = query.unwrap_or_default();
+    rank::validate_names(&query.ranks)?;
+    entry_flags::validate_names(&query.flags)?;
+    let format = export_format(format)?;
+    let profile = profile
+        .map(|name| find_profile(&app_handle, &name))
+        .transpose()?;
+    if let Some((_, profile)) = &profile {
+        query.ranks.extend(profile.rank_metrics());
+    }
+    state
+        .with_result(slot, move |result| {
+            let mut page = length::query_entries(&result.entries, &query);
+            if let Some((settings, profile)) = &profile {
+                let context = profile_context(settings, result, &format)?;
+                let rows = page
+                    .entries
+                    .iter()
+                    .map(|entry| context.render_row(profile, entry, &entry.ranks, &format))
+                    .collect();
+                page.table = Some(ProfileTable {
+                    headers: profile.headers(),
+                    rows,
+                });
+            }
+            Ok(page)
+        })
+        .await
+}
+
+/// 按结果中的顺序分页获取最近一次分析的条目，limit 缺省时取到末尾；筛选与排名见 get_entries
+#[tauri::command]
+async fn get_results_page(
+    state: State<'_, AppState>,
+    offset: usize,
+    limit: Option<usize>,
+    slot: Option<String>,
+) -> Result<EntryPage, String> {
+    let query = EntryQuery {
+        offset,
+        limit,
+        ..Default::default()
+    };
+    state
+        .with_result(slot, move |result| {
+            Ok(length::query_entries(&result.entries, &query))
+        })
+        .await
+}
+
+/// 一次取回最近一次分析的完整结果（条目与元数据）；条目较多时返回的数据量很大，宜优先分页获取
+#[tauri::command]
+async fn get_all_results(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<AnalysisResult, String> {
+    state.clone_result(slot).await
+}
+
+/// 内置与 settings.json 中自定义的输出配置
+#[tauri::command]
+async fn get_output_profiles(app_handle: AppHandle) -> Result<Vec<OutputProfile>, String> {
+    Ok(profile_settings(&app_handle)?.all_profiles())
+}
+
+/// 内置与 settings.json 中自定义的分析预设
+#[tauri::command]
+async fn list_presets(app_handle: AppHandle) -> Result<Vec<AnalysisPreset>, String> {
+    Ok(preset_settings(&app_handle)?.all_presets())
+}
+
+/// 按名称查找分析预设
+#[tauri::command]
+async fn get_preset(app_handle: AppHandle, name: String) -> Result<AnalysisPreset, String> {
+    preset_settings(&app_handle)?.find(&name)
+}
+
+/// 把一组分析选项保存为自定义预设，写入 settings.json；与已有预设同名时替换
+#[tauri::command]
+async fn save_preset(
+    app_handle: AppHandle,
+    name: String,
+    options: AnalysisOptions,
+    description: Option<String>,
+    profile: Option<String>,
+) -> Result<(), String> {
+    if let Some(profile) = &profile {
+        profile_settings(&app_handle)?.find(profile)?;
+    }
+    let preset = AnalysisPreset {
+        name: name.trim().to_string(),
+        description: description.unwrap_or_default(),
+        options,
+        profile,
+    };
+    presets::save_preset(&settings_path(&app_handle)?, preset)
+}
+
+/// 某个条目的详情，含频率最高的部分（max_part_index 对应的 PartInfo，来源文件见其 source）
+#[tauri::command]
+async fn get_entry_detail(
+    state: State<'_, AppState>,
+    word: String,
+    pos: String,
+    slot: Option<String>,
+) -> Result<EntryDetail, String> {
+    state
+        .with_result(slot, move |result| {
+            result
+                .entry_detail(&word, &pos)
+                .ok_or_else(|| format!("词表中没有 {word}（{pos}）"))
+        })
+        .await
+}
+
+/// 当前结果的语料规模：原始词数、分母词数、词型数与各部分的词数
+#[tauri::command]
+async fn get_corpus_totals(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<CorpusTotals, String> {
+    state.with_result(slot, |result| Ok(result.totals())).await
+}
+
+/// 某个词在各语料部分的频次，下标对应 metadata.parts；需以 keep_parts 分析
+#[tauri::command]
+async fn get_part_frequencies(
+    state: State<'_, AppState>,
+    word: String,
+    pos: String,
+    slot: Option<String>,
+) -> Result<Vec<f64>, String> {
+    state
+        .with_result(slot, move |result| {
+            result
+                .part_frequencies(&word, &pos)
+                .ok_or_else(|| "结果未保留各部分词频，请开启 keep_parts 重新分析".to_string())
+        })
+        .await
+}
+
+/// 仅改动指标选项（rate_base、range_threshold、min_frequency）时，以 slot 中结果的计数重算指标，替换该槽中的结果
+#[tauri::command]
+async fn recompute_metrics(
+    state: State<'_, AppState>,
+    options: AnalysisOptions,
+    slot: Option<String>,
+) -> Result<AnalysisResult, String> {
+    state
+        .results
+        .run(move |slots| {
+            let previous = slots.get_mut(slot.as_deref())?;
+            let result = corpus_pipeline::recompute_metrics(previous, &options)?;
+            *previous = result.clone();
+            Ok(result)
+        })
+        .await
+}
+
+/// 只对给定文件重新分词，替换其在 slot 中结果的部分并重算指标（该结果须开启 keep_parts），写回同一槽；
+/// 原结果中没有的文件追加，磁盘上已不存在的文件移出并记入 warnings
+#[tauri::command]
+async fn reanalyze_files(
+    state: State<'_, AppState>,
+    paths: Vec<String>,
+    slot: Option<String>,
+) -> Result<AnalysisResult, String> {
+    let nlp = state.nlp.clone();
+    let results = state.results.clone();
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    tauri::async_runtime::spawn_blocking(move || {
+        let nlp_guard = nlp.lock().unwrap();
+        let nlp = nlp_guard.as_deref().ok_or("NLP模型未加载")?;
+        let (name, previous) = slot_copy(&results, slot)?;
+        let updated = reanalyze::reanalyze_files(nlp, &previous, &paths)?;
+        results.blocking_store(&name, updated.clone());
+        Ok(updated)
+    })
+    .await
+    .map_err(|e| format!("重新分析任务失败: {e}"))?
+}
+
+/// 按文件大小与修改时间找出自 slot 中的结果分析以来变化或删除的文件，只重新分析这些文件并写回同一槽；
+/// 没有变化时返回原结果
+#[tauri::command]
+async fn refresh_analysis(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<AnalysisResult, String> {
+    let nlp = state.nlp.clone();
+    let results = state.results.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let nlp_guard = nlp.lock().unwrap();
+        let nlp = nlp_guard.as_deref().ok_or("NLP模型未加载")?;
+        let (name, previous) = slot_copy(&results, slot)?;
+        match reanalyze::refresh_analysis(nlp, &previous)? {
+            Some(updated) => {
+                results.blocking_store(&name, updated.clone());
+                Ok(updated)
+            }
+            None => Ok(previous),
+        }
+    })
+    .await
+    .map_err(|e| format!("重新分析任务失败: {e}"))?
+}
+
+/// slot 实际对应的槽名及其结果的副本；重新分词在工作线程之外进行，完成后再写回该槽
+fn slot_copy(
+    results: &ResultWorker,
+    slot: Option<String>,
+) -> Result<(String, AnalysisResult), String> {
+    results.blocking_run(move |slots| {
+        let name = slots.resolve(slot.as_deref())?;
+        let result = slots.get(Some(&name))?.clone();
+        Ok((name, result))
+    })
+}
+
+/// 最近一次分析结果中标注一致度最低的高频条目（总频次默认不低于 5，默认前 100 个），供复核词性标注；
+/// 须开启 pos_confidence 分析
+#[tauri::command]
+async fn get_low_confidence_tags(
+    state: State<'_, AppState>,
+    min_frequency: Option<f64>,
+    limit: Option<usize>,
+    slot: Option<String>,
+) -> Result<Vec<LowConfidenceItem>, String> {
+    state
+        .with_result(slot, move |result| {
+            tag_confidence::low_confidence_report(
+                &result.entries,
+                min_frequency.unwrap_or(5.0),
+                limit.unwrap_or(100),
+            )
+        })
+        .await
+}
+
+/// 最近一次分析结果中的未登录词线索（疑似被切碎的高频单字、含领域用字的词），按估计影响取前 top_n 个（默认 100）；
+/// 相邻单字须开启 oov_pairs 分析，未开启时只能按 options.domain_words 给出候选
+#[tauri::command]
+async fn get_oov_report(
+    state: State<'_, AppState>,
+    top_n: Option<usize>,
+    options: Option<OovOptions>,
+    slot: Option<String>,
+) -> Result<OovReport, String> {
+    state
+        .with_result(slot, move |result| {
+            oov::oov_report(result, top_n.unwrap_or(100), &options.unwrap_or_default())
+        })
+        .await
+}
+
+/// 将最近一次分析结果的未登录词线索导出为 CSV（word, pos, frequency, impact, adjacent_count, adjacent, domain_words），
+/// 返回绝对路径
+#[tauri::command]
+async fn export_oov_report(
+    state: State<'_, AppState>,
+    path: String,
+    top_n: Option<usize>,
+    options: Option<OovOptions>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let report = state
+        .with_result(slot, move |result| {
+            oov::oov_report(result, top_n.unwrap_or(100), &options.unwrap_or_default())
+        })
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_oov_csv(w, &report.candidates, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 对最近一次分析结果的语料部分做自助法重抽（b 轮），返回类符数与 TTR 的分位数，
+/// 以及当前前 2N 名的词留在前 N 名的比例；在阻塞线程池中并行计算
+#[tauri::command]
+async fn bootstrap_corpus(
+    state: State<'_, AppState>,
+    b: usize,
+    top_n: usize,
+    seed: Option<u64>,
+    slot: Option<String>,
+) -> Result<CorpusBootstrap, String> {
+    let result = state.clone_result(slot).await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        bootstrap::bootstrap_corpus(&result, b, top_n, seed)
+    })
+    .await
+    .map_err(|e| format!("重抽任务失败: {e}"))?
+}
+
+/// 诊断命令（界面不提供入口）：按多种置换重排最近一次结果的语料部分，检查抽样词的指标是否不变；
+/// sample_words 为 0 时检查全部词，省略时检查 DEFAULT_SAMPLE_WORDS 个
+#[tauri::command]
+async fn verify_order_invariance(
+    state: State<'_, AppState>,
+    sample_words: Option<usize>,
+    slot: Option<String>,
+) -> Result<OrderInvarianceReport, String> {
+    let result = state.clone_result(slot).await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        order_invariance::verify_order_invariance(
+            &result,
+            sample_words.unwrap_or(order_invariance::DEFAULT_SAMPLE_WORDS),
+        )
+    })
+    .await
+    .map_err(|e| format!("顺序自检任务失败: {e}"))?
+}
+
+/// 最近一次分析结果中各指标两两之间的 Spearman 相关
+#[tauri::command]
+async fn get_metric_correlations(
+    state: State<'_, AppState>,
+    slot: Option<String>,
+) -> Result<Vec<MetricCorrelation>, String> {
+    state
+        .with_result(slot, |result| {
+            Ok(correlation::metric_correlations(&result.entries))
+        })
+        .await
+}
+
+/// 两个指标下秩差最大的词（默认前 100 个）
+#[tauri::command]
+async fn get_metric_disagreements(
+    state: State<'_, AppState>,
+    metric_a: String,
+    metric_b: String,
+    limit: Option<usize>,
+    slot: Option<String>,
+) -> Result<Vec<RankDisagreement>, String> {
+    state
+        .with_result(slot, move |result| {
+            correlation::rank_disagreements(
+                &result.entries,
+                &metric_a,
+                &metric_b,
+                limit.unwrap_or(100),
+            )
+            .ok_or_else(|| format!("未知指标: {metric_a} 或 {metric_b}"))
+        })
+        .await
+}
+
+/// 将指标相关矩阵导出为 CSV，返回绝对路径
+#[tauri::command]
+async fn export_metric_correlations(
+    state: State<'_, AppState>,
+    path: String,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let correlations = state
+        .with_result(slot, |result| {
+            Ok(correlation::metric_correlations(&result.entries))
+        })
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_correlations_csv(w, &correlations, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 将两个指标的秩差列表导出为 CSV，返回绝对路径
+#[tauri::command]
+async fn export_metric_disagreements(
+    state: State<'_, AppState>,
+    path: String,
+    metric_a: String,
+    metric_b: String,
+    limit: Option<usize>,
+    format: Option<ExportOptions>,
+    slot: Option<String>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    let disagreements = state
+        .with_result(slot, move |result| {
+            correlation::rank_disagreements(
+                &result.entries,
+                &metric_a,
+                &metric_b,
+                limit.unwrap_or(usize::MAX),
+            )
+            .ok_or_else(|| format!("未知指标: {metric_a} 或 {metric_b}"))
+        })
+        .await?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_disagreements_csv(w, &disagreements, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 把 from 槽（缺省为最近写入的槽）中的结果复制到 name 槽保留，之后的分析不会覆盖它；已有同名槽时替换
+#[tauri::command]
+async fn pin_result(
+    state: State<'_, AppState>,
+    name: String,
+    from: Option<String>,
+) -> Result<Vec<ResultSlotInfo>, String> {
+    state.results.pin(&name, from.as_deref()).await?;
+    Ok(state.results.list().await)
+}
+
+/// 后端保留的各结果槽，按写入先后；current 标出不指定槽名时使用的槽
+#[tauri::command]
+async fn list_results(state: State<'_, AppState>) -> Result<Vec<ResultSlotInfo>, String> {
+    Ok(state.results.list().await)
+}
+
+/// 移除 name 槽，释放其占用的内存
+#[tauri::command]
+async fn drop_result(
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<Vec<ResultSlotInfo>, String> {
+    if !state.results.remove(&name).await {
+        return Err(format!("没有名为 {name} 的分析结果"));
+    }
+    Ok(state.results.list().await)
+}
+
+/// 将后端保留的 slot 中的结果（缺省为最近写入的槽）保存为会话文件，不依赖前端持有的条目
+#[tauri::command]
+async fn save_session(
+    state: State<'_, AppState>,
+    path: String,
+    slot: Option<String>,
+) -> Result<(), String> {
+    state
+        .with_result_copy(slot, move |result| {
+            session::save(Path::new(&path), result).map_err(|e| e.to_string())
+        })
+        .await
+}
+
+/// 打开会话文件，存入 slot 槽（缺省以文件名去掉扩展名为槽名），不覆盖其他槽中的结果；该槽成为最近写入的槽。
+/// 同 start_analysis 只返回摘要（耗时记为 0），条目按页取用
+#[tauri::command]
+async fn open_session(
+    state: State<'_, AppState>,
+    path: String,
+    slot: Option<String>,
+) -> Result<AnalysisSummary, String> {
+    let result = load_session(path.clone()).await?;
+    let summary = result.summary(0);
+    let name = slot.unwrap_or_else(|| {
+        Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| LATEST_SLOT.to_string())
+    });
+    state.results.insert(&name, result).await?;
+    Ok(summary)
+}
+
+/// 合并多个会话文件（各自须开启 keep_parts 分析），在并集上重新计算指标；合并结果可照常保存与导出
+#[tauri::command]
+async fn merge_sessions(
+    state: State<'_, AppState>,
+    paths: Vec<String>,
+) -> Result<AnalysisResult, String> {
+    let merged = blocking(move || {
+        let sessions = paths
+            .iter()
+            .map(|path| Ok((path.clone(), read_session(path)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        merge::merge_results(sessions)
+    })
+    .await?;
+    state.results.store(LATEST_SLOT, merged.clone()).await;
+    Ok(merged)
+}
+
+/// 导入外部计数表（长表或宽表），在其上计算分布指标；导入结果可照常排序、比较、保存与导出
+#[tauri::command]
+async fn import_count_matrix(
+    state: State<'_, AppState>,
+    path: String,
+    format: MatrixFormat,
+    part_sizes: Option<String>,
+    options: Option<AnalysisOptions>,
+) -> Result<AnalysisResult, String> {
+    let options = options.unwrap_or_default();
+    let imported = blocking(move || {
+        count_import::import_count_matrix(
+            Path::new(&path),
+            format,
+            part_sizes.as_deref().map(Path::new),
+            &options,
+        )
+    })
+    .await?;
+    state.results.store(LATEST_SLOT, imported.clone()).await;
+    Ok(imported)
+}
+
+/// 比较两个会话文件：path_a 为基准，列出新增、消失及 metric 变化超过 threshold（默认 0）的词
+#[tauri::command]
+async fn diff_sessions(
+    path_a: String,
+    path_b: String,
+    metric: String,
+    threshold: Option<f64>,
+) -> Result<ResultDiff, String> {
+    blocking(move || {
+        let a = read_session(&path_a)?;
+        let b = read_session(&path_b)?;
+        diff::diff_results(&a, &b, &metric, threshold.unwrap_or(0.0))
+            .ok_or_else(|| format!("未知指标: {metric}"))
+    })
+    .await
+}
+
+/// 以会话文件为基准，与 slot 中的结果（缺省为最近写入的槽）比较
+#[tauri::command]
+async fn diff_with_current(
+    state: State<'_, AppState>,
+    path: String,
+    metric: String,
+    threshold: Option<f64>,
+    slot: Option<String>,
+) -> Result<ResultDiff, String> {
+    let baseline = load_session(path).await?;
+    state
+        .with_result(slot, move |result| {
+            diff::diff_results(&baseline, result, &metric, threshold.unwrap_or(0.0))
+                .ok_or_else(|| format!("未知指标: {metric}"))
+        })
+        .await
+}
+
+/// 比较两个结果槽：base 为基准，other 缺省为最近写入的槽；其余同 diff_sessions
+#[tauri::command]
+async fn diff_results(
+    state: State<'_, AppState>,
+    base: String,
+    other: Option<String>,
+    metric: String,
+    threshold: Option<f64>,
+) -> Result<ResultDiff, String> {
+    state
+        .results
+        .run(move |slots| {
+            let a = slots.get(Some(&base))?;
+            let b = slots.get(other.as_deref())?;
+            diff::diff_results(a, b, &metric, threshold.unwrap_or(0.0))
+                .ok_or_else(|| format!("未知指标: {metric}"))
+        })
+        .await
+}
+
+/// 将差异报告导出为 CSV，返回绝对路径
+#[tauri::command]
+async fn export_diff(
+    path: String,
+    diff: ResultDiff,
+    format: Option<ExportOptions>,
+) -> Result<String, String> {
+    let format = export_format(format)?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            export::write_diff_csv(w, &diff, &format)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 导出格式缺省取默认值，并校验小数点与分隔符不冲突
+fn export_format(format: Option<ExportOptions>) -> Result<ExportOptions, String> {
+    let format = format.unwrap_or_default();
+    format.validate()?;
+    Ok(format)
+}
+
+/// 导出命令返回的绝对路径
+fn absolute_path(path: &str) -> Result<String, String> {
+    let absolute = std::path::absolute(path).map_err(|e| format!("无法解析路径 {path}: {e}"))?;
+    Ok(absolute.to_string_lossy().to_string())
+}
+
+fn read_session(path: &str) -> Result<AnalysisResult, String> {
+    session::load(Path::new(path)).map_err(|e| format!("会话读取失败 {path}: {e}"))
+}
+
+/// 在阻塞线程池中读取会话文件
+async fn load_session(path: String) -> Result<AnalysisResult, String> {
+    blocking(move || read_session(&path)).await
+}
+
+/// 读取停用词表文件，返回词列表（供前端放入分析选项）；新词表只影响之后的分析，
+/// stale_results 列出以其他停用词表得出的已有结果，可经 apply_filters_to_current_result 事后过滤
+#[tauri::command]
+async fn load_stopwords(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<LoadedResource, String> {
+    let items = blocking(move || {
+        filter::read_stopwords(Path::new(&path))
+            .map_err(|e| format!("无法读取停用词表 {path}: {e}"))
+    })
+    .await?;
+    Ok(loaded_resource(&state, ResourceKind::Stopwords, items).await)
+}
+
+/// 读取短语表文件，返回短语列表（供前端放入分析选项）；stale_results 同 load_stopwords，短语须重新分析
+#[tauri::command]
+async fn load_phrases(state: State<'_, AppState>, path: String) -> Result<LoadedResource, String> {
+    let items = blocking(move || {
+        phrases::read_phrases(Path::new(&path)).map_err(|e| format!("无法读取短语表 {path}: {e}"))
+    })
+    .await?;
+    Ok(loaded_resource(&state, ResourceKind::Phrases, items).await)
+}
+
+/// 读取目标词表文件，返回词列表（供前端放入分析选项）；stale_results 同 load_stopwords，目标词须重新分析
+#[tauri::command]
+async fn load_target_words(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<LoadedResource, String> {
+    let items = blocking(move || {
+        targets::read_target_words(Path::new(&path))
+            .map_err(|e| format!("无法读取目标词表 {path}: {e}"))
+    })
+    .await?;
+    Ok(loaded_resource(&state, ResourceKind::TargetWords, items).await)
+}
+
+/// 读入的词表，附上所用词表与之不同的已有结果
+async fn loaded_resource(
+    state: &AppState,
+    kind: ResourceKind,
+    items: Vec<String>,
+) -> LoadedResource {
+    let compared = items.clone();
+    let stale_results = state
+        .results
+        .run(move |slots| slots.stale_results(&[(kind, &compared)]))
+        .await;
+    LoadedResource {
+        items,
+        stale_results,
+    }
+}
+
+/// 在 slot 中结果（缺省为最近写入的槽）保留的计数上施加 options 中新增的停用词与排除词性、并按其指标选项重算，
+/// 不重新分词；结果存入 name 槽（缺省为 "filtered"），原结果保持不变。返回新结果的摘要，条目按页取用
+#[tauri::command]
+async fn apply_filters_to_current_result(
+    state: State<'_, AppState>,
+    options: AnalysisOptions,
+    name: Option<String>,
+    slot: Option<String>,
+) -> Result<AnalysisSummary, String> {
+    let name = name.unwrap_or_else(|| "filtered".to_string());
+    state
+        .results
+        .run(move |slots| {
+            let source = slots.resolve(slot.as_deref())?;
+            if source == name.trim() {
+                return Err(format!("事后过滤的结果不能覆盖原结果 {source}，请另取名称"));
+            }
+            let filtered = corpus_pipeline::apply_filters(slots.get(Some(&source))?, &options)?;
+            let summary = filtered.summary(0);
+            slots.insert(&name, filtered)?;
+            Ok(summary)
+        })
+        .await
+}
+
+/// 把停用词表、目标词表、短语表与其余分析选项打成资源包（ZIP），返回绝对路径；
+/// 选项按 preset 与 options 合并，规则同 start_analysis
+#[tauri::command]
+async fn export_resource_bundle(
+    app_handle: AppHandle,
+    path: String,
+    preset: Option<String>,
+    options: Option<serde_json::Value>,
+) -> Result<String, String> {
+    let options = analysis_options(&app_handle, preset.as_deref(), options)?;
+    blocking(move || {
+        write_atomic(Path::new(&path), |w| {
+            resource_bundle::write_bundle(w, &options).map(drop)
+        })
+        .map_err(|e| e.to_string())?;
+        absolute_path(&path)
+    })
+    .await
+}
+
+/// 导入资源包：逐项校验后把通过的资源安装到应用数据目录，并作为当前的导入资源；
+/// 返回清单、各项资源的校验结果与拼成的分析选项，以及所用词表与之不同的已有结果（同 load_stopwords）
+#[tauri::command]
+async fn import_resource_bundle(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ImportedBundle, String> {
+    let dir = resource_dir(&app_handle)?;
+    let bundle = blocking(move || {
+        let bytes = std::fs::read(&path).map_err(|e| format!("无法读取资源包 {path}: {e}"))?;
+        let bundle = resource_bundle::read_bundle(&bytes)?;
+        bundle.install(&dir).map_err(|e| e.to_string())?;
+        Ok(bundle)
+    })
+    .await?;
+    let options = bundle.options().clone();
+    *state.resources.lock().unwrap() = Some(options.clone());
+    let stale_results = state
+        .results
+        .run(move |slots| {
+            let lists: Vec<(ResourceKind, &[String])> = ResourceKind::ALL
+                .iter()
+                .filter_map(|&kind| Some((kind, kind.word_list(&options)?)))
+                .collect();
+            slots.stale_results(&lists)
+        })
+        .await;
+    Ok(ImportedBundle {
+        bundle,
+        stale_results,
+    })
+}
+
+/// 最近导入的资源包拼成的分析选项（供前端作为分析选项）；从未导入过时为 None
+#[tauri::command]
+async fn get_imported_resources(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<AnalysisOptions>, String> {
+    if let Some(options) = state.resources.lock().unwrap().clone() {
+        return Ok(Some(options));
+    }
+    let dir = resource_dir(&app_handle)?;
+    let installed = blocking(move || resource_bundle::load_installed(&dir)).await?;
+    let mut resources = state.resources.lock().unwrap();
+    // 读回期间可能已有资源包导入，以导入的为准
+    if resources.is_none() {
+        *resources = installed.map(|bundle| bundle.options().clone());
+    }
+    Ok(resources.clone())
+}
+
+/// 各指标的定义、取值范围、出处与方向，供界面提示
+#[tauri::command]
+async fn get_metric_descriptions() -> Vec<MetricDescription> {
+    metric_descriptions::metric_descriptions().to_vec()
+}
+
+/// 各数值字段的取值范围、方向、显示位数与分组，供界面按指标着色
+#[tauri::command]
+async fn get_metric_schema() -> Vec<MetricSchema> {
+    metric_descriptions::metric_schema()
+}
+
+/// 条目标记的名称与说明，供界面筛选
+#[tauri::command]
+async fn get_entry_flags() -> Vec<EntryFlag> {
+    entry_flags::FLAGS.to_vec()
+}
+
+/// 条目表可选的列名（ExportOptions::columns），以及最近一次导出所用的列；从未指定过时为空（全部列）
+#[tauri::command]
+async fn get_export_columns(app_handle: AppHandle) -> Result<(Vec<String>, Vec<String>), String> {
+    let settings = profile_settings(&app_handle)?;
+    Ok((export::column_names(), settings.export_columns))
+}
+
+/// 按名称获取词元策略预设（default / recommended）
+#[tauri::command]
+async fn get_token_policy_preset(name: String) -> Result<TokenPolicy, String> {
+    TokenPolicy::preset(&name).ok_or_else(|| format!("未知的词元策略: {name}"))
+}
+
+/// 词性标注集说明，供界面提示与过滤选项使用
+#[tauri::command]
+async fn get_tagset() -> Vec<TagInfo> {
+    tagset::tagset()
+}
+
+/// 在系统文件管理器中显示文件（选中）或目录
+#[tauri::command]
+async fn reveal_in_file_manager(path: String) -> Result<(), String> {
+    file_manager::reveal(&path)
+}
+
+/// 加载NLP模型：在阻塞线程池中读取与校验，各阶段以 "model-load-progress" 事件上报。
+/// 校验通过后才替换当前模型，加载失败时原有模型照常可用；已有加载进行中时直接返回错误
 #[tauri::command]
 async fn load_models(
+    app_handle: AppHandle,
     state: State<'_, AppState>,
     cws_path: String,
     pos_path: String,
 ) -> Result<(), String> {
-    // 自动适配多平台模型路径
-    let cws = get_model_path(&cws_path).to_string_lossy().to_string();
-    let pos = get_model_path(&pos_path).to_string_lossy().to_string();
-    let nlp = LtpNlp::load(&cws, &pos).map_err(|e| format!("模型加载失败: {e}"))?;
+    if state.loading_models.swap(true, Ordering::SeqCst) {
+        return Err("模型正在加载中，请等待当前加载完成".to_string());
+    }
+    let _loading = LoadingGuard(&state.loading_models);
+    let nlp = tauri::async_runtime::spawn_blocking(move || {
+        load_tokenizer(&cws_path, &pos_path, &|phase| {
+            app_handle
+                .emit(MODEL_LOAD_PROGRESS_EVENT, ModelLoadProgress::from(phase))
+                .ok();
+        })
+    })
+    .await
+    .map_err(|e| format!("模型加载失败: {e}"))??;
     *state.nlp.lock().unwrap() = Some(nlp);
     Ok(())
 }
 
+/// 结束时（含命令被取消）清除加载中标记
+struct LoadingGuard<'a>(&'a AtomicBool);
+
+impl Drop for LoadingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// 加载 LTP 分词与词性模型
+#[cfg(feature = "ltp")]
+fn load_tokenizer(
+    cws_path: &str,
+    pos_path: &str,
+    progress: &dyn Fn(ModelLoadPhase),
+) -> Result<Box<dyn Tokenizer>, String> {
+    // 自动适配多平台模型路径
+    let cws = get_model_path(cws_path).to_string_lossy().to_string();
+    let pos = get_model_path(pos_path).to_string_lossy().to_string();
+    let nlp = LtpNlp::load_with_progress(&cws, &pos, progress)
+        .map_err(|e| format!("模型加载失败: {e}"))?;
+    Ok(Box::new(nlp))
+}
+
+/// 未启用 ltp 特性时使用规则分词器，忽略模型路径，不上报加载阶段
+#[cfg(not(feature = "ltp"))]
+fn load_tokenizer(
+    _cws_path: &str,
+    _pos_path: &str,
+    _progress: &dyn Fn(ModelLoadPhase),
+) -> Result<Box<dyn Tokenizer>, String> {
+    Ok(Box::new(RuleTokenizer))
+}
+
 /// 获取跨平台模型路径
+#[cfg(feature = "ltp")]
 fn get_model_path(filename: &str) -> PathBuf {
     // 优先查找exe同级/legacy目录（打包后）和开发时legacy目录
     let exe_dir = current_exe()
@@ -88,13 +1967,141 @@ fn get_model_path(filename: &str) -> PathBuf {
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .manage(AppState {
-            nlp: Arc::new(Mutex::new(None)),
-        })
-        .invoke_handler(tauri::generate_handler![start_analysis, load_models,])
+        .manage(AppState::new())
+        .invoke_handler(tauri::generate_handler![
+            start_analysis,
+            resume_analysis,
+            cancel_analysis,
+            get_analysis_status,
+            find_checkpoint,
+            suggest_partition,
+            start_watching,
+            stop_watching,
+            load_models,
+            export_csv,
+            export_dictionary,
+            export_results_json,
+            export_results,
+            export_selection,
+            get_result_schema,
+            get_run_report_schema,
+            get_frequency_spectrum,
+            get_filter_report,
+            export_vertical,
+            export_frequency_matrix,
+            get_group_keywords,
+            export_group_keywords,
+            get_length_distribution,
+            get_character_families,
+            export_character_families,
+            get_entries,
+            get_results_page,
+            get_all_results,
+            get_token_policy_preset,
+            get_tagset,
+            load_stopwords,
+            load_phrases,
+            load_target_words,
+            export_resource_bundle,
+            import_resource_bundle,
+            get_imported_resources,
+            get_metric_descriptions,
+            get_metric_schema,
+            get_entry_flags,
+            get_word_sketch,
+            get_export_columns,
+            save_session,
+            pin_result,
+            list_results,
+            drop_result,
+            open_session,
+            merge_sessions,
+            import_count_matrix,
+            diff_sessions,
+            diff_with_current,
+            diff_results,
+            export_diff,
+            get_corpus_totals,
+            get_part_frequencies,
+            get_entry_detail,
+            list_presets,
+            get_preset,
+            save_preset,
+            recompute_metrics,
+            apply_filters_to_current_result,
+            reanalyze_files,
+            refresh_analysis,
+            get_output_profiles,
+            get_metric_correlations,
+            bootstrap_corpus,
+            verify_order_invariance,
+            get_low_confidence_tags,
+            get_oov_report,
+            export_oov_report,
+            compute_list_coverage,
+            export_list_coverage,
+            export_near_duplicates,
+            export_pos_histogram,
+            get_metric_disagreements,
+            export_metric_correlations,
+            export_metric_disagreements,
+            reveal_in_file_manager,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use betawordlist_lib::analysis::{corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer};
+    use std::sync::mpsc;
+    use std::thread;
+    use tauri::async_runtime::block_on;
+
+    /// 工作线程忙于长时间的请求时，取消与状态查询照常立即返回；
+    /// with_result_copy 的任务在工作线程之外执行，执行期间其他命令的请求照常得到应答
+    #[test]
+    fn commands_are_not_blocked_by_busy_worker() {
+        let state = AppState::new();
+        let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+        let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+        block_on(state.results.store(LATEST_SLOT, result));
+        let run = AnalysisRun::default();
+        state.runs.lock().unwrap().push(run.clone());
+
+        let (release, wait) = mpsc::channel::<()>();
+        let (started, copying) = mpsc::channel();
+        let (finish, finishing) = mpsc::channel::<()>();
+        thread::scope(|scope| {
+            let results = state.results.clone();
+            let busy = scope.spawn(move || results.blocking_run(move |_| wait.recv().ok()));
+            assert_eq!(state.latest_run(), Some(run.run_id.clone()));
+            assert!(!state.cancel_run(Some("其他运行")));
+            assert!(state.cancel_run(None));
+            assert!(run.is_cancelled());
+            release.send(()).unwrap();
+            busy.join().unwrap();
+
+            let copy = scope.spawn(|| {
+                block_on(state.with_result_copy(None, move |result| {
+                    started.send(()).unwrap();
+                    finishing.recv().unwrap();
+                    Ok(result.entries.len())
+                }))
+            });
+            copying.recv().unwrap();
+            assert_eq!(block_on(state.results.list()).len(), 1);
+            finish.send(()).unwrap();
+            assert_eq!(copy.join().unwrap(), Ok(3));
+        });
+    }
+}
@@ -0,0 +1,171 @@
+// watcher.rs
+// 监视目录：轮询文件变化，防抖后增量重新分析并推送 results-updated 事件
+//
+// 采用标准库轮询（比较大小与修改时间）而非平台文件事件，跨平台行为一致，也不依赖额外的库。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use betawordlist_lib::analysis::{
+    corpus_pipeline::{self, AnalysisResult, PartCounts},
+    nlp::Tokenizer,
+    options::AnalysisOptions,
+    result_slots::LATEST_SLOT,
+    worker::ResultWorker,
+};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 最后一次变化后静默多久才重新分析，连续写入合并为一次
+const DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// 文件指纹：(大小, 修改时间)
+type Fingerprint = (u64, Option<SystemTime>);
+
+/// 每次重新分析后推送的摘要
+#[derive(Serialize, Clone)]
+pub struct WatchSummary {
+    pub files: usize,
+    /// 本次重新分词的文件数（其余复用缓存）
+    pub reanalyzed_files: usize,
+    pub total_tokens: u64,
+    pub total_types: usize,
+}
+
+/// 运行中的目录监视，drop 时停止
+pub struct Watcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// 启动监视线程：立即分析一次，之后每当目录内匹配扩展名的文件新增、修改或删除时增量重新分析
+    pub fn start(
+        app_handle: AppHandle,
+        nlp: Arc<Mutex<Option<Box<dyn Tokenizer>>>>,
+        results: ResultWorker,
+        dir: PathBuf,
+        extensions: Vec<String>,
+        options: AnalysisOptions,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let extensions: Vec<String> = extensions
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_lowercase())
+            .collect();
+        let thread = thread::spawn(move || {
+            let mut cache: HashMap<PathBuf, (Fingerprint, Option<PartCounts>)> = HashMap::new();
+            let mut analyzed: Option<BTreeMap<PathBuf, Fingerprint>> = None;
+            let mut last_seen = BTreeMap::new();
+            let mut last_change = Instant::now();
+            while !thread_stop.load(Ordering::Relaxed) {
+                let snapshot = scan(&dir, &extensions);
+                if snapshot != last_seen {
+                    last_seen = snapshot;
+                    last_change = Instant::now();
+                }
+                let settled = last_change.elapsed() >= DEBOUNCE || analyzed.is_none();
+                if settled && analyzed.as_ref() != Some(&last_seen) {
+                    let nlp_guard = nlp.lock().unwrap();
+                    if let Some(nlp) = nlp_guard.as_deref() {
+                        let (updated, summary) = reanalyze(nlp, &last_seen, &mut cache, &options);
+                        drop(nlp_guard);
+                        results.blocking_store(LATEST_SLOT, updated);
+                        app_handle.emit("results-updated", summary).ok();
+                        analyzed = Some(last_seen.clone());
+                    }
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+/// 递归列出目录下扩展名匹配的文件及其指纹，路径有序
+fn scan(dir: &Path, extensions: &[String]) -> BTreeMap<PathBuf, Fingerprint> {
+    let mut files = BTreeMap::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(read_dir) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let matches = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e.to_lowercase()));
+            if matches {
+                let fingerprint = (meta.len(), meta.modified().ok());
+                files.insert(path, fingerprint);
+            }
+        }
+    }
+    files
+}
+
+/// 只对新增或变化的文件重新分词，已删除文件移出缓存，再汇总全部部分
+fn reanalyze(
+    nlp: &dyn Tokenizer,
+    snapshot: &BTreeMap<PathBuf, Fingerprint>,
+    cache: &mut HashMap<PathBuf, (Fingerprint, Option<PartCounts>)>,
+    options: &AnalysisOptions,
+) -> (AnalysisResult, WatchSummary) {
+    cache.retain(|path, _| snapshot.contains_key(path));
+    let mut reanalyzed_files = 0;
+    for (path, fingerprint) in snapshot {
+        if cache
+            .get(path)
+            .is_some_and(|(cached, _)| cached == fingerprint)
+        {
+            continue;
+        }
+        let part = corpus_pipeline::count_file(nlp, path, options);
+        cache.insert(path.clone(), (*fingerprint, part));
+        reanalyzed_files += 1;
+    }
+
+    // 被语言筛查跳过的文件不形成语料部分
+    let file_paths: Vec<PathBuf> = snapshot
+        .keys()
+        .filter(|p| cache[*p].1.is_some())
+        .cloned()
+        .collect();
+    let parts = file_paths.iter().filter_map(|p| cache[p].1.as_ref());
+    let result = corpus_pipeline::analyze_parts(nlp, &file_paths, parts, options);
+    let summary = WatchSummary {
+        files: file_paths.len(),
+        reanalyzed_files,
+        total_tokens: result.metadata.total_tokens,
+        total_types: result.metadata.total_types,
+    };
+    (result, summary)
+}
@@ -0,0 +1,31 @@
+// atomic.rs
+// atomic 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 写入中途失败时目标文件不变，也不留下临时文件
+#[test]
+fn failed_write_keeps_target() {
+    use betawordlist_lib::analysis::atomic::write_atomic;
+    use std::io;
+
+    let dir = TempDir::new("atomic");
+    let path = dir.join("result.csv");
+    write_atomic(&path, |w| w.write_all(b"word,frequency\n")).unwrap();
+
+    // 写入中途失败时原文件不变，也不留下临时文件
+    let failed = write_atomic(&path, |w| {
+        w.write_all(b"half")?;
+        Err(io::Error::other("中断"))
+    });
+    assert!(failed.is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "word,frequency\n");
+    let names: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name())
+        .collect();
+    assert_eq!(names, ["result.csv"]);
+}
@@ -0,0 +1,40 @@
+// batch_export.rs
+// batch_export 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 按格式逐一导出并报告，未知格式单独报错
+#[test]
+fn exports_each_format_once() {
+    use betawordlist_lib::analysis::{
+        batch_export::{export_batch, BatchOptions},
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let dir = TempDir::new("batch");
+    let formats = ["csv", "tsv", "xlsx", "docx", "antconc"].map(String::from);
+    let reports = export_batch(
+        &result,
+        None,
+        &dir.join("result"),
+        &formats,
+        &BatchOptions::default(),
+    );
+
+    assert!(reports[..3].iter().all(|r| r.error.is_none()));
+    assert_eq!(reports[3].error.as_deref(), Some("未知的导出格式: docx"));
+    let antconc = std::fs::read_to_string(dir.join("result.antconc.txt")).unwrap();
+    assert!(antconc.starts_with("#Word Types: 3\n#Word Tokens: 5\n#Search Hits: 0\n1\t2\t2\t乙\n"));
+    let csv = std::fs::read_to_string(dir.join("result.csv")).unwrap();
+    let tsv = std::fs::read_to_string(dir.join("result.tsv")).unwrap();
+    assert_eq!(csv.replace(',', "\t"), tsv);
+    assert!(std::fs::read(dir.join("result.xlsx"))
+        .unwrap()
+        .starts_with(b"PK"));
+}
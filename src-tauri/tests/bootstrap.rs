@@ -0,0 +1,38 @@
+// bootstrap.rs
+// bootstrap 模块的测试
+
+/// 自助法的前 N 名稳定性与分位数，同一种子结果相同
+#[test]
+fn bootstrap_is_reproducible() {
+    use betawordlist_lib::analysis::{
+        bootstrap::bootstrap_corpus, corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = [
+        "甲 甲 甲 乙 丙",
+        "甲 甲 乙 丁",
+        "甲 甲 甲 乙 乙 戊",
+        "甲 甲 丙",
+    ]
+    .map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let bootstrap = bootstrap_corpus(&result, 200, 1, Some(7)).unwrap();
+
+    // 甲 在每个部分都最多，总是第一名；乙 从不进入前 1 名
+    let stability: Vec<(&str, usize, f64)> = bootstrap
+        .top_stability
+        .iter()
+        .map(|s| (s.word.as_str(), s.rank, s.top_n_percent))
+        .collect();
+    assert_eq!(stability[0], ("甲", 1, 100.0));
+    assert_eq!(stability[1], ("乙", 2, 0.0));
+    assert!(bootstrap.types.min >= 2.0 && bootstrap.types.max <= 5.0);
+    assert!(bootstrap.types.p2_5 <= bootstrap.types.median);
+    assert!(bootstrap.ttr.median > 0.0 && bootstrap.ttr.median <= 1.0);
+
+    // 同一种子结果相同
+    let again = bootstrap_corpus(&result, 200, 1, Some(7)).unwrap();
+    assert_eq!(again.types, bootstrap.types);
+    assert!(bootstrap_corpus(&result, 0, 1, None).is_err());
+}
@@ -0,0 +1,47 @@
+// character_families.rs
+// character_families 模块的测试
+
+/// 字族合计同一词形的各词性，不计单字词，默认不含拉丁字母
+#[test]
+fn families_count_multi_character_words() {
+    use betawordlist_lib::analysis::{
+        character_families::{character_families, CharacterFamilyOptions},
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["人工 人工 工人 人 人 T恤 。", "人工 工人 人人 人 工人 工人"].map(String::from);
+    let mut entries =
+        analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default()).entries;
+    // 同一词形的另一个词性
+    let mut other = entries.iter().find(|e| e.word == "人工").unwrap().clone();
+    other.pos = "b".into();
+    other.frequency = 2.0;
+    entries.push(other);
+
+    let families = character_families(&entries, &CharacterFamilyOptions::default());
+    let ren = &families.characters[0];
+    assert_eq!(ren.character, "人");
+    // 人工（两个词性合计 5 次）、工人、人人；单字词 人 不算
+    assert_eq!(
+        (ren.family_size, ren.family_frequency, ren.hapax_members),
+        (3, 10.0, 1)
+    );
+    assert_eq!(ren.members, ["人工", "工人", "人人"]);
+    assert_eq!(families.characters[1].character, "工");
+    assert_eq!(families.characters.len(), 2);
+    assert_eq!(
+        (families.words[0].word.as_str(), families.words[0].frequency),
+        ("人工", 5.0)
+    );
+    assert_eq!(families.words[2].components, ["人", "人"]);
+
+    // 含拉丁字母的词默认不参与
+    let latin = CharacterFamilyOptions {
+        include_latin: true,
+    };
+    let families = character_families(&entries, &latin);
+    assert!(families.characters.iter().any(|c| c.character == "T"));
+    assert!(families.words.iter().any(|w| w.components == ["T", "恤"]));
+}
@@ -0,0 +1,81 @@
+// checkpoint.rs
+// checkpoint 模块的测试
+
+mod common;
+
+use common::{corpus_files, TempDir};
+
+/// 取消后从检查点续跑，指标与一次跑完的结果逐项相同
+#[test]
+fn cancelled_run_resumes_from_checkpoint() {
+    use betawordlist_lib::analysis::{
+        checkpoint::Checkpoint,
+        corpus_pipeline::{analyze_corpus, AnalysisAbort},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::{
+            AnalysisComplete, AnalysisError, AnalysisProgress, FileCounted, NoopReporter,
+            ProgressReporter,
+        },
+        word_entry::WordEntry,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 计数满 k 个文件后请求取消
+    struct CancelAfter(usize, AtomicUsize);
+
+    impl ProgressReporter for CancelAfter {
+        fn progress(&self, _event: &AnalysisProgress) {}
+        fn file_counted(&self, _event: &FileCounted) {
+            self.1.fetch_add(1, Ordering::SeqCst);
+        }
+        fn complete(&self, _event: &AnalysisComplete) {}
+        fn error(&self, _event: &AnalysisError) {}
+        fn cancelled(&self) -> bool {
+            self.1.load(Ordering::SeqCst) >= self.0
+        }
+    }
+
+    let files = corpus_files(3);
+    let options = AnalysisOptions {
+        checkpoint_every: Some(1),
+        file_counts_top: Some(1),
+        ..Default::default()
+    };
+    let root = TempDir::new("checkpoint");
+    let checkpoint = Checkpoint::new(root.path(), &files, &options, "rule");
+
+    let k = 2;
+    let reporter = CancelAfter(k, AtomicUsize::new(0));
+    let aborted = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &options,
+        Some(&checkpoint),
+        &reporter,
+    );
+    assert!(matches!(aborted, Err(AnalysisAbort::Cancelled)));
+    assert_eq!(checkpoint.info(files.len()).unwrap().completed_files, k);
+
+    let resumed = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &options,
+        Some(&checkpoint),
+        &NoopReporter,
+    )
+    .unwrap();
+    assert_eq!(resumed.metadata.resumed_parts, k);
+    assert!(checkpoint.info(files.len()).is_none());
+
+    let uninterrupted =
+        analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+    let rows = |entries: &[WordEntry]| -> Vec<String> {
+        entries
+            .iter()
+            .map(|e| format!("{} {} {} {:?}", e.word, e.pos, e.frequency, e.metrics))
+            .collect()
+    };
+    assert_eq!(rows(&resumed.entries), rows(&uninterrupted.entries));
+    assert_eq!(resumed.metadata.parts.len(), 3);
+}
@@ -0,0 +1,549 @@
+// corpus_pipeline.rs
+// corpus_pipeline 模块的测试
+
+mod common;
+
+use common::{article, corpus_files, fixture, TempDir};
+
+/// 句数与段数计入摘要与总量，改用空白分词的文件只计段数
+#[test]
+fn summary_counts_sentences_and_paragraphs() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        language::{LanguageScreen, ScreenMode},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let dir = TempDir::new("text_units");
+    let chinese = dir.write("a.txt", "第一段。还是第一段！\n\n第二段没有句号\n");
+    let english = dir.write("b.txt", "First line. Second sentence.\nAnother line\n");
+    let options = AnalysisOptions {
+        language_screen: LanguageScreen {
+            mode: ScreenMode::Whitespace,
+            min_han_ratio: 0.5,
+        },
+        ..Default::default()
+    };
+    let files = [chinese, english];
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+
+    // 改用空白分词的文件只计段数
+    let units: Vec<(Option<u64>, Option<u64>)> = result
+        .metadata
+        .files
+        .iter()
+        .map(|f| (f.sentences, f.paragraphs))
+        .collect();
+    assert_eq!(units, [(Some(3), Some(2)), (None, Some(2))]);
+    let summary = result.summary(0);
+    assert_eq!((summary.sentences, summary.paragraphs), (Some(3), Some(4)));
+    let totals = result.totals();
+    assert_eq!((totals.sentences, totals.paragraphs), (Some(3), Some(4)));
+}
+
+/// 各部分词数之和等于语料词数，过滤后仍然成立
+#[test]
+fn totals_add_up_across_parts() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 乙 ， 丙", "甲 。", "", "丁 丁 乙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let totals = result.totals();
+    assert_eq!(
+        (totals.raw_tokens, totals.tokens, totals.types, totals.parts),
+        (10, 10, 6, 4)
+    );
+    // 未启用过滤时，各部分词数之和、语料词数与全部条目的频次之和一致
+    let by_part: u64 = totals.by_part.iter().map(|p| p.tokens).sum();
+    let entries: f64 = result.entries.iter().map(|e| e.frequency).sum();
+    assert_eq!(by_part, totals.tokens);
+    assert_eq!(entries, totals.tokens as f64);
+    assert_eq!(totals.listed_tokens, entries);
+    assert_eq!(totals.by_part[2].tokens, 0);
+
+    // 标点计入原始词数，按分母策略从部分词数中扣除；各部分之和仍等于语料词数
+    let options = AnalysisOptions {
+        token_policy: betawordlist_lib::analysis::token_policy::TokenPolicy::RECOMMENDED,
+        ..Default::default()
+    };
+    let filtered = analyze_texts(&WhitespaceTokenizer, &texts, &options).totals();
+    assert_eq!((filtered.raw_tokens, filtered.tokens), (10, 8));
+    assert_eq!(
+        filtered.by_part.iter().map(|p| p.tokens).sum::<u64>(),
+        filtered.tokens
+    );
+    assert_eq!(
+        filtered.by_part.iter().map(|p| p.raw_tokens).sum::<u64>(),
+        filtered.raw_tokens
+    );
+    assert_eq!(filtered.listed_tokens, 8.0);
+}
+
+/// 条目详情给出频率最高的部分，空文本不参与最小值
+#[test]
+fn entry_detail_reports_max_part() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 乙 乙", "", "甲 甲"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let detail = result.entry_detail("甲", "ws").unwrap();
+    assert_eq!(detail.entry.max_part_index, Some(2));
+    assert_eq!(detail.max_part.unwrap().source, "text_2");
+    // 空文本不参与最小值
+    assert_eq!(detail.entry.metrics.min_part_rate, Some(0.25));
+    assert_eq!(detail.entry.metrics.max_part_rate, Some(1.0));
+    assert!(result.entry_detail("丙", "ws").is_none());
+}
+
+/// 严格模式处理完全部文件后中止，列出所有出错的文件
+#[test]
+fn strict_mode_aborts_on_file_errors() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_corpus, AnalysisAbort},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let dir = fixture("corpus");
+    let files = [
+        dir.join("01_spring.txt"),
+        dir.join("missing_a.txt"),
+        dir.join("missing_b.txt"),
+    ];
+    let lenient = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    assert_eq!(lenient.metadata.file_errors.len(), 2);
+
+    // 严格模式处理完全部文件后中止，列出所有出错的文件
+    let strict = AnalysisOptions {
+        strict: true,
+        ..Default::default()
+    };
+    match analyze_corpus(&RuleTokenizer, &files, &strict, None, &NoopReporter) {
+        Err(AnalysisAbort::FileErrors(problems)) => {
+            assert_eq!(problems.len(), 2);
+            assert!(problems[1].contains("missing_b.txt"));
+        }
+        other => panic!("{other:?}"),
+    }
+    let clean = analyze_corpus(&RuleTokenizer, &files[..1], &strict, None, &NoopReporter).unwrap();
+    assert!(clean.metadata.options.strict);
+}
+
+/// 示例语料按文件切分：每个文件一个部分，词频之和等于总词数
+#[test]
+fn fixture_corpus_per_file_totals() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus, nlp::RuleTokenizer, options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let files = corpus_files(3);
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+
+    let tokens: Vec<u64> = result.metadata.parts.iter().map(|p| p.tokens).collect();
+    assert_eq!(tokens, [66, 68, 64]);
+    assert_eq!(result.entries.len(), 122);
+    assert_eq!(
+        result.entries.iter().map(|e| e.frequency).sum::<f64>(),
+        198.0
+    );
+    let top: Vec<(&str, f64, usize)> = result.entries[..3]
+        .iter()
+        .map(|e| (e.word.as_str(), e.frequency, e.metrics.range))
+        .collect();
+    assert_eq!(top, [("。", 11.0, 3), ("，", 8.0, 3), ("在", 7.0, 3)]);
+}
+
+/// 逐句切分时 range 即含该词的句子数
+#[test]
+fn per_sentence_range_counts_sentences() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::{AnalysisOptions, PartitionMode},
+        progress::NoopReporter,
+    };
+
+    let files = corpus_files(3);
+    let options = AnalysisOptions {
+        partition: PartitionMode::PerSentence,
+        ..Default::default()
+    };
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+
+    // 三个文件各有 4、3、4 句
+    assert_eq!(result.metadata.parts.len(), 11);
+    assert_eq!(result.metadata.parts[4].sub_index, Some(0));
+    // 含 "在" 的句子：01 第 2、3 句，02 第 2 句，03 第 2、3 句
+    let zai = result.entries.iter().find(|e| e.word == "在").unwrap();
+    assert_eq!(zai.frequency, 7.0);
+    assert_eq!(zai.metrics.range, 5);
+}
+
+/// 合并短句的门槛大于文件词数时，指标与按文件切分一致
+#[test]
+fn merged_sentences_match_per_file() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::{AnalysisOptions, PartitionMode},
+        progress::NoopReporter,
+    };
+
+    let files = corpus_files(3);
+    let analyze = |options: &AnalysisOptions| {
+        analyze_corpus(&RuleTokenizer, &files, options, None, &NoopReporter).unwrap()
+    };
+    let merged = analyze(&AnalysisOptions {
+        partition: PartitionMode::PerSentence,
+        merge_small_parts: true,
+        min_part_tokens: usize::MAX,
+        ..Default::default()
+    });
+    let per_file = analyze(&AnalysisOptions::default());
+
+    let parts = &merged.metadata.parts;
+    assert_eq!(parts.len(), 3);
+    assert_eq!(parts[1].units, [0, 1, 2]);
+    assert_eq!(parts[1].label(), "02_library.txt#0-2");
+    let merges = merged.metadata.part_merges.as_ref().unwrap();
+    assert_eq!(
+        (merges.units, merges.merges, merges.merged_parts),
+        (11, 8, 3)
+    );
+    for (a, b) in parts.iter().zip(&per_file.metadata.parts) {
+        assert_eq!(a.tokens, b.tokens);
+    }
+    assert_eq!(merged.entries.len(), per_file.entries.len());
+    for entry in &per_file.entries {
+        let other = merged
+            .entries
+            .iter()
+            .find(|e| (&e.word, &e.pos) == (&entry.word, &entry.pos))
+            .unwrap();
+        assert_eq!(other.frequency, entry.frequency);
+        assert_eq!(other.metrics.range, entry.metrics.range);
+        assert_eq!(other.metrics.dp, entry.metrics.dp);
+        assert_eq!(other.metrics.juilland_d, entry.metrics.juilland_d);
+    }
+
+    // 门槛不高时只合并短句，不跨文件；按文件切分的结果不记合并
+    let small = analyze(&AnalysisOptions {
+        partition: PartitionMode::PerSentence,
+        merge_small_parts: true,
+        min_part_tokens: 12,
+        ..Default::default()
+    });
+    let units: Vec<usize> = small.metadata.parts.iter().map(|p| p.units.len()).collect();
+    assert_eq!(units.iter().sum::<usize>(), 11);
+    assert!(small.metadata.parts.len() < 11);
+    assert!(per_file.metadata.part_merges.is_none());
+}
+
+/// 跳过与去重的文件不形成部分，词频向量按最终的部分列表对齐
+#[test]
+fn frequency_vectors_follow_final_parts() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_corpus, analyze_texts, AnalysisAbort},
+        language::{LanguageScreen, ScreenMode},
+        near_duplicates::{DuplicatePolicy, NearDuplicateOptions},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::NoopReporter,
+        word_analyzer::{CorpusWordAnalyzer, PartLengthMismatch},
+        word_entry::WordEntry,
+    };
+    use std::fs;
+
+    let dir = TempDir::new("part_lengths");
+    let files = ["first", "english", "missing", "copy", "last"].map(|name| dir.join(name));
+    fs::write(&files[0], article(0)).unwrap();
+    fs::write(&files[1], "plain English text only").unwrap();
+    fs::write(&files[3], article(0)).unwrap();
+    fs::write(&files[4], article(12345)).unwrap();
+    let options = AnalysisOptions {
+        language_screen: LanguageScreen {
+            mode: ScreenMode::Skip,
+            ..Default::default()
+        },
+        near_duplicates: Some(NearDuplicateOptions {
+            policy: DuplicatePolicy::KeepLonger,
+            ..Default::default()
+        }),
+        keep_parts: true,
+        ..Default::default()
+    };
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+    assert_eq!(result.metadata.parts.len(), 3);
+    assert_eq!(result.metadata.inconsistent_entries, 0);
+
+    // 各条目的部分频次与 3 个部分一一对应；按传入的 5 个文件定长的向量会被拒绝，
+    // 计算指标时遇到这样的条目则中止分析，而不是略去条目
+    let sizes: Vec<f64> = result
+        .metadata
+        .parts
+        .iter()
+        .map(|p| p.tokens as f64)
+        .collect();
+    let total = sizes.iter().sum();
+    for entry in &result.entries {
+        let v = result.part_frequencies(&entry.word, &entry.pos).unwrap();
+        let analyzer = CorpusWordAnalyzer::new(v.clone(), sizes.clone(), total).unwrap();
+        assert_eq!(analyzer.get_dp(), entry.metrics.dp);
+        let mut padded = v;
+        padded.resize(files.len(), 0.0);
+        let error = CorpusWordAnalyzer::new(padded, sizes.clone(), total).err();
+        assert_eq!(
+            error,
+            Some(PartLengthMismatch {
+                frequencies: 5,
+                part_sizes: 3
+            })
+        );
+    }
+    let mismatch = PartLengthMismatch {
+        frequencies: 5,
+        part_sizes: 3,
+    };
+    let abort = AnalysisAbort::PartLengths("春天/n".into(), mismatch);
+    assert_eq!(
+        abort.to_string(),
+        "条目 春天/n 的部分频次数（5）与部分词数的个数（3）不一致，计数有误，分析已中止"
+    );
+
+    let texts = [article(0), String::new(), article(12345)];
+    let expected = analyze_texts(&RuleTokenizer, &texts, &AnalysisOptions::default());
+    let metrics = |entries: &[WordEntry]| {
+        let mut metrics: Vec<String> = entries
+            .iter()
+            .map(|e| format!("{} {:?}", e.word, e.metrics))
+            .collect();
+        metrics.sort();
+        metrics
+    };
+    assert_eq!(metrics(&result.entries), metrics(&expected.entries));
+}
+
+/// 两遍计数与单遍计数对保留下来的词给出相同的结果
+#[test]
+fn two_pass_matches_single_pass() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+        word_entry::WordEntry,
+    };
+
+    let texts = ["甲 乙 甲 丙", "甲 乙 丁", "乙 戊 甲"].map(String::from);
+    let mut options = AnalysisOptions {
+        min_frequency: Some(2.0),
+        ..Default::default()
+    };
+    let single = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    options.two_pass = true;
+    let double = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+
+    let sorted = |mut entries: Vec<WordEntry>| {
+        entries.sort_by(|a, b| a.word.cmp(&b.word));
+        format!("{entries:?}")
+    };
+    assert_eq!(single.entries.len(), 2);
+    assert_eq!(sorted(single.entries), sorted(double.entries));
+    assert_eq!(single.metadata.min_frequency_removed, 3);
+    assert_eq!(double.metadata.min_frequency_removed, 3);
+}
+
+/// 位置按文本顺序在全语料中连续编号
+#[test]
+fn positions_run_across_corpus() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    };
+    let texts = ["甲 乙 甲", "丙 乙"].map(String::from);
+    let options = AnalysisOptions {
+        track_positions: true,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let entry = |word: &str| result.entries.iter().find(|e| e.word == word).unwrap();
+    assert_eq!(entry("乙").first_occurrence_part, Some(0));
+    assert_eq!(entry("乙").first_occurrence_fraction, Some(0.2));
+    assert_eq!(entry("乙").occurrence_span_fraction, Some(0.6));
+    assert_eq!(entry("丙").first_occurrence_part, Some(1));
+    assert_eq!(entry("丙").first_occurrence_fraction, Some(0.6));
+    assert_eq!(entry("丙").occurrence_span_fraction, Some(0.0));
+
+    let plain = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    assert!(plain
+        .entries
+        .iter()
+        .all(|e| e.first_occurrence_fraction.is_none()));
+}
+
+/// 重算结果与用新选项重新分析一致，影响计数的选项被拒绝
+#[test]
+fn recompute_matches_fresh_analysis() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_texts, recompute_metrics},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        word_entry::WordEntry,
+    };
+
+    let texts = ["甲 乙 甲 丙", "甲 乙 丁", "乙 戊 甲"].map(String::from);
+    let previous = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let options = AnalysisOptions {
+        rate_base: 1000.0,
+        min_frequency: Some(2.0),
+        ..Default::default()
+    };
+    let recomputed = recompute_metrics(&previous, &options).unwrap();
+    let fresh = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let sorted = |mut entries: Vec<WordEntry>| {
+        entries.sort_by(|a, b| a.word.cmp(&b.word));
+        format!("{entries:?}")
+    };
+    assert_eq!(sorted(recomputed.entries), sorted(fresh.entries));
+
+    // 影响计数的选项不能只重算指标
+    let stopwords = AnalysisOptions {
+        stopwords: vec!["甲".to_string()],
+        ..Default::default()
+    };
+    assert!(recompute_metrics(&previous, &stopwords).is_err());
+}
+
+/// 事后过滤与用新选项重新分析一致，已移出的词无法恢复
+#[test]
+fn filters_match_fresh_analysis() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_texts, apply_filters},
+        filter::DenominatorPolicy,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        word_entry::WordEntry,
+    };
+
+    let texts = ["甲 的 乙 甲 了", "甲 乙 的 丁", "乙 了 戊 甲 的"].map(String::from);
+    let before = AnalysisOptions {
+        stopwords: vec!["的".into()],
+        ..Default::default()
+    };
+    let previous = analyze_texts(&WhitespaceTokenizer, &texts, &before);
+    let sorted = |mut entries: Vec<WordEntry>| {
+        entries.sort_by(|a, b| a.word.cmp(&b.word));
+        format!(
+            "{:?}",
+            entries
+                .iter()
+                .map(|e| (&e.word, e.frequency, &e.metrics.dp))
+                .collect::<Vec<_>>()
+        )
+    };
+
+    // 新增停用词，并改为从部分词数中扣除停用词：各词的 DP 随之改变，与重新分析一致
+    let options = AnalysisOptions {
+        stopwords: vec!["的".into(), "了".into()],
+        denominator: DenominatorPolicy {
+            stopwords: true,
+            ..Default::default()
+        },
+        min_frequency: Some(2.0),
+        ..Default::default()
+    };
+    let filtered = apply_filters(&previous, &options).unwrap();
+    let fresh = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    assert_eq!(sorted(filtered.entries.clone()), sorted(fresh.entries));
+    assert_eq!(filtered.metadata.filter_removed.stopwords, 5);
+    assert_eq!(filtered.metadata.total_tokens, fresh.metadata.total_tokens);
+    assert!(filtered
+        .metadata
+        .warnings
+        .iter()
+        .any(|w| w.contains("事后过滤")));
+
+    // 已移出词表的词无法恢复
+    assert!(apply_filters(&filtered, &AnalysisOptions::default()).is_err());
+    // 影响计数的其他选项仍需重新分析
+    let phrases = AnalysisOptions {
+        phrases: vec!["甲 乙".into()],
+        ..before
+    };
+    assert!(apply_filters(&previous, &phrases).is_err());
+}
+
+/// 并入的近重复文件读取失败时只记下该文件，主文件照常计数
+#[test]
+fn unreadable_merged_duplicate_keeps_primary() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        near_duplicates::{DuplicatePolicy, NearDuplicateOptions},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::{
+            AnalysisComplete, AnalysisError, AnalysisProgress, FileCounted, NoopReporter,
+            ProgressReporter,
+        },
+    };
+    use std::path::PathBuf;
+
+    /// 近重复分组之后、读取各文件之前删除给定的文件
+    struct RemoveBeforeReading(PathBuf);
+
+    impl ProgressReporter for RemoveBeforeReading {
+        fn progress(&self, _event: &AnalysisProgress) {}
+        fn file_counted(&self, _event: &FileCounted) {}
+        fn complete(&self, _event: &AnalysisComplete) {}
+        fn error(&self, _event: &AnalysisError) {}
+        fn cancelled(&self) -> bool {
+            std::fs::remove_file(&self.0).ok();
+            false
+        }
+    }
+
+    let dir = TempDir::new("merged_duplicate");
+    let copy = dir.write("a.txt", article(0));
+    let primary = dir.write("b.txt", format!("本文转载自某报\n{}", article(0)));
+    let options = AnalysisOptions {
+        near_duplicates: Some(NearDuplicateOptions {
+            policy: DuplicatePolicy::Merge,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let files = [copy.clone(), primary.clone()];
+    let reporter = RemoveBeforeReading(copy);
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &reporter).unwrap();
+    assert_eq!(result.metadata.file_errors.len(), 1);
+    assert!(result.metadata.file_errors[0].contains("a.txt"));
+
+    let alone = analyze_corpus(
+        &RuleTokenizer,
+        &[primary],
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    assert_eq!(result.metadata.parts.len(), 1);
+    assert_eq!(result.metadata.total_tokens, alone.metadata.total_tokens);
+}
@@ -0,0 +1,66 @@
+// count_import.rs
+// count_import 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 长表与宽表导入的指标与直接分析一致，错误附行号
+#[test]
+fn imported_counts_match_analysis() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        count_import::{import_count_matrix, MatrixFormat},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        word_entry::WordEntry,
+    };
+
+    let dir = TempDir::new("count_import");
+    let long = dir.write(
+        "long.csv",
+        "file,word,pos,count\na,甲,ws,2\na,乙,ws,1\nb,乙,ws,1\nb,丙,ws,1\n",
+    );
+    let wide = dir.write(
+        "wide.tsv",
+        "word\tpos\ta\tb\n甲\tws\t2\t0\n乙\tws\t1\t1\n丙\tws\t0\t1\n",
+    );
+    let options = AnalysisOptions::default();
+
+    // 与直接分析同样的文本一致
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let analyzed = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let metrics = |entries: &[WordEntry]| {
+        let mut rows: Vec<String> = entries
+            .iter()
+            .map(|e| format!("{} {} {:?}", e.word, e.frequency, e.metrics))
+            .collect();
+        rows.sort();
+        rows
+    };
+    for (path, format) in [(&long, MatrixFormat::Long), (&wide, MatrixFormat::Wide)] {
+        let imported = import_count_matrix(path, format, None, &options).unwrap();
+        assert_eq!(imported.metadata.parts.len(), 2);
+        assert_eq!(imported.metadata.parts[0].source, "a");
+        assert_eq!(metrics(&imported.entries), metrics(&analyzed.entries));
+    }
+
+    // 另给部分词数
+    let sizes = dir.write("sizes.csv", "file,size\na,10\nb,5\n");
+    let imported = import_count_matrix(&long, MatrixFormat::Long, Some(&sizes), &options).unwrap();
+    assert_eq!(imported.metadata.total_tokens, 15);
+
+    // 校验：负数、重复行、列数不符，附行号
+    let error = |text: &str, format| {
+        let path = dir.write("bad.csv", text);
+        import_count_matrix(&path, format, None, &options).unwrap_err()
+    };
+    assert!(error("word,pos,file,count\n甲,ws,a,-1\n", MatrixFormat::Long).starts_with("第 2 行"));
+    let duplicate = error(
+        "word,pos,file,count\n甲,ws,a,1\n乙,ws,a,1\n甲,ws,a,2\n",
+        MatrixFormat::Long,
+    );
+    assert!(duplicate.starts_with("第 4 行") && duplicate.contains("第 2 行"));
+    assert!(error("word,pos,a,b\n甲,ws,1\n", MatrixFormat::Wide).starts_with("第 2 行"));
+    assert!(error("word,pos,a,a\n甲,ws,1,1\n", MatrixFormat::Wide).starts_with("第 1 行"));
+}
@@ -0,0 +1,29 @@
+// coverage.rs
+// coverage 模块的测试
+
+/// 词表在全语料与各文件上的覆盖率
+#[test]
+fn coverage_of_word_list() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, coverage::list_coverage, nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+    use std::collections::HashSet;
+
+    let texts = ["甲 乙 甲 丙", "甲 乙 丁 丁"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let list: HashSet<String> = ["甲", "乙"].map(String::from).into();
+    let coverage = list_coverage(&result, &list, 10).unwrap();
+
+    assert_eq!(coverage.corpus.covered_tokens, 5.0);
+    assert_eq!(coverage.corpus.token_coverage, Some(5.0 / 8.0));
+    assert_eq!(coverage.corpus.type_coverage, Some(0.5));
+    // "丙" 只出现一次，不计它时 5 / 7
+    assert_eq!(coverage.corpus.uncovered_hapaxes, 1);
+    assert_eq!(
+        coverage.corpus.token_coverage_excluding_hapaxes,
+        Some(5.0 / 7.0)
+    );
+    assert_eq!(coverage.files[0].token_coverage, Some(0.75));
+    assert_eq!(coverage.uncovered[0].word, "丁");
+}
@@ -0,0 +1,61 @@
+// dictionary.rs
+// dictionary 模块的测试
+
+/// 按 U 取前 N 个条目分段，截断处的并列附说明
+#[test]
+fn dictionary_bands_and_ties() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        dictionary::{self, DictionaryOptions},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 甲 乙 丙 丁", "甲 乙 丙 戊", "甲 乙 丁 戊"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let options = DictionaryOptions {
+        n: 4,
+        band_size: 2,
+        ..Default::default()
+    };
+    let dictionary = dictionary::build(&result, &options).unwrap();
+
+    let words: Vec<(usize, usize, &str)> = dictionary
+        .entries
+        .iter()
+        .map(|e| (e.band, e.rank, e.entry.word.as_str()))
+        .collect();
+    // 丙、丁、戊 频次相同，戊 分布更均匀；丙、丁 的 U 相同，在截断处并列，按词形取 丁
+    assert_eq!(
+        words,
+        [(1, 1, "甲"), (1, 2, "乙"), (2, 3, "戊"), (2, 4, "丁")]
+    );
+    assert!(dictionary
+        .tie_note
+        .unwrap()
+        .contains("收入 1 个、舍去 1 个"));
+    assert_eq!(dictionary.bands.len(), 2);
+    assert_eq!(
+        (
+            dictionary.bands[1].first_rank,
+            dictionary.bands[1].last_rank
+        ),
+        (3, 4)
+    );
+    assert_eq!(dictionary.bands[0].pos_counts, [("ws".to_string(), 2)]);
+
+    let by_frequency = DictionaryOptions {
+        n: 2,
+        order_by: "frequency".into(),
+        ..options
+    };
+    let dictionary = dictionary::build(&result, &by_frequency).unwrap();
+    assert_eq!(dictionary.entries[1].value, 3.0);
+    assert!(dictionary.tie_note.is_none());
+
+    let unknown = DictionaryOptions {
+        order_by: "zipf".into(),
+        ..Default::default()
+    };
+    assert!(dictionary::build(&result, &unknown).is_err());
+}
@@ -0,0 +1,55 @@
+// dispersion_metrics.rs
+// dispersion_metrics 模块的测试
+
+/// to_row 与 to_header、序列化字段一一对应
+#[test]
+fn row_follows_header() {
+    use betawordlist_lib::analysis::dispersion_metrics::DispersionMetrics;
+
+    let metrics = DispersionMetrics {
+        range: 3,
+        dp: Some(0.123456789012),
+        ..Default::default()
+    };
+    let header = DispersionMetrics::to_header();
+    let row = metrics.to_row(4);
+    assert_eq!(header.len(), row.len());
+    let serialized = serde_json::to_value(&metrics).unwrap();
+    assert_eq!(header.len(), serialized.as_object().unwrap().len());
+
+    let cell = |name: &str| &row[header.iter().position(|h| h == name).unwrap()];
+    assert_eq!(cell("range"), "3");
+    assert_eq!(cell("dp"), "0.1235");
+    assert_eq!(cell("gini"), "");
+
+    let display = format!("{metrics:.2}");
+    assert!(display
+        .lines()
+        .any(|line| line.starts_with("dp ") && line.ends_with(" 0.12")));
+    assert_eq!(display.lines().count(), header.len());
+}
+
+/// 清洗后序列化的 JSON 中不会出现 NaN 或 Infinity
+#[test]
+fn sanitized_metrics_serialize_without_nan() {
+    use betawordlist_lib::analysis::dispersion_metrics::DispersionMetrics;
+
+    let mut metrics = DispersionMetrics::default();
+    let poison = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+    let floats = metrics.float_fields_mut().len();
+    for (i, (_, value)) in metrics.float_fields_mut().into_iter().enumerate() {
+        *value = Some(poison[i % poison.len()]);
+    }
+    // 除 range 与 range_at_threshold 两个部分数外都是浮点字段
+    assert_eq!(floats, DispersionMetrics::FIELD_NAMES.len() - 2);
+    assert_eq!(metrics.sanitize().len(), floats);
+
+    let json = serde_json::to_string(&metrics).unwrap();
+    assert!(!json.contains("NaN") && !json.contains("inf"));
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let object = parsed.as_object().unwrap();
+    assert_eq!(object.len(), DispersionMetrics::FIELD_NAMES.len());
+    for (name, _) in metrics.fields().iter().filter(|(_, v)| v.is_none()) {
+        assert!(object[*name].is_null(), "{name}");
+    }
+}
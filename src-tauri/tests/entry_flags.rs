@@ -0,0 +1,51 @@
+// entry_flags.rs
+// entry_flags 模块的测试
+
+/// 条目标记只取决于条目本身
+#[test]
+fn flags_follow_entry() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        entry_flags::{
+            flags_for, ABSENT_TARGET, CONTAINS_LATIN, HAPAX, MERGED_VARIANTS, NORMALIZED,
+        },
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        token_policy::LatinCase,
+    };
+
+    let texts = ["iPhone iphone 手机 手机", "IPHONE 平板 iPad"].map(String::from);
+    let options = AnalysisOptions {
+        latin_case: LatinCase::Fold,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let entry = |word: &str| result.entries.iter().find(|e| e.word == word).unwrap();
+
+    assert_eq!(
+        entry("iphone").flags,
+        [NORMALIZED, MERGED_VARIANTS, CONTAINS_LATIN]
+    );
+    assert_eq!(entry("ipad").flags, [NORMALIZED, CONTAINS_LATIN, HAPAX]);
+    assert!(entry("手机").flags.is_empty());
+    assert_eq!(entry("平板").flags, [HAPAX]);
+    // 标记只取决于条目本身
+    assert_eq!(flags_for(entry("iphone")), entry("iphone").flags);
+
+    // 不折叠时无从判断是否经过规范化
+    let preserved = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let iphone = preserved
+        .entries
+        .iter()
+        .find(|e| e.word == "iphone")
+        .unwrap();
+    assert_eq!(iphone.flags, [CONTAINS_LATIN, HAPAX]);
+
+    let options = AnalysisOptions {
+        target_words: vec!["手机".into(), "电脑".into()],
+        ..Default::default()
+    };
+    let targeted = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let absent = targeted.entries.iter().find(|e| e.word == "电脑").unwrap();
+    assert_eq!(absent.flags, [ABSENT_TARGET]);
+}
@@ -0,0 +1,35 @@
+// entry_id.rs
+// entry_id 模块的测试
+
+/// 规范化签名只记决定词形的选项
+#[test]
+fn signature_names_normalization() {
+    use betawordlist_lib::analysis::{
+        entry_id,
+        options::AnalysisOptions,
+        token_policy::{LatinCase, TokenPolicy},
+    };
+
+    let default = AnalysisOptions::default();
+    assert_eq!(
+        entry_id::normalization_signature(&default),
+        "id1;latin_case=preserve;sanitize=0"
+    );
+    let fold = AnalysisOptions {
+        latin_case: LatinCase::Fold,
+        token_policy: TokenPolicy::RECOMMENDED,
+        ..Default::default()
+    };
+    let lower = AnalysisOptions {
+        latin_case: LatinCase::Lower,
+        ..fold.clone()
+    };
+    assert_eq!(
+        entry_id::normalization_signature(&fold),
+        "id1;latin_case=lower;sanitize=1"
+    );
+    assert_eq!(
+        entry_id::normalization_signature(&fold),
+        entry_id::normalization_signature(&lower)
+    );
+}
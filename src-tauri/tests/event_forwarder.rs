@@ -0,0 +1,114 @@
+// event_forwarder.rs
+// event_forwarder 模块的测试
+
+/// 下游消费很慢时分析线程不受影响，中间进度被合并，终止事件仍然送达
+#[test]
+fn slow_consumer_does_not_block_analysis() {
+    use betawordlist_lib::analysis::{
+        event_forwarder::ForwardingReporter,
+        metadata::PartInfo,
+        progress::{
+            AnalysisComplete, AnalysisError, AnalysisProgress, FileCounted, ProgressReporter,
+            RecordingReporter, ReportedEvent,
+        },
+    };
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// 每个事件耗时 5ms 的下游，记录收到各事件的时刻
+    #[derive(Default)]
+    struct SlowReporter {
+        inner: RecordingReporter,
+        received: Mutex<Vec<Instant>>,
+    }
+
+    impl SlowReporter {
+        fn take(&self) {
+            thread::sleep(Duration::from_millis(5));
+            self.received.lock().unwrap().push(Instant::now());
+        }
+    }
+
+    impl ProgressReporter for SlowReporter {
+        fn progress(&self, e: &AnalysisProgress) {
+            self.take();
+            self.inner.progress(e)
+        }
+        fn file_counted(&self, e: &FileCounted) {
+            self.take();
+            self.inner.file_counted(e)
+        }
+        fn complete(&self, e: &AnalysisComplete) {
+            self.take();
+            self.inner.complete(e)
+        }
+        fn error(&self, e: &AnalysisError) {
+            self.take();
+            self.inner.error(e)
+        }
+    }
+
+    let slow = Arc::new(SlowReporter::default());
+    let interval = Duration::from_millis(20);
+    let forwarder = ForwardingReporter::with_limits(slow.clone(), 4, interval);
+    let progress = |current| AnalysisProgress {
+        current,
+        total: 2000,
+        file: String::new(),
+        vocab_entries: 0,
+        estimated_bytes: 0,
+        tokens_done: 0,
+        tokens_total_estimate: 0,
+        files_in_event: 1,
+        files_per_second: None,
+        phase: Default::default(),
+        types_done: 0,
+        types_total: 0,
+    };
+
+    let started = Instant::now();
+    for current in 1..=2000 {
+        forwarder.progress(&progress(current));
+        if current % 100 == 0 {
+            forwarder.file_counted(&FileCounted::new(PartInfo::default(), &[], 0));
+        }
+    }
+    forwarder.complete(&AnalysisComplete {
+        types: 0,
+        tokens: 0,
+        duration_ms: 0,
+        errors: Vec::new(),
+    });
+    // 下游逐个处理需 10 秒以上，分析线程入队几乎不耗时
+    assert!(started.elapsed() < Duration::from_secs(1));
+    let dropped = forwarder.dropped();
+    forwarder.finish();
+
+    let events = slow.inner.events.lock().unwrap();
+    // 终止事件不丢弃，且在最新进度之后送出
+    assert!(matches!(events.last(), Some(ReportedEvent::Complete(_))));
+    let last_progress = events.iter().rev().find_map(|e| match e {
+        ReportedEvent::Progress(p) => Some(p.current),
+        _ => None,
+    });
+    assert_eq!(last_progress, Some(2000));
+    // 中间进度被合并；队列满时 file-counted 被丢弃并计数
+    let progress_events = events
+        .iter()
+        .filter(|e| matches!(e, ReportedEvent::Progress(_)))
+        .count();
+    assert!(progress_events < 20);
+    let counted = events
+        .iter()
+        .filter(|e| matches!(e, ReportedEvent::FileCounted(_)))
+        .count();
+    assert!(dropped > 0);
+    assert_eq!(counted + dropped, 20);
+    // 非终止事件之间不短于限速间隔
+    let received = slow.received.lock().unwrap();
+    let spacing = received[..received.len() - 1]
+        .windows(2)
+        .map(|w| w[1] - w[0]);
+    assert!(spacing.into_iter().all(|d| d >= interval));
+}
@@ -0,0 +1,228 @@
+// export.rs
+// export 模块的测试
+
+mod common;
+
+use common::corpus_files;
+
+/// 数值按精确十进制值舍入，恰好居中时取偶
+#[test]
+fn numbers_round_half_to_even() {
+    use betawordlist_lib::analysis::export::ExportOptions;
+
+    let options = ExportOptions {
+        precision: 4,
+        decimal_separator: ',',
+        delimiter: ';',
+        ..ExportOptions::default()
+    };
+    for v in [
+        0.123456789,
+        12.0,
+        1e-7,
+        2.5e-5,
+        0.00015,
+        123456.78915,
+        -3.25159,
+    ] {
+        let back: f64 = options.format_number(v).replace(',', ".").parse().unwrap();
+        assert!((back - v).abs() <= 1e-4);
+    }
+
+    let two = ExportOptions {
+        precision: 2,
+        ..ExportOptions::default()
+    };
+    assert_eq!(two.format_number(0.125), "0.12");
+    assert_eq!(two.format_number(0.375), "0.38");
+    assert_eq!(two.format_number(3.0), "3");
+    assert_eq!(two.format_number(-0.001), "0");
+}
+
+/// 条目表的列名与各行的单元格一一对应
+#[test]
+fn header_matches_rows() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        export::{write_csv, ExportOptions},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let options = ExportOptions {
+        columns: ["rank_frequency", "word", "frequency", "range", "examples"]
+            .map(String::from)
+            .to_vec(),
+        ..ExportOptions::default()
+    };
+    let mut out = Vec::new();
+    write_csv(&mut out, &result, &options).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "rank_frequency,word,frequency,range,examples");
+    // 未采集例句，examples 为空
+    assert!(lines.contains(&"1,乙,2,2,"));
+
+    // 折叠大小写时，surface_form 列写出频次最高的原文写法
+    use betawordlist_lib::analysis::token_policy::LatinCase;
+    let texts = ["WiFi wifi WiFi", "手机"].map(String::from);
+    let fold = AnalysisOptions {
+        latin_case: LatinCase::Fold,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &fold);
+    let options = ExportOptions {
+        columns: ["word", "surface_form", "frequency"]
+            .map(String::from)
+            .to_vec(),
+        ..ExportOptions::default()
+    };
+    let mut out = Vec::new();
+    write_csv(&mut out, &result, &options).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    assert!(csv.contains("\nwifi,WiFi,3\n") && csv.contains("\n手机,手机,1\n"));
+}
+
+/// 某个写出端出错后其余写出端照常写完
+#[test]
+fn failing_sink_does_not_stop_others() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        export::{export_rows, ExportOptions, RowSink, TableSink},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+    use std::io::{self, Write};
+
+    struct Full;
+    impl Write for Full {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("磁盘已满"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let csv = ExportOptions::default();
+    let tsv = ExportOptions {
+        delimiter: '\t',
+        ..ExportOptions::default()
+    };
+    let (mut a, mut b) = (Vec::new(), Vec::new());
+    let mut csv_sink = TableSink::new(&mut a, &csv);
+    let mut tsv_sink = TableSink::new(&mut b, &tsv);
+    let mut full_sink = TableSink::new(Full, &csv);
+    let outcomes = export_rows(
+        &result,
+        &[],
+        &mut [
+            &mut csv_sink as &mut dyn RowSink,
+            &mut full_sink,
+            &mut tsv_sink,
+        ],
+    );
+    assert!(outcomes[0].is_ok() && outcomes[1].is_err() && outcomes[2].is_ok());
+    drop((csv_sink, tsv_sink));
+    assert_eq!(
+        String::from_utf8(a).unwrap().replace(',', "\t"),
+        String::from_utf8(b).unwrap()
+    );
+}
+
+/// 各组一张表，表名去掉不允许的字符并截断，重名时加序号
+#[test]
+fn group_keyword_sheet_names() {
+    use betawordlist_lib::analysis::{
+        export::{self, ExportOptions},
+        group_keywords::GroupKeywords,
+    };
+
+    let group = |name: &str| GroupKeywords {
+        group: name.to_string(),
+        parts: 1,
+        tokens: 100.0,
+        keywords: Vec::new(),
+    };
+    let groups = [group("新闻/国内"), group("新闻:国内"), group("")];
+    let mut xlsx = Vec::new();
+    export::write_group_keywords_xlsx(&mut xlsx, &groups, &ExportOptions::default()).unwrap();
+    let text = String::from_utf8_lossy(&xlsx);
+    assert!(text.contains("<sheet name=\"新闻_国内\""));
+    assert!(text.contains("<sheet name=\"新闻_国内_2\""));
+    assert!(text.contains("<sheet name=\"group\""));
+}
+
+/// 词性分布表只在异常的部分与词性上填 z
+#[test]
+fn pos_histogram_marks_outliers() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        export::{write_pos_histogram_csv, ExportOptions},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let files = corpus_files(2);
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    let part = &result.metadata.parts[0];
+    assert_eq!(part.pos_counts.values().sum::<u64>(), part.raw_tokens);
+
+    let mut out = Vec::new();
+    write_pos_histogram_csv(&mut out, &result.metadata, &ExportOptions::default()).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    assert!(csv.starts_with("part,label,pos,tokens,share,z\n0,01_spring.txt,"));
+    let rows = result
+        .metadata
+        .parts
+        .iter()
+        .map(|p| p.pos_counts.len())
+        .sum::<usize>();
+    assert_eq!(csv.lines().count(), rows + 1);
+}
+
+/// 频率词典每段一张表，summary 表附并列说明
+#[test]
+fn dictionary_workbook_has_band_sheets() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        dictionary::{self, DictionaryOptions},
+        export::{self, ExportOptions},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 甲 乙 丙 丁", "甲 乙 丙 戊", "甲 乙 丁 戊"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let options = DictionaryOptions {
+        n: 4,
+        band_size: 2,
+        order_by: "dp".into(),
+    };
+    let dictionary = dictionary::build(&result, &options).unwrap();
+
+    let mut bytes = Vec::new();
+    export::write_dictionary_xlsx(&mut bytes, &dictionary, &ExportOptions::default()).unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+    for sheet in ["summary", "band_1", "band_2"] {
+        assert!(text.contains(&format!("<sheet name=\"{sheet}\"")));
+    }
+
+    let mut csv = Vec::new();
+    export::write_dictionary_csv(&mut csv, &dictionary, &ExportOptions::default()).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("band,rank,word,pos,char_length,frequency,juilland_d,dp,range\n"));
+    assert_eq!(csv.lines().count(), 5);
+}
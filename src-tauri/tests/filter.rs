@@ -0,0 +1,135 @@
+// filter.rs
+// filter 模块的测试
+
+mod common;
+
+use common::fixture;
+
+/// 数字是否计入部分词数会改变其余各词的 DP
+#[test]
+fn denominator_changes_dp() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_corpus, AnalysisResult},
+        filter::DenominatorPolicy,
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let dir = fixture("digits");
+    let files = [dir.join("01_report.txt"), dir.join("02_essay.txt")];
+    let kept = AnalysisOptions {
+        excluded_pos: vec!["m".to_string()],
+        ..Default::default()
+    };
+    let dropped = AnalysisOptions {
+        denominator: DenominatorPolicy {
+            excluded_pos: true,
+            ..Default::default()
+        },
+        ..kept.clone()
+    };
+    let kept = analyze_corpus(&RuleTokenizer, &files, &kept, None, &NoopReporter).unwrap();
+    let dropped = analyze_corpus(&RuleTokenizer, &files, &dropped, None, &NoopReporter).unwrap();
+
+    // 两种设置的原始词数相同；扣除数字后，财报一侧的部分词数少了全部数字
+    let numbers = dropped.metadata.filter_removed.excluded_pos;
+    assert_eq!(numbers, 20);
+    assert_eq!(
+        kept.metadata.total_raw_tokens,
+        dropped.metadata.total_raw_tokens
+    );
+    assert_eq!(
+        kept.metadata.parts[0].tokens - dropped.metadata.parts[0].tokens,
+        numbers
+    );
+    assert_eq!(
+        kept.metadata.parts[1].tokens,
+        dropped.metadata.parts[1].tokens
+    );
+    assert!(kept.metadata.size_exclusions.is_empty());
+    assert_eq!(dropped.metadata.size_exclusions, ["excluded_pos"]);
+    assert_eq!(
+        dropped.metadata.filter_report.filters[0].corpus_words_removed,
+        numbers
+    );
+
+    // 词频不变，DP 随分母变化
+    let dp = |result: &AnalysisResult| {
+        let gong = result.entries.iter().find(|e| e.word == "公").unwrap();
+        (gong.frequency, gong.metrics.dp.unwrap())
+    };
+    let (kept_frequency, kept_dp) = dp(&kept);
+    let (dropped_frequency, dropped_dp) = dp(&dropped);
+    assert_eq!(kept_frequency, dropped_frequency);
+    assert!(kept_dp - dropped_dp > 0.04, "{kept_dp} vs {dropped_dp}");
+}
+
+/// 过滤报告按各项过滤计入移除的词元与词型
+#[test]
+fn report_counts_each_filter() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    };
+
+    let texts = ["的 书 的 我", "的 书 了"].map(String::from);
+    let options = AnalysisOptions {
+        stopwords: vec!["的".to_string(), "了".to_string()],
+        min_frequency: Some(2.0),
+        ..Default::default()
+    };
+    let report = analyze_texts(&WhitespaceTokenizer, &texts, &options)
+        .metadata
+        .filter_report;
+
+    // 停用词只移出词表，分母不变
+    assert_eq!(report.total_corpus_words, 7);
+    let stopwords = &report.filters[0];
+    assert_eq!((stopwords.tokens_removed, stopwords.types_removed), (4, 2));
+    assert_eq!(stopwords.corpus_words_removed, 0);
+    assert_eq!(stopwords.top_removed[0].word, "的");
+    assert_eq!(stopwords.top_removed[0].frequency, 3.0);
+    let by_file: Vec<u64> = stopwords.by_file.iter().map(|f| f.tokens).collect();
+    assert_eq!(by_file, [2, 2]);
+
+    let min_frequency = &report.filters[1];
+    assert_eq!(min_frequency.filter, "min_frequency");
+    assert_eq!(min_frequency.top_removed[0].word, "我");
+    assert_eq!(min_frequency.tokens_removed, 1);
+
+    // 两遍计数时第一遍淘汰的词同样计入
+    let two_pass = AnalysisOptions {
+        two_pass: true,
+        ..options
+    };
+    let report2 = analyze_texts(&WhitespaceTokenizer, &texts, &two_pass)
+        .metadata
+        .filter_report;
+    assert_eq!(report2, report);
+
+    // 结构性噪声在计数前移出词元流，按序列计入，同时从分母中扣除
+    let novel = ["第一章\n春 天", "第二章\n春"].map(String::from);
+    let options = AnalysisOptions {
+        structural_noise: true,
+        ..Default::default()
+    };
+    let report = analyze_texts(&WhitespaceTokenizer, &novel, &options)
+        .metadata
+        .filter_report;
+    assert_eq!(report.total_corpus_words, 3);
+    let noise = &report.filters[0];
+    assert_eq!(noise.filter, "structural_noise");
+    assert_eq!(
+        (
+            noise.tokens_removed,
+            noise.types_removed,
+            noise.corpus_words_removed
+        ),
+        (2, 2, 2)
+    );
+    let first = &noise.top_removed[0];
+    assert_eq!(
+        (first.word.as_str(), first.pos.as_str()),
+        ("第一章", "chapter_heading")
+    );
+}
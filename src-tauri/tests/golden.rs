@@ -0,0 +1,37 @@
+// golden.rs
+// golden 模块的测试
+
+/// 回归基准值与当前实现一致，微小漂移也会被发现
+#[test]
+fn golden_values_match() {
+    use betawordlist_lib::analysis::golden::{self, GOLDEN_WORDS};
+
+    let dir = golden::fixture_dir();
+    let mismatches = golden::check(&dir).unwrap();
+    assert!(
+        mismatches.is_empty(),
+        "{mismatches:#?}\n指标有意变动时运行 betawordlist golden --update 并提交基准值的变动"
+    );
+
+    // 基准值覆盖高频与低频词
+    let expected =
+        golden::parse_golden(&std::fs::read_to_string(dir.join("expected.tsv")).unwrap()).unwrap();
+    assert_eq!(expected.len(), GOLDEN_WORDS);
+    assert!(expected[0].frequency > 10.0 && expected.last().unwrap().frequency == 1.0);
+
+    // 数值的微小漂移也会被发现
+    let result = golden::analyze_fixture(&dir).unwrap();
+    let mut drifted = expected.clone();
+    let dp = drifted[3]
+        .metrics
+        .iter_mut()
+        .find(|(name, _)| name == "dp")
+        .unwrap();
+    dp.1 = dp.1.map(|v| v * (1.0 + 1e-6));
+    let found = golden::compare(&drifted, &result);
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        (found[0].word.as_str(), found[0].field.as_str()),
+        (drifted[3].word.as_str(), "dp")
+    );
+}
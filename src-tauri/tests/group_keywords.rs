@@ -0,0 +1,55 @@
+// group_keywords.rs
+// group_keywords 模块的测试
+
+/// 各组对其余各组的关键词，只有一个组时无从比较
+#[test]
+fn keywords_per_group() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        group_keywords::{group_keywords, GroupKeywordOptions},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+    use std::collections::HashMap;
+
+    let texts = [
+        "球队 比赛 进球 球队 比赛 的 了",
+        "比赛 球队 教练 进球 的 了",
+        "股市 上涨 投资 股市 的 了 比赛",
+        "投资 股市 基金 上涨 的 了",
+    ]
+    .map(String::from);
+    let options = AnalysisOptions {
+        keep_parts: true,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let groups: HashMap<String, String> = [
+        ("text_0", "体育"),
+        ("text_1", "体育"),
+        ("text_2", "财经"),
+        ("text_3", "财经"),
+    ]
+    .into_iter()
+    .map(|(part, group)| (part.to_string(), group.to_string()))
+    .collect();
+
+    let options = GroupKeywordOptions {
+        top_n: 2,
+        min_frequency: 2.0,
+    };
+    let keywords =
+        group_keywords(&result.metadata.parts, &result.parts, &groups, &options).unwrap();
+    assert_eq!(keywords.len(), 2);
+    let sport = &keywords[0];
+    assert_eq!((sport.group.as_str(), sport.parts), ("体育", 2));
+    let words: Vec<&str> = sport.keywords.iter().map(|k| k.word.as_str()).collect();
+    // 比赛在财经组也出现过一次，排在其后；的、了 两组一样多，不是关键词
+    assert_eq!(words, ["球队", "进球"]);
+    assert!(sport.keywords[0].log_ratio.is_finite() && sport.keywords[0].rest_frequency == 0.0);
+    assert_eq!(keywords[1].keywords[0].word, "股市");
+
+    // 只有一个组时无从比较
+    let one: HashMap<String, String> = [("text_0".to_string(), "体育".to_string())].into();
+    assert!(group_keywords(&result.metadata.parts, &result.parts, &one, &options).is_err());
+}
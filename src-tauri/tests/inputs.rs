@@ -0,0 +1,36 @@
+// inputs.rs
+// inputs 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 目录只在开启 recursive 时展开，跳过非文本文件
+#[test]
+fn directories_expand_recursively() {
+    use betawordlist_lib::analysis::inputs::{validate_files, InputOptions, InvalidInput};
+
+    let root = TempDir::new("inputs");
+    let a = root.write("a.txt", "甲");
+    let b = root.write("子目录/b.txt", "乙");
+    let pdf = root.write("c.pdf", "");
+    let dir = root.path().to_path_buf();
+
+    let options = InputOptions::default();
+    assert_eq!(validate_files(&[], &options), Err(InvalidInput::Empty));
+    assert!(matches!(
+        validate_files(std::slice::from_ref(&dir), &options),
+        Err(InvalidInput::Directory(_))
+    ));
+    assert!(matches!(
+        validate_files(&[pdf], &options),
+        Err(InvalidInput::NotText(_))
+    ));
+
+    let recursive = InputOptions {
+        recursive: true,
+        ..Default::default()
+    };
+    let files = validate_files(std::slice::from_ref(&dir), &recursive).unwrap();
+    assert_eq!(files, [a, b]);
+}
@@ -0,0 +1,54 @@
+// interop.rs
+// interop 模块的测试
+
+mod common;
+
+use common::fixture;
+
+/// 夹具语料导出的开头几行与 fixtures/interop 中的样例逐字节一致
+#[test]
+fn interop_samples_match() {
+    use betawordlist_lib::analysis::{
+        export::{export_rows, RowSink},
+        golden,
+        interop::{InteropSink, InteropTool},
+    };
+
+    let result = golden::analyze_fixture(&golden::fixture_dir()).unwrap();
+    let samples = fixture("interop");
+    for (tool, sample) in [
+        (InteropTool::AntConc, "antconc_head.txt"),
+        (InteropTool::LancsBox, "lancsbox_head.tsv"),
+    ] {
+        let mut out = Vec::new();
+        let mut sink = InteropSink::new(&mut out, tool);
+        let outcomes = export_rows(&result, &[], &mut [&mut sink as &mut dyn RowSink]);
+        assert!(outcomes[0].is_ok());
+        drop(sink);
+        let expected = std::fs::read(samples.join(sample)).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&out[..expected.len()]),
+            String::from_utf8_lossy(&expected),
+            "{sample}"
+        );
+        // 工作/v 与 工作/n 在 antconc 中合为一行
+        let text = String::from_utf8(out).unwrap();
+        let work: Vec<&str> = text
+            .lines()
+            .filter(|line| line.contains("\t工作"))
+            .collect();
+        match tool {
+            InteropTool::AntConc => assert!(work.len() == 1 && work[0].ends_with("\t2\t1\t工作")),
+            InteropTool::LancsBox => assert_eq!(work.len(), 0),
+        }
+        assert_eq!(
+            text.lines()
+                .filter(|line| line.starts_with("工作\t"))
+                .count(),
+            match tool {
+                InteropTool::AntConc => 0,
+                InteropTool::LancsBox => 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,34 @@
+// json_export.rs
+// json_export 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 导出的 JSON 按会话文件读回后与原文档相同
+#[test]
+fn json_round_trips() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        json_export::{read_json, write_json, JsonExportOptions, ResultDocument},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 甲 丙", "甲 乙 丁", "乙 戊 甲"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let dir = TempDir::new("json_export");
+    let path = dir.join("result.json");
+
+    for pretty in [true, false] {
+        let file = std::fs::File::create(&path).unwrap();
+        write_json(file, &result, &JsonExportOptions { pretty }).unwrap();
+        assert_eq!(read_json(&path).unwrap(), ResultDocument::new(&result));
+    }
+
+    // 未计算的指标写为 null，而不是省略
+    let value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(value["schemaVersion"], 1);
+    assert!(value["entries"][0]["metrics"]["withinFileDp"].is_null());
+}
@@ -0,0 +1,69 @@
+// language.rs
+// language 模块的测试
+
+mod common;
+
+use common::{fixture, TempDir};
+
+/// 字母类字符按文字分类，其和为汉字占比的分母
+#[test]
+fn script_histogram_counts_letters() {
+    use betawordlist_lib::analysis::language::ScriptHistogram;
+
+    let scripts = ScriptHistogram::of("汉字abc，123 カナ한글Ωé！\u{200B}");
+    assert_eq!(
+        (scripts.han, scripts.latin, scripts.kana, scripts.hangul),
+        (2, 4, 2, 2)
+    );
+    assert_eq!((scripts.other_letters, scripts.digits), (1, 3));
+    assert_eq!(
+        (scripts.punctuation, scripts.whitespace, scripts.other),
+        (2, 1, 1)
+    );
+    assert_eq!(scripts.chars(), 18);
+    assert_eq!(scripts.han_ratio(), Some(2.0 / 11.0));
+
+    let mut total = ScriptHistogram::of("中文");
+    total.add(&scripts);
+    assert_eq!(total.han, 4);
+    assert_eq!(ScriptHistogram::of("123 ！").han_ratio(), None);
+}
+
+/// 汉字占比低的文件按全文统计列出
+#[test]
+fn low_han_files_are_listed() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus, nlp::RuleTokenizer, options::AnalysisOptions,
+        progress::NoopReporter,
+    };
+
+    let dir = TempDir::new("scripts");
+    let english = dir.write("abstract.txt", "Corpus linguistics, 2024: 语料库 studies.");
+    let spring = fixture("corpus/01_spring.txt");
+    let files = [spring, english.clone()];
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+
+    let metadata = &result.metadata;
+    let scripts = metadata.files[1].scripts.unwrap();
+    assert_eq!((scripts.han, scripts.latin, scripts.digits), (3, 24, 4));
+    assert!(metadata.files[0].scripts.unwrap().han_ratio().unwrap() > 0.99);
+    // 语料合计为各文件之和
+    let han: u64 = metadata.files.iter().map(|f| f.scripts.unwrap().han).sum();
+    assert_eq!(metadata.scripts.han, han);
+    assert_eq!(result.totals().scripts, metadata.scripts);
+    // 默认阈值 0.5 之下的文件被标出并给出提示，但照常分析
+    assert_eq!(metadata.low_han_files.len(), 1);
+    assert!((metadata.low_han_files[0].han_ratio - 3.0 / 27.0).abs() < 1e-12);
+    assert!(metadata
+        .warnings
+        .iter()
+        .any(|w| w.contains("low_han_files")));
+    assert_eq!(metadata.parts.len(), 2);
+}
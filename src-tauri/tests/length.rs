@@ -0,0 +1,30 @@
+// length.rs
+// length 模块的测试
+
+/// 按词长与标记筛选并分页，附筛选结果内的排名
+#[test]
+fn query_filters_and_pages() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        length::{query_entries, EntryQuery},
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+    };
+
+    let texts = ["甲 乙 WiFi 甲", "乙 丙 Java"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let query = EntryQuery {
+        flags: vec!["hapax".into()],
+        ..Default::default()
+    };
+    assert_eq!(query_entries(&result.entries, &query).total, 3);
+    let query = EntryQuery {
+        flags: vec!["hapax".into(), "contains_latin".into()],
+        max_length: Some(4),
+        ..Default::default()
+    };
+    let page = query_entries(&result.entries, &query);
+    let mut words: Vec<&str> = page.entries.iter().map(|e| e.word.as_str()).collect();
+    words.sort();
+    assert_eq!((page.total, words), (2, vec!["Java", "WiFi"]));
+}
@@ -0,0 +1,87 @@
+// metric_descriptions.rs
+// metric_descriptions 模块的测试
+
+/// 每个序列化后的指标字段都有说明，且说明的都是可比较的指标
+#[test]
+fn every_metric_is_described() {
+    use betawordlist_lib::analysis::{
+        diff, dispersion_metrics::DispersionMetrics, metric_descriptions,
+    };
+
+    let names: Vec<&str> = metric_descriptions::metric_descriptions()
+        .iter()
+        .map(|d| d.name)
+        .collect();
+    assert_eq!(names[..2], ["frequency", "gt_adjusted_frequency"]);
+    assert_eq!(names[2..], DispersionMetrics::FIELD_NAMES);
+    assert!(names.iter().all(|name| diff::is_known_metric(name)));
+
+    let serialized = serde_json::to_value(DispersionMetrics::default()).unwrap();
+    for field in serialized.as_object().unwrap().keys() {
+        assert!(
+            metric_descriptions::description(field).is_some(),
+            "{field} 缺少说明"
+        );
+    }
+}
+
+/// 每个序列化后的指标字段都有结构信息
+#[test]
+fn every_metric_has_schema() {
+    use betawordlist_lib::analysis::{
+        dispersion_metrics::DispersionMetrics,
+        metric_descriptions::{
+            description, metric_descriptions, metric_schema, MetricDirection, MetricGroup,
+            UpperBound,
+        },
+    };
+
+    let schema = metric_schema();
+    assert_eq!(schema.len(), metric_descriptions().len());
+    let names: Vec<&str> = schema.iter().map(|s| s.name).collect();
+    assert_eq!(names[2..], DispersionMetrics::FIELD_NAMES);
+    let serialized = serde_json::to_value(DispersionMetrics::default()).unwrap();
+    let fields = serialized.as_object().unwrap();
+    assert_eq!(fields.len(), DispersionMetrics::FIELD_NAMES.len());
+    for field in fields.keys() {
+        assert!(
+            schema.iter().any(|s| s.name == field),
+            "{field} 缺少结构信息"
+        );
+    }
+
+    let find = |name: &str| schema.iter().find(|s| s.name == name).unwrap();
+    // DP 越小越均匀，Juilland D 越大越均匀
+    let dp = find("dp");
+    assert_eq!(dp.direction, MetricDirection::LowerIsMoreEven);
+    assert_eq!(
+        dp.max,
+        UpperBound::Value {
+            max: 1.0,
+            inclusive: false
+        }
+    );
+    let d = find("juilland_d");
+    assert_eq!(
+        (d.direction, d.group, d.min),
+        (
+            MetricDirection::HigherIsMoreEven,
+            MetricGroup::Dispersion,
+            0.0
+        )
+    );
+    assert_eq!(find("range").max, UpperBound::PartCount);
+    let frequency = find("frequency");
+    assert_eq!(
+        (frequency.direction, frequency.max, frequency.precision),
+        (MetricDirection::Neutral, UpperBound::Unbounded, 0)
+    );
+    assert_eq!(find("ft_adjusted_by_da").group, MetricGroup::Adjusted);
+
+    // 说明中的取值范围由同样的上下界写出
+    let ranges: Vec<String> = ["frequency", "range", "juilland_d", "dp"]
+        .iter()
+        .map(|name| description(name).unwrap().range.to_string())
+        .collect();
+    assert_eq!(ranges, ["≥ 0", "0 – n", "[0, 1]", "[0, 1)"]);
+}
@@ -0,0 +1,90 @@
+// model_file.rs
+// model_file 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 截断、同步标记不符与非 AVRO 文件都被识别
+#[test]
+fn container_structure_is_checked() {
+    use betawordlist_lib::analysis::model_file::{read_container, ModelFailure, AVRO_MAGIC};
+
+    // 元数据为一项 avro.codec = deflate，随后是同步标记与一个 3 字节的数据块
+    let sync = [7u8; 16];
+    let mut bytes = AVRO_MAGIC.to_vec();
+    bytes.push(2); // 1 项（zigzag 编码）
+    bytes.push(20); // 键长 10
+    bytes.extend(b"avro.codec");
+    bytes.push(14); // 值长 7
+    bytes.extend(b"deflate");
+    bytes.push(0); // 元数据结束
+    bytes.extend(sync);
+    bytes.extend([2, 6, 1, 2, 3]); // 1 个对象，3 字节
+    bytes.extend(sync);
+
+    let container = read_container(&bytes).unwrap();
+    assert_eq!((container.codec.as_str(), container.blocks), ("deflate", 1));
+
+    // 下载中断：末尾缺了几个字节
+    assert_eq!(
+        read_container(&bytes[..bytes.len() - 5]),
+        Err(ModelFailure::Truncated)
+    );
+    // 只剩魔数
+    assert_eq!(read_container(AVRO_MAGIC), Err(ModelFailure::Truncated));
+    // 元数据项数为 i64::MIN（zigzag 编码全为 1）
+    let mut overflow = AVRO_MAGIC.to_vec();
+    overflow.extend([0xff; 9]);
+    overflow.push(0x01);
+    assert_eq!(read_container(&overflow), Err(ModelFailure::Truncated));
+    // 数据块后的同步标记不符
+    let mut corrupted = bytes.clone();
+    *corrupted.last_mut().unwrap() = 0;
+    assert_eq!(read_container(&corrupted), Err(ModelFailure::Truncated));
+    // 不是 AVRO 文件
+    assert_eq!(
+        read_container(b"PK\x03\x04 zip archive"),
+        Err(ModelFailure::NotAvro)
+    );
+    assert_eq!(read_container(b""), Err(ModelFailure::NotAvro));
+}
+
+/// 无法解析的模型文件给出原因与补充说明
+#[test]
+fn broken_model_files_are_diagnosed() {
+    use betawordlist_lib::analysis::model_file::{diagnose, ModelFailure, AVRO_MAGIC};
+
+    let dir = TempDir::new("model_file");
+    let write = |name: &str, bytes: &[u8]| dir.write(name, bytes);
+    let container = |codec: &[u8]| {
+        let mut bytes = AVRO_MAGIC.to_vec();
+        bytes.extend([2, 20]);
+        bytes.extend(b"avro.codec");
+        bytes.push(codec.len() as u8 * 2);
+        bytes.extend(codec);
+        bytes.push(0);
+        bytes.extend([9u8; 16]);
+        bytes
+    };
+
+    // 选错了文件
+    let text = write("notes.txt", "这是一份词表".as_bytes());
+    assert_eq!(diagnose(&text), (ModelFailure::NotAvro, None));
+    // 下载中断：同步标记不全
+    let complete = container(b"deflate");
+    let truncated = write("truncated.bin", &complete[..complete.len() - 4]);
+    assert_eq!(diagnose(&truncated).0, ModelFailure::Truncated);
+    // 容器完整，多半是其他版本的模型
+    let other = write("other.bin", &complete);
+    assert_eq!(diagnose(&other), (ModelFailure::SchemaMismatch, None));
+    let snappy = write("snappy.bin", &container(b"snappy"));
+    let (failure, detail) = diagnose(&snappy);
+    assert_eq!(failure, ModelFailure::SchemaMismatch);
+    assert!(detail.unwrap().contains("snappy"));
+    // 文件不存在
+    assert_eq!(
+        diagnose(&dir.join("missing.bin")).0,
+        ModelFailure::Unreadable
+    );
+}
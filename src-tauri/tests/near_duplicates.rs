@@ -0,0 +1,38 @@
+// near_duplicates.rs
+// near_duplicates 模块的测试
+
+mod common;
+
+use common::{article, TempDir};
+
+/// 近重复文件按策略分组
+#[test]
+fn near_duplicates_are_grouped() {
+    use betawordlist_lib::analysis::near_duplicates::{
+        resolve, DuplicatePolicy, NearDuplicateOptions,
+    };
+
+    let dir = TempDir::new("near_duplicates");
+    let files = [
+        dir.write("a.txt", article(0)),
+        dir.write("b.txt", format!("本文转载自某报\n{}", article(0))),
+        dir.write("c.txt", article(12345)),
+    ];
+
+    let options = NearDuplicateOptions::default();
+    let resolution = resolve(&files, &options);
+    assert_eq!(resolution.pairs.len(), 1);
+    assert!(resolution.pairs[0].similarity > 0.9);
+    assert_eq!(resolution.groups, [vec![0], vec![1], vec![2]]);
+
+    let merge = NearDuplicateOptions {
+        policy: DuplicatePolicy::Merge,
+        ..options
+    };
+    assert_eq!(resolve(&files, &merge).groups, [vec![1, 0], vec![2]]);
+    let longer = NearDuplicateOptions {
+        policy: DuplicatePolicy::KeepLonger,
+        ..options
+    };
+    assert_eq!(resolve(&files, &longer).groups, [vec![1], vec![2]]);
+}
@@ -0,0 +1,29 @@
+// nlp.rs
+// nlp 模块的测试
+
+/// 模型不可用的提示附上原因，选反文件时只提示交换
+#[test]
+fn incompatible_model_message() {
+    use betawordlist_lib::analysis::{
+        model_file::ModelFailure,
+        nlp::{ModelIncompatible, ModelKind},
+    };
+
+    let error = ModelIncompatible {
+        model: ModelKind::Cws,
+        path: "cws_model.bin".into(),
+        reason: "AVRO 文件不完整".into(),
+        failure: ModelFailure::Truncated,
+        swapped: false,
+    };
+    assert_eq!(
+        error.to_string(),
+        "分词模型 cws_model.bin 不可用: AVRO 文件不完整。文件不完整，可能下载中断，请重新下载"
+    );
+    // 选反了文件时只提示交换
+    let swapped = ModelIncompatible {
+        swapped: true,
+        ..error
+    };
+    assert!(swapped.to_string().ends_with("请交换两个文件后重试"));
+}
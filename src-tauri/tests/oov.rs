@@ -0,0 +1,57 @@
+// oov.rs
+// oov 模块的测试
+
+/// 高频单字按相邻次数列为候选，未统计相邻时按领域词表列出
+#[test]
+fn oov_candidates_from_adjacent_singles() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        oov::{oov_report, OovOptions},
+        options::AnalysisOptions,
+    };
+
+    // "区块链" 被切成三个单字
+    let texts = ["区 块 链 技术 的 发展", "区 块 链 的 区 块 链 应用 的"].map(String::from);
+    let options = AnalysisOptions {
+        oov_pairs: Some(100),
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let pairs = &result.metadata.adjacent_singles.as_ref().unwrap().pairs;
+    assert_eq!(
+        (
+            pairs[0].left.as_str(),
+            pairs[0].right.as_str(),
+            pairs[0].count
+        ),
+        ("区", "块", 3)
+    );
+
+    let options = OovOptions {
+        min_frequency: 3.0,
+        ..Default::default()
+    };
+    let report = oov_report(&result, 10, &options).unwrap();
+    let words: Vec<(&str, u64)> = report
+        .candidates
+        .iter()
+        .map(|c| (c.word.as_str(), c.adjacent_count))
+        .collect();
+    // 区块、块链 各相邻 3 次，链的、的区 各 1 次；"技术" 等不是单字
+    assert_eq!(words, [("块", 6), ("区", 4), ("链", 4), ("的", 2)]);
+    assert_eq!(report.candidates[0].adjacent[0].joined, "区块");
+
+    // 未统计相邻单字时只能按领域词表列出含领域用字的词
+    let plain = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    assert!(oov_report(&plain, 10, &OovOptions::default()).is_err());
+    let domain = OovOptions {
+        domain_words: vec!["区块链".into(), "技术".into()],
+        ..Default::default()
+    };
+    let report = oov_report(&plain, 10, &domain).unwrap();
+    assert!(!report.adjacency_counted);
+    let words: Vec<&str> = report.candidates.iter().map(|c| c.word.as_str()).collect();
+    assert_eq!(words, ["区", "块", "链"]);
+    assert_eq!(report.candidates[0].domain_words, ["区块链"]);
+}
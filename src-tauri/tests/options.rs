@@ -0,0 +1,61 @@
+// options.rs
+// options 模块的测试
+
+/// 速览模式按每百万频次与前 N 名截断结果，并记入元数据
+#[test]
+fn quick_look_truncates_by_rate_and_rank() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::{AnalysisOptions, QuickLook},
+    };
+
+    let texts = ["甲 甲 甲 乙 乙 丙 丙 丁", "甲 乙 丙 戊"].map(String::from);
+    let quick = QuickLook {
+        top_n: 2,
+        min_per_million: 200_000.0,
+    };
+    let options = AnalysisOptions {
+        quick_look: Some(quick),
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+
+    // 12 个词元按每百万 20 万折算最低频次 2.4：丁、戊 先被筛掉；乙、丙 同频按词形排序后截断
+    let words: Vec<&str> = result.entries.iter().map(|e| e.word.as_str()).collect();
+    assert_eq!(words, ["甲", "丙"]);
+    let truncation = result.metadata.truncation.as_ref().unwrap();
+    assert_eq!((truncation.top_n, truncation.types_before), (2, 3));
+    assert!((truncation.min_frequency - 2.4).abs() < 1e-9);
+    assert!(result.summary(0).truncated);
+    assert!(result
+        .metadata
+        .warnings
+        .iter()
+        .any(|w| w.contains("速览模式")));
+
+    let full = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    assert!(full.metadata.truncation.is_none() && full.entries.len() == 5);
+}
+
+/// seed 只在抽样或采集例句时影响结果
+#[test]
+fn seed_is_material_only_when_used() {
+    use betawordlist_lib::analysis::options::AnalysisOptions;
+
+    let base = AnalysisOptions::default();
+    let reseeded = |options: &AnalysisOptions| AnalysisOptions {
+        seed: Some(7),
+        ..options.clone()
+    };
+    assert!(base.material_changes(&reseeded(&base)).is_empty());
+    let examples = AnalysisOptions {
+        collect_examples: 3,
+        ..Default::default()
+    };
+    assert_eq!(examples.material_changes(&reseeded(&examples)), ["seed"]);
+    // 只有一方采集例句时，例句本身已是差异
+    assert!(base
+        .material_changes(&reseeded(&examples))
+        .contains(&"seed".to_string()));
+}
@@ -0,0 +1,39 @@
+// order_invariance.rs
+// order_invariance 模块的测试
+
+mod common;
+
+use common::corpus_files;
+
+/// 各部分换序后指标不变
+#[test]
+fn metrics_ignore_part_order() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::{AnalysisOptions, PartitionMode},
+        order_invariance::verify_order_invariance,
+        progress::NoopReporter,
+    };
+
+    // 夹具语料按句切分，得到多个大小不同的部分
+    let files = corpus_files(3);
+    let options = AnalysisOptions {
+        partition: PartitionMode::PerSentence,
+        keep_parts: true,
+        ..Default::default()
+    };
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+
+    let report = verify_order_invariance(&result, 0).unwrap();
+    assert!(report.parts > 3);
+    assert_eq!(report.words_checked, result.entries.len());
+    assert_eq!(report.permutations.len(), 5);
+    assert!(report.comparisons > 0);
+    assert!(report.passed(), "{:?}", report.violations);
+
+    // 抽样检查
+    let sampled = verify_order_invariance(&result, 5).unwrap();
+    assert_eq!(sampled.words_checked, 5);
+    assert!(sampled.passed());
+}
@@ -0,0 +1,34 @@
+// partition_advice.rs
+// partition_advice 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 按候选块数估算每块的词次
+#[test]
+fn partition_suggestion_for_file() {
+    use betawordlist_lib::analysis::partition_advice::{suggest_partition, MIN_STABLE_PART_TOKENS};
+
+    let dir = TempDir::new("partition_advice");
+    // 40000 词次
+    let file = dir.write("corpus.txt", "人。".repeat(20000));
+
+    let suggestion = suggest_partition(std::slice::from_ref(&file), &[]);
+    assert_eq!(suggestion.estimated_tokens, 40000.0);
+    let chunks: Vec<(usize, bool)> = suggestion
+        .candidates
+        .iter()
+        .map(|c| (c.chunks, c.stable))
+        .collect();
+    assert_eq!(chunks, [(10, true), (50, false), (100, false)]);
+    assert_eq!(suggestion.recommended, Some(10));
+    assert_eq!(suggestion.warnings.len(), 2);
+
+    let requested = suggest_partition(&[file], &[40]);
+    assert_eq!(
+        requested.candidates[0].tokens_per_chunk,
+        MIN_STABLE_PART_TOKENS as f64
+    );
+    assert!(requested.warnings.is_empty());
+}
@@ -0,0 +1,36 @@
+// paths.rs
+// paths 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 接近 MAX_PATH 的路径规范化后仍可读写
+#[test]
+fn long_paths_are_readable() {
+    use betawordlist_lib::analysis::paths::normalize;
+    use std::fs;
+
+    // 含空格与汉字的路径
+    let dir = TempDir::new("路径 测试");
+    let file = dir.join("语料 一.txt");
+    fs::write(normalize(&file), "你好").unwrap();
+    assert_eq!(fs::read_to_string(normalize(&file)).unwrap(), "你好");
+
+    // 超过 260 个字符的路径在 Windows 上改用 \\?\ 前缀
+    #[cfg(windows)]
+    {
+        let long_dir = dir
+            .join("很长的子目录名".repeat(20))
+            .join("再来一层".repeat(20));
+        let long_file = long_dir.join("语料 二.txt");
+        assert!(long_file.to_string_lossy().chars().count() > 260);
+        let normalized = normalize(&long_file);
+        assert!(normalized.to_string_lossy().starts_with(r"\\?\"));
+        fs::create_dir_all(normalize(&long_dir)).unwrap();
+        fs::write(&normalized, "长路径").unwrap();
+        assert_eq!(fs::read_to_string(&normalized).unwrap(), "长路径");
+    }
+
+    fs::remove_dir_all(normalize(dir.path())).unwrap();
+}
@@ -0,0 +1,32 @@
+// pos_histogram.rs
+// pos_histogram 模块的测试
+
+/// 词性占比异常的部分按 z 值判定
+#[test]
+fn pos_outliers_by_z_score() {
+    use betawordlist_lib::analysis::{
+        metadata::PartInfo,
+        pos_histogram::{outliers, PosOutlierOptions},
+    };
+    use std::collections::BTreeMap;
+
+    let part = |index: usize, n: u64, wp: u64| PartInfo {
+        index,
+        source: format!("{index}.txt"),
+        pos_counts: BTreeMap::from([("n".to_string(), n), ("wp".to_string(), wp)]),
+        ..Default::default()
+    };
+    let mut parts: Vec<PartInfo> = (0..14).map(|i| part(i, 90, 10 + i as u64 % 2)).collect();
+    parts.push(part(14, 20, 80));
+
+    let flagged = outliers(&parts, &PosOutlierOptions::default());
+    assert_eq!(flagged.len(), 1);
+    assert_eq!((flagged[0].part, flagged[0].label.as_str()), (14, "14.txt"));
+    assert!(flagged[0].share > 0.79 && flagged[0].z > 3.5);
+
+    let off = PosOutlierOptions {
+        z_threshold: 0.0,
+        ..Default::default()
+    };
+    assert!(outliers(&parts, &off).is_empty());
+}
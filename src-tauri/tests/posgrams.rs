@@ -0,0 +1,68 @@
+// posgrams.rs
+// posgrams 模块的测试
+
+/// 词性序列按部分计数并计算分布指标
+#[test]
+fn posgrams_are_counted_per_part() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        posgrams::{PosGramOptions, POSGRAM_POS},
+    };
+
+    let texts = ["买 3 本 书 。 读 2 遍", "有 5 个 人"].map(String::from);
+    let options = AnalysisOptions {
+        posgrams: Some(PosGramOptions {
+            n: 2,
+            min_frequency: 3.0,
+        }),
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let mut patterns: Vec<(&str, f64, usize)> = result
+        .entries
+        .iter()
+        .filter(|e| e.pos == POSGRAM_POS)
+        .map(|e| (e.word.as_str(), e.frequency, e.metrics.range))
+        .collect();
+    patterns.sort_by(|a, b| a.0.cmp(b.0));
+    // ws+ws 只出现 2 次，低于最低频次
+    assert_eq!(patterns, [("m+ws", 3.0, 2), ("ws+m", 3.0, 2)]);
+
+    // 部分词数与词条不受影响
+    let plain = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    assert_eq!(result.metadata.total_tokens, plain.metadata.total_tokens);
+    assert_eq!(result.entries.len(), plain.entries.len() + 2);
+}
+
+/// 词性序列可以重叠
+#[test]
+fn overlapping_posgrams() {
+    use betawordlist_lib::analysis::posgrams;
+
+    let tokens = [
+        ("我", "r"),
+        ("在", "p"),
+        ("家", "n"),
+        ("看", "v"),
+        ("书", "n"),
+        ("。", "wp"),
+        ("去", "v"),
+        ("图书馆", "n"),
+        ("，", "wp"),
+        ("借", "v"),
+        ("书", "n"),
+    ]
+    .map(|(w, p)| (w.to_string(), p.to_string()));
+    let bigrams = posgrams::count(&tokens, 2);
+    assert_eq!(bigrams["v+n"], 3.0);
+    assert_eq!(bigrams["p+n"], 1.0);
+    // 只有 家 看；句末与逗号两侧不相连
+    assert_eq!(bigrams["n+v"], 1.0);
+    assert!(!bigrams.keys().any(|k| k.contains("wp")));
+
+    let trigrams = posgrams::count(&tokens, 3);
+    assert_eq!(trigrams["p+n+v"], 1.0);
+    assert_eq!(trigrams.values().sum::<f64>(), 3.0);
+}
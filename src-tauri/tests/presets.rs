@@ -0,0 +1,65 @@
+// presets.rs
+// presets 模块的测试
+
+/// 同名的自定义预设替换内置预设
+#[test]
+fn custom_presets_replace_builtin() {
+    use betawordlist_lib::analysis::presets::{AnalysisPreset, PresetSettings};
+
+    let mut custom = AnalysisPreset::pedagogical_list();
+    custom.options.min_frequency = Some(10.0);
+    let settings = PresetSettings {
+        presets: vec![custom],
+    };
+
+    let names: Vec<String> = settings.all_presets().into_iter().map(|p| p.name).collect();
+    assert_eq!(
+        names,
+        [
+            "frequency_dictionary",
+            "dispersion_research",
+            "quick_look",
+            "pedagogical_list"
+        ]
+    );
+    let preset = settings.find("pedagogical_list").unwrap();
+    assert_eq!(preset.options.min_frequency, Some(10.0));
+    assert!(settings.find("bootstrap").is_err());
+}
+
+/// 速览模式只保留实词并关闭附加统计
+#[test]
+fn quick_look_keeps_content_words() {
+    use betawordlist_lib::analysis::{
+        options::{AnalysisOptions, QuickLook},
+        presets::apply_quick_look,
+    };
+
+    let mut options = AnalysisOptions {
+        burstiness_window: Some(1000),
+        track_positions: true,
+        collect_examples: 3,
+        excluded_pos: vec!["wp".to_string()],
+        ..Default::default()
+    };
+    apply_quick_look(&mut options);
+    assert_eq!(
+        (options.burstiness_window, options.collect_examples),
+        (Some(1000), 3)
+    );
+    assert_eq!(options.excluded_pos, ["wp"]);
+
+    options.quick_look = Some(QuickLook::default());
+    apply_quick_look(&mut options);
+    assert_eq!(
+        (options.burstiness_window, options.track_positions),
+        (None, false)
+    );
+    assert_eq!(options.collect_examples, 0);
+    // 功能词词性并入已有的排除列表，不重复
+    assert!(options.excluded_pos.contains(&"u".to_string()));
+    assert_eq!(
+        options.excluded_pos.iter().filter(|p| *p == "wp").count(),
+        1
+    );
+}
@@ -0,0 +1,246 @@
+// progress.rs
+// progress 模块的测试
+
+mod common;
+
+use common::corpus_files;
+
+/// 进度事件的词次估算随文件完成修正，结束时等于实际词次
+#[test]
+fn token_estimate_converges_to_total() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::{ProgressPhase, RecordingReporter, ReportedEvent},
+    };
+
+    let files = corpus_files(3);
+    let reporter = RecordingReporter::default();
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &reporter,
+    )
+    .unwrap();
+
+    let events = reporter.events.lock().unwrap();
+    let progress: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            ReportedEvent::Progress(p) => Some(p),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(progress[0].tokens_done, result.metadata.parts[0].tokens);
+    assert!(progress
+        .windows(2)
+        .all(|w| w[0].tokens_done <= w[1].tokens_done));
+    assert!(progress
+        .windows(2)
+        .all(|w| w[0].vocab_entries <= w[1].vocab_entries));
+    // 全部完成后估算即为实际词次
+    let last = progress.last().unwrap();
+    assert_eq!(last.tokens_done, result.metadata.total_tokens);
+    assert_eq!(last.tokens_total_estimate, result.metadata.total_tokens);
+    // 文件阶段之后是计算指标阶段，其最后一个事件已算完全部词型
+    assert_eq!(progress[0].phase, ProgressPhase::Files);
+    assert_eq!(last.phase, ProgressPhase::Metrics);
+    let types = result.metadata.total_types;
+    assert_eq!((last.types_done, last.types_total), (types, types));
+    match events.last().unwrap() {
+        ReportedEvent::Complete(c) => {
+            assert_eq!(
+                (c.types, c.tokens),
+                (result.metadata.total_types, result.metadata.total_tokens)
+            )
+        }
+        other => panic!("{other:?}"),
+    }
+}
+
+/// 指标进度按间隔上报，最后一个事件等于总数
+#[test]
+fn metric_progress_ends_at_total() {
+    use betawordlist_lib::analysis::progress::{
+        report_metric_progress, AnalysisProgress, ProgressPhase, RecordingReporter, ReportedEvent,
+        METRIC_PROGRESS_EVERY,
+    };
+
+    let base = AnalysisProgress {
+        current: 3,
+        total: 3,
+        file: "c.txt".into(),
+        vocab_entries: 0,
+        estimated_bytes: 0,
+        tokens_done: 0,
+        tokens_total_estimate: 0,
+        files_in_event: 0,
+        files_per_second: None,
+        phase: ProgressPhase::Files,
+        types_done: 0,
+        types_total: 0,
+    };
+    let reporter = RecordingReporter::default();
+    let total = 3 * METRIC_PROGRESS_EVERY;
+    let sum = report_metric_progress(&reporter, &base, |progress| {
+        progress.start(total);
+        // 等取样线程报出总数已知的首个事件再开始，事件数不取决于计算快慢
+        while reporter.events.lock().unwrap().is_empty() {
+            std::thread::yield_now();
+        }
+        (0..total).inspect(|_| progress.entry_done()).sum::<usize>()
+    });
+    assert_eq!(sum, total * (total - 1) / 2);
+
+    let events = reporter.events.lock().unwrap();
+    let metrics: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            ReportedEvent::Progress(p) if p.phase == ProgressPhase::Metrics => Some(p),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(metrics.len(), events.len());
+    assert!(metrics.len() >= 2);
+    assert_eq!(metrics[0].types_done, 0);
+    // 相邻事件至少相隔 METRIC_PROGRESS_EVERY 个条目，work 结束后的最后一个事件除外
+    let (last, earlier) = metrics.split_last().unwrap();
+    assert!(earlier
+        .windows(2)
+        .all(|w| w[1].types_done >= w[0].types_done + METRIC_PROGRESS_EVERY));
+    assert!(earlier.iter().all(|p| p.types_done <= last.types_done));
+    assert_eq!((last.types_done, last.types_total), (total, total));
+    assert_eq!((last.current, last.file.as_str()), (3, "c.txt"));
+}
+
+/// 文件少时逐个上报，文件多时事件间隔接近 target
+#[test]
+fn cadence_follows_target() {
+    use betawordlist_lib::analysis::progress::ProgressCadence;
+    use std::time::Duration;
+
+    let target = Duration::from_millis(50);
+    // 按各文件的耗时（秒）模拟，返回各事件的 (完成时刻, 合并的文件数)
+    let simulate = |costs: &[f64]| {
+        let mut cadence = ProgressCadence::new(costs.len(), costs.len(), target);
+        let mut elapsed = 0.0;
+        let mut events = Vec::new();
+        for (i, cost) in costs.iter().enumerate() {
+            elapsed += cost;
+            if let Some(batch) = cadence.files_done(1, i + 1, Duration::from_secs_f64(elapsed)) {
+                events.push((elapsed, batch.files));
+            }
+        }
+        assert_eq!(events.iter().map(|e| e.1).sum::<usize>(), costs.len());
+        assert_eq!(events.last().unwrap().0, elapsed);
+        events
+    };
+
+    // 500 个文件：每个文件一个事件
+    let events = simulate(&[0.001; 500]);
+    assert_eq!(events.len(), 500);
+    assert!(!ProgressCadence::new(500, 500, target).aggregated());
+
+    // 5 万个小文件共 5 秒：约 100 个事件，而不是 5 万个
+    let events = simulate(&vec![0.0001; 50_000]);
+    assert!((80..=130).contains(&events.len()), "{}", events.len());
+
+    // 先快后慢：1 万个文件，前一半每个 0.05ms，后一半每个 2ms（约 10 秒）
+    let costs: Vec<f64> = (0..10_000)
+        .map(|i| if i < 5000 { 0.00005 } else { 0.002 })
+        .collect();
+    let events = simulate(&costs);
+    assert!((150..=280).contains(&events.len()), "{}", events.len());
+    // 慢下来之后每个事件约合并 25 个文件
+    let (_, files) = events[events.len() - 10];
+    assert!((20..=30).contains(&files), "{files}");
+    // 单个文件耗时超过 target 时，每个文件完成都上报（第一个慢文件的事件带上此前未报的快文件）
+    let events = simulate(&[vec![0.00001; 2000], vec![0.2; 20]].concat());
+    assert!(events[events.len() - 20].1 > 1);
+    assert!(events[events.len() - 19..]
+        .iter()
+        .all(|&(_, files)| files == 1));
+}
+
+/// file_counted 按频次降序给出各部分的高频词
+#[test]
+fn file_counted_reports_top_words() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::{RecordingReporter, ReportedEvent},
+    };
+
+    let files = corpus_files(2);
+    let options = AnalysisOptions {
+        file_counts_top: Some(3),
+        ..Default::default()
+    };
+    let reporter = RecordingReporter::default();
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &reporter).unwrap();
+
+    let events = reporter.events.lock().unwrap();
+    let counted: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            ReportedEvent::FileCounted(c) => Some(c),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(counted.len(), 2);
+    assert_eq!(counted[1].part.index, 1);
+    assert_eq!(counted[1].part.tokens, result.metadata.parts[1].tokens);
+    assert_eq!(counted[0].counts.len(), 3);
+    assert!(counted[0].truncated);
+    assert!(counted[0].counts[0].2 >= counted[0].counts[2].2);
+}
+
+/// 处理完第一个文件后取消，分析在下一个文件前中止并上报 error
+#[test]
+fn cancelled_run_stops_before_next_file() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::{analyze_corpus, AnalysisAbort},
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::*,
+    };
+    use std::sync::Mutex;
+
+    struct CancelAfterFirst {
+        run: AnalysisRun,
+        errors: Mutex<Vec<String>>,
+    }
+
+    impl ProgressReporter for CancelAfterFirst {
+        fn progress(&self, _event: &AnalysisProgress) {
+            self.run.cancel();
+        }
+        fn file_counted(&self, _event: &FileCounted) {}
+        fn complete(&self, _event: &AnalysisComplete) {}
+        fn error(&self, event: &AnalysisError) {
+            self.errors.lock().unwrap().push(event.message.clone());
+        }
+        fn cancelled(&self) -> bool {
+            self.run.is_cancelled()
+        }
+    }
+
+    let files = corpus_files(2);
+    let reporter = CancelAfterFirst {
+        run: AnalysisRun::default(),
+        errors: Mutex::new(Vec::new()),
+    };
+    let outcome = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &reporter,
+    );
+    assert!(matches!(outcome, Err(AnalysisAbort::Cancelled)));
+    assert_eq!(*reporter.errors.lock().unwrap(), ["分析已取消"]);
+}
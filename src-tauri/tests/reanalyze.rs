@@ -0,0 +1,71 @@
+// reanalyze.rs
+// reanalyze 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 替换的部分留在原位置，新文件追加，缺失的文件移出
+#[test]
+fn reanalyzed_parts_replace_in_place() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+        progress::NoopReporter, reanalyze, word_entry::WordEntry,
+    };
+    use std::fs;
+
+    let dir = TempDir::new("reanalyze");
+    let a = dir.write("a.txt", "甲 乙");
+    let b = dir.write("b.txt", "乙 丙");
+    let c = dir.join("c.txt");
+    let options = AnalysisOptions {
+        keep_parts: true,
+        ..Default::default()
+    };
+    let tokenizer = WhitespaceTokenizer;
+    let previous = analyze_corpus(
+        &tokenizer,
+        &[a.clone(), b.clone()],
+        &options,
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+
+    // 改写 b、新增 c、删除 a
+    fs::write(&b, "乙 丙 丙 丁").unwrap();
+    fs::write(&c, "甲 戊").unwrap();
+    fs::remove_file(&a).unwrap();
+    assert_eq!(
+        reanalyze::changed_files(&previous.metadata),
+        [a.clone(), b.clone()]
+    );
+
+    let updated =
+        reanalyze::reanalyze_files(&tokenizer, &previous, &[b.clone(), c.clone(), a.clone()])
+            .unwrap();
+    let fresh = analyze_corpus(
+        &tokenizer,
+        &[b.clone(), c.clone()],
+        &options,
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    let sorted = |mut entries: Vec<WordEntry>| {
+        entries.sort_by(|x, y| x.word.cmp(&y.word));
+        format!("{entries:?}")
+    };
+    assert_eq!(sorted(updated.entries.clone()), sorted(fresh.entries));
+    assert_eq!(updated.metadata.files.len(), 2);
+    assert!(updated
+        .metadata
+        .warnings
+        .iter()
+        .any(|w| w.contains("a.txt")));
+
+    // 没有变化时不重新分析
+    assert!(reanalyze::refresh_analysis(&tokenizer, &updated)
+        .unwrap()
+        .is_none());
+}
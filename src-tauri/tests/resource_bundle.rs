@@ -0,0 +1,90 @@
+// resource_bundle.rs
+// resource_bundle 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 资源包读回同样的选项，缺失或改动的资源被标出
+#[test]
+fn bundle_round_trips() {
+    use betawordlist_lib::analysis::{
+        options::AnalysisOptions,
+        resource_bundle::{read_bundle, write_bundle, ResourceStatus, MANIFEST_FILE},
+        zip::{read_entries, ZipWriter},
+    };
+    use std::collections::HashMap;
+
+    let options = AnalysisOptions {
+        stopwords: vec!["的".into(), "了".into()],
+        phrases: vec!["人工 智能".into()],
+        rate_base: 1000.0,
+        ..Default::default()
+    };
+    let bytes = write_bundle(Vec::new(), &options).unwrap();
+    let bundle = read_bundle(&bytes).unwrap();
+    assert!(bundle.is_complete());
+    let items: Vec<Option<usize>> = bundle.resources.iter().map(|r| r.items).collect();
+    assert_eq!(items, [None, Some(2), Some(0), Some(1)]);
+    assert_eq!(bundle.options().stopwords, options.stopwords);
+    assert_eq!(bundle.options().phrases, options.phrases);
+    assert_eq!(bundle.options().rate_base, 1000.0);
+
+    // 去掉停用词表、改动短语表后重新打包：清单仍是原来的
+    let mut files: HashMap<String, Vec<u8>> = read_entries(&bytes).unwrap().into_iter().collect();
+    files.remove("stopwords.txt");
+    files.insert("phrases.txt".into(), "机器 学习\n".into());
+    let mut zip = ZipWriter::new(Vec::new());
+    zip.add(MANIFEST_FILE, &files.remove(MANIFEST_FILE).unwrap())
+        .unwrap();
+    for (name, data) in &files {
+        zip.add(name, data).unwrap();
+    }
+    let partial = read_bundle(&zip.finish().unwrap()).unwrap();
+    let statuses: Vec<ResourceStatus> = partial.resources.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        [
+            ResourceStatus::Ok,
+            ResourceStatus::Missing,
+            ResourceStatus::Ok,
+            ResourceStatus::Corrupt
+        ]
+    );
+    assert!(!partial.is_complete());
+    assert!(partial.options().stopwords.is_empty() && partial.options().phrases.is_empty());
+    assert_eq!(partial.options().rate_base, 1000.0);
+
+    // 没有清单的 ZIP 不是资源包
+    let mut zip = ZipWriter::new(Vec::new());
+    zip.add("stopwords.txt", "的\n".as_bytes()).unwrap();
+    assert!(read_bundle(&zip.finish().unwrap()).is_err());
+}
+
+/// 安装只写入通过校验的资源
+#[test]
+fn install_writes_accepted_resources() {
+    use betawordlist_lib::analysis::{
+        options::AnalysisOptions,
+        resource_bundle::{load_installed, read_bundle, write_bundle},
+    };
+
+    let root = TempDir::new("resource_bundle");
+    let dir = root.join("resources");
+    assert!(load_installed(&dir).unwrap().is_none());
+
+    let options = AnalysisOptions {
+        target_words: vec!["春天".into()],
+        ..Default::default()
+    };
+    let bundle = read_bundle(&write_bundle(Vec::new(), &options).unwrap()).unwrap();
+    bundle.install(&dir).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("target_words.txt")).unwrap(),
+        "春天\n"
+    );
+
+    let installed = load_installed(&dir).unwrap().unwrap();
+    assert!(installed.is_complete());
+    assert_eq!(installed.options().target_words, ["春天"]);
+}
@@ -0,0 +1,95 @@
+// result_slots.rs
+// result_slots 模块的测试
+
+/// 结果槽按写入先后排列，缺省取最近写入的槽
+#[test]
+fn slots_keep_write_order() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        result_slots::{ResultSlots, LATEST_SLOT, MAX_SLOTS},
+    };
+
+    let texts = ["甲 乙 的", "乙 丙 的"].map(String::from);
+    let mut slots = ResultSlots::default();
+    assert!(slots.get(None).is_err());
+
+    // 保留停用词改动前的结果，再以新选项分析
+    slots.store(
+        LATEST_SLOT,
+        analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default()),
+    );
+    slots.pin("before", None).unwrap();
+    let options = AnalysisOptions {
+        stopwords: vec!["的".into()],
+        ..Default::default()
+    };
+    slots.store(
+        LATEST_SLOT,
+        analyze_texts(&WhitespaceTokenizer, &texts, &options),
+    );
+
+    // 不指定槽名时取最近写入的槽
+    assert_eq!(slots.get(None).unwrap().entries.len(), 3);
+    assert_eq!(slots.get(Some("before")).unwrap().entries.len(), 4);
+    let listed = slots.list();
+    let names: Vec<(&str, bool)> = listed
+        .iter()
+        .map(|s| (s.name.as_str(), s.current))
+        .collect();
+    assert_eq!(names, [("before", false), ("latest", true)]);
+    assert!(slots.get(Some("after")).is_err());
+
+    // 槽名不能为空，槽数有上限
+    assert!(slots.pin(" ", None).is_err());
+    for i in 2..MAX_SLOTS {
+        slots.pin(&format!("slot_{i}"), Some("before")).unwrap();
+    }
+    assert!(slots.pin("one_more", None).is_err());
+    // 覆盖已有的槽不受上限限制；复制不改变最近写入的槽
+    slots.pin("before", None).unwrap();
+    assert_eq!(slots.get(Some("before")).unwrap().entries.len(), 3);
+    assert_eq!(slots.resolve(None).unwrap(), LATEST_SLOT);
+
+    assert!(slots.remove("before"));
+    assert!(!slots.remove("before"));
+    assert_eq!(slots.list().len(), MAX_SLOTS - 1);
+}
+
+/// 所用词表不同的槽按集合比较列出
+#[test]
+fn stale_results_compare_lists_as_sets() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        resource_bundle::ResourceKind,
+        result_slots::{ResultSlots, LATEST_SLOT},
+    };
+
+    let texts = ["甲 乙 的", "乙 丙 的"].map(String::from);
+    let mut slots = ResultSlots::default();
+    let options = AnalysisOptions {
+        stopwords: vec!["的".into(), "了".into()],
+        ..Default::default()
+    };
+    slots.store(
+        "plain",
+        analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default()),
+    );
+    slots.store(
+        LATEST_SLOT,
+        analyze_texts(&WhitespaceTokenizer, &texts, &options),
+    );
+
+    // 顺序不同的同一词表不算改动
+    let loaded = ["了".to_string(), "的".to_string()];
+    let stale = slots.stale_results(&[(ResourceKind::Stopwords, &loaded)]);
+    let names: Vec<&str> = stale.iter().map(|s| s.slot.as_str()).collect();
+    assert_eq!(names, ["plain"]);
+    assert_eq!(stale[0].lists, [ResourceKind::Stopwords]);
+    assert!(slots
+        .stale_results(&[(ResourceKind::Phrases, &[])])
+        .is_empty());
+}
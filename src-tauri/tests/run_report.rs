@@ -0,0 +1,101 @@
+// run_report.rs
+// run_report 模块的测试
+
+/// 成功的运行报告取自结果元数据
+#[test]
+fn succeeded_report_counts() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        run_report::{options_hash, RunReport, RunStatus},
+    };
+
+    let texts = ["甲 乙 甲 丙", "乙 丁"].map(String::from);
+    let options = AnalysisOptions {
+        two_pass: true,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let report = RunReport::succeeded(&result, Some(42), vec!["/tmp/out.csv".into()]);
+
+    assert_eq!((report.status, report.exit_code), (RunStatus::Succeeded, 0));
+    let counts = report.counts.as_ref().unwrap();
+    assert_eq!((counts.total_tokens, counts.entries), (6, 4));
+    // 选项之间相互影响的提示也汇入 warnings
+    assert!(report.warnings.iter().any(|w| w.contains("两遍计数")));
+    assert_eq!(
+        report.options_hash.as_deref(),
+        Some(options_hash(&options).as_str())
+    );
+    assert_eq!(report.timing.elapsed_ms, Some(42));
+}
+
+/// 各处记录的提示按固定顺序汇集
+#[test]
+fn warnings_are_collected() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+        run_report::collect_warnings,
+    };
+
+    let texts = ["甲 乙 的 甲", "乙 的 丙"].map(String::from);
+    let options = AnalysisOptions {
+        stopwords: vec!["的".into()],
+        ..Default::default()
+    };
+    let mut result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    assert!(collect_warnings(&result.metadata)
+        .iter()
+        .any(|w| w.contains("stopwords") && w.contains("2 个词元")));
+
+    result
+        .metadata
+        .record_sanitized(&["dp", "dp", "juilland_d"]);
+    let warnings = collect_warnings(&result.metadata);
+    assert!(
+        warnings.last().unwrap().contains("3 个指标值")
+            && warnings.last().unwrap().contains("dp: 2")
+    );
+}
+
+/// 运行报告的字段与 JSON Schema 一致
+#[test]
+fn report_matches_schema() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        run_report::{report_schema, RunReport, REPORT_SCHEMA_VERSION},
+    };
+
+    let schema = serde_json::to_value(report_schema()).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+
+    // 成功与失败的报告都只含 Schema 中列出的字段
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let reports = [
+        RunReport::succeeded(&result, Some(5), vec!["out.csv".into()]),
+        RunReport::failed(1, "无法读取", None, None),
+    ];
+    for report in &reports {
+        let value = serde_json::to_value(report).unwrap();
+        let fields = value.as_object().unwrap();
+        assert_eq!(fields.len(), properties.len());
+        assert!(fields.keys().all(|key| properties.contains_key(key)));
+        assert_eq!(value["schemaVersion"], REPORT_SCHEMA_VERSION);
+    }
+    for field in [
+        "status",
+        "exitCode",
+        "optionsHash",
+        "timing",
+        "counts",
+        "warnings",
+        "fileErrors",
+        "outputs",
+    ] {
+        assert!(properties.contains_key(field), "{field}");
+    }
+}
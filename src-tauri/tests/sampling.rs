@@ -0,0 +1,143 @@
+// sampling.rs
+// sampling 模块的测试
+
+mod common;
+
+use common::fixture;
+
+/// 只分析抽中的文件，元数据记录样本，同一种子抽得同一样本
+#[test]
+fn analysis_uses_recorded_sample() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        nlp::RuleTokenizer,
+        options::AnalysisOptions,
+        progress::{NoopReporter, ProgressPhase, RecordingReporter, ReportedEvent},
+        sampling::{SampleSize, SamplingOptions},
+    };
+    use std::path::PathBuf;
+
+    let dir = fixture("golden/raw");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    let options = AnalysisOptions {
+        sampling: Some(SamplingOptions {
+            size: SampleSize::Fraction(0.25),
+            seed: 2024,
+        }),
+        seed: Some(11),
+        ..Default::default()
+    };
+    let reporter = RecordingReporter::default();
+    let result = analyze_corpus(&RuleTokenizer, &files, &options, None, &reporter).unwrap();
+
+    // 只分析样本，元数据记录种子、序列编号与抽中的文件
+    let sample = result.metadata.sample.as_ref().unwrap();
+    assert_eq!(
+        (sample.seed, sample.input_files, sample.files.len()),
+        (2024, 12, 3)
+    );
+    assert_eq!(result.metadata.seed, 11);
+    let analyzed: Vec<&str> = result
+        .metadata
+        .files
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+    assert_eq!(analyzed, sample.files);
+    assert_eq!(result.metadata.parts.len(), 3);
+    assert!(result
+        .metadata
+        .warnings
+        .iter()
+        .any(|w| w.contains("试点抽样")));
+
+    // 进度的总数为样本的文件数
+    let totals: Vec<usize> = reporter
+        .events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match event {
+            ReportedEvent::Progress(p) if p.phase == ProgressPhase::Files => Some(p.total),
+            _ => None,
+        })
+        .collect();
+    assert!(!totals.is_empty() && totals.iter().all(|&total| total == 3));
+
+    // 同一种子再次分析得到同一样本
+    let again = analyze_corpus(&RuleTokenizer, &files, &options, None, &NoopReporter).unwrap();
+    assert_eq!(again.metadata.sample, result.metadata.sample);
+}
+
+/// 固定种子的样本确定，比例与词次预算按选项抽取
+#[test]
+fn sampling_modes() {
+    use betawordlist_lib::analysis::sampling::{sample_files, SampleSize, SamplingOptions};
+    use std::path::PathBuf;
+
+    let dir = fixture("golden/raw");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    let names = |sample: &[PathBuf]| -> Vec<String> {
+        sample
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    };
+
+    // 固定种子与序列编号的样本是确定的，与输入顺序无关
+    let options = SamplingOptions {
+        size: SampleSize::Files(4),
+        seed: 42,
+    };
+    let (sample, record) = sample_files(&files, &options, 1);
+    assert_eq!(
+        names(&sample),
+        [
+            "01_school.txt",
+            "03_cooking.txt",
+            "10_music.txt",
+            "11_city.txt"
+        ]
+    );
+    assert_eq!((record.input_files, record.files.len()), (12, 4));
+    files.reverse();
+    let (reversed, _) = sample_files(&files, &options, 1);
+    assert_eq!(
+        names(&reversed),
+        names(&sample).into_iter().rev().collect::<Vec<_>>()
+    );
+
+    // 换一个序列编号得到不同的样本
+    let (other, _) = sample_files(&files, &SamplingOptions { seed: 7, ..options }, 1);
+    assert_eq!(other.len(), 4);
+    assert_ne!(names(&other), names(&reversed));
+
+    // 比例四舍五入，至少一个文件
+    let fraction = SamplingOptions {
+        size: SampleSize::Fraction(0.3),
+        seed: 42,
+    };
+    assert_eq!(sample_files(&files, &fraction, 1).0.len(), 4);
+    let tiny = SamplingOptions {
+        size: SampleSize::Fraction(0.01),
+        seed: 42,
+    };
+    assert_eq!(sample_files(&files, &tiny, 1).0.len(), 1);
+
+    // 词次预算：累计估算词次达到预算即停
+    let budget = SamplingOptions {
+        size: SampleSize::Tokens(100),
+        seed: 42,
+    };
+    let (sample, record) = sample_files(&files, &budget, 1);
+    let estimated = record.estimated_tokens.unwrap();
+    assert!(estimated >= 100 && sample.len() < files.len());
+}
@@ -0,0 +1,52 @@
+// segment_watchdog.rs
+// segment_watchdog 模块的测试
+
+/// 超时的批被跳过，取消时提前放弃
+#[test]
+fn stuck_batches_are_skipped() {
+    use betawordlist_lib::analysis::{
+        nlp::Tokenizer,
+        segment_watchdog::{SegmentWatchdog, Segmented},
+    };
+    use std::thread;
+    use std::time::Duration;
+
+    /// 遇到 "慢" 时卡住一秒
+    struct Sluggish;
+
+    impl Tokenizer for Sluggish {
+        fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+            if text.contains('慢') {
+                thread::sleep(Duration::from_secs(1));
+            }
+            text.chars()
+                .map(|c| (c.to_string(), "n".to_string()))
+                .collect()
+        }
+        fn engine(&self) -> &'static str {
+            "sluggish"
+        }
+    }
+
+    thread::scope(|scope| {
+        let watchdog = SegmentWatchdog::new(scope, &Sluggish, Duration::from_millis(200));
+        let never = || false;
+        match watchdog.segment_pos_batch(&["春天", "来了"], &never) {
+            Segmented::Done(sentences) => assert_eq!(sentences[1][0].0, "来"),
+            other => panic!("{other:?}"),
+        }
+        assert!(matches!(
+            watchdog.segment_pos_batch(&["慢"], &never),
+            Segmented::TimedOut
+        ));
+        // 被放弃的调用仍在后台运行，不妨碍后续分词
+        assert!(matches!(
+            watchdog.segment_pos_batch(&["快"], &never),
+            Segmented::Done(_)
+        ));
+        assert!(matches!(
+            watchdog.segment_pos_batch(&["慢"], &|| true),
+            Segmented::Cancelled
+        ));
+    });
+}
@@ -0,0 +1,134 @@
+// selection.rs
+// selection 模块的测试
+
+mod common;
+
+use common::TempDir;
+
+/// 按 id、词形或筛选条件选出条目，找不到的条目单独列出
+#[test]
+fn selection_keeps_requested_entries() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        length::EntryQuery,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        selection::{select, EntryKey, Selection},
+    };
+
+    let texts = ["甲 乙 甲 WiFi", "乙 丙 Java"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let id = |word: &str| {
+        result
+            .entries
+            .iter()
+            .find(|e| e.word == word)
+            .unwrap()
+            .id
+            .clone()
+    };
+    let word_pos = |word: &str| EntryKey::WordPos {
+        word: word.into(),
+        pos: "ws".into(),
+    };
+
+    // 按给出的顺序，id 与 (词形, 词性) 可以混用；找不到的标识另行报告
+    let keys = vec![
+        EntryKey::Id(id("丙")),
+        word_pos("甲"),
+        EntryKey::Id("0000000000000000".into()),
+        word_pos("丁"),
+        EntryKey::Id(id("甲")),
+    ];
+    let selected = select(&result, &Selection::Keys(keys));
+    let words: Vec<&str> = selected
+        .result
+        .entries
+        .iter()
+        .map(|e| e.word.as_str())
+        .collect();
+    assert_eq!(words, ["丙", "甲"]);
+    assert_eq!(
+        selected.unknown,
+        [EntryKey::Id("0000000000000000".into()), word_pos("丁")]
+    );
+    assert!(selected
+        .result
+        .metadata
+        .warnings
+        .last()
+        .unwrap()
+        .contains("2 个"));
+
+    // 按筛选条件导出全部匹配的条目，不受分页影响
+    let query = EntryQuery {
+        flags: vec!["contains_latin".into()],
+        limit: Some(1),
+        ..Default::default()
+    };
+    let selected = select(&result, &Selection::Query(query));
+    let mut words: Vec<&str> = selected
+        .result
+        .entries
+        .iter()
+        .map(|e| e.word.as_str())
+        .collect();
+    words.sort();
+    assert_eq!((words, selected.unknown.len()), (vec!["Java", "WiFi"], 0));
+}
+
+/// 只导出所选条目，没有可导出的条目时不写出文件
+#[test]
+fn selected_entries_are_exported() {
+    use betawordlist_lib::analysis::{
+        batch_export::BatchOptions,
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        selection::{export_selection, EntryKey, Selection},
+    };
+
+    let texts = ["甲 乙 甲", "乙 丙"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let dir = TempDir::new("selection");
+    let key = |word: &str| EntryKey::WordPos {
+        word: word.into(),
+        pos: "ws".into(),
+    };
+
+    let selection = Selection::Keys(vec![key("丙"), key("丁"), key("甲")]);
+    let formats = ["csv", "vertical"].map(String::from);
+    let report = export_selection(
+        &result,
+        &selection,
+        &dir.join("picked"),
+        &formats,
+        &BatchOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        (report.exported, report.unknown.as_slice()),
+        (2, &[key("丁")][..])
+    );
+    assert!(report.formats[0].error.is_none());
+    assert!(report.formats[1].error.as_deref().unwrap().contains("竖排"));
+    assert!(report.formats[1].written.is_empty() && !dir.join("picked.vrt").exists());
+    let csv = std::fs::read_to_string(dir.join("picked.csv")).unwrap();
+    let words: Vec<&str> = csv
+        .lines()
+        .skip(1)
+        .map(|l| l.split(',').next().unwrap())
+        .collect();
+    assert_eq!(words, ["丙", "甲"]);
+
+    let nothing = Selection::Keys(vec![key("丁")]);
+    let error = export_selection(
+        &result,
+        &nothing,
+        &dir.join("none"),
+        &formats,
+        &BatchOptions::default(),
+    );
+    assert!(error.unwrap_err().contains("丁"));
+    assert!(!dir.join("none.csv").exists());
+}
@@ -0,0 +1,137 @@
+// spectrum.rs
+// spectrum 模块的测试
+
+mod common;
+
+use common::corpus_files;
+
+/// Σ m·V(m) 加上尾部词次等于语料的总词次
+#[test]
+fn spectrum_adds_up_to_tokens() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus, nlp::RuleTokenizer, options::AnalysisOptions,
+        progress::NoopReporter, spectrum::frequency_spectrum,
+    };
+
+    let files = corpus_files(3);
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        &files,
+        &AnalysisOptions::default(),
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    let tokens: u64 = result.metadata.parts.iter().map(|p| p.tokens).sum();
+
+    for cap in [1, 5, u64::MAX] {
+        let spectrum = frequency_spectrum(result.entries.iter().map(|e| e.frequency), cap);
+        let sum: u64 = spectrum.spectrum.iter().map(|&(m, v)| m * v).sum();
+        assert_eq!(sum + spectrum.tail_tokens, tokens);
+        assert_eq!(spectrum.total_tokens, tokens);
+        assert_eq!(spectrum.total_types, result.entries.len() as u64);
+    }
+}
+
+/// Gale & Sampson 文中韵律数据的 r*
+#[test]
+fn sgt_matches_gale_sampson() {
+    use betawordlist_lib::analysis::spectrum::simple_good_turing;
+
+    let counts = [
+        (1, 120),
+        (2, 40),
+        (3, 24),
+        (4, 13),
+        (5, 15),
+        (6, 5),
+        (7, 11),
+        (8, 2),
+        (9, 2),
+        (10, 1),
+        (12, 3),
+        (14, 2),
+        (15, 1),
+        (16, 1),
+        (17, 3),
+        (19, 1),
+        (20, 3),
+        (21, 2),
+        (23, 3),
+        (24, 3),
+        (25, 3),
+        (26, 2),
+        (27, 2),
+        (28, 1),
+        (31, 2),
+        (32, 2),
+        (33, 1),
+        (34, 2),
+        (36, 2),
+        (41, 3),
+        (43, 1),
+        (45, 3),
+        (46, 1),
+        (47, 1),
+        (50, 1),
+        (71, 1),
+        (84, 1),
+        (101, 1),
+        (105, 1),
+        (121, 1),
+        (124, 1),
+        (146, 1),
+        (162, 1),
+        (193, 1),
+        (199, 1),
+        (224, 1),
+        (226, 1),
+        (254, 1),
+        (257, 1),
+        (339, 1),
+        (421, 1),
+        (456, 1),
+        (481, 1),
+        (483, 1),
+        (1140, 1),
+        (1256, 1),
+        (1322, 1),
+        (1530, 1),
+        (2131, 1),
+        (2395, 1),
+        (6925, 1),
+        (7846, 1),
+    ];
+    let frequencies = counts
+        .iter()
+        .flat_map(|&(r, n)| std::iter::repeat_n(r as f64, n));
+    let r_star = simple_good_turing(frequencies).unwrap();
+
+    for (r, expected) in [
+        (1, 0.7628),
+        (2, 1.7064),
+        (3, 2.6798),
+        (4, 3.6640),
+        (5, 4.6534),
+    ] {
+        assert!(
+            (r_star[&r] - expected).abs() < 1e-4,
+            "r = {r}: {}",
+            r_star[&r]
+        );
+    }
+    // 已见类型的总量为 (1 - P0)·N，P0 = 120 / 30902
+    let seen: f64 = counts.iter().map(|&(r, n)| n as f64 * r_star[&r]).sum();
+    assert!((seen - (30902.0 - 120.0)).abs() < 1e-6);
+}
+
+/// 斜率不低于 -1 时改用 Turing 估计
+#[test]
+fn sgt_falls_back_to_turing() {
+    use betawordlist_lib::analysis::spectrum::simple_good_turing;
+
+    let r_star = simple_good_turing([1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    for (r, turing) in [(1, 2.0), (2, 3.0), (3, 4.0), (4, 5.0), (5, 5.0)] {
+        assert!((r_star[&r] - 14.0 * turing / 19.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,32 @@
+// structural.rs
+// structural 模块的测试
+
+/// 章节标题、罗马数字与页码被移出词元流
+#[test]
+fn structural_noise_is_removed() {
+    use betawordlist_lib::analysis::{
+        nlp::{RuleTokenizer, Tokenizer},
+        structural,
+    };
+
+    let text = "第一章 春天\n花开了。\nIV\n他说 I am Ⅻ\n- 12 -\n第三回\n我第一回来";
+    let (kept, removed) = structural::strip(text, RuleTokenizer.segment_pos(text));
+
+    let found: Vec<(&str, &str, usize)> = removed
+        .iter()
+        .map(|n| (n.text.as_str(), n.rule, n.tokens))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("第一章", "chapter_heading", 3),
+            ("IV", "roman_numeral", 1),
+            ("Ⅻ", "roman_numeral", 1),
+            ("-12-", "page_number", 3),
+            ("第三回", "chapter_heading", 3),
+        ]
+    );
+    // 行中的 "第一回" 与英文的 I 保留
+    let words: String = kept.iter().map(|(w, _)| w.as_str()).collect();
+    assert_eq!(words, "春天花开了。他说Iam我第一回来");
+}
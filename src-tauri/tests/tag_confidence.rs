@@ -0,0 +1,45 @@
+// tag_confidence.rs
+// tag_confidence 模块的测试
+
+/// 词性标注不一致的条目按一致度升序列出
+#[test]
+fn low_confidence_entries() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::Tokenizer, options::AnalysisOptions, tag_confidence,
+    };
+
+    /// 以 "词/词性" 书写的文本
+    struct Tagged;
+
+    impl Tokenizer for Tagged {
+        fn segment_pos(&self, text: &str) -> Vec<(String, String)> {
+            text.split_whitespace()
+                .filter_map(|t| t.split_once('/'))
+                .map(|(w, p)| (w.to_string(), p.to_string()))
+                .collect()
+        }
+        fn engine(&self) -> &'static str {
+            "tagged"
+        }
+    }
+
+    let texts = ["研究/v 研究/n 研究/v 我/r", "研究/n 研究/v 我/r 在/p 在/v"].map(String::from);
+    let options = AnalysisOptions {
+        pos_confidence: true,
+        ..Default::default()
+    };
+    let result = analyze_texts(&Tagged, &texts, &options);
+
+    let report = tag_confidence::low_confidence_report(&result.entries, 2.0, 10).unwrap();
+    let items: Vec<(&str, &str, f64)> = report
+        .iter()
+        .map(|i| (i.word.as_str(), i.pos.as_str(), i.pos_tag_share))
+        .collect();
+    // 研究 3/5 为 v、2/5 为 n；在 各 1 次，频次不足；我 总是 r
+    assert_eq!(items, [("研究", "n", 0.4), ("研究", "v", 0.6)]);
+    assert_eq!(report[0].alternatives[0].pos, "v");
+
+    let plain = analyze_texts(&Tagged, &texts, &AnalysisOptions::default());
+    assert!(plain.entries.iter().all(|e| e.pos_tag_share.is_none()));
+    assert!(tag_confidence::low_confidence_report(&plain.entries, 2.0, 10).is_err());
+}
@@ -0,0 +1,42 @@
+// targets.rs
+// targets 模块的测试
+
+/// 目标词表中未出现的词按目标词表顺序列出
+#[test]
+fn absent_targets_are_listed() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions, targets,
+    };
+
+    let texts = ["甲 乙 甲 丙", "丁 乙"].map(String::from);
+    let options = AnalysisOptions {
+        target_words: ["甲", "戊", "甲", "己"].map(String::from).to_vec(),
+        ..Default::default()
+    };
+    assert_eq!(
+        targets::absent_targets(&options, ["甲", "乙"]),
+        ["戊", "己"]
+    );
+
+    // 目标词模式下只有目标词有条目，部分词数仍计入全部词元；未出现的目标词频次为 0
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    assert_eq!(result.metadata.total_tokens, 6);
+    let jia = result.entries.iter().find(|e| e.word == "甲").unwrap();
+    assert_eq!((jia.frequency, jia.metrics.range), (2.0, 1));
+    let full = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    let full_jia = full.entries.iter().find(|e| e.word == "甲").unwrap();
+    assert_eq!(
+        format!("{:?}", jia.metrics),
+        format!("{:?}", full_jia.metrics)
+    );
+    assert!(!result.entries.iter().any(|e| e.word == "乙"));
+    let absent: Vec<&str> = result
+        .entries
+        .iter()
+        .filter(|e| e.pos == targets::ABSENT_POS)
+        .map(|e| e.word.as_str())
+        .collect();
+    assert_eq!(absent, ["戊", "己"]);
+    let wu = result.entries.iter().find(|e| e.word == "戊").unwrap();
+    assert_eq!((wu.frequency, wu.metrics.range), (0.0, 0));
+}
@@ -0,0 +1,38 @@
+// text.rs
+// text 模块的测试
+
+/// 切出的各句依次相接即为原文
+#[test]
+fn sentences_join_back_to_text() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::RuleTokenizer, options::AnalysisOptions, text,
+        word_entry::WordEntry,
+    };
+
+    let content = "他说：“好。”我们走吧！！\n第二段……结束";
+    let sentences = text::split_text_sentences(content);
+    assert_eq!(
+        sentences,
+        ["他说：“好。”", "我们走吧！！\n", "第二段……", "结束"]
+    );
+    assert_eq!(sentences.concat(), content);
+
+    // 分批分词只改变调用方式，词元按原文顺序拼接
+    let texts = [content.to_string()];
+    let whole = analyze_texts(&RuleTokenizer, &texts, &AnalysisOptions::default());
+    let options = AnalysisOptions {
+        segment_batch_size: 2,
+        ..Default::default()
+    };
+    let batched = analyze_texts(&RuleTokenizer, &texts, &options);
+    let counts = |entries: &[WordEntry]| {
+        let mut counts: Vec<(String, String, f64)> = entries
+            .iter()
+            .map(|e| (e.word.clone(), e.pos.clone(), e.frequency))
+            .collect();
+        counts.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        counts
+    };
+    assert_eq!(batched.metadata.total_tokens, whole.metadata.total_tokens);
+    assert_eq!(counts(&batched.entries), counts(&whole.entries));
+}
@@ -0,0 +1,195 @@
+// token_policy.rs
+// token_policy 模块的测试
+
+mod common;
+
+use common::fixture;
+
+/// 分词器拆开的 emoji 序列被拼合
+#[test]
+fn split_emoji_are_joined() {
+    use betawordlist_lib::analysis::{
+        nlp::{RuleTokenizer, Tokenizer},
+        token_policy::join_emoji,
+    };
+
+    let tokens = RuleTokenizer.segment_pos("好👩\u{200D}💻👍🏽🇨🇳❤\u{FE0F}葛\u{E0100}");
+    assert_eq!(tokens.len(), 12);
+    let joined = join_emoji(tokens);
+    let pairs: Vec<(&str, &str)> = joined
+        .iter()
+        .map(|(w, p)| (w.as_str(), p.as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            ("好", "n"),
+            ("👩\u{200D}💻", "emo"),
+            ("👍🏽", "emo"),
+            ("🇨🇳", "emo"),
+            ("❤\u{FE0F}", "emo"),
+            ("葛\u{E0100}", "n"),
+        ]
+    );
+}
+
+/// emoji 与扩展 B 区汉字经分词、清理、切句、例句与导出都不被拆开
+#[test]
+fn emoji_survive_full_pipeline() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_corpus,
+        export::{write_csv, ExportOptions},
+        nlp::RuleTokenizer,
+        options::{AnalysisOptions, PartitionMode},
+        progress::NoopReporter,
+        text::char_length,
+        token_policy::TokenPolicy,
+    };
+
+    let file = fixture("emoji/social.txt");
+    let options = AnalysisOptions {
+        token_policy: TokenPolicy {
+            sanitize: true,
+            ..Default::default()
+        },
+        partition: PartitionMode::PerSentence,
+        collect_examples: 1,
+        ..Default::default()
+    };
+    let result = analyze_corpus(
+        &RuleTokenizer,
+        std::slice::from_ref(&file),
+        &options,
+        None,
+        &NoopReporter,
+    )
+    .unwrap();
+    let find = |word: &str| result.entries.iter().find(|e| e.word == word);
+
+    let family = find("👩\u{200D}👧\u{200D}👦").unwrap();
+    assert_eq!(
+        (family.pos.as_str(), family.frequency, family.char_length),
+        ("emo", 1.0, 1)
+    );
+    assert_eq!(find("👍🏽").unwrap().frequency, 2.0);
+    assert_eq!(find("👍🏿").unwrap().frequency, 1.0);
+    assert_eq!(find("😀").unwrap().frequency, 2.0);
+    assert!(find("👩\u{200D}💻").is_some() && find("🇨🇳").is_some() && find("❤\u{FE0F}").is_some());
+    assert_eq!(find("𠀀").unwrap().pos, "n");
+    // 修饰符与 ZWJ 不会单独成词
+    assert!(find("🏽").is_none() && find("👩").is_none() && find("\u{200D}").is_none());
+    assert!(result.entries.iter().all(|e| char_length(&e.word) == 1));
+    // 例句完整保留 emoji 序列
+    assert!(family.examples[0].contains("小朋友👩\u{200D}👧\u{200D}👦一起"));
+
+    let mut out = Vec::new();
+    write_csv(&mut out, &result, &ExportOptions::default()).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    assert!(csv.contains("\n👩\u{200D}👧\u{200D}👦,emo,"));
+    assert!(csv.contains("\n𠀁,n,"));
+
+    // emo 可按词性过滤
+    let without = AnalysisOptions {
+        excluded_pos: vec!["emo".to_string()],
+        ..options
+    };
+    let result = analyze_corpus(&RuleTokenizer, &[file], &without, None, &NoopReporter).unwrap();
+    assert!(result.entries.iter().all(|e| e.pos != "emo"));
+    assert_eq!(result.metadata.filter_removed.excluded_pos, 11);
+}
+
+/// 不可见字符被删去，连接 emoji 的 ZWJ 保留
+#[test]
+fn invisible_characters_are_removed() {
+    use betawordlist_lib::analysis::token_policy::{sanitize, TokenPolicyCounts};
+
+    let tokens = ["\u{FEFF}甲", "\u{200B}", "乙\u{200B}丙", "\u{3000}", "丁"]
+        .map(|w| (w.to_string(), "n".to_string()))
+        .to_vec();
+    let mut counts = TokenPolicyCounts::default();
+    let words: Vec<String> = sanitize(tokens, &mut counts)
+        .into_iter()
+        .map(|(w, _)| w)
+        .collect();
+    assert_eq!(words, ["甲", "乙丙", "丁"]);
+    assert_eq!(counts.invisible_chars, 3);
+    assert_eq!(counts.blank_tokens, 2);
+
+    // emoji 序列中的 ZWJ 保留，汉字之间的 ZWJ 删去
+    let tokens = vec![
+        ("👩\u{200D}💻".to_string(), "emo".to_string()),
+        ("甲\u{200D}乙".to_string(), "n".to_string()),
+    ];
+    let words: Vec<String> = sanitize(tokens, &mut counts)
+        .into_iter()
+        .map(|(w, _)| w)
+        .collect();
+    assert_eq!(words, ["👩\u{200D}💻", "甲乙"]);
+
+    // 清理先于计数，零宽字符不再作为标点计入部分词数
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+        token_policy::TokenPolicy,
+    };
+    let texts = ["甲\u{200B} \u{FEFF} 乙\u{3000}甲".to_string()];
+    let mut options = AnalysisOptions::default();
+    assert_eq!(
+        analyze_texts(&WhitespaceTokenizer, &texts, &options)
+            .metadata
+            .parts[0]
+            .tokens,
+        5
+    );
+    options.token_policy = TokenPolicy {
+        sanitize: true,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    assert_eq!(result.metadata.parts[0].tokens, 3);
+    assert_eq!(result.metadata.token_policy_removed.blank_tokens, 2);
+}
+
+/// 拉丁字母按 latin_case 折叠，全角字母仍为全角
+#[test]
+fn latin_case_folding() {
+    use betawordlist_lib::analysis::token_policy::{fold_latin, LatinCase};
+
+    assert_eq!(fold_latin("iPhone", LatinCase::Lower), "iphone");
+    assert_eq!(fold_latin("McDonald", LatinCase::Lower), "mcdonald");
+    assert_eq!(fold_latin("McDonald's", LatinCase::Lower), "McDonald's");
+    assert_eq!(fold_latin("Élan2", LatinCase::Fold), "élan2");
+    assert_eq!(fold_latin("ＩＰｈｏｎｅ", LatinCase::Lower), "ｉｐｈｏｎｅ");
+    // 中英混合与汉字词元不变
+    assert_eq!(fold_latin("iPhone手机", LatinCase::Lower), "iPhone手机");
+    assert_eq!(fold_latin("手机", LatinCase::Lower), "手机");
+    assert_eq!(fold_latin("iPhone", LatinCase::Preserve), "iPhone");
+
+    // Fold 时条目以小写为准，surface_forms 记录各原始写法
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+    };
+    let texts = [
+        "iPhone IPHONE 手机 iPhone",
+        "iphone ＩＰＨＯＮＥ iPhone手机",
+    ]
+    .map(String::from);
+    let options = AnalysisOptions {
+        latin_case: LatinCase::Fold,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let entry = |word: &str| result.entries.iter().find(|e| e.word == word).unwrap();
+    assert_eq!(entry("iphone").frequency, 4.0);
+    let variants: Vec<(&str, f64)> = entry("iphone")
+        .surface_forms
+        .iter()
+        .map(|v| (v.form.as_str(), v.frequency))
+        .collect();
+    assert_eq!(
+        variants,
+        [("iPhone", 2.0), ("IPHONE", 1.0), ("iphone", 1.0)]
+    );
+    assert_eq!(entry("ｉｐｈｏｎｅ").surface_forms[0].form, "ＩＰＨＯＮＥ");
+    assert!(entry("iPhone手机").surface_forms.is_empty());
+    assert!(entry("手机").surface_forms.is_empty());
+}
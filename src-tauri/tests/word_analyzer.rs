@@ -0,0 +1,131 @@
+// word_analyzer.rs
+// word_analyzer 模块的测试
+
+/// 给出的总词数与部分词数之和不符时，S_adj 为 None 而不截断
+#[test]
+fn inconsistent_total_keeps_part_shares() {
+    use betawordlist_lib::analysis::word_analyzer::CorpusWordAnalyzer;
+
+    // 给出的总词数小于部分词数之和：s 仍按部分词数归一，S_adj 因部分不一致为 None，而不是截断为 1
+    let analyzer = CorpusWordAnalyzer::new(vec![4.0, 4.0], vec![100.0, 100.0], 100.0).unwrap();
+    assert!(!analyzer.parts_consistent());
+    assert_eq!(analyzer.get_roschengren_s_adj(), None);
+    assert_eq!(analyzer.get_dp(), Some(0.0));
+}
+
+/// 各部分等大时 S 与 S_adj 相同
+#[test]
+fn s_equals_s_adj_for_equal_parts() {
+    use betawordlist_lib::analysis::word_analyzer::CorpusWordAnalyzer;
+
+    let v = vec![3.0, 0.0, 1.0, 5.0];
+    let analyzer = CorpusWordAnalyzer::new(v, vec![100.0; 4], 400.0).unwrap();
+    let s = analyzer.get_roschengren_s().unwrap();
+    let s_adj = analyzer.get_roschengren_s_adj().unwrap();
+    assert!((s - s_adj).abs() < 1e-12);
+
+    // 词数为 0 的部分却有频次：两者均为 None
+    let broken = CorpusWordAnalyzer::new(vec![3.0, 2.0], vec![100.0, 0.0], 100.0).unwrap();
+    assert_eq!(broken.get_roschengren_s(), None);
+    assert_eq!(broken.get_roschengren_s_adj(), None);
+}
+
+/// 基尼系数：均匀分布为 0
+#[test]
+fn gini_of_part_rates() {
+    use betawordlist_lib::analysis::word_analyzer::CorpusWordAnalyzer;
+
+    let even = CorpusWordAnalyzer::new(vec![2.0, 2.0, 2.0], vec![100.0; 3], 300.0).unwrap();
+    assert_eq!(even.get_gini(), Some(0.0));
+    assert_eq!(even.get_max_part_share(), Some(1.0 / 3.0));
+
+    // 全部集中在一个部分：(n - 1) / n
+    let one = CorpusWordAnalyzer::new(vec![0.0, 0.0, 0.0, 4.0], vec![100.0; 4], 400.0).unwrap();
+    assert!((one.get_gini().unwrap() - 0.75).abs() < 1e-12);
+    assert_eq!(one.get_max_part_share(), Some(1.0));
+
+    let single = CorpusWordAnalyzer::new(vec![3.0], vec![100.0], 100.0).unwrap();
+    assert_eq!(
+        (single.get_max_part_share(), single.get_gini()),
+        (Some(1.0), Some(0.0))
+    );
+    let absent = CorpusWordAnalyzer::new(vec![0.0, 0.0], vec![100.0; 2], 200.0).unwrap();
+    assert_eq!(
+        (absent.get_max_part_share(), absent.get_gini()),
+        (Some(0.0), Some(0.0))
+    );
+}
+
+/// 同为最大时取靠前的部分
+#[test]
+fn max_part_rate_prefers_first_part() {
+    use betawordlist_lib::analysis::word_analyzer::CorpusWordAnalyzer;
+
+    // 第 2 部分为空：不参与最小值
+    let sizes = vec![100.0, 200.0, 0.0, 50.0];
+    let analyzer = CorpusWordAnalyzer::new(vec![2.0, 6.0, 0.0, 1.0], sizes, 350.0).unwrap();
+    assert_eq!(analyzer.get_min_part_rate(), Some(0.02));
+    assert_eq!(analyzer.get_max_part_rate(), Some((1, 0.03)));
+    let metrics = analyzer.calculate_all_metrics();
+    assert_eq!(
+        (metrics.min_part_rate, metrics.max_part_rate),
+        (Some(0.02), Some(0.03))
+    );
+
+    // 未出现的非空部分最小值为 0
+    let sparse = CorpusWordAnalyzer::new(vec![0.0, 3.0], vec![100.0, 100.0], 200.0).unwrap();
+    assert_eq!(sparse.get_min_part_rate(), Some(0.0));
+    let empty = CorpusWordAnalyzer::new(vec![0.0], vec![0.0], 0.0).unwrap();
+    assert_eq!(
+        (empty.get_min_part_rate(), empty.get_max_part_rate()),
+        (None, None)
+    );
+}
+
+/// 随机的一致输入下，每个指标都落在 metric_schema 给出的取值范围内
+#[test]
+fn metrics_stay_in_schema_ranges() {
+    use betawordlist_lib::analysis::{
+        metric_descriptions::{metric_schema, UpperBound},
+        word_analyzer::CorpusWordAnalyzer,
+    };
+    use quickcheck::{quickcheck, TestResult};
+
+    fn bounded(parts: Vec<(u16, u16)>) -> TestResult {
+        if parts.is_empty() {
+            return TestResult::discard();
+        }
+        let sizes: Vec<f64> = parts.iter().map(|&(size, _)| f64::from(size)).collect();
+        let v: Vec<f64> = parts
+            .iter()
+            .map(|&(size, freq)| f64::from(u32::from(freq) % (u32::from(size) + 1)))
+            .collect();
+        let total = sizes.iter().sum();
+        let n = parts.len() as f64;
+        let analyzer = CorpusWordAnalyzer::new(v, sizes, total).unwrap();
+        let metrics = analyzer.calculate_all_metrics();
+        if metrics.roschengren_s.is_none() || metrics.roschengren_s_adj.is_none() {
+            return TestResult::error("一致的输入 S / S_adj 不应为 None");
+        }
+        for (name, value) in metrics.fields() {
+            let (Some(value), Some(schema)) =
+                (value, metric_schema().into_iter().find(|s| s.name == name))
+            else {
+                continue;
+            };
+            let within = value >= schema.min
+                && match schema.max {
+                    UpperBound::Unbounded => value.is_finite(),
+                    UpperBound::PartCount => value <= n,
+                    UpperBound::Value { max, inclusive } => {
+                        value < max || (inclusive && value == max)
+                    }
+                };
+            if !within {
+                return TestResult::error(format!("{name} = {value} 越界"));
+            }
+        }
+        TestResult::passed()
+    }
+    quickcheck(bounded as fn(Vec<(u16, u16)>) -> TestResult);
+}
@@ -0,0 +1,38 @@
+// word_entry.rs
+// word_entry 模块的测试
+
+/// 查找条目时匹配条目本身与各记录的写法
+#[test]
+fn search_forms_include_variants() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts, nlp::WhitespaceTokenizer, options::AnalysisOptions,
+        token_policy::LatinCase,
+    };
+
+    let texts = ["WiFi wifi WIFI", "WiFi Wifi 手机"].map(String::from);
+    let options = AnalysisOptions {
+        latin_case: LatinCase::Fold,
+        max_surface_forms: 2,
+        ..Default::default()
+    };
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &options);
+    let entry = |word: &str| result.entries.iter().find(|e| e.word == word).unwrap();
+
+    let forms: Vec<(&str, f64, bool)> = entry("wifi")
+        .surface_forms
+        .iter()
+        .map(|v| (v.form.as_str(), v.frequency, v.other))
+        .collect();
+    assert_eq!(
+        forms,
+        [
+            ("WiFi", 2.0, false),
+            ("WIFI", 1.0, false),
+            ("other", 2.0, true)
+        ]
+    );
+    assert_eq!(entry("wifi").top_surface_form(), "WiFi");
+    assert_eq!(entry("wifi").search_forms(), ["wifi", "WiFi", "WIFI"]);
+    assert_eq!(entry("手机").top_surface_form(), "手机");
+    assert_eq!(entry("手机").search_forms(), ["手机"]);
+}
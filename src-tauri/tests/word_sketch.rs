@@ -0,0 +1,79 @@
+// word_sketch.rs
+// word_sketch 模块的测试
+
+/// 开启规范化时按全部写法匹配节点词
+#[test]
+fn sketch_matches_all_forms() {
+    use betawordlist_lib::analysis::word_sketch::{
+        builtin_relations, SketchCounter, SketchOptions,
+    };
+
+    let tokens: Vec<(String, String)> = ["用/v WiFi/n 。/wp 连/v wifi/n 。/wp 开/v WIFI/n"]
+        .iter()
+        .flat_map(|s| s.split(' '))
+        .map(|t| {
+            let (w, p) = t.split_once('/').unwrap();
+            (w.to_string(), p.to_string())
+        })
+        .collect();
+    let options = SketchOptions {
+        min_frequency: 1.0,
+        ..Default::default()
+    };
+
+    let mut canonical = SketchCounter::new("wifi", "n", builtin_relations());
+    canonical.add_text(&tokens);
+    assert_eq!(canonical.finish(&options).frequency, 1.0);
+
+    let mut all = SketchCounter::new("wifi", "n", builtin_relations())
+        .with_forms(["wifi", "WiFi", "WIFI"].map(String::from).to_vec());
+    all.add_text(&tokens);
+    let sketch = all.finish(&options);
+    assert_eq!((sketch.word.as_str(), sketch.frequency), ("wifi", 3.0));
+}
+
+/// 搭配词按 logDice、共现次数与词形排序
+#[test]
+fn sketch_orders_by_log_dice() {
+    use betawordlist_lib::analysis::word_sketch::{
+        builtin_relations, SketchCounter, SketchOptions,
+    };
+
+    let text = |s: &str| -> Vec<(String, String)> {
+        s.split(' ')
+            .map(|t| {
+                let (w, p) = t.split_once('/').unwrap();
+                (w.to_string(), p.to_string())
+            })
+            .collect()
+    };
+    let mut counter = SketchCounter::new("问题", "n", builtin_relations());
+    counter.add_text(&text(
+        "解决/v 了/u 这个/r 问题/n 。/wp 解决/v 两/m 个/q 大/a 问题/n 。/wp",
+    ));
+    counter.add_text(&text("研究/v 新/a 的/u 问题/n ，/wp 问题/n 很/d 多/a"));
+    let sketch = counter.finish(&SketchOptions {
+        min_frequency: 1.0,
+        ..Default::default()
+    });
+    assert_eq!(sketch.frequency, 4.0);
+
+    let names: Vec<&str> = sketch.relations.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["object_of", "modifier"]);
+    // 这个（r）不在允许的间隔词性中，第一句的 解决 不计
+    let verbs: Vec<(&str, f64)> = sketch.relations[0]
+        .collocates
+        .iter()
+        .map(|c| (c.word.as_str(), c.frequency))
+        .collect();
+    assert_eq!(verbs, [("研究", 1.0), ("解决", 1.0)]);
+    assert!(
+        sketch.relations[0].collocates[0].log_dice > sketch.relations[0].collocates[1].log_dice
+    );
+    let adjectives: Vec<&str> = sketch.relations[1]
+        .collocates
+        .iter()
+        .map(|c| c.word.as_str())
+        .collect();
+    assert_eq!(adjectives, ["大", "新"]);
+}
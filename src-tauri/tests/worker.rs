@@ -0,0 +1,73 @@
+// worker.rs
+// worker 模块的测试
+
+/// 各线程的请求由工作线程依次处理
+#[test]
+fn concurrent_requests_are_serialized() {
+    use betawordlist_lib::analysis::{
+        corpus_pipeline::analyze_texts,
+        nlp::WhitespaceTokenizer,
+        options::AnalysisOptions,
+        result_slots::{LATEST_SLOT, MAX_SLOTS},
+        worker::ResultWorker,
+    };
+    use std::thread;
+    use tauri::async_runtime::block_on;
+
+    let worker = ResultWorker::spawn();
+    assert!(block_on(worker.run(|slots| slots.get(None).is_err())));
+
+    let texts = ["甲 乙 的", "乙 丙 的"].map(String::from);
+    let result = analyze_texts(&WhitespaceTokenizer, &texts, &AnalysisOptions::default());
+    block_on(worker.store(LATEST_SLOT, result.clone()));
+
+    // 多个线程同时写入、复制、列出、分页与移除，阻塞与异步的调用混用；
+    // 每条消息独占结果槽，读到的结果总是完整的
+    let handles: Vec<_> = (0..8)
+        .map(|t| {
+            let worker = worker.clone();
+            let result = result.clone();
+            thread::spawn(move || {
+                for i in 0..50 {
+                    let name = format!("t{t}");
+                    match i % 5 {
+                        0 => worker.blocking_store(LATEST_SLOT, result.clone()),
+                        1 => {
+                            // 槽数已满时复制失败，属正常情况
+                            let _ = block_on(worker.pin(&name, None));
+                        }
+                        2 => {
+                            let listed = block_on(worker.list());
+                            assert!(!listed.is_empty() && listed.len() <= MAX_SLOTS);
+                            assert_eq!(listed.iter().filter(|slot| slot.current).count(), 1);
+                        }
+                        3 => {
+                            let entries =
+                                worker.blocking_run(|slots| slots.get(None).unwrap().entries.len());
+                            assert_eq!(entries, 4);
+                        }
+                        _ => {
+                            block_on(worker.remove(&name));
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(block_on(worker.list())
+        .iter()
+        .any(|slot| slot.name == LATEST_SLOT));
+
+    // 闭包 panic 不影响之后的消息
+    let failed = thread::spawn({
+        let worker = worker.clone();
+        move || block_on(worker.run(|slots| slots.get(Some("missing")).unwrap().entries.len()))
+    })
+    .join();
+    assert!(failed.is_err());
+    let slots = worker.blocking_run(|slots| slots.list().len());
+    assert_eq!(slots, block_on(worker.list()).len());
+}
@@ -0,0 +1,29 @@
+// xlsx.rs
+// xlsx 模块的测试
+
+/// XLSX 含各表，文本单元格经转义写出
+#[test]
+fn xlsx_sheets_and_escaped_cells() {
+    use betawordlist_lib::analysis::xlsx::{Cell, XlsxWriter};
+
+    let mut xlsx = XlsxWriter::new(Vec::new());
+    xlsx.start_sheet("entries").unwrap();
+    xlsx.row(&[Cell::Text("word"), Cell::Text("frequency")])
+        .unwrap();
+    xlsx.row(&[Cell::Text("<甲&乙>"), Cell::Number(2.5)])
+        .unwrap();
+    xlsx.row(&[Cell::Empty, Cell::Number(f64::NAN)]).unwrap();
+    xlsx.start_sheet("metadata").unwrap();
+    let bytes = xlsx.finish().unwrap();
+
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+    // 中央目录结尾记录：共 6 个条目（内容类型、两份关系、工作簿与两张工作表）
+    let end = &bytes[bytes.len() - 22..];
+    assert_eq!(&end[..4], b"PK\x05\x06");
+    assert_eq!(u16::from_le_bytes([end[10], end[11]]), 6);
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(
+        text.contains("<c t=\"inlineStr\"><is><t>&lt;甲&amp;乙&gt;</t></is></c><c><v>2.5</v></c>")
+    );
+    assert!(text.contains("<sheet name=\"metadata\" sheetId=\"2\" r:id=\"rId2\"/>"));
+}
@@ -0,0 +1,38 @@
+// zip.rs
+// zip 模块的测试
+
+/// ZIP 条目原样读回，数据损坏或截断时报错
+#[test]
+fn zip_round_trip_detects_damage() {
+    use betawordlist_lib::analysis::zip::{read_entries, ZipWriter};
+
+    let mut zip = ZipWriter::new(Vec::new());
+    zip.add("a.txt", "甲\n乙\n".as_bytes()).unwrap();
+    zip.start_entry("dir/b.txt").unwrap();
+    zip.write(b"hello, ").unwrap();
+    zip.write(b"world").unwrap();
+    zip.end_entry().unwrap();
+    let bytes = zip.finish().unwrap();
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+
+    let entries = read_entries(&bytes).unwrap();
+    assert_eq!(
+        entries[0],
+        ("a.txt".to_string(), "甲\n乙\n".as_bytes().to_vec())
+    );
+    assert_eq!(
+        entries[1],
+        ("dir/b.txt".to_string(), b"hello, world".to_vec())
+    );
+
+    // 数据被改动后 CRC 不符
+    let mut damaged = bytes.clone();
+    let at = damaged.windows(5).position(|w| w == b"hello").unwrap();
+    damaged[at] = b'j';
+    assert!(read_entries(&damaged)
+        .unwrap_err()
+        .to_string()
+        .contains("dir/b.txt"));
+    // 截断的文件找不到中央目录
+    assert!(read_entries(&bytes[..bytes.len() / 2]).is_err());
+}